@@ -14,6 +14,7 @@ use axum::{
     Router,
 };
 use diesel::prelude::*;
+use diesel_gaussdb::query_builder::SearchBuilder;
 use diesel_gaussdb::GaussDBConnection;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
@@ -23,8 +24,17 @@ use std::env;
 use std::sync::Arc;
 use tokio::sync::{Mutex, oneshot};
 
+diesel::table! {
+    users (id) {
+        id -> Integer,
+        name -> Text,
+        email -> Text,
+        age -> Nullable<Integer>,
+    }
+}
+
 /// 用户数据结构
-#[derive(Debug, Serialize, Deserialize, diesel::QueryableByName)]
+#[derive(Debug, Serialize, Deserialize, diesel::Queryable, diesel::QueryableByName)]
 struct User {
     #[diesel(sql_type = diesel::sql_types::Integer)]
     id: i32,
@@ -338,31 +348,16 @@ async fn search_users(
 ) -> Result<Json<ApiResponse<Vec<User>>>, StatusCode> {
     let mut conn = establish_connection_async().await?;
 
-    let mut sql = "SELECT id, name, email, age FROM users WHERE 1=1".to_string();
-
-    if let Some(name) = params.get("name") {
-        sql.push_str(&format!(" AND name ILIKE '%{}%'", name));
-    }
-
-    if let Some(email) = params.get("email") {
-        sql.push_str(&format!(" AND email ILIKE '%{}%'", email));
-    }
-
-    if let Some(min_age) = params.get("min_age") {
-        if let Ok(age) = min_age.parse::<i32>() {
-            sql.push_str(&format!(" AND age >= {}", age));
-        }
-    }
-
-    if let Some(max_age) = params.get("max_age") {
-        if let Ok(age) = max_age.parse::<i32>() {
-            sql.push_str(&format!(" AND age <= {}", age));
-        }
-    }
-
-    sql.push_str(" ORDER BY id LIMIT 50");
+    let age = |key: &str| params.get(key).and_then(|value| value.parse::<i32>().ok());
+    let page = params.get("page").and_then(|value| value.parse().ok()).unwrap_or(1);
 
-    let users: Vec<User> = diesel::sql_query(sql)
+    // 所有过滤条件都作为绑定参数发送，未提供的条件不会生成 WHERE 子句
+    let users: Vec<User> = SearchBuilder::new(users::table.order(users::id))
+        .contains(users::name, params.get("name").map(String::as_str))
+        .contains(users::email, params.get("email").map(String::as_str))
+        .range(users::age, age("min_age"), age("max_age"))
+        .paginate(page, 50)
+        .into_query()
         .load(&mut conn)
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
@@ -11,6 +11,8 @@ use axum::{
     Router,
 };
 use diesel::prelude::*;
+use diesel_gaussdb::prelude::GaussDBStringExpressionMethods;
+use diesel_gaussdb::query_builder::{contains_pattern, SearchBuilder};
 use diesel_gaussdb::GaussDBConnection;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -21,8 +23,18 @@ use std::env;
 use std::sync::Arc;
 use tokio::sync::oneshot;
 
+diesel::table! {
+    posts (id) {
+        id -> Integer,
+        title -> Text,
+        content -> Text,
+        author_id -> Integer,
+        published -> Bool,
+    }
+}
+
 /// 博客文章结构
-#[derive(Debug, Serialize, Deserialize, diesel::QueryableByName)]
+#[derive(Debug, Serialize, Deserialize, diesel::Queryable, diesel::QueryableByName)]
 struct Post {
     #[diesel(sql_type = diesel::sql_types::Integer)]
     id: i32,
@@ -493,23 +505,25 @@ async fn search_posts(
     let mut conn = establish_connection()
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let mut sql = "SELECT id, title, content, author_id, published FROM posts WHERE published = true".to_string();
-
-    if let Some(keyword) = params.get("q") {
-        sql.push_str(&format!(" AND (title ILIKE '%{}%' OR content ILIKE '%{}%')", keyword, keyword));
-    }
-
-    if let Some(author_id) = params.get("author_id") {
-        if let Ok(id) = author_id.parse::<i32>() {
-            sql.push_str(&format!(" AND author_id = {}", id));
-        }
-    }
+    let keyword = params.get("q").and_then(|q| contains_pattern(q));
+    let author_id = params.get("author_id").and_then(|id| id.parse::<i32>().ok());
+    let page = params.get("page").and_then(|page| page.parse().ok()).unwrap_or(1);
 
-    sql.push_str(" ORDER BY id DESC LIMIT 20");
-
-    let posts: Vec<Post> = diesel::sql_query(sql)
-        .load(&mut conn)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // 关键字和作者都作为绑定参数发送，未提供的条件不会生成 WHERE 子句
+    let posts: Vec<Post> = SearchBuilder::new(
+        posts::table
+            .filter(posts::published.eq(true))
+            .order(posts::id.desc()),
+    )
+    .filter_opt(keyword, |pattern| {
+        GaussDBStringExpressionMethods::ilike(posts::title, pattern.clone())
+            .or(GaussDBStringExpressionMethods::ilike(posts::content, pattern))
+    })
+    .equal_to(posts::author_id, author_id)
+    .paginate(page, 20)
+    .into_query()
+    .load(&mut conn)
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     Ok(Json(ApiResponse::success(posts)))
 }
@@ -381,9 +381,9 @@ pub async fn demo_search_and_filtering(db_manager: &DatabaseManager) -> Result<(
     let products_with_dimensions = db_manager.execute_query(|conn| {
         sql_query("
             SELECT name, dimensions,
-                   (dimensions->>'length')::FLOAT as length,
-                   (dimensions->>'width')::FLOAT as width,
-                   (dimensions->>'height')::FLOAT as height
+                   (dimensions->>'length')::REAL as length,
+                   (dimensions->>'width')::REAL as width,
+                   (dimensions->>'height')::REAL as height
             FROM products
             WHERE dimensions IS NOT NULL
             AND (dimensions->>'length')::FLOAT > 150
@@ -249,6 +249,39 @@ fn test_complex_query_performance(conn: &mut GaussDBConnection) -> Result<Perfor
     Ok(PerformanceResult::new("复杂联表查询".to_string(), total_time, query_count))
 }
 
+/// 分析复杂查询的服务端执行计划
+///
+/// 使用 `explain_and_run` 在同一次执行中获取查询结果和 `EXPLAIN ANALYZE` 计划摘要
+fn profile_complex_query(conn: &mut GaussDBConnection) -> Result<()> {
+    info!("🔍 分析复杂查询执行计划...");
+
+    let start_time = Instant::now();
+    let (rows, plan) = conn.explain_and_run(diesel::sql_query(
+        "SELECT u.name, COUNT(p.id) as post_count
+         FROM test_users u
+         LEFT JOIN test_posts p ON u.id = p.author_id
+         WHERE u.age > 25
+         GROUP BY u.id, u.name
+         HAVING COUNT(p.id) > 0
+         ORDER BY post_count DESC
+         LIMIT 10"
+    ))?;
+    let client_time = start_time.elapsed();
+
+    info!("📊 执行计划摘要: 复杂联表查询");
+    info!("  返回行数: {}", rows.len());
+    info!("  估算总代价: {:.2}", plan.total_cost);
+    info!("  估算行数: {} / 实际行数: {}", plan.estimated_rows, plan.actual_rows);
+    info!("  根节点实际耗时: {:.3} ms", plan.actual_time_ms);
+    if let Some(execution_time) = plan.execution_time_ms {
+        info!("  服务端总耗时: {:.3} ms", execution_time);
+    }
+    info!("  客户端总耗时: {:?}", client_time);
+    info!("");
+
+    Ok(())
+}
+
 /// 测试事务性能
 fn test_transaction_performance(conn: &mut GaussDBConnection) -> Result<PerformanceResult> {
     info!("🔄 测试事务性能...");
@@ -309,6 +342,9 @@ fn main() -> Result<()> {
     // 复杂查询性能测试
     results.push(test_complex_query_performance(&mut connection)?);
 
+    // 复杂查询执行计划分析
+    profile_complex_query(&mut connection)?;
+
     // 事务性能测试
     results.push(test_transaction_performance(&mut connection)?);
 
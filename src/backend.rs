@@ -110,6 +110,61 @@ impl GaussDBTypeMetadata {
     }
 }
 
+/// Well-known OIDs of built-in types as `(typname, oid, array_oid)`
+///
+/// These match PostgreSQL's `pg_type` entries, which GaussDB keeps unchanged.
+const BUILTIN_TYPES: &[(&str, u32, u32)] = &[
+    ("bool", 16, 1000),
+    ("bytea", 17, 1001),
+    ("name", 19, 1003),
+    ("int8", 20, 1016),
+    ("int2", 21, 1005),
+    ("int2vector", 22, 1006),
+    ("int4", 23, 1007),
+    ("text", 25, 1009),
+    ("oid", 26, 1028),
+    ("oidvector", 30, 1013),
+    ("json", 114, 199),
+    ("float4", 700, 1021),
+    ("float8", 701, 1022),
+    ("bpchar", 1042, 1014),
+    ("varchar", 1043, 1015),
+    ("date", 1082, 1182),
+    ("time", 1083, 1183),
+    ("timestamp", 1114, 1115),
+    ("timestamptz", 1184, 1185),
+    ("interval", 1186, 1187),
+    ("timetz", 1266, 1270),
+    ("numeric", 1700, 1231),
+    ("uuid", 2950, 2951),
+    ("jsonb", 3802, 3807),
+];
+
+impl GaussDBTypeMetadata {
+    /// Metadata of a built-in type, resolved without querying the server
+    ///
+    /// `type_name` is the `pg_type.typname` of the type, e.g. `int4` or
+    /// `timestamptz`. Types in a schema other than `pg_catalog` are never
+    /// considered built-in.
+    pub fn builtin(type_name: &str, schema: Option<&str>) -> Option<Self> {
+        if !matches!(schema, None | Some("pg_catalog")) {
+            return None;
+        }
+        BUILTIN_TYPES
+            .iter()
+            .find(|(name, _, _)| *name == type_name)
+            .map(|&(_, oid, array_oid)| Self::new(oid, array_oid))
+    }
+}
+
+/// The `typname` of the built-in type with the given OID
+pub(crate) fn builtin_type_name(oid: u32) -> Option<&'static str> {
+    BUILTIN_TYPES
+        .iter()
+        .find(|&&(_, type_oid, _)| type_oid == oid)
+        .map(|&(name, _, _)| name)
+}
+
 impl From<(u32, u32)> for GaussDBTypeMetadata {
     fn from((oid, array_oid): (u32, u32)) -> Self {
         Self::new(oid, array_oid)
@@ -138,15 +193,73 @@ pub trait GaussDBMetadataLookup {
         Self: 'a;
 }
 
+/// Lookup for serializing binds without a connection
+///
+/// Built-in types have static OIDs and never reach it; custom types are
+/// left unresolved.
+pub(crate) struct UnresolvedLookup;
+
+impl GaussDBMetadataLookup for UnresolvedLookup {
+    fn lookup_type(&mut self, type_name: &str, _schema: Option<&str>) -> GaussDBTypeMetadata {
+        GaussDBTypeMetadata::from_result(Err(FailedToLookupTypeError::new(type_name)))
+    }
+
+    fn as_any<'a>(&mut self) -> &mut (dyn std::any::Any + 'a)
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+/// Lookup for tests, knowing only the `(name, oid, array_oid)` types it is
+/// given and panicking on any other lookup
+#[cfg(test)]
+pub(crate) struct TestLookup(&'static [(&'static str, u32, u32)]);
+
+#[cfg(test)]
+impl TestLookup {
+    /// A lookup for tests that only use types with static OIDs
+    pub(crate) fn builtin() -> Self {
+        TestLookup(&[])
+    }
+
+    /// A lookup that also knows `types`
+    pub(crate) fn with_types(types: &'static [(&'static str, u32, u32)]) -> Self {
+        TestLookup(types)
+    }
+}
+
+#[cfg(test)]
+impl GaussDBMetadataLookup for TestLookup {
+    fn lookup_type(&mut self, type_name: &str, _schema: Option<&str>) -> GaussDBTypeMetadata {
+        match self.0.iter().find(|(name, _, _)| *name == type_name) {
+            Some(&(_, oid, array_oid)) => GaussDBTypeMetadata::new(oid, array_oid),
+            None => panic!("unexpected type lookup for {}", type_name),
+        }
+    }
+
+    fn as_any<'a>(&mut self) -> &mut (dyn std::any::Any + 'a)
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
 impl SqlDialect for GaussDB {
     type ReturningClause = GaussDBReturningClause;
     type OnConflictClause = GaussDBOnConflictClause;
+    // A `None` in an insertable value renders `DEFAULT`, also per row of a
+    // multi-row insert, so the column default applies instead of `NULL`
     type InsertWithDefaultKeyword = sql_dialect::default_keyword_for_insert::IsoSqlDefaultKeyword;
     type BatchInsertSupport = sql_dialect::batch_insert_support::PostgresLikeBatchInsertSupport;
     type ConcatClause = sql_dialect::concat_clause::ConcatWithPipesClause;
     type DefaultValueClauseForInsert = sql_dialect::default_value_clause::AnsiDefaultValueClause;
     type EmptyFromClauseSyntax = sql_dialect::from_clause_syntax::AnsiSqlFromClauseSyntax;
-    type SelectStatementSyntax = sql_dialect::select_statement_syntax::AnsiSqlSelectStatement;
+    // Like the ANSI syntax, but tells aliased expressions whether they are
+    // walked in the select clause
+    type SelectStatementSyntax = GaussDBSelectStatement;
     type ExistsSyntax = sql_dialect::exists_syntax::AnsiSqlExistsSyntax;
     type ArrayComparison = sql_dialect::array_comparison::AnsiSqlArrayComparison;
     type AliasSyntax = sql_dialect::alias_syntax::AsAliasSyntax;
@@ -230,6 +343,18 @@ impl HasSqlType<diesel::sql_types::Time> for GaussDB {
     }
 }
 
+impl HasSqlType<diesel::sql_types::Interval> for GaussDB {
+    fn metadata(_: &mut (dyn GaussDBMetadataLookup + 'static)) -> GaussDBTypeMetadata {
+        GaussDBTypeMetadata::new(1186, 1187) // interval, _interval
+    }
+}
+
+impl HasSqlType<crate::types::sql_types::Timetz> for GaussDB {
+    fn metadata(_: &mut (dyn GaussDBMetadataLookup + 'static)) -> GaussDBTypeMetadata {
+        GaussDBTypeMetadata::new(1266, 1270) // timetz, _timetz
+    }
+}
+
 impl HasSqlType<diesel::sql_types::Timestamp> for GaussDB {
     fn metadata(_: &mut (dyn GaussDBMetadataLookup + 'static)) -> GaussDBTypeMetadata {
         GaussDBTypeMetadata::new(1114, 1115) // timestamp, _timestamp
@@ -242,6 +367,24 @@ impl HasSqlType<diesel::sql_types::Bool> for GaussDB {
     }
 }
 
+impl HasSqlType<crate::types::sql_types::Int2Vector> for GaussDB {
+    fn metadata(_: &mut (dyn GaussDBMetadataLookup + 'static)) -> GaussDBTypeMetadata {
+        GaussDBTypeMetadata::new(22, 1006) // int2vector, _int2vector
+    }
+}
+
+impl HasSqlType<crate::types::sql_types::OidVector> for GaussDB {
+    fn metadata(_: &mut (dyn GaussDBMetadataLookup + 'static)) -> GaussDBTypeMetadata {
+        GaussDBTypeMetadata::new(30, 1013) // oidvector, _oidvector
+    }
+}
+
+impl HasSqlType<crate::types::sql_types::Name> for GaussDB {
+    fn metadata(_: &mut (dyn GaussDBMetadataLookup + 'static)) -> GaussDBTypeMetadata {
+        GaussDBTypeMetadata::new(19, 1003) // name, _name
+    }
+}
+
 impl DieselReserveSpecialization for GaussDB {}
 impl TrustedBackend for GaussDB {}
 
@@ -251,6 +394,8 @@ impl TrustedBackend for GaussDB {}
 pub struct GaussDBOnConflictClause;
 
 impl sql_dialect::on_conflict_clause::SupportsOnConflictClause for GaussDBOnConflictClause {}
+// `ON CONFLICT (...) DO UPDATE SET ... = excluded.col` uses the PostgreSQL syntax
+impl sql_dialect::on_conflict_clause::PgLikeOnConflictClause for GaussDBOnConflictClause {}
 
 /// GaussDB-specific RETURNING clause support
 #[derive(Debug, Copy, Clone)]
@@ -258,6 +403,10 @@ pub struct GaussDBReturningClause;
 
 impl sql_dialect::returning_clause::SupportsReturningClause for GaussDBReturningClause {}
 
+/// GaussDB-specific SELECT statement syntax
+#[derive(Debug, Copy, Clone)]
+pub struct GaussDBSelectStatement;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,4 +430,65 @@ mod tests {
         assert_eq!(metadata.oid().unwrap(), 23);
         assert_eq!(metadata.array_oid().unwrap(), 1007);
     }
+
+    #[test]
+    fn test_builtin_type_metadata() {
+        let int4 = GaussDBTypeMetadata::builtin("int4", None).unwrap();
+        assert_eq!(int4.oid().unwrap(), 23);
+        assert_eq!(int4.array_oid().unwrap(), 1007);
+
+        let timestamptz = GaussDBTypeMetadata::builtin("timestamptz", Some("pg_catalog")).unwrap();
+        assert_eq!(timestamptz.oid().unwrap(), 1184);
+        assert_eq!(timestamptz.array_oid().unwrap(), 1185);
+    }
+
+    #[test]
+    fn test_builtin_type_name() {
+        assert_eq!(builtin_type_name(23), Some("int4"));
+        assert_eq!(builtin_type_name(1184), Some("timestamptz"));
+        assert_eq!(builtin_type_name(1007), None);
+    }
+
+    #[test]
+    fn test_builtin_type_metadata_requires_catalog_schema() {
+        assert!(GaussDBTypeMetadata::builtin("int4", Some("public")).is_none());
+        assert!(GaussDBTypeMetadata::builtin("my_enum", None).is_none());
+    }
+
+    #[test]
+    fn test_builtin_types_match_static_sql_types() {
+        use diesel::sql_types::*;
+
+        fn static_metadata<ST>() -> GaussDBTypeMetadata
+        where
+            GaussDB: HasSqlType<ST>,
+        {
+            <GaussDB as HasSqlType<ST>>::metadata(&mut TestLookup::builtin())
+        }
+
+        let pairs = [
+            ("bool", static_metadata::<Bool>()),
+            ("bytea", static_metadata::<Binary>()),
+            ("int2", static_metadata::<SmallInt>()),
+            ("int4", static_metadata::<Integer>()),
+            ("int8", static_metadata::<BigInt>()),
+            ("float4", static_metadata::<Float>()),
+            ("float8", static_metadata::<Double>()),
+            ("text", static_metadata::<Text>()),
+            ("date", static_metadata::<Date>()),
+            ("time", static_metadata::<Time>()),
+            ("timestamp", static_metadata::<Timestamp>()),
+            ("timetz", static_metadata::<crate::types::sql_types::Timetz>()),
+            ("int2vector", static_metadata::<crate::types::sql_types::Int2Vector>()),
+            ("oidvector", static_metadata::<crate::types::sql_types::OidVector>()),
+            ("name", static_metadata::<crate::types::sql_types::Name>()),
+            ("numeric", static_metadata::<Numeric>()),
+            ("json", static_metadata::<Json>()),
+            ("jsonb", static_metadata::<Jsonb>()),
+        ];
+
+        for (name, metadata) in pairs {
+            assert_eq!(GaussDBTypeMetadata::builtin(name, None), Some(metadata), "{}", name);
+        }
+    }
 }
@@ -0,0 +1,121 @@
+//! Advisory lock support for GaussDB connections
+//!
+//! Advisory locks are session-level locks on an application defined `i64`
+//! key. They are not tied to any table and are useful to coordinate jobs
+//! across processes that share a database.
+
+use crate::connection::GaussDBConnection;
+use diesel::define_sql_function;
+use diesel::prelude::*;
+use diesel::result::QueryResult;
+use diesel::sql_types::BigInt;
+use std::fmt;
+
+define_sql_function! {
+    /// The `pg_try_advisory_lock(key)` SQL function
+    fn pg_try_advisory_lock(key: BigInt) -> diesel::sql_types::Bool;
+}
+
+define_sql_function! {
+    /// The `pg_advisory_unlock(key)` SQL function
+    fn pg_advisory_unlock(key: BigInt) -> diesel::sql_types::Bool;
+}
+
+/// Take the session-level advisory lock `key`, waiting until it is free
+///
+/// The server runs deadlock detection on advisory locks too, so waiting in
+/// a cycle fails with a database error instead of blocking forever.
+pub(crate) fn lock(connection: &mut GaussDBConnection, key: i64) -> QueryResult<()> {
+    diesel::sql_query("SELECT pg_advisory_lock($1)")
+        .bind::<BigInt, _>(key)
+        .execute(connection)
+        .map(|_| ())
+}
+
+/// Take the advisory lock `key` if it is free, returning whether it was taken
+pub(crate) fn try_lock(connection: &mut GaussDBConnection, key: i64) -> QueryResult<bool> {
+    diesel::select(pg_try_advisory_lock(key)).get_result(connection)
+}
+
+/// Release the advisory lock `key`, returning whether it was held
+pub(crate) fn unlock(connection: &mut GaussDBConnection, key: i64) -> QueryResult<bool> {
+    diesel::select(pg_advisory_unlock(key)).get_result(connection)
+}
+
+/// A held advisory lock that is released when dropped
+///
+/// Created by [`GaussDBConnection::advisory_lock_guard`] or
+/// [`GaussDBConnection::try_advisory_lock_guard`]. The connection stays
+/// usable through [`connection`](Self::connection) while the lock is held.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel::connection::SimpleConnection;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+/// const NIGHTLY_REPORT: i64 = 42;
+///
+/// if let Some(mut guard) = conn.try_advisory_lock_guard(NIGHTLY_REPORT)? {
+///     guard.connection().batch_execute("REFRESH MATERIALIZED VIEW nightly_report")?;
+///     guard.unlock()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AdvisoryLockGuard<'conn> {
+    connection: &'conn mut GaussDBConnection,
+    key: i64,
+    is_released: bool,
+}
+
+impl<'conn> AdvisoryLockGuard<'conn> {
+    pub(crate) fn new(connection: &'conn mut GaussDBConnection, key: i64) -> Self {
+        AdvisoryLockGuard {
+            connection,
+            key,
+            is_released: false,
+        }
+    }
+
+    /// The key of the held lock
+    pub fn key(&self) -> i64 {
+        self.key
+    }
+
+    /// The connection holding the lock
+    pub fn connection(&mut self) -> &mut GaussDBConnection {
+        self.connection
+    }
+
+    /// Release the lock now and report errors
+    ///
+    /// Returns `false` if the server no longer held the lock. The guard will
+    /// not try to release it again when dropped.
+    pub fn unlock(mut self) -> QueryResult<bool> {
+        self.is_released = true;
+        unlock(self.connection, self.key)
+    }
+}
+
+impl<'conn> fmt::Debug for AdvisoryLockGuard<'conn> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdvisoryLockGuard")
+            .field("key", &self.key)
+            .field("is_released", &self.is_released)
+            .finish()
+    }
+}
+
+// Release the lock when the guard goes out of scope
+impl<'conn> Drop for AdvisoryLockGuard<'conn> {
+    fn drop(&mut self) {
+        if !self.is_released {
+            // Errors are ignored since we're in Drop; the server releases
+            // the lock anyway when the session ends
+            let _ = unlock(self.connection, self.key);
+            self.is_released = true;
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Bind parameter forwarding for GaussDB connections
+//!
+//! Diesel serializes every bind parameter into the binary wire format
+//! through `ToSql`. This module hands those bytes to the gaussdb client
+//! unchanged, together with the parameter types Diesel reported, so values
+//! like arrays travel as a single binary parameter.
+//!
+//! The parameter type OIDs are always sent when the statement is prepared.
+//! Otherwise the server infers the type from the context of the
+//! placeholder, which is ambiguous for e.g. `$1 IS NULL` and may not match
+//! the binary value Diesel serialized.
+//!
+//! The number of parameters of a statement is limited, see
+//! [`GaussDBConnection::set_bind_parameter_limit`](super::GaussDBConnection::set_bind_parameter_limit).
+
+use std::error::Error;
+use std::fmt;
+
+use bytes::BytesMut;
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::query_builder::QueryFragment;
+use diesel::result::{Error as DieselError, QueryResult};
+use diesel::sql_types::TypeMetadata;
+use gaussdb::types::{IsNull, Kind, ToSql, Type};
+use gaussdb::{Client, Row, RowIter, Statement};
+
+use crate::backend::{GaussDB, GaussDBTypeMetadata};
+
+/// The default maximum number of bind parameters of a statement
+///
+/// The wire protocol sends the parameter count as a 16-bit integer.
+pub const DEFAULT_BIND_PARAMETER_LIMIT: usize = 65535;
+
+/// Error returned when a statement has more bind parameters than allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyBindParameters {
+    /// The number of bind parameters of the statement
+    pub count: usize,
+    /// The configured limit
+    pub limit: usize,
+}
+
+impl fmt::Display for TooManyBindParameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "statement has {} bind parameters, but at most {} are allowed; \
+             insert the rows in smaller chunks, or use `unnest_insert`, which \
+             sends each column as a single array parameter",
+            self.count, self.limit
+        )
+    }
+}
+
+impl Error for TooManyBindParameters {}
+
+/// Serialize the binds of `source`, failing if there are more than `limit`
+pub(crate) fn collect_binds<T>(
+    source: &T,
+    lookup: &mut <GaussDB as TypeMetadata>::MetadataLookup,
+    limit: usize,
+) -> QueryResult<RawBytesBindCollector<GaussDB>>
+where
+    T: QueryFragment<GaussDB> + ?Sized,
+{
+    let mut collector = RawBytesBindCollector::<GaussDB>::new();
+    source.collect_binds(&mut collector, lookup, &GaussDB)?;
+
+    let count = collector.binds.len();
+    if count > limit {
+        return Err(DieselError::QueryBuilderError(Box::new(TooManyBindParameters { count, limit })));
+    }
+    Ok(collector)
+}
+
+/// A bind parameter that was already serialized by Diesel
+#[derive(Debug)]
+pub(crate) struct RawBind {
+    bytes: Option<Vec<u8>>,
+}
+
+impl ToSql for RawBind {
+    fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match &self.bytes {
+            Some(bytes) => {
+                out.extend_from_slice(bytes);
+                Ok(IsNull::No)
+            }
+            None => Ok(IsNull::Yes),
+        }
+    }
+
+    // The type was already checked by Diesel when serializing the value
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn to_sql_checked(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.to_sql(ty, out)
+    }
+}
+
+/// The type sent for a parameter when preparing the statement
+///
+/// Custom types like enums have no built-in `Type`, so one is made up from
+/// the OID Diesel looked up; only the OID is sent to the server. If the
+/// lookup failed, OID 0 leaves the type to the server.
+fn param_type(metadata: &GaussDBTypeMetadata) -> Type {
+    let oid = metadata.oid().unwrap_or(0);
+    Type::from_oid(oid).unwrap_or_else(|| Type::new(String::new(), oid, Kind::Simple, String::new()))
+}
+
+/// SQL text together with its serialized bind parameters
+#[derive(Debug)]
+pub(crate) struct BoundQuery {
+    pub(crate) sql: String,
+    binds: Vec<RawBind>,
+    param_types: Vec<Type>,
+    metadata: Vec<GaussDBTypeMetadata>,
+}
+
+impl BoundQuery {
+    /// Build a query from the SQL text and the collected binds
+    pub(crate) fn new(sql: String, collector: RawBytesBindCollector<GaussDB>) -> Self {
+        let param_types = collector.metadata.iter().map(param_type).collect();
+        let metadata = collector.metadata;
+
+        let binds = collector
+            .binds
+            .into_iter()
+            .map(|bytes| RawBind { bytes })
+            .collect();
+
+        BoundQuery { sql, binds, param_types, metadata }
+    }
+
+    /// The bind parameter types, which are part of the statement cache key
+    pub(crate) fn metadata(&self) -> &[GaussDBTypeMetadata] {
+        &self.metadata
+    }
+
+    fn params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.binds.iter().map(|bind| bind as &(dyn ToSql + Sync)).collect()
+    }
+
+    pub(crate) fn prepare(&self, client: &mut Client) -> Result<Statement, gaussdb::Error> {
+        client.prepare_typed(&self.sql, &self.param_types)
+    }
+
+    /// Run a prepared statement and return all rows
+    pub(crate) fn query(&self, client: &mut Client, statement: &Statement) -> Result<Vec<Row>, gaussdb::Error> {
+        client.query(statement, &self.params())
+    }
+
+    /// Run a prepared statement and stream its rows as they arrive
+    pub(crate) fn query_iter<'c>(
+        &self,
+        client: &'c mut Client,
+        statement: &Statement,
+    ) -> Result<RowIter<'c>, gaussdb::Error> {
+        client.query_raw(statement, self.params())
+    }
+
+    /// Run a prepared statement and return the number of affected rows
+    pub(crate) fn execute(&self, client: &mut Client, statement: &Statement) -> Result<u64, gaussdb::Error> {
+        client.execute(statement, &self.params())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{FailedToLookupTypeError, UnresolvedLookup};
+
+    #[test]
+    fn test_param_types_cover_every_bind() {
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector.binds = vec![Some(vec![0, 0, 0, 1]), None, Some(vec![1]), Some(vec![0])];
+        collector.metadata = vec![
+            GaussDBTypeMetadata::new(1007, 0),
+            GaussDBTypeMetadata::new(999_999, 0),
+            GaussDBTypeMetadata::new(16, 1000),
+            GaussDBTypeMetadata::from_result(Err(FailedToLookupTypeError::new("missing"))),
+        ];
+
+        let query = BoundQuery::new("SELECT $1, $2, $3, $4".to_string(), collector);
+
+        let oids = query.param_types.iter().map(Type::oid).collect::<Vec<_>>();
+        assert_eq!(oids, vec![1007, 999_999, 16, 0]);
+        assert_eq!(query.param_types[0], Type::INT4_ARRAY);
+        assert_eq!(query.param_types[2], Type::BOOL);
+        assert_eq!(query.binds.len(), 4);
+        assert!(query.binds[1].bytes.is_none());
+    }
+
+    diesel::table! {
+        bind_items (id) {
+            id -> Integer,
+            name -> Text,
+        }
+    }
+
+    fn insert_rows(count: i32) -> Vec<(i32, String)> {
+        (0..count).map(|i| (i, format!("item_{}", i))).collect()
+    }
+
+    #[test]
+    fn test_oversized_insert_is_rejected() {
+        use diesel::prelude::*;
+
+        // 35000 rows of 2 columns each
+        let rows = insert_rows(35_000);
+        let values = rows
+            .iter()
+            .map(|(id, name)| (bind_items::id.eq(*id), bind_items::name.eq(name)))
+            .collect::<Vec<_>>();
+        let query = diesel::insert_into(bind_items::table).values(&values);
+
+        let error = collect_binds(&query, &mut UnresolvedLookup, DEFAULT_BIND_PARAMETER_LIMIT).unwrap_err();
+        match &error {
+            DieselError::QueryBuilderError(e) => assert_eq!(
+                e.downcast_ref::<TooManyBindParameters>(),
+                Some(&TooManyBindParameters { count: 70_000, limit: 65_535 })
+            ),
+            other => panic!("expected a query builder error, got {:?}", other),
+        }
+        let message = error.to_string();
+        assert!(message.contains("statement has 70000 bind parameters"), "{}", message);
+        assert!(message.contains("unnest_insert"), "{}", message);
+    }
+
+    #[test]
+    fn test_bind_parameter_limit_is_configurable() {
+        use diesel::prelude::*;
+
+        let rows = insert_rows(3);
+        let values = rows
+            .iter()
+            .map(|(id, name)| (bind_items::id.eq(*id), bind_items::name.eq(name)))
+            .collect::<Vec<_>>();
+        let query = diesel::insert_into(bind_items::table).values(&values);
+
+        assert_eq!(collect_binds(&query, &mut UnresolvedLookup, 6).unwrap().binds.len(), 6);
+        assert!(collect_binds(&query, &mut UnresolvedLookup, 5).is_err());
+    }
+
+    #[test]
+    fn test_null_bind_keeps_its_type() {
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector.binds = vec![None];
+        collector.metadata = vec![GaussDBTypeMetadata::new(23, 1007)];
+
+        let query = BoundQuery::new("SELECT $1 IS NULL".to_string(), collector);
+
+        assert_eq!(query.param_types, vec![Type::INT4]);
+    }
+}
@@ -5,10 +5,13 @@
 //! into memory at once.
 
 use crate::backend::GaussDB;
-use crate::connection::{GaussDBConnection, row::GaussDBRow};
+use crate::connection::{quote_identifier, GaussDBConnection, row::GaussDBRow};
 use diesel::result::{QueryResult, Error as DieselError};
-use diesel::query_builder::{QueryFragment, QueryId, QueryBuilder};
-use diesel::connection::SimpleConnection;
+use diesel::query_builder::{AstPass, QueryFragment, QueryId, QueryBuilder};
+use diesel::connection::{Connection, SimpleConnection};
+use diesel::expression::{is_aggregate, AppearsOnTable, Expression, ValidGrouping};
+use diesel::sql_types::Bool;
+use diesel::Table;
 use std::fmt;
 
 /// A cursor for iterating over large result sets in batches
@@ -52,6 +55,7 @@ use std::fmt;
 pub struct GaussDBCursor<'conn> {
     name: String,
     connection: &'conn mut GaussDBConnection,
+    transaction_depth: u32,
     is_closed: bool,
 }
 
@@ -72,18 +76,22 @@ impl<'conn> GaussDBCursor<'conn> {
     ///
     /// Returns an error if the cursor declaration fails or if a cursor
     /// with the same name already exists.
+    ///
+    /// The name is quoted, so it is case sensitive.
     pub fn declare(
         connection: &'conn mut GaussDBConnection,
         name: &str,
         query: &str,
     ) -> QueryResult<Self> {
-        let declare_sql = format!("DECLARE {} CURSOR FOR {}", name, query);
+        let declare_sql = format!("DECLARE {} CURSOR FOR {}", quote_identifier(name), query);
         
         connection.batch_execute(&declare_sql)?;
+        let transaction_depth = connection.transaction_depth();
         
         Ok(GaussDBCursor {
             name: name.to_string(),
             connection,
+            transaction_depth,
             is_closed: false,
         })
     }
@@ -137,7 +145,7 @@ impl<'conn> GaussDBCursor<'conn> {
             ));
         }
 
-        let fetch_sql = format!("FETCH {} FROM {}", count, self.name);
+        let fetch_sql = format!("FETCH {} FROM {}", count, quote_identifier(&self.name));
         
         {
             let empty_params: Vec<&(dyn gaussdb::types::ToSql + Sync)> = vec![];
@@ -167,7 +175,7 @@ impl<'conn> GaussDBCursor<'conn> {
     /// Returns an error if the fetch operation fails or if the cursor
     /// has been closed.
     pub fn fetch_all(&mut self) -> QueryResult<Vec<GaussDBRow<'static>>> {
-        let fetch_sql = format!("FETCH ALL FROM {}", self.name);
+        let fetch_sql = format!("FETCH ALL FROM {}", quote_identifier(&self.name));
         
         {
             let empty_params: Vec<&(dyn gaussdb::types::ToSql + Sync)> = vec![];
@@ -208,7 +216,7 @@ impl<'conn> GaussDBCursor<'conn> {
             ));
         }
 
-        let move_sql = format!("MOVE {} FROM {}", position, self.name);
+        let move_sql = format!("MOVE {} FROM {}", position, quote_identifier(&self.name));
         self.connection.batch_execute(&move_sql)
     }
 
@@ -226,12 +234,99 @@ impl<'conn> GaussDBCursor<'conn> {
             return Ok(());
         }
 
-        let close_sql = format!("CLOSE {}", self.name);
+        let close_sql = format!("CLOSE {}", quote_identifier(&self.name));
         let result = self.connection.batch_execute(&close_sql);
         self.is_closed = true;
         result
     }
 
+    /// Execute a statement on the connection that owns this cursor
+    ///
+    /// While the cursor is open it holds the connection, so other
+    /// statements of its transaction are run through the cursor. Returns
+    /// the number of affected rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cursor has been closed or if the connection
+    /// is no longer in the transaction that declared it.
+    pub fn execute<T>(&mut self, statement: T) -> QueryResult<usize>
+    where
+        T: QueryFragment<GaussDB> + QueryId,
+    {
+        self.check_usable()?;
+        self.connection.execute_returning_count(&statement)
+    }
+
+    /// Execute an `UPDATE` or `DELETE` of the row the cursor was last moved to
+    ///
+    /// `statement` builds the statement from a [`CurrentOf`] predicate,
+    /// which renders `WHERE CURRENT OF "cursor_name"`. The predicate borrows
+    /// the cursor, so it can't outlive it or be used with another cursor.
+    /// Use it as the only filter; the cursor query must read the updated
+    /// table without joins or aggregates. Returns the number of affected
+    /// rows.
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::connection::cursor::CursorDsl;
+    /// # diesel::table! { jobs (id) { id -> Integer, state -> Text, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// conn.transaction::<_, diesel::result::Error, _>(|conn| {
+    ///     let mut cursor = conn.declare_cursor("job_scan", "SELECT id, state FROM jobs FOR UPDATE")?;
+    ///     while !cursor.fetch(1)?.is_empty() {
+    ///         cursor.execute_current_of(|current| {
+    ///             diesel::update(jobs::table)
+    ///                 .set(jobs::state.eq("done"))
+    ///                 .filter(current)
+    ///         })?;
+    ///     }
+    ///     cursor.close()
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as [`execute`](Self::execute).
+    pub fn execute_current_of<'c, F, T>(&'c mut self, statement: F) -> QueryResult<usize>
+    where
+        F: FnOnce(CurrentOf<'c>) -> T,
+        T: QueryFragment<GaussDB> + QueryId,
+    {
+        self.check_usable()?;
+        let GaussDBCursor { name, connection, .. } = self;
+        let statement = statement(CurrentOf { cursor_name: name });
+        connection.execute_returning_count(&statement)
+    }
+
+    /// Check that the cursor can still be used
+    ///
+    /// A cursor only exists in the transaction that declared it. The
+    /// cursor holds the connection, so that transaction is still open if
+    /// the connection is at the same depth; a broken transaction manager
+    /// reports depth `0`.
+    fn check_usable(&mut self) -> QueryResult<()> {
+        if self.is_closed {
+            return Err(DieselError::DatabaseError(
+                diesel::result::DatabaseErrorKind::UnableToSendCommand,
+                Box::new("Cursor has been closed".to_string())
+            ));
+        }
+        if self.transaction_depth == 0 || self.connection.transaction_depth() != self.transaction_depth {
+            return Err(DieselError::DatabaseError(
+                diesel::result::DatabaseErrorKind::Unknown,
+                Box::new(format!(
+                    "Cursor {} can only be used in the transaction that declared it",
+                    self.name
+                ))
+            ));
+        }
+        Ok(())
+    }
+
     /// Get the name of the cursor
     pub fn name(&self) -> &str {
         &self.name
@@ -257,13 +352,48 @@ impl<'conn> Drop for GaussDBCursor<'conn> {
     fn drop(&mut self) {
         if !self.is_closed {
             // Try to close the cursor, but ignore errors since we're in Drop
-            let close_sql = format!("CLOSE {}", self.name);
+            let close_sql = format!("CLOSE {}", quote_identifier(&self.name));
             let _ = self.connection.batch_execute(&close_sql);
             self.is_closed = true;
         }
     }
 }
 
+/// The `CURRENT OF cursor_name` predicate, see
+/// [`GaussDBCursor::execute_current_of`]
+///
+/// It only appears on a single table, which is what `UPDATE` and `DELETE`
+/// filter, and can't be selected.
+#[derive(Debug, Clone, Copy)]
+pub struct CurrentOf<'c> {
+    cursor_name: &'c str,
+}
+
+impl Expression for CurrentOf<'_> {
+    type SqlType = Bool;
+}
+
+impl<T: Table> AppearsOnTable<T> for CurrentOf<'_> {}
+
+impl ValidGrouping<()> for CurrentOf<'_> {
+    type IsAggregate = is_aggregate::No;
+}
+
+impl QueryId for CurrentOf<'_> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl QueryFragment<GaussDB> for CurrentOf<'_> {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        // Quoted like in `DECLARE`, so it refers to the same cursor
+        out.unsafe_to_cache_prepared();
+        out.push_sql("CURRENT OF ");
+        out.push_identifier(self.cursor_name)?;
+        Ok(())
+    }
+}
+
 /// Extension trait for GaussDBConnection to provide cursor functionality
 pub trait CursorDsl {
     /// Declare a cursor with the given name and query
@@ -287,3 +417,42 @@ impl CursorDsl for GaussDBConnection {
         GaussDBCursor::declare_query(self, name, query)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::prelude::*;
+
+    diesel::table! {
+        jobs (id) {
+            id -> Integer,
+            state -> Text,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(query: T) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_update_where_current_of_sql() {
+        let query = diesel::update(jobs::table)
+            .set(jobs::state.eq("done"))
+            .filter(CurrentOf { cursor_name: "job_scan" });
+
+        assert_eq!(
+            generate_sql(query),
+            r#"UPDATE "jobs" SET "state" = $1 WHERE CURRENT OF "job_scan""#
+        );
+    }
+
+    #[test]
+    fn test_delete_where_current_of_sql() {
+        let query = diesel::delete(jobs::table.filter(CurrentOf { cursor_name: "Job\"Scan" }));
+
+        assert_eq!(generate_sql(query), r#"DELETE FROM "jobs" WHERE CURRENT OF "Job""Scan""#);
+    }
+}
@@ -0,0 +1,515 @@
+//! `EXPLAIN ANALYZE` support for GaussDB connections
+//!
+//! [`GaussDBConnection::explain_and_run`] runs a query once under
+//! `EXPLAIN ANALYZE` and still returns its rows. The rows are captured with
+//! `CREATE TEMPORARY TABLE ... AS` inside the analyzed statement, numbered
+//! in the order the query produced them, and read back in that order
+//! through a cursor, so the plan describes the execution that produced
+//! them.
+//!
+//! [`GaussDBConnection::explain_and_run`]: crate::GaussDBConnection::explain_and_run
+//!
+//! With the `serde_json` feature, [`PlanTree`] turns the output of
+//! `EXPLAIN (FORMAT JSON)` into a tree of [`PlanNode`]s for tools that
+//! analyze plans programmatically.
+
+/// Summary of an `EXPLAIN ANALYZE` plan
+///
+/// The numbers are taken from the root node of the plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanSummary {
+    /// Estimated total cost of the plan
+    pub total_cost: f64,
+    /// Estimated number of rows returned
+    pub estimated_rows: u64,
+    /// Actual time spent in the root node, in milliseconds
+    pub actual_time_ms: f64,
+    /// Actual number of rows returned
+    pub actual_rows: u64,
+    /// Total runtime reported by the server, in milliseconds
+    pub execution_time_ms: Option<f64>,
+    /// The full text of the plan
+    pub plan: String,
+}
+
+impl PlanSummary {
+    /// Parse the text output of `EXPLAIN ANALYZE`
+    ///
+    /// Returns `None` if the plan has no node with both estimated and actual
+    /// figures, e.g. when `explain_perf_mode` is not `normal`.
+    pub fn parse(plan: &str) -> Option<Self> {
+        let root = plan
+            .lines()
+            .find(|line| line.contains("cost=") && line.contains("actual time="))?;
+
+        let total_cost = range_end(field(root, "cost=")?)?;
+        let estimated_rows = field(root, "rows=")?.parse().ok()?;
+
+        let actual = &root[root.find("(actual ")?..];
+        let actual_time_ms = range_end(field(actual, "time=")?)?;
+        let actual_rows = field(actual, "rows=")?.parse().ok()?;
+
+        // GaussDB reports "Total runtime", newer PostgreSQL "Execution Time"
+        let execution_time_ms = plan.lines().find_map(|line| {
+            let line = line.trim();
+            let value = line
+                .strip_prefix("Total runtime:")
+                .or_else(|| line.strip_prefix("Execution Time:"))?;
+            value.trim().trim_end_matches("ms").trim().parse().ok()
+        });
+
+        Some(PlanSummary {
+            total_cost,
+            estimated_rows,
+            actual_time_ms,
+            actual_rows,
+            execution_time_ms,
+            plan: plan.to_string(),
+        })
+    }
+}
+
+/// The value following `key` up to the next space or closing parenthesis
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(key)? + key.len();
+    let rest = &line[start..];
+    let end = rest.find([' ', ')']).unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// The upper bound of a `start..end` pair
+fn range_end(value: &str) -> Option<f64> {
+    value.split_once("..")?.1.parse().ok()
+}
+
+/// A plan parsed from `EXPLAIN (FORMAT JSON)` output
+///
+/// ```rust
+/// # use diesel_gaussdb::connection::explain::PlanTree;
+/// let tree = PlanTree::parse_json(r#"[{"Plan": {"Node Type": "Seq Scan",
+///     "Relation Name": "users", "Total Cost": 35.5, "Plan Rows": 2550}}]"#)?;
+/// assert_eq!(tree.root.node_type, "Seq Scan");
+/// assert_eq!(tree.root.relation_name.as_deref(), Some("users"));
+/// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+#[cfg(feature = "serde_json")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanTree {
+    /// The root node of the plan
+    pub root: PlanNode,
+    /// Time spent planning, in milliseconds, if reported
+    pub planning_time_ms: Option<f64>,
+    /// Total runtime reported by `ANALYZE`, in milliseconds
+    pub execution_time_ms: Option<f64>,
+}
+
+/// A node of a [`PlanTree`]
+///
+/// Cost estimates are missing with `COSTS OFF` and the actual figures
+/// without `ANALYZE`, or for nodes that never ran. All other properties of
+/// the node, such as `Hash Cond`, `Filter` or GaussDB's stream and
+/// distribution details, are kept in [`details`](Self::details).
+#[cfg(feature = "serde_json")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanNode {
+    /// Kind of the node, e.g. `Hash Join` or `Seq Scan`
+    pub node_type: String,
+    /// Relation scanned by the node
+    pub relation_name: Option<String>,
+    /// Alias of the scanned relation
+    pub alias: Option<String>,
+    /// Role of the node for its parent: `Outer`, `Inner`, `SubPlan`,
+    /// `InitPlan` or `Member`
+    pub parent_relationship: Option<String>,
+    /// Name of the subplan the node starts, e.g. `SubPlan 1`
+    pub subplan_name: Option<String>,
+    /// Estimated cost before the first row is returned
+    pub startup_cost: Option<f64>,
+    /// Estimated total cost
+    pub total_cost: Option<f64>,
+    /// Estimated number of rows returned
+    pub estimated_rows: Option<f64>,
+    /// Estimated average width of the rows, in bytes
+    pub estimated_width: Option<u64>,
+    /// Actual time before the first row was returned, in milliseconds
+    pub actual_startup_time_ms: Option<f64>,
+    /// Actual total time per loop, in milliseconds
+    pub actual_total_time_ms: Option<f64>,
+    /// Actual number of rows returned per loop
+    pub actual_rows: Option<f64>,
+    /// Number of times the node was executed
+    pub actual_loops: Option<u64>,
+    /// The remaining properties of the node
+    pub details: serde_json::Map<String, serde_json::Value>,
+    /// Child nodes, including subplans
+    pub children: Vec<PlanNode>,
+}
+
+#[cfg(feature = "serde_json")]
+type PlanParseError = Box<dyn std::error::Error + Send + Sync>;
+
+#[cfg(feature = "serde_json")]
+impl PlanTree {
+    /// Parse the output of `EXPLAIN (FORMAT JSON)`
+    ///
+    /// Accepts the one-element array the server returns as well as the
+    /// plan object inside it.
+    pub fn parse_json(json: &str) -> Result<Self, PlanParseError> {
+        Self::from_json_value(serde_json::from_str(json)?)
+    }
+
+    /// Build the tree from already decoded `EXPLAIN (FORMAT JSON)` output
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, PlanParseError> {
+        use serde_json::Value;
+
+        let mut explain = match value {
+            Value::Array(items) => items
+                .into_iter()
+                .next()
+                .ok_or("EXPLAIN output contains no plan")?,
+            value => value,
+        };
+        let explain = explain.as_object_mut().ok_or("EXPLAIN output is not an object")?;
+        let plan = explain.remove("Plan").ok_or("EXPLAIN output has no \"Plan\"")?;
+
+        Ok(PlanTree {
+            root: PlanNode::from_json_value(plan)?,
+            planning_time_ms: number(explain, "Planning Time"),
+            // GaussDB reports "Total Runtime", newer PostgreSQL "Execution Time"
+            execution_time_ms: number(explain, "Total Runtime").or_else(|| number(explain, "Execution Time")),
+        })
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl PlanNode {
+    /// Build a node and its children from one `"Plan"` object
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, PlanParseError> {
+        use serde_json::Value;
+
+        let mut details = match value {
+            Value::Object(details) => details,
+            _ => return Err("plan node is not an object".into()),
+        };
+        let node_type = match details.remove("Node Type") {
+            Some(Value::String(node_type)) => node_type,
+            _ => return Err("plan node has no \"Node Type\"".into()),
+        };
+        let children = match details.remove("Plans") {
+            Some(Value::Array(plans)) => plans
+                .into_iter()
+                .map(PlanNode::from_json_value)
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("\"Plans\" of a plan node is not an array".into()),
+            None => Vec::new(),
+        };
+
+        let mut take_string = |key: &str| match details.remove(key) {
+            Some(Value::String(value)) => Some(value),
+            _ => None,
+        };
+        let relation_name = take_string("Relation Name");
+        let alias = take_string("Alias");
+        let parent_relationship = take_string("Parent Relationship");
+        let subplan_name = take_string("Subplan Name");
+
+        let mut take_number = |key: &str| {
+            let value = number(&details, key);
+            if value.is_some() {
+                details.remove(key);
+            }
+            value
+        };
+        let startup_cost = take_number("Startup Cost");
+        let total_cost = take_number("Total Cost");
+        let estimated_rows = take_number("Plan Rows");
+        let estimated_width = take_number("Plan Width").map(|width| width as u64);
+        let actual_startup_time_ms = take_number("Actual Startup Time");
+        let actual_total_time_ms = take_number("Actual Total Time");
+        let actual_rows = take_number("Actual Rows");
+        let actual_loops = take_number("Actual Loops").map(|loops| loops as u64);
+
+        Ok(PlanNode {
+            node_type,
+            relation_name,
+            alias,
+            parent_relationship,
+            subplan_name,
+            startup_cost,
+            total_cost,
+            estimated_rows,
+            estimated_width,
+            actual_startup_time_ms,
+            actual_total_time_ms,
+            actual_rows,
+            actual_loops,
+            details,
+            children,
+        })
+    }
+
+    /// This node and all nodes below it, depth first
+    pub fn nodes(&self) -> Vec<&PlanNode> {
+        let mut nodes = vec![self];
+        let mut i = 0;
+        while i < nodes.len() {
+            let node = nodes[i];
+            nodes.splice(i + 1..i + 1, node.children.iter());
+            i += 1;
+        }
+        nodes
+    }
+}
+
+/// A numeric property, which some versions report as a string
+#[cfg(feature = "serde_json")]
+fn number(object: &serde_json::Map<String, serde_json::Value>, key: &str) -> Option<f64> {
+    match object.get(key)? {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(number) => number.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// The column numbering the captured rows, which keeps their order
+pub(crate) const ROW_NUMBER_COLUMN: &str = "diesel_gaussdb_row_number";
+
+/// The `EXPLAIN ANALYZE` statement capturing the rows of `query` in `table`
+///
+/// The first column of `table` numbers the rows in the order `query`
+/// returned them.
+pub(crate) fn explain_analyze_into(table: &str, query: &str) -> String {
+    format!(
+        "EXPLAIN ANALYZE CREATE TEMPORARY TABLE {} AS \
+         SELECT row_number() OVER () AS {}, q.* FROM ({}) AS q",
+        table, ROW_NUMBER_COLUMN, query
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAUSSDB_PLAN: &str = "\
+ Hash Join  (cost=1.07..2.19 rows=3 width=36) (actual time=0.061..0.068 rows=3 loops=1)
+   Hash Cond: (p.author_id = u.id)
+   ->  Seq Scan on posts p  (cost=0.00..1.05 rows=5 width=8) (actual time=0.004..0.006 rows=5 loops=1)
+   ->  Hash  (cost=1.03..1.03 rows=3 width=36) (actual time=0.019..0.019 rows=3 loops=1)
+ Total runtime: 0.412 ms";
+
+    #[test]
+    fn test_parse_root_node() {
+        let summary = PlanSummary::parse(GAUSSDB_PLAN).unwrap();
+        assert_eq!(summary.total_cost, 2.19);
+        assert_eq!(summary.estimated_rows, 3);
+        assert_eq!(summary.actual_time_ms, 0.068);
+        assert_eq!(summary.actual_rows, 3);
+        assert_eq!(summary.execution_time_ms, Some(0.412));
+        assert_eq!(summary.plan, GAUSSDB_PLAN);
+    }
+
+    #[test]
+    fn test_parse_postgres_execution_time() {
+        let plan = "\
+Seq Scan on items  (cost=0.00..35.50 rows=2550 width=4) (actual time=0.008..0.009 rows=25 loops=1)
+Planning Time: 0.030 ms
+Execution Time: 0.020 ms";
+        let summary = PlanSummary::parse(plan).unwrap();
+        assert_eq!(summary.total_cost, 35.5);
+        assert_eq!(summary.estimated_rows, 2550);
+        assert_eq!(summary.actual_rows, 25);
+        assert_eq!(summary.execution_time_ms, Some(0.02));
+    }
+
+    #[test]
+    fn test_parse_plan_without_actual_figures() {
+        let plan = "Seq Scan on items  (cost=0.00..35.50 rows=2550 width=4)";
+        assert_eq!(PlanSummary::parse(plan), None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    const GAUSSDB_JSON_PLAN: &str = r#"[
+      {
+        "Plan": {
+          "Node Type": "Hash Join",
+          "Join Type": "Inner",
+          "Startup Cost": 1.07,
+          "Total Cost": 2.19,
+          "Plan Rows": 3,
+          "Plan Width": 36,
+          "Actual Startup Time": 0.061,
+          "Actual Total Time": 0.068,
+          "Actual Rows": 3,
+          "Actual Loops": 1,
+          "Hash Cond": "(p.author_id = u.id)",
+          "Filter": "(alternatives: SubPlan 1 or hashed SubPlan 2)",
+          "Plans": [
+            {
+              "Node Type": "Seq Scan",
+              "Parent Relationship": "Outer",
+              "Relation Name": "posts",
+              "Alias": "p",
+              "Startup Cost": 0.00,
+              "Total Cost": 1.05,
+              "Plan Rows": 5,
+              "Plan Width": 8,
+              "Actual Startup Time": 0.004,
+              "Actual Total Time": 0.006,
+              "Actual Rows": 5,
+              "Actual Loops": 1
+            },
+            {
+              "Node Type": "Hash",
+              "Parent Relationship": "Inner",
+              "Startup Cost": 1.03,
+              "Total Cost": 1.03,
+              "Plan Rows": 3,
+              "Plan Width": 36,
+              "Actual Startup Time": 0.019,
+              "Actual Total Time": 0.019,
+              "Actual Rows": 3,
+              "Actual Loops": 1,
+              "Plans": [
+                {
+                  "Node Type": "Seq Scan",
+                  "Parent Relationship": "Outer",
+                  "Relation Name": "users",
+                  "Alias": "u",
+                  "Startup Cost": 0.00,
+                  "Total Cost": 1.03,
+                  "Plan Rows": 3,
+                  "Plan Width": 36,
+                  "Actual Startup Time": 0.003,
+                  "Actual Total Time": 0.004,
+                  "Actual Rows": 3,
+                  "Actual Loops": 1
+                }
+              ]
+            },
+            {
+              "Node Type": "Seq Scan",
+              "Parent Relationship": "SubPlan",
+              "Subplan Name": "SubPlan 1",
+              "Relation Name": "banned",
+              "Alias": "banned",
+              "Startup Cost": 0.00,
+              "Total Cost": 1.01,
+              "Plan Rows": 1,
+              "Plan Width": 4,
+              "Actual Loops": 0
+            }
+          ]
+        },
+        "Total Runtime": 0.412
+      }
+    ]"#;
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_parse_json_plan_tree() {
+        let tree = PlanTree::parse_json(GAUSSDB_JSON_PLAN).unwrap();
+        assert_eq!(tree.execution_time_ms, Some(0.412));
+        assert_eq!(tree.planning_time_ms, None);
+
+        let root = &tree.root;
+        assert_eq!(root.node_type, "Hash Join");
+        assert_eq!(root.relation_name, None);
+        assert_eq!(root.startup_cost, Some(1.07));
+        assert_eq!(root.total_cost, Some(2.19));
+        assert_eq!(root.estimated_rows, Some(3.0));
+        assert_eq!(root.estimated_width, Some(36));
+        assert_eq!(root.actual_total_time_ms, Some(0.068));
+        assert_eq!(root.actual_rows, Some(3.0));
+        assert_eq!(root.actual_loops, Some(1));
+        assert_eq!(root.details["Hash Cond"], "(p.author_id = u.id)");
+        assert_eq!(root.details["Join Type"], "Inner");
+        assert!(!root.details.contains_key("Plans"));
+        assert!(!root.details.contains_key("Total Cost"));
+        assert_eq!(root.children.len(), 3);
+
+        let posts = &root.children[0];
+        assert_eq!(posts.relation_name.as_deref(), Some("posts"));
+        assert_eq!(posts.alias.as_deref(), Some("p"));
+        assert_eq!(posts.parent_relationship.as_deref(), Some("Outer"));
+        assert!(posts.children.is_empty());
+
+        let users = &root.children[1].children[0];
+        assert_eq!(users.relation_name.as_deref(), Some("users"));
+
+        // A subplan that never ran has no actual time or rows
+        let subplan = &root.children[2];
+        assert_eq!(subplan.parent_relationship.as_deref(), Some("SubPlan"));
+        assert_eq!(subplan.subplan_name.as_deref(), Some("SubPlan 1"));
+        assert_eq!(subplan.actual_loops, Some(0));
+        assert_eq!(subplan.actual_rows, None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_plan_nodes_depth_first() {
+        let tree = PlanTree::parse_json(GAUSSDB_JSON_PLAN).unwrap();
+        let nodes: Vec<_> = tree
+            .root
+            .nodes()
+            .into_iter()
+            .map(|node| (node.node_type.as_str(), node.relation_name.as_deref()))
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                ("Hash Join", None),
+                ("Seq Scan", Some("posts")),
+                ("Hash", None),
+                ("Seq Scan", Some("users")),
+                ("Seq Scan", Some("banned")),
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_parse_json_plan_variations() {
+        // A bare plan object with GaussDB stream nodes, costs off and
+        // numbers reported as strings
+        let tree = PlanTree::parse_json(
+            r#"{
+                "Plan": {
+                    "Node Type": "Streaming (type: GATHER)",
+                    "Spawn on": "All datanodes",
+                    "Plans": [
+                        {"Node Type": "Seq Scan", "Relation Name": "t", "Plan Rows": "42"}
+                    ]
+                },
+                "Planning Time": 0.1,
+                "Execution Time": 0.5
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(tree.root.node_type, "Streaming (type: GATHER)");
+        assert_eq!(tree.root.total_cost, None);
+        assert_eq!(tree.root.details["Spawn on"], "All datanodes");
+        assert_eq!(tree.root.children[0].estimated_rows, Some(42.0));
+        assert_eq!(tree.planning_time_ms, Some(0.1));
+        assert_eq!(tree.execution_time_ms, Some(0.5));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_parse_invalid_json_plans() {
+        assert!(PlanTree::parse_json("not json").is_err());
+        assert!(PlanTree::parse_json("[]").is_err());
+        assert!(PlanTree::parse_json(r#"[{"Total Runtime": 1.0}]"#).is_err());
+        assert!(PlanTree::parse_json(r#"[{"Plan": {"Total Cost": 1.0}}]"#).is_err());
+        assert!(PlanTree::parse_json(r#"[{"Plan": {"Node Type": "Result", "Plans": {}}}]"#).is_err());
+    }
+
+    #[test]
+    fn test_explain_analyze_into() {
+        assert_eq!(
+            explain_analyze_into("t1", "SELECT 1 AS one"),
+            "EXPLAIN ANALYZE CREATE TEMPORARY TABLE t1 AS \
+             SELECT row_number() OVER () AS diesel_gaussdb_row_number, q.* FROM (SELECT 1 AS one) AS q"
+        );
+    }
+}
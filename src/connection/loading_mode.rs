@@ -8,7 +8,8 @@ use crate::backend::GaussDB;
 use crate::connection::{GaussDBConnection, row::GaussDBRow};
 use diesel::result::{QueryResult, Error as DieselError};
 use diesel::query_builder::{QueryFragment, QueryId, QueryBuilder};
-use diesel::connection::SimpleConnection;
+use diesel::connection::{AnsiTransactionManager, SimpleConnection, TransactionManager};
+use std::collections::VecDeque;
 use std::marker::PhantomData;
 
 /// Trait for different loading modes
@@ -123,14 +124,29 @@ pub struct GaussDBRowByRowLoadingMode<ST> {
     _phantom: PhantomData<ST>,
 }
 
+/// Number of rows a [`GaussDBRowIterator`] fetches per round trip by default
+pub const DEFAULT_FETCH_SIZE: usize = 1000;
+
 /// Iterator for row-by-row loading
 ///
 /// This iterator allows processing query results one row at a time,
-/// which is memory-efficient for large datasets.
+/// which is memory-efficient for large datasets. The rows are read from a
+/// server-side cursor in batches of [`with_fetch_size`](Self::with_fetch_size)
+/// rows, by default the size set with [`LoadingModeDsl::with_fetch_size`] on
+/// the connection, so a larger size saves round trips while a smaller one
+/// holds fewer rows in memory.
+///
+/// A cursor only exists inside a transaction. If none is open, the
+/// iterator begins one and commits it when dropped; otherwise the cursor
+/// lives in the caller's transaction, which is left untouched.
 pub struct GaussDBRowIterator<'conn> {
     connection: &'conn mut GaussDBConnection,
     cursor_name: String,
-    is_finished: bool,
+    fetch_size: usize,
+    buffer: VecDeque<GaussDBRow<'static>>,
+    fetch_count: usize,
+    is_exhausted: bool,
+    owns_transaction: bool,
 }
 
 impl<'conn> GaussDBRowIterator<'conn> {
@@ -138,46 +154,95 @@ impl<'conn> GaussDBRowIterator<'conn> {
     fn new(connection: &'conn mut GaussDBConnection, sql: &str) -> QueryResult<Self> {
         // Generate a unique cursor name
         let cursor_name = format!("row_iterator_{}", std::ptr::addr_of!(*connection) as usize);
-        
+
+        let owns_transaction = !connection.is_in_transaction();
+        if owns_transaction {
+            AnsiTransactionManager::begin_transaction(connection)?;
+        }
+
         // Declare a cursor for the query
         let declare_sql = format!("DECLARE {} CURSOR FOR {}", cursor_name, sql);
-        connection.batch_execute(&declare_sql)?;
-        
+        if let Err(e) = connection.batch_execute(&declare_sql) {
+            if owns_transaction {
+                let _ = AnsiTransactionManager::rollback_transaction(connection);
+            }
+            return Err(e);
+        }
+
         Ok(GaussDBRowIterator {
+            fetch_size: connection.fetch_size,
             connection,
             cursor_name,
-            is_finished: false,
+            buffer: VecDeque::new(),
+            fetch_count: 0,
+            is_exhausted: false,
+            owns_transaction,
         })
     }
 
+    /// Fetch up to `fetch_size` rows per round trip
+    ///
+    /// Applies to all following fetches. A size of 0 is treated as 1.
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::connection::loading_mode::LoadingModeDsl;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// let mut rows = conn
+    ///     .create_sql_row_iterator("SELECT * FROM large_table")?
+    ///     .with_fetch_size(200);
+    /// while let Some(row) = rows.next()? {
+    ///     println!("Processing row: {:?}", row);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_fetch_size(mut self, fetch_size: usize) -> Self {
+        self.fetch_size = fetch_size.max(1);
+        self
+    }
+
+    /// The number of rows fetched per round trip
+    pub fn fetch_size(&self) -> usize {
+        self.fetch_size
+    }
+
+    /// The number of `FETCH` statements sent so far
+    pub fn fetch_count(&self) -> usize {
+        self.fetch_count
+    }
+
     /// Get the next row from the iterator
     pub fn next(&mut self) -> QueryResult<Option<GaussDBRow<'static>>> {
-        if self.is_finished {
-            return Ok(None);
+        if self.buffer.is_empty() && !self.is_exhausted {
+            self.fetch_batch()?;
         }
+        Ok(self.buffer.pop_front())
+    }
 
-        let fetch_sql = format!("FETCH 1 FROM {}", self.cursor_name);
-        
-        {
-            let empty_params: Vec<&(dyn gaussdb::types::ToSql + Sync)> = vec![];
-            let rows = self.connection.raw_connection().query(&fetch_sql, &empty_params)
-                .map_err(|e| DieselError::DatabaseError(
-                    diesel::result::DatabaseErrorKind::UnableToSendCommand,
-                    Box::new(format!("GaussDB cursor fetch error: {}", e))
-                ))?;
+    fn fetch_batch(&mut self) -> QueryResult<()> {
+        let fetch_sql = format!("FETCH {} FROM {}", self.fetch_size, self.cursor_name);
 
-            if rows.is_empty() {
-                self.is_finished = true;
-                Ok(None)
-            } else {
-                Ok(Some(GaussDBRow::new_owned(rows.into_iter().next().unwrap())))
-            }
+        let empty_params: Vec<&(dyn gaussdb::types::ToSql + Sync)> = vec![];
+        let rows = self.connection.raw_connection().query(&fetch_sql, &empty_params)
+            .map_err(|e| DieselError::DatabaseError(
+                diesel::result::DatabaseErrorKind::UnableToSendCommand,
+                Box::new(format!("GaussDB cursor fetch error: {}", e))
+            ))?;
+        self.fetch_count += 1;
+
+        // A short batch is the last one, which saves a final empty fetch
+        if rows.len() < self.fetch_size {
+            self.is_exhausted = true;
         }
+        self.buffer.extend(rows.into_iter().map(GaussDBRow::new_owned));
+        Ok(())
     }
 
     /// Check if the iterator has finished
     pub fn is_finished(&self) -> bool {
-        self.is_finished
+        self.is_exhausted && self.buffer.is_empty()
     }
 }
 
@@ -186,6 +251,12 @@ impl<'conn> Drop for GaussDBRowIterator<'conn> {
         // Close the cursor when the iterator is dropped
         let close_sql = format!("CLOSE {}", self.cursor_name);
         let _ = self.connection.batch_execute(&close_sql);
+
+        if self.owns_transaction
+            && AnsiTransactionManager::commit_transaction(self.connection).is_err()
+        {
+            let _ = AnsiTransactionManager::rollback_transaction(self.connection);
+        }
     }
 }
 
@@ -250,6 +321,29 @@ pub trait LoadingModeDsl {
 
     /// Create a row iterator from SQL string for processing large result sets
     fn create_sql_row_iterator(&mut self, sql: &str) -> QueryResult<GaussDBRowIterator<'_>>;
+
+    /// Fetch up to `fetch_size` rows per round trip in row iterators
+    ///
+    /// Applies to the iterators created afterwards on this connection; a
+    /// single iterator can still change it with
+    /// [`GaussDBRowIterator::with_fetch_size`]. A size of 0 is treated as 1.
+    /// Defaults to [`DEFAULT_FETCH_SIZE`].
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::connection::loading_mode::LoadingModeDsl;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// let mut rows = conn
+    ///     .with_fetch_size(200)
+    ///     .create_sql_row_iterator("SELECT * FROM large_table")?;
+    /// while let Some(row) = rows.next()? {
+    ///     println!("Processing row: {:?}", row);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn with_fetch_size(&mut self, fetch_size: usize) -> &mut Self;
 }
 
 impl LoadingModeDsl for GaussDBConnection {
@@ -307,4 +401,9 @@ impl LoadingModeDsl for GaussDBConnection {
     fn create_sql_row_iterator(&mut self, sql: &str) -> QueryResult<GaussDBRowIterator<'_>> {
         GaussDBRowIterator::new(self, sql)
     }
+
+    fn with_fetch_size(&mut self, fetch_size: usize) -> &mut Self {
+        self.fetch_size = fetch_size.max(1);
+        self
+    }
 }
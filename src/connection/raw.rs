@@ -21,7 +21,7 @@ impl RawConnection {
         use gaussdb::{Config, NoTls};
         use std::str::FromStr;
 
-        let config = Config::from_str(database_url)
+        let config = Config::from_str(&super::dsn::normalize(database_url)?)
             .map_err(|e| diesel::ConnectionError::CouldntSetupConfiguration(DieselError::DatabaseError(
                 DatabaseErrorKind::UnableToSendCommand,
                 Box::new(format!("Invalid database URL: {}", e))
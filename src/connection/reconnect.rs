@@ -0,0 +1,253 @@
+//! Automatic reconnection support for GaussDB connections
+//!
+//! When the server drops a connection, every following statement fails until
+//! the connection is re-established. With an opt-in [`ReconnectPolicy`] the
+//! connection re-establishes itself using the original URL and retries the
+//! failed statement once. Statements are never retried inside an open
+//! transaction, because the transaction state is lost together with the
+//! connection; a `ClosedConnection` error is returned instead.
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError, QueryResult};
+
+/// Policy controlling what happens when the connection to the server is lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReconnectPolicy {
+    /// Never reconnect; connection errors are returned as-is (default)
+    #[default]
+    Disabled,
+    /// Re-establish the connection and retry the failed statement once,
+    /// but only when no transaction is open
+    RetryOnce,
+}
+
+impl ReconnectPolicy {
+    /// Whether this policy ever reconnects
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, ReconnectPolicy::Disabled)
+    }
+}
+
+/// A failed statement, tagged with whether the connection itself was lost
+#[derive(Debug)]
+pub(crate) struct StatementFailure {
+    pub(crate) connection_lost: bool,
+    pub(crate) error: DieselError,
+}
+
+/// Client-side driver errors after which the protocol state is unknown
+///
+/// These are matched against the start of the driver's error message,
+/// which is the only part of the error kind it exposes.
+const FATAL_CLIENT_ERRORS: &[&str] = &[
+    "error communicating with the server",
+    "unexpected message from server",
+    "error parsing response from server",
+    "connection closed",
+];
+
+/// Whether a failed statement left the session unusable
+///
+/// `closed` is whether the driver reports the connection as closed,
+/// `severity` is the severity of the server error, if the server sent one,
+/// and `message` is the driver's description of the error. The server ends
+/// the session after `FATAL` and `PANIC` errors; any other server error,
+/// including a cancelled or timed out statement, only aborts the statement.
+pub(crate) fn is_fatal_failure(closed: bool, severity: Option<&str>, message: &str) -> bool {
+    if closed {
+        return true;
+    }
+    match severity {
+        Some(severity) => matches!(severity, "FATAL" | "PANIC"),
+        None => FATAL_CLIENT_ERRORS.iter().any(|prefix| message.starts_with(prefix)),
+    }
+}
+
+/// Run `op`, reconnecting and retrying once if the policy allows it
+///
+/// `in_transaction` must be `true` whenever a transaction is open on the
+/// connection; in that case a lost connection is reported as a
+/// `ClosedConnection` error and neither `reconnect` nor a retry happen.
+pub(crate) fn run_with_reconnect<C, T, Op, Re>(
+    target: &mut C,
+    policy: ReconnectPolicy,
+    in_transaction: bool,
+    mut op: Op,
+    reconnect: Re,
+) -> QueryResult<T>
+where
+    Op: FnMut(&mut C) -> Result<T, StatementFailure>,
+    Re: FnOnce(&mut C) -> QueryResult<()>,
+{
+    let failure = match op(target) {
+        Ok(value) => return Ok(value),
+        Err(failure) => failure,
+    };
+
+    if !failure.connection_lost || !policy.is_enabled() {
+        return Err(failure.error);
+    }
+
+    if in_transaction {
+        return Err(DieselError::DatabaseError(
+            DatabaseErrorKind::ClosedConnection,
+            Box::new(format!(
+                "Connection to GaussDB was lost inside an open transaction; \
+                 the statement was not retried because the transaction state is gone ({})",
+                failure.error
+            )),
+        ));
+    }
+
+    reconnect(target)?;
+    op(target).map_err(|failure| failure.error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A stand-in for a connection whose server side can be dropped
+    struct FakeConnection {
+        alive: bool,
+        reconnects: u32,
+        executions: u32,
+    }
+
+    impl FakeConnection {
+        fn dropped() -> Self {
+            FakeConnection { alive: false, reconnects: 0, executions: 0 }
+        }
+
+        fn execute(&mut self) -> Result<usize, StatementFailure> {
+            self.executions += 1;
+            if self.alive {
+                Ok(1)
+            } else {
+                Err(StatementFailure {
+                    connection_lost: true,
+                    error: DieselError::DatabaseError(
+                        DatabaseErrorKind::UnableToSendCommand,
+                        Box::new("GaussDB execute error: connection closed".to_string()),
+                    ),
+                })
+            }
+        }
+
+        fn reconnect(&mut self) -> QueryResult<()> {
+            self.reconnects += 1;
+            self.alive = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_reconnect_and_retry_once_outside_transaction() {
+        let mut conn = FakeConnection::dropped();
+        let result = run_with_reconnect(
+            &mut conn,
+            ReconnectPolicy::RetryOnce,
+            false,
+            FakeConnection::execute,
+            FakeConnection::reconnect,
+        );
+
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(conn.reconnects, 1);
+        assert_eq!(conn.executions, 2);
+    }
+
+    #[test]
+    fn test_no_retry_inside_transaction() {
+        let mut conn = FakeConnection::dropped();
+        let result = run_with_reconnect(
+            &mut conn,
+            ReconnectPolicy::RetryOnce,
+            true,
+            FakeConnection::execute,
+            FakeConnection::reconnect,
+        );
+
+        match result {
+            Err(DieselError::DatabaseError(DatabaseErrorKind::ClosedConnection, info)) => {
+                assert!(info.message().contains("inside an open transaction"));
+            }
+            other => panic!("expected ClosedConnection error, got {:?}", other),
+        }
+        assert_eq!(conn.reconnects, 0);
+        assert_eq!(conn.executions, 1);
+    }
+
+    #[test]
+    fn test_disabled_policy_returns_original_error() {
+        let mut conn = FakeConnection::dropped();
+        let result = run_with_reconnect(
+            &mut conn,
+            ReconnectPolicy::default(),
+            false,
+            FakeConnection::execute,
+            FakeConnection::reconnect,
+        );
+
+        assert!(matches!(
+            result,
+            Err(DieselError::DatabaseError(DatabaseErrorKind::UnableToSendCommand, _))
+        ));
+        assert_eq!(conn.reconnects, 0);
+    }
+
+    #[test]
+    fn test_fatal_failures() {
+        assert!(is_fatal_failure(true, None, "connection closed"));
+        assert!(is_fatal_failure(
+            false,
+            Some("FATAL"),
+            "db error: FATAL: terminating connection due to administrator command"
+        ));
+        assert!(is_fatal_failure(false, Some("PANIC"), "db error: PANIC: out of shared memory"));
+        assert!(is_fatal_failure(false, None, "unexpected message from server"));
+        assert!(is_fatal_failure(
+            false,
+            None,
+            "error communicating with the server: Broken pipe (os error 32)"
+        ));
+    }
+
+    #[test]
+    fn test_recoverable_failures() {
+        // statement_timeout and pg_cancel_backend both end in query_canceled
+        assert!(!is_fatal_failure(
+            false,
+            Some("ERROR"),
+            "db error: ERROR: canceling statement due to statement timeout"
+        ));
+        assert!(!is_fatal_failure(
+            false,
+            Some("ERROR"),
+            "db error: ERROR: canceling statement due to user request"
+        ));
+        assert!(!is_fatal_failure(false, None, "error serializing parameter 0"));
+        assert!(!is_fatal_failure(false, None, "query returned an unexpected number of rows"));
+    }
+
+    #[test]
+    fn test_non_fatal_errors_are_not_retried() {
+        let mut conn = FakeConnection::dropped();
+        let result: QueryResult<usize> = run_with_reconnect(
+            &mut conn,
+            ReconnectPolicy::RetryOnce,
+            false,
+            |conn| {
+                conn.executions += 1;
+                Err(StatementFailure {
+                    connection_lost: false,
+                    error: DieselError::NotFound,
+                })
+            },
+            FakeConnection::reconnect,
+        );
+
+        assert!(matches!(result, Err(DieselError::NotFound)));
+        assert_eq!(conn.reconnects, 0);
+        assert_eq!(conn.executions, 1);
+    }
+}
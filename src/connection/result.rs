@@ -119,6 +119,69 @@ impl<'a> ExactSizeIterator for GaussDBResultIterator<'a> {
     }
 }
 
+/// Rows of a query streamed from the server
+///
+/// This is the cursor returned by `LoadConnection::load`, so
+/// `RunQueryDsl::load_iter` yields rows as they arrive instead of collecting
+/// the whole result first. The stream borrows the connection until it is
+/// dropped. A failure while fetching is yielded as an `Err` item and ends
+/// the stream.
+#[cfg(feature = "gaussdb")]
+pub struct GaussDBRowStream<'conn> {
+    rows: gaussdb::RowIter<'conn>,
+    /// Where the connection keeps the SQLSTATE of its last failed statement
+    last_error_code: &'conn mut Option<gaussdb::error::SqlState>,
+    is_finished: bool,
+}
+
+#[cfg(feature = "gaussdb")]
+impl<'conn> GaussDBRowStream<'conn> {
+    pub(crate) fn new(
+        rows: gaussdb::RowIter<'conn>,
+        last_error_code: &'conn mut Option<gaussdb::error::SqlState>,
+    ) -> Self {
+        GaussDBRowStream {
+            rows,
+            last_error_code,
+            is_finished: false,
+        }
+    }
+}
+
+#[cfg(feature = "gaussdb")]
+impl<'conn> Iterator for GaussDBRowStream<'conn> {
+    type Item = QueryResult<GaussDBRow<'conn>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use fallible_iterator::FallibleIterator;
+
+        if self.is_finished {
+            return None;
+        }
+        match self.rows.next() {
+            Ok(Some(row)) => Some(Ok(GaussDBRow::new_owned(row))),
+            Ok(None) => {
+                self.is_finished = true;
+                None
+            }
+            Err(error) => {
+                self.is_finished = true;
+                *self.last_error_code = error.code().cloned();
+                Some(Err(convert_gaussdb_error(error)))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "gaussdb")]
+impl<'conn> fmt::Debug for GaussDBRowStream<'conn> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GaussDBRowStream")
+            .field("is_finished", &self.is_finished)
+            .finish()
+    }
+}
+
 /// Error information for GaussDB database errors
 #[derive(Debug)]
 pub struct GaussDBErrorInformation {
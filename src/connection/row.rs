@@ -6,7 +6,9 @@
 use crate::backend::GaussDB;
 use crate::value::{GaussDBValue, TypeOidLookup};
 use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSqlRow, StaticallySizedRow};
 use diesel::row::*;
+use std::error::Error;
 use std::fmt;
 
 /// A row from a GaussDB query result
@@ -64,48 +66,144 @@ impl<'a> GaussDBRow<'a> {
         }
     }
 
+    /// Decode this row as `T`, e.g. a tuple, using the SQL types `ST`
+    ///
+    /// Fails with [`ColumnCountMismatch`] instead of decoding if the row
+    /// does not have exactly as many columns as `T` reads.
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::connection::loading_mode::LoadingModeDsl;
+    /// # use diesel::sql_types::{Integer, Text};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// for row in conn.load_sql_with_default("SELECT id, name FROM users")? {
+    ///     let (id, name) = row.decode::<(Integer, Text), (i32, String)>()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode<ST, T>(&self) -> deserialize::Result<T>
+    where
+        T: FromSqlRow<ST, GaussDB> + StaticallySizedRow<ST, GaussDB>,
+    {
+        check_column_count(T::FIELD_COUNT, self.len())?;
+        T::build_from_row(self)
+    }
+
+    /// Borrow the text value of the column at `index` from this row
+    ///
+    /// Returns `Ok(None)` for `NULL` and an error if the column does not
+    /// exist or isn't valid UTF-8. Unlike loading a `String`, nothing is
+    /// copied; the `&str` can be used as long as the row is alive.
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::connection::loading_mode::LoadingModeDsl;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// let mut total_len = 0;
+    /// for row in conn.load_sql_with_default("SELECT name FROM users")? {
+    ///     total_len += row.get_str(0)?.map_or(0, str::len);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_str(&self, index: usize) -> deserialize::Result<Option<&str>> {
+        if index >= self.len() {
+            return Err(format!("column index {} is out of range for a row of {} columns", index, self.len()).into());
+        }
+        self.get_raw_value(index).map(|value| value.as_str()).transpose()
+    }
+
     /// Get a field by name
     pub fn get_field_by_name(&self, name: &str) -> Option<GaussDBField<'_>> {
         self.find_column_index(name)
             .and_then(|idx| self.get_field(idx))
     }
 
-    /// Find the index of a column by name
-    fn find_column_index(&self, _name: &str) -> Option<usize> {
-        {
-            let _row = match &self.inner {
-                GaussDBRowInner::Borrowed(row) => row,
-                GaussDBRowInner::Owned(row) => row,
-            };
-            
-            // gaussdb crate doesn't expose column names directly
-            // We'll need to implement this based on the actual API
-            // For now, return None as a placeholder
-            None
+    fn inner_row(&self) -> &gaussdb::Row {
+        match &self.inner {
+            GaussDBRowInner::Borrowed(row) => row,
+            GaussDBRowInner::Owned(row) => row,
         }
     }
 
+    /// Find the index of a column by name
+    fn find_column_index(&self, name: &str) -> Option<usize> {
+        self.inner_row()
+            .columns()
+            .iter()
+            .position(|column| column.name() == name)
+    }
+
     /// Get the column name at the given index
-    fn column_name(&self, _index: usize) -> Option<&str> {
-        {
-            // gaussdb crate doesn't expose column names directly
-            // This would need to be implemented based on the actual API
-            None
-        }
+    fn column_name(&self, index: usize) -> Option<&str> {
+        self.inner_row().columns().get(index).map(|column| column.name())
     }
 
     /// Get the raw value at the given index
-    fn get_raw_value(&self, _index: usize) -> Option<GaussDBValue<'_>> {
-        {
-            let _row = match &self.inner {
-                GaussDBRowInner::Borrowed(row) => row,
-                GaussDBRowInner::Owned(row) => row,
-            };
-            
-            // This would need to be implemented based on the gaussdb crate API
-            // For now, return a placeholder
-            Some(GaussDBValue::new(None, 0))
-        }
+    ///
+    /// Returns `None` for SQL `NULL`, as expected by Diesel's `Field::value`.
+    fn get_raw_value(&self, index: usize) -> Option<GaussDBValue<'_>> {
+        let row = self.inner_row();
+        let type_oid = row.columns().get(index)?.type_().oid();
+        let RawField(bytes) = row.try_get(index).ok()?;
+        bytes.map(|bytes| GaussDBValue::new(Some(bytes), type_oid))
+    }
+}
+
+/// The undecoded binary representation of a column value
+struct RawField<'a>(Option<&'a [u8]>);
+
+impl<'a> gaussdb::types::FromSql<'a> for RawField<'a> {
+    fn from_sql(
+        _ty: &gaussdb::types::Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(RawField(Some(raw)))
+    }
+
+    fn from_sql_null(
+        _ty: &gaussdb::types::Type,
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(RawField(None))
+    }
+
+    // Decoding happens later through Diesel's `FromSql`
+    fn accepts(_ty: &gaussdb::types::Type) -> bool {
+        true
+    }
+}
+
+/// Error returned when a row has a different number of columns than the
+/// type it is decoded into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnCountMismatch {
+    /// The number of columns the target type reads
+    pub expected: usize,
+    /// The number of columns of the result
+    pub actual: usize,
+}
+
+impl fmt::Display for ColumnCountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "column count mismatch: the result type expects {} columns, but the query returned {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for ColumnCountMismatch {}
+
+/// Fail with [`ColumnCountMismatch`] unless `actual` equals `expected`
+pub(crate) fn check_column_count(expected: usize, actual: usize) -> deserialize::Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(Box::new(ColumnCountMismatch { expected, actual }))
     }
 }
 
@@ -231,5 +329,24 @@ impl TypeOidLookup for GaussDBField<'_> {
 
 #[cfg(test)]
 mod tests {
-    // Tests will be added when row functionality is fully implemented
+    use super::*;
+
+    #[test]
+    fn test_matching_column_count_is_accepted() {
+        assert!(check_column_count(2, 2).is_ok());
+    }
+
+    #[test]
+    fn test_column_count_mismatch_names_both_counts() {
+        let error = check_column_count(2, 3).unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<ColumnCountMismatch>(),
+            Some(&ColumnCountMismatch { expected: 2, actual: 3 })
+        );
+        assert_eq!(
+            error.to_string(),
+            "column count mismatch: the result type expects 2 columns, but the query returned 3"
+        );
+    }
 }
@@ -0,0 +1,284 @@
+//! Multi-statement scripts with per-statement results
+//!
+//! [`GaussDBConnection::run_script`] splits a script into its statements
+//! and runs them one after another with the simple query protocol. Unlike
+//! `batch_execute`, it keeps what each statement returned and reports the
+//! position of a failing statement.
+//!
+//! [`GaussDBConnection::run_script`]: crate::GaussDBConnection::run_script
+
+use diesel::result::Error;
+use std::fmt;
+
+/// The outcome of one statement of a script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptResult {
+    /// Position of the statement in the script, starting at 0
+    pub index: usize,
+    /// The statement as sent to the server
+    pub statement: String,
+    /// Number of rows the server reported for the statement
+    ///
+    /// This is the number of inserted, updated, deleted or returned rows,
+    /// and `0` for DDL and other statements without a row count.
+    pub rows_affected: u64,
+    /// The returned rows in text format, `None` for SQL `NULL`
+    ///
+    /// Empty for statements that don't return rows.
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+impl ScriptResult {
+    /// The first column of the first returned row
+    ///
+    /// Returns `None` if the statement returned no rows or the value is
+    /// `NULL`.
+    pub fn scalar(&self) -> Option<&str> {
+        self.rows.first()?.first()?.as_deref()
+    }
+}
+
+#[cfg(feature = "gaussdb")]
+impl ScriptResult {
+    /// Collect the messages the server sent for one statement
+    pub(crate) fn from_messages(
+        index: usize,
+        statement: &str,
+        messages: Vec<gaussdb::SimpleQueryMessage>,
+    ) -> Self {
+        let mut result = ScriptResult {
+            index,
+            statement: statement.to_string(),
+            rows_affected: 0,
+            rows: Vec::new(),
+        };
+        for message in messages {
+            match message {
+                gaussdb::SimpleQueryMessage::Row(row) => result
+                    .rows
+                    .push((0..row.len()).map(|i| row.get(i).map(str::to_string)).collect()),
+                gaussdb::SimpleQueryMessage::CommandComplete(rows) => result.rows_affected = rows,
+                _ => {}
+            }
+        }
+        result
+    }
+}
+
+/// The error returned by [`GaussDBConnection::run_script`]
+///
+/// Statements before the failing one were executed; unless the script
+/// manages its own transaction their changes are committed.
+///
+/// [`GaussDBConnection::run_script`]: crate::GaussDBConnection::run_script
+#[derive(Debug)]
+pub struct ScriptError {
+    /// Position of the failing statement in the script, starting at 0
+    pub statement_index: usize,
+    /// The failing statement
+    pub statement: String,
+    /// Results of the statements that ran before the failing one
+    pub completed: Vec<ScriptResult>,
+    /// The error reported for the failing statement
+    pub error: Error,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "statement {} of the script failed: {}",
+            self.statement_index, self.error
+        )
+    }
+}
+
+impl std::error::Error for ScriptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Split a script into statements at top-level semicolons
+///
+/// Semicolons inside quoted strings and identifiers, dollar-quoted bodies
+/// and comments don't end a statement. Statements consisting only of
+/// whitespace and comments are dropped.
+pub(crate) fn split_statements(script: &str) -> Vec<&str> {
+    let bytes = script.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_content = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b';' => {
+                if has_content {
+                    statements.push(script[start..i].trim());
+                }
+                start = i + 1;
+                has_content = false;
+                i += 1;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = script[i..].find('\n').map_or(bytes.len(), |end| i + end + 1);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_block_comment(bytes, i);
+            }
+            b'\'' => {
+                let escapes = i > 0 && matches!(bytes[i - 1], b'E' | b'e') && !is_word_byte(bytes, i - 1);
+                i = skip_quoted(bytes, i, b'\'', escapes);
+                has_content = true;
+            }
+            b'"' => {
+                i = skip_quoted(bytes, i, b'"', false);
+                has_content = true;
+            }
+            b'$' => {
+                i = match dollar_tag(script, i) {
+                    Some(tag) => script[i + tag.len()..]
+                        .find(tag)
+                        .map_or(bytes.len(), |end| i + tag.len() + end + tag.len()),
+                    None => i + 1,
+                };
+                has_content = true;
+            }
+            byte => {
+                has_content |= !byte.is_ascii_whitespace();
+                i += 1;
+            }
+        }
+    }
+
+    if has_content {
+        statements.push(script[start..].trim());
+    }
+    statements
+}
+
+/// Whether the byte before `i` continues an identifier, so that `bytes[i]`
+/// is not the start of a token
+fn is_word_byte(bytes: &[u8], i: usize) -> bool {
+    i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')
+}
+
+/// The index after the quoted section starting at `start`
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if backslash_escapes && bytes[i] == b'\\' {
+            i += 2;
+        } else if bytes[i] == quote {
+            // A doubled quote is an escaped quote
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// The index after the block comment starting at `start`; comments nest
+fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            depth += 1;
+            i += 2;
+        } else if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// The dollar-quote tag (`$$` or `$name$`) starting at `start`, if any
+///
+/// `$1` style parameters and `$` inside identifiers are not tags.
+fn dollar_tag(script: &str, start: usize) -> Option<&str> {
+    let bytes = script.as_bytes();
+    if is_word_byte(bytes, start) {
+        return None;
+    }
+    let name_len = bytes[start + 1..]
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+        .count();
+    let end = start + 1 + name_len;
+    if bytes.get(end) != Some(&b'$') || bytes.get(start + 1).is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+    Some(&script[start..=end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_simple_script() {
+        let script = "CREATE TABLE t (id INT);\n INSERT INTO t VALUES (1);SELECT COUNT(*) FROM t;\n";
+        assert_eq!(
+            split_statements(script),
+            vec!["CREATE TABLE t (id INT)", "INSERT INTO t VALUES (1)", "SELECT COUNT(*) FROM t"]
+        );
+    }
+
+    #[test]
+    fn test_split_ignores_quoted_semicolons() {
+        let script = r#"INSERT INTO t VALUES ('a;b', E'it\'s;', 'x'';y'); SELECT "odd;name" FROM t"#;
+        assert_eq!(
+            split_statements(script),
+            vec![
+                r#"INSERT INTO t VALUES ('a;b', E'it\'s;', 'x'';y')"#,
+                r#"SELECT "odd;name" FROM t"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_dollar_quoted_body() {
+        let script = "CREATE FUNCTION f() RETURNS INT AS $body$ BEGIN RETURN 1; END; $body$ LANGUAGE plpgsql;\
+                      SELECT $$;$$, $1";
+        assert_eq!(
+            split_statements(script),
+            vec![
+                "CREATE FUNCTION f() RETURNS INT AS $body$ BEGIN RETURN 1; END; $body$ LANGUAGE plpgsql",
+                "SELECT $$;$$, $1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_drops_comments_only_statements() {
+        let script = "-- setup; still a comment\nSELECT 1; /* a /* nested */ ; comment */ ; ;\n-- trailing";
+        assert_eq!(
+            split_statements(script),
+            vec!["-- setup; still a comment\nSELECT 1"]
+        );
+    }
+
+    #[test]
+    fn test_scalar() {
+        let result = ScriptResult {
+            index: 0,
+            statement: "SELECT COUNT(*) FROM t".to_string(),
+            rows_affected: 1,
+            rows: vec![vec![Some("3".to_string())]],
+        };
+        assert_eq!(result.scalar(), Some("3"));
+    }
+}
@@ -0,0 +1,322 @@
+//! Session settings for GaussDB connections
+//!
+//! Run-time parameters such as `application_name` are changed with the
+//! `set_config()` SQL function, the function form of `SET`. The name and
+//! value are sent as bind parameters, so they never need to be quoted.
+//!
+//! `SET ROLE` is a statement of its own, see [`SetRole`], and so is
+//! `SET client_encoding`, see [`SetClientEncoding`]. Settings scoped to a
+//! single transaction are collected in [`LocalSettings`].
+
+use crate::backend::GaussDB;
+use crate::connection::GaussDBConnection;
+use crate::query_builder::quote_literal;
+use diesel::connection::SimpleConnection;
+use diesel::define_sql_function;
+use diesel::prelude::*;
+use diesel::query_builder::{AstPass, QueryBuilder, QueryFragment};
+use diesel::result::{Error, QueryResult};
+use diesel::sql_types::{Bool, Text};
+
+define_sql_function! {
+    /// The `set_config(setting_name, new_value, is_local)` SQL function
+    fn set_config(setting_name: Text, new_value: Text, is_local: Bool) -> Text;
+}
+
+/// Set the run-time parameter `key` to `value`
+///
+/// With `is_local` the setting behaves like `SET LOCAL` and is reset when
+/// the current transaction ends. Outside of a transaction it would end with
+/// the statement itself, so that returns [`Error::NotInTransaction`].
+pub(crate) fn set(
+    connection: &mut GaussDBConnection,
+    key: &str,
+    value: &str,
+    is_local: bool,
+) -> QueryResult<()> {
+    if is_local && !connection.is_in_transaction() {
+        return Err(Error::NotInTransaction);
+    }
+
+    diesel::select(set_config(key, value, is_local))
+        .get_result::<String>(connection)
+        .map(|_| ())
+}
+
+/// Run-time parameters to `SET LOCAL` at the start of a transaction
+///
+/// Holds an ordered list of settings such as `statement_timeout` or
+/// `search_path` for a single request. [`apply`](Self::apply) sets each one
+/// with `set_config(key, value, true)`, in the order they were added, and
+/// the server reverts all of them when the transaction commits or rolls
+/// back, so there is nothing to undo afterwards. See
+/// [`GaussDBConnection::transaction_with_settings`].
+///
+/// Settings can also be collected from a map; they are then applied in the
+/// iteration order of that map.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalSettings {
+    settings: Vec<(String, String)>,
+}
+
+impl LocalSettings {
+    /// No settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the setting `key = value`, applied after the ones added before
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.settings.push((key.into(), value.into()));
+        self
+    }
+
+    /// The settings, in the order they are applied
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.settings.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Is there nothing to set?
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+
+    /// Set every parameter for the rest of the current transaction
+    ///
+    /// Returns [`Error::NotInTransaction`] outside of a transaction without
+    /// sending anything, since the settings would end with their own
+    /// statements. Stops at the first setting the server rejects.
+    pub fn apply(&self, connection: &mut GaussDBConnection) -> QueryResult<()> {
+        if !connection.is_in_transaction() {
+            return Err(Error::NotInTransaction);
+        }
+
+        self.iter().try_for_each(|(key, value)| set(connection, key, value, true))
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for LocalSettings {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), |settings, (key, value)| settings.set(key, value))
+    }
+}
+
+/// A `SET [LOCAL] ROLE` or `RESET ROLE` statement
+///
+/// The role name is quoted as an identifier. `SET ROLE` can't take bind
+/// parameters, so a password is sent as an escaped string literal. See
+/// [`GaussDBConnection::set_role`].
+#[derive(Debug, Clone, Copy)]
+pub struct SetRole<'a> {
+    role: Option<&'a str>,
+    password: Option<&'a str>,
+    is_local: bool,
+}
+
+impl<'a> SetRole<'a> {
+    /// `SET ROLE role`, or `SET LOCAL ROLE role` with `is_local`
+    pub fn new(role: &'a str, is_local: bool) -> Self {
+        SetRole {
+            role: Some(role),
+            password: None,
+            is_local,
+        }
+    }
+
+    /// `RESET ROLE`, going back to the role the session started with
+    pub fn reset() -> Self {
+        SetRole {
+            role: None,
+            password: None,
+            is_local: false,
+        }
+    }
+
+    /// Add the `PASSWORD` of the role, which GaussDB asks for when
+    /// switching to a role that is not a member of the current one
+    pub fn with_password(mut self, password: &'a str) -> Self {
+        self.password = Some(password);
+        self
+    }
+}
+
+impl QueryFragment<GaussDB> for SetRole<'_> {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        let Some(role) = self.role else {
+            out.push_sql("RESET ROLE");
+            return Ok(());
+        };
+        if role.is_empty() {
+            return Err(Error::QueryBuilderError("SET ROLE requires a role name".into()));
+        }
+
+        out.push_sql(if self.is_local { "SET LOCAL ROLE " } else { "SET ROLE " });
+        out.push_identifier(role)?;
+        if let Some(password) = self.password {
+            out.push_sql(" PASSWORD ");
+            out.push_sql(&quote_literal(password));
+        }
+        Ok(())
+    }
+}
+
+/// Run a `SET ROLE` statement
+///
+/// Like `set_config`, a local role outside of a transaction would end
+/// with the statement itself, so that returns [`Error::NotInTransaction`].
+pub(crate) fn set_role(connection: &mut GaussDBConnection, statement: SetRole<'_>) -> QueryResult<()> {
+    if statement.is_local && !connection.is_in_transaction() {
+        return Err(Error::NotInTransaction);
+    }
+
+    let mut query_builder = crate::query_builder::GaussDBQueryBuilder::new();
+    statement.to_sql(&mut query_builder, &GaussDB)?;
+    connection.batch_execute(&query_builder.finish())
+}
+
+/// A `SET client_encoding TO 'UTF8'` statement
+///
+/// The driver decodes every text value as UTF-8, so only the names of the
+/// UTF-8 encoding (`UTF8`, `UTF-8` and `UNICODE`, in any case) are accepted
+/// and rendered as `'UTF8'`; other names are rejected before anything is
+/// sent. See
+/// [`GaussDBConnection::set_client_encoding`].
+#[derive(Debug, Clone, Copy)]
+pub struct SetClientEncoding<'a> {
+    encoding: &'a str,
+}
+
+impl<'a> SetClientEncoding<'a> {
+    /// `SET client_encoding TO 'UTF8'`, if `encoding` names UTF-8
+    pub fn new(encoding: &'a str) -> Self {
+        SetClientEncoding { encoding }
+    }
+}
+
+/// Is `encoding` a name of UTF-8?
+///
+/// Like the server, this ignores case and any non-alphanumeric characters.
+fn is_utf8_encoding(encoding: &str) -> bool {
+    let normalized: String = encoding
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    normalized == "utf8" || normalized == "unicode"
+}
+
+impl QueryFragment<GaussDB> for SetClientEncoding<'_> {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        if !is_utf8_encoding(self.encoding) {
+            return Err(Error::QueryBuilderError(
+                format!(
+                    "Unsupported client encoding {:?}: text values are decoded as UTF-8, \
+                     so the client encoding must be UTF8. The server converts from the \
+                     database encoding",
+                    self.encoding
+                )
+                .into(),
+            ));
+        }
+
+        out.push_sql("SET client_encoding TO 'UTF8'");
+        Ok(())
+    }
+}
+
+/// Run a `SET client_encoding` statement
+pub(crate) fn set_client_encoding(
+    connection: &mut GaussDBConnection,
+    statement: SetClientEncoding<'_>,
+) -> QueryResult<()> {
+    let mut query_builder = crate::query_builder::GaussDBQueryBuilder::new();
+    statement.to_sql(&mut query_builder, &GaussDB)?;
+    connection.batch_execute(&query_builder.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_sql(statement: SetRole<'_>) -> QueryResult<String> {
+        let mut query_builder = crate::query_builder::GaussDBQueryBuilder::new();
+        statement.to_sql(&mut query_builder, &GaussDB)?;
+        Ok(query_builder.finish())
+    }
+
+    #[test]
+    fn test_set_role() {
+        assert_eq!(generate_sql(SetRole::new("tenant_a", false)).unwrap(), r#"SET ROLE "tenant_a""#);
+        assert_eq!(
+            generate_sql(SetRole::new("tenant_a", true)).unwrap(),
+            r#"SET LOCAL ROLE "tenant_a""#
+        );
+        assert_eq!(generate_sql(SetRole::reset()).unwrap(), "RESET ROLE");
+    }
+
+    #[test]
+    fn test_set_role_quotes_name_and_password() {
+        assert_eq!(
+            generate_sql(SetRole::new(r#"x"; DROP TABLE users; --"#, false)).unwrap(),
+            r#"SET ROLE "x""; DROP TABLE users; --""#
+        );
+        assert_eq!(
+            generate_sql(SetRole::new("Tenant", true).with_password(r"it's\secret")).unwrap(),
+            r#"SET LOCAL ROLE "Tenant" PASSWORD E'it''s\\secret'"#
+        );
+    }
+
+    #[test]
+    fn test_set_role_rejects_empty_name() {
+        assert!(generate_sql(SetRole::new("", false)).is_err());
+    }
+
+    #[test]
+    fn test_local_settings_keep_order() {
+        let settings = LocalSettings::new()
+            .set("statement_timeout", "5s")
+            .set("search_path", "tenant_a, public")
+            .set("statement_timeout", "1s");
+        assert_eq!(
+            settings.iter().collect::<Vec<_>>(),
+            [
+                ("statement_timeout", "5s"),
+                ("search_path", "tenant_a, public"),
+                ("statement_timeout", "1s"),
+            ]
+        );
+
+        let collected: LocalSettings = [("search_path", "tenant_a"), ("app.tenant_id", "42")]
+            .into_iter()
+            .collect();
+        assert_eq!(collected, LocalSettings::new().set("search_path", "tenant_a").set("app.tenant_id", "42"));
+        assert!(LocalSettings::new().is_empty());
+    }
+
+    #[test]
+    fn test_set_client_encoding() {
+        let generate_sql = |statement: SetClientEncoding<'_>| -> QueryResult<String> {
+            let mut query_builder = crate::query_builder::GaussDBQueryBuilder::new();
+            statement.to_sql(&mut query_builder, &GaussDB)?;
+            Ok(query_builder.finish())
+        };
+
+        for encoding in ["UTF8", "utf-8", "Unicode", "utf8'"] {
+            assert_eq!(
+                generate_sql(SetClientEncoding::new(encoding)).unwrap(),
+                "SET client_encoding TO 'UTF8'"
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_client_encoding_rejects_other_encodings() {
+        let mut query_builder = crate::query_builder::GaussDBQueryBuilder::new();
+        for encoding in ["GBK", "LATIN1", "SQL_ASCII", "", "UTF8'; DROP TABLE users; --"] {
+            let err = SetClientEncoding::new(encoding)
+                .to_sql(&mut query_builder, &GaussDB)
+                .unwrap_err();
+            assert!(err.to_string().contains("must be UTF8"), "{}", err);
+        }
+    }
+}
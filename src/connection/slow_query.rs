@@ -0,0 +1,248 @@
+//! Logging of slow queries
+//!
+//! [`SlowQueryLogger`] is an [`Instrumentation`] that times every query
+//! between the `StartQuery` and `FinishQuery` events the connection emits
+//! around the server round trip. Queries taking longer than the configured
+//! threshold are logged at warn level under the `diesel_gaussdb::slow_query`
+//! target; faster queries are not logged at all.
+//!
+//! The logger can wrap another instrumentation, which keeps receiving every
+//! event unchanged:
+//!
+//! ```rust,no_run
+//! # use diesel::prelude::*;
+//! # use diesel_gaussdb::connection::{GaussDBConnection, SlowQueryLogger};
+//! # use std::time::Duration;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+//! let logger = SlowQueryLogger::new(Duration::from_millis(200))
+//!     .wrap(|event: diesel::connection::InstrumentationEvent<'_>| println!("{:?}", event));
+//! conn.set_instrumentation(logger);
+//! # Ok(())
+//! # }
+//! ```
+
+use diesel::connection::{Instrumentation, InstrumentationEvent};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Source of the current time used to measure queries
+///
+/// Only needed to replace the system clock, e.g. in tests.
+pub trait Clock: Send + 'static {
+    /// The current instant
+    fn now(&self) -> Instant;
+}
+
+/// The monotonic system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A query that exceeded the threshold of a [`SlowQueryLogger`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowQuery {
+    /// The SQL text of the query
+    pub sql: String,
+    /// Time between sending the query and receiving its result
+    pub elapsed: Duration,
+    /// Whether the query failed
+    pub failed: bool,
+}
+
+impl fmt::Display for SlowQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "slow query ({:?}", self.elapsed)?;
+        if self.failed {
+            write!(f, ", failed")?;
+        }
+        write!(f, "): {}", self.sql)
+    }
+}
+
+type Sink = Box<dyn FnMut(&SlowQuery) + Send>;
+
+/// Instrumentation logging queries slower than a threshold
+pub struct SlowQueryLogger {
+    threshold: Duration,
+    clock: Box<dyn Clock>,
+    sink: Sink,
+    inner: Option<Box<dyn Instrumentation>>,
+    started: Option<Instant>,
+}
+
+impl fmt::Debug for SlowQueryLogger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlowQueryLogger")
+            .field("threshold", &self.threshold)
+            .field("wraps_instrumentation", &self.inner.is_some())
+            .finish()
+    }
+}
+
+impl SlowQueryLogger {
+    /// Log queries taking longer than `threshold`
+    pub fn new(threshold: Duration) -> Self {
+        SlowQueryLogger {
+            threshold,
+            clock: Box::new(SystemClock),
+            sink: Box::new(|query: &SlowQuery| {
+                log::warn!(target: "diesel_gaussdb::slow_query", "{}", query);
+            }),
+            inner: None,
+            started: None,
+        }
+    }
+
+    /// Forward every event to `inner` as well
+    pub fn wrap(mut self, inner: impl Instrumentation) -> Self {
+        self.inner = Some(Box::new(inner));
+        self
+    }
+
+    /// Measure queries with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Hand slow queries to `sink` instead of the `log` crate
+    pub fn on_slow_query(mut self, sink: impl FnMut(&SlowQuery) + Send + 'static) -> Self {
+        self.sink = Box::new(sink);
+        self
+    }
+
+    /// The configured threshold
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+}
+
+impl Instrumentation for SlowQueryLogger {
+    fn on_connection_event(&mut self, event: InstrumentationEvent<'_>) {
+        match event {
+            InstrumentationEvent::StartQuery { .. } => {
+                self.started = Some(self.clock.now());
+            }
+            InstrumentationEvent::FinishQuery { query, error, .. } => {
+                if let Some(started) = self.started.take() {
+                    let elapsed = self.clock.now().saturating_duration_since(started);
+                    if elapsed > self.threshold {
+                        (self.sink)(&SlowQuery {
+                            sql: query.to_string(),
+                            elapsed,
+                            failed: error.is_some(),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(inner) = &mut self.inner {
+            inner.on_connection_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::StrQueryHelper;
+    use std::sync::{Arc, Mutex};
+
+    /// A clock that only moves when told to
+    #[derive(Clone)]
+    struct FakeClock(Arc<Mutex<Instant>>);
+
+    impl FakeClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn run_query(logger: &mut SlowQueryLogger, clock: &FakeClock, sql: &str, takes: Duration) {
+        let query = StrQueryHelper::new(sql);
+        logger.on_connection_event(InstrumentationEvent::start_query(&query));
+        clock.advance(takes);
+        logger.on_connection_event(InstrumentationEvent::finish_query(&query, None));
+    }
+
+    #[test]
+    fn test_only_slow_queries_are_logged() {
+        let clock = FakeClock(Arc::new(Mutex::new(Instant::now())));
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&logged);
+        let mut logger = SlowQueryLogger::new(Duration::from_millis(100))
+            .with_clock(clock.clone())
+            .on_slow_query(move |query| sink.lock().unwrap().push(query.clone()));
+
+        run_query(&mut logger, &clock, "SELECT 1", Duration::from_millis(5));
+        run_query(&mut logger, &clock, "SELECT pg_sleep(1)", Duration::from_secs(1));
+        run_query(&mut logger, &clock, "SELECT 2", Duration::from_millis(100));
+
+        assert_eq!(
+            *logged.lock().unwrap(),
+            vec![SlowQuery {
+                sql: "SELECT pg_sleep(1)".to_string(),
+                elapsed: Duration::from_secs(1),
+                failed: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_failed_slow_query_is_marked() {
+        let clock = FakeClock(Arc::new(Mutex::new(Instant::now())));
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&logged);
+        let mut logger = SlowQueryLogger::new(Duration::from_millis(10))
+            .with_clock(clock.clone())
+            .on_slow_query(move |query| sink.lock().unwrap().push(query.clone()));
+
+        let query = StrQueryHelper::new("SELECT * FROM missing");
+        let error = diesel::result::Error::NotFound;
+        logger.on_connection_event(InstrumentationEvent::start_query(&query));
+        clock.advance(Duration::from_millis(50));
+        logger.on_connection_event(InstrumentationEvent::finish_query(&query, Some(&error)));
+
+        let logged = logged.lock().unwrap();
+        assert_eq!(logged.len(), 1);
+        assert!(logged[0].failed);
+        assert_eq!(logged[0].to_string(), "slow query (50ms, failed): SELECT * FROM missing");
+    }
+
+    #[test]
+    fn test_wrapped_instrumentation_sees_every_event() {
+        let clock = FakeClock(Arc::new(Mutex::new(Instant::now())));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        let mut logger = SlowQueryLogger::new(Duration::from_secs(1))
+            .with_clock(clock.clone())
+            .on_slow_query(|_| {})
+            .wrap(move |event: InstrumentationEvent<'_>| {
+                let name = match event {
+                    InstrumentationEvent::StartQuery { .. } => "start",
+                    InstrumentationEvent::FinishQuery { .. } => "finish",
+                    _ => "other",
+                };
+                seen.lock().unwrap().push(name);
+            });
+
+        run_query(&mut logger, &clock, "SELECT 1", Duration::from_millis(1));
+        logger.on_connection_event(InstrumentationEvent::begin_transaction(1.try_into().unwrap()));
+
+        assert_eq!(*events.lock().unwrap(), vec!["start", "finish", "other"]);
+    }
+}
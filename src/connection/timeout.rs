@@ -0,0 +1,325 @@
+//! Per-query timeouts for GaussDB connections
+//!
+//! [`GaussDBConnection::with_timeout`] runs a single query with
+//! `statement_timeout` set for that query only. The setting is made with
+//! `SET LOCAL` inside a transaction of its own, so the server reverts it
+//! when that transaction ends and it never applies to later queries.
+//!
+//! Diesel's [`DatabaseErrorKind`] has no variant for a cancelled statement,
+//! so the queries return a [`TimeoutError`] instead, which tells a timeout
+//! apart from other errors. A timeout is recognized by the SQLSTATE the
+//! server reports, `57014` (`query_canceled`).
+
+use crate::connection::{session, GaussDBConnection};
+use diesel::define_sql_function;
+use diesel::prelude::*;
+use diesel::query_dsl::methods::ExecuteDsl;
+use diesel::query_dsl::LoadQuery;
+use diesel::result::{DatabaseErrorInformation, DatabaseErrorKind, Error, QueryResult};
+use diesel::sql_types::Text;
+use gaussdb::error::SqlState;
+use std::fmt;
+use std::time::Duration;
+
+define_sql_function! {
+    /// The `current_setting(setting_name)` SQL function
+    fn current_setting(setting_name: Text) -> Text;
+}
+
+/// The value of `statement_timeout` for `timeout`, in whole milliseconds
+///
+/// A timeout below one millisecond is rounded up, since `0` would disable
+/// the timeout altogether.
+pub(crate) fn statement_timeout_value(timeout: Duration) -> String {
+    let millis = (timeout.as_nanos() + 999_999) / 1_000_000;
+    format!("{}ms", millis)
+}
+
+/// Error information for a query cancelled by its timeout
+///
+/// Held by [`TimeoutError::Timeout`]. The message is the one the server
+/// sent.
+#[derive(Debug)]
+pub struct QueryTimeout {
+    timeout: Duration,
+    message: String,
+}
+
+impl QueryTimeout {
+    /// The timeout the query exceeded
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl DatabaseErrorInformation for QueryTimeout {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn details(&self) -> Option<&str> {
+        None
+    }
+
+    fn hint(&self) -> Option<&str> {
+        None
+    }
+
+    fn table_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn column_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn constraint_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn statement_position(&self) -> Option<i32> {
+        None
+    }
+}
+
+impl fmt::Display for QueryTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The error of a query run by [`WithTimeout`]
+#[derive(Debug)]
+pub enum TimeoutError {
+    /// The query was cancelled because it exceeded the timeout
+    Timeout(QueryTimeout),
+    /// Any other error, unchanged
+    Query(Error),
+}
+
+impl TimeoutError {
+    /// Whether the query was cancelled by its timeout
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, TimeoutError::Timeout(_))
+    }
+
+    /// Classify `error`, given the SQLSTATE of the statement that failed
+    ///
+    /// While the timeout is set, a `query_canceled` error from the server is
+    /// taken to be the timeout.
+    fn new(error: Error, code: Option<SqlState>, timeout: Duration) -> Self {
+        match error {
+            Error::DatabaseError(_, info) if code == Some(SqlState::QUERY_CANCELED) => {
+                TimeoutError::Timeout(QueryTimeout {
+                    timeout,
+                    message: info.message().to_owned(),
+                })
+            }
+            error => TimeoutError::Query(error),
+        }
+    }
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutError::Timeout(timeout) => {
+                write!(f, "Query exceeded its timeout of {:?}: {}", timeout.timeout, timeout)
+            }
+            TimeoutError::Query(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for TimeoutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TimeoutError::Timeout(_) => None,
+            TimeoutError::Query(error) => Some(error),
+        }
+    }
+}
+
+/// Lets `?` return a [`TimeoutError`] from code returning Diesel errors
+///
+/// A timeout becomes a [`DatabaseErrorKind::Unknown`] holding the
+/// [`QueryTimeout`].
+impl From<TimeoutError> for Error {
+    fn from(error: TimeoutError) -> Self {
+        match error {
+            TimeoutError::Timeout(timeout) => Error::DatabaseError(DatabaseErrorKind::Unknown, Box::new(timeout)),
+            TimeoutError::Query(error) => error,
+        }
+    }
+}
+
+/// A connection that runs its next query with a timeout
+///
+/// Created by [`GaussDBConnection::with_timeout`]. The query runs in a
+/// transaction that starts with `SET LOCAL statement_timeout`, so the
+/// setting ends with it. Inside an outer transaction the query runs in a
+/// savepoint instead, and the previous `statement_timeout` is restored
+/// once the query succeeds; a failed query rolls back the savepoint, which
+/// reverts the setting as well.
+///
+/// A query exceeding the timeout fails with [`TimeoutError::Timeout`].
+/// Any other error is returned unchanged in [`TimeoutError::Query`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel_gaussdb::connection::TimeoutError;
+/// # use std::time::Duration;
+/// # diesel::table! { users { id -> Integer, name -> Text, } }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+/// match conn.with_timeout(Duration::from_secs(2)).load::<_, String>(users::table.select(users::name)) {
+///     Ok(names) => println!("{} users", names.len()),
+///     Err(TimeoutError::Timeout(_)) => println!("gave up after 2s"),
+///     Err(e) => return Err(e.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct WithTimeout<'conn> {
+    connection: &'conn mut GaussDBConnection,
+    timeout: Duration,
+}
+
+impl<'conn> WithTimeout<'conn> {
+    pub(crate) fn new(connection: &'conn mut GaussDBConnection, timeout: Duration) -> Self {
+        WithTimeout {
+            connection,
+            timeout,
+        }
+    }
+
+    /// The timeout applied to the query
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Load all rows of `query`, like [`RunQueryDsl::load`]
+    pub fn load<'query, Q, U>(self, query: Q) -> Result<Vec<U>, TimeoutError>
+    where
+        Q: RunQueryDsl<GaussDBConnection> + LoadQuery<'query, GaussDBConnection, U>,
+    {
+        self.run(|conn| query.load(conn))
+    }
+
+    /// Load the single row of `query`, like [`RunQueryDsl::get_result`]
+    pub fn get_result<'query, Q, U>(self, query: Q) -> Result<U, TimeoutError>
+    where
+        Q: RunQueryDsl<GaussDBConnection> + LoadQuery<'query, GaussDBConnection, U>,
+    {
+        self.run(|conn| query.get_result(conn))
+    }
+
+    /// Execute `query`, like [`RunQueryDsl::execute`]
+    pub fn execute<Q>(self, query: Q) -> Result<usize, TimeoutError>
+    where
+        Q: RunQueryDsl<GaussDBConnection> + ExecuteDsl<GaussDBConnection>,
+    {
+        self.run(|conn| query.execute(conn))
+    }
+
+    /// Run `f` with the timeout applied to every statement it sends
+    ///
+    /// The timeout applies to each statement on its own, not to `f` as a
+    /// whole.
+    pub fn run<T, F>(self, f: F) -> Result<T, TimeoutError>
+    where
+        F: FnOnce(&mut GaussDBConnection) -> QueryResult<T>,
+    {
+        let timeout = self.timeout;
+        let is_nested = self.connection.is_in_transaction();
+        self.connection.last_error_code = None;
+
+        let result = self
+            .connection
+            .transaction(|conn| {
+                let previous = if is_nested {
+                    Some(diesel::select(current_setting("statement_timeout")).get_result::<String>(conn)?)
+                } else {
+                    None
+                };
+                session::set(conn, "statement_timeout", &statement_timeout_value(timeout), true)?;
+
+                let value = f(conn)?;
+                if let Some(previous) = previous {
+                    session::set(conn, "statement_timeout", &previous, true)?;
+                }
+                Ok(value)
+            });
+        result.map_err(|e| TimeoutError::new(e, self.connection.last_error_code.take(), timeout))
+    }
+}
+
+impl fmt::Debug for WithTimeout<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithTimeout")
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database_error(message: &str) -> Error {
+        Error::DatabaseError(DatabaseErrorKind::UnableToSendCommand, Box::new(message.to_string()))
+    }
+
+    #[test]
+    fn test_statement_timeout_value() {
+        assert_eq!(statement_timeout_value(Duration::from_secs(2)), "2000ms");
+        assert_eq!(statement_timeout_value(Duration::from_millis(150)), "150ms");
+        assert_eq!(statement_timeout_value(Duration::from_micros(1500)), "2ms");
+        assert_eq!(statement_timeout_value(Duration::from_nanos(1)), "1ms");
+    }
+
+    #[test]
+    fn test_query_canceled_is_timeout() {
+        let error = TimeoutError::new(
+            database_error("GaussDB query error: db error: ERROR: canceling statement due to statement timeout"),
+            Some(SqlState::QUERY_CANCELED),
+            Duration::from_millis(100),
+        );
+
+        assert!(error.is_timeout());
+        match error {
+            TimeoutError::Timeout(timeout) => assert_eq!(timeout.timeout(), Duration::from_millis(100)),
+            TimeoutError::Query(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_other_errors_pass_through() {
+        // The code decides, whatever the message says
+        let error = TimeoutError::new(
+            database_error("canceling statement due to statement timeout"),
+            Some(SqlState::UNDEFINED_TABLE),
+            Duration::from_millis(100),
+        );
+        assert!(matches!(
+            error,
+            TimeoutError::Query(Error::DatabaseError(DatabaseErrorKind::UnableToSendCommand, _))
+        ));
+
+        let error = TimeoutError::new(Error::NotFound, Some(SqlState::QUERY_CANCELED), Duration::from_millis(100));
+        assert!(matches!(error, TimeoutError::Query(Error::NotFound)));
+    }
+
+    #[test]
+    fn test_timeout_converts_to_database_error() {
+        let error = TimeoutError::new(
+            database_error("canceled"),
+            Some(SqlState::QUERY_CANCELED),
+            Duration::from_secs(1),
+        );
+        let error = Error::from(error);
+        assert!(matches!(error, Error::DatabaseError(DatabaseErrorKind::Unknown, ref info) if info.message() == "canceled"));
+    }
+}
@@ -0,0 +1,284 @@
+//! Server version detection for GaussDB
+//!
+//! This module parses the string returned by `SELECT version()` into a
+//! structured [`GaussDBVersion`] and offers feature detection on top of it.
+//! GaussDB and openGauss report a PostgreSQL 9.2 compatibility version
+//! followed by their own kernel version, so the kernel version is what
+//! matters for feature gating.
+
+use std::fmt;
+
+/// The database product reported by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GaussDBProduct {
+    /// Huawei GaussDB (reports `GaussDB Kernel ...`)
+    GaussDB,
+    /// openGauss (reports `openGauss ...`)
+    OpenGauss,
+    /// Vanilla PostgreSQL
+    PostgreSQL,
+}
+
+/// SQL features whose availability depends on the server version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GaussDBFeature {
+    /// `MERGE INTO ... USING ...`
+    Merge,
+    /// `GROUPS` frame units in window definitions
+    WindowFrameGroups,
+    /// `EXCLUDE` clauses in window frame definitions
+    WindowFrameExclude,
+    /// `unnest(a, b, ...)` with several arrays
+    MultiArrayUnnest,
+    /// The `jsonb` type and its operators
+    Jsonb,
+}
+
+/// A parsed server version
+///
+/// `major`, `minor` and `patch` refer to the product's own version: the kernel
+/// version for GaussDB (e.g. `505.1.0`, or `500.2.10` for `V500R002C10`), the
+/// openGauss release for openGauss and the server version for PostgreSQL.
+/// The PostgreSQL compatibility version reported by GaussDB and openGauss is
+/// available separately through [`GaussDBVersion::postgres_compat`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GaussDBVersion {
+    product: GaussDBProduct,
+    major: u32,
+    minor: u32,
+    patch: u32,
+    postgres_compat: Option<(u32, u32, u32)>,
+    raw: String,
+}
+
+impl GaussDBVersion {
+    /// Parse the output of `SELECT version()`
+    ///
+    /// Returns `None` if the string does not contain a recognizable version.
+    ///
+    /// ```rust
+    /// use diesel_gaussdb::connection::version::{GaussDBProduct, GaussDBVersion};
+    ///
+    /// let version = GaussDBVersion::parse(
+    ///     "gaussdb (GaussDB Kernel 505.1.0 build 2ffb5b49) compiled at 2024-06-27 19:34:21 \
+    ///      commit 0 last mr  release",
+    /// ).unwrap();
+    /// assert_eq!(version.product(), GaussDBProduct::GaussDB);
+    /// assert_eq!(version.major(), 505);
+    /// ```
+    pub fn parse(version: &str) -> Option<Self> {
+        let raw = version.trim().to_string();
+        let postgres_compat = find_after(&raw, "PostgreSQL ").and_then(parse_dotted);
+
+        let (product, numbers) = if let Some(rest) = find_after(&raw, "GaussDB Kernel ") {
+            (GaussDBProduct::GaussDB, parse_kernel_version(rest)?)
+        } else if let Some(rest) = find_after(&raw, "openGauss ") {
+            (GaussDBProduct::OpenGauss, parse_dotted(rest)?)
+        } else if let Some(compat) = postgres_compat {
+            (GaussDBProduct::PostgreSQL, compat)
+        } else {
+            return None;
+        };
+
+        let postgres_compat = match product {
+            GaussDBProduct::PostgreSQL => None,
+            _ => postgres_compat,
+        };
+
+        Some(GaussDBVersion {
+            product,
+            major: numbers.0,
+            minor: numbers.1,
+            patch: numbers.2,
+            postgres_compat,
+            raw,
+        })
+    }
+
+    /// The product that reported this version
+    pub fn product(&self) -> GaussDBProduct {
+        self.product
+    }
+
+    /// The major version number
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// The minor version number
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// The patch version number
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+
+    /// The PostgreSQL version GaussDB or openGauss claims compatibility with
+    pub fn postgres_compat(&self) -> Option<(u32, u32, u32)> {
+        self.postgres_compat
+    }
+
+    /// The unparsed version string
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// Whether this version is at least `major.minor.patch`
+    pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+
+    /// Check whether the server supports the given feature
+    pub fn supports(&self, feature: GaussDBFeature) -> bool {
+        match (self.product, feature) {
+            (GaussDBProduct::GaussDB, GaussDBFeature::Merge) => true,
+            (GaussDBProduct::OpenGauss, GaussDBFeature::Merge) => self.at_least(2, 0, 0),
+            (GaussDBProduct::PostgreSQL, GaussDBFeature::Merge) => self.at_least(15, 0, 0),
+
+            (GaussDBProduct::PostgreSQL, GaussDBFeature::WindowFrameGroups)
+            | (GaussDBProduct::PostgreSQL, GaussDBFeature::WindowFrameExclude) => {
+                self.at_least(11, 0, 0)
+            }
+            (_, GaussDBFeature::WindowFrameGroups) | (_, GaussDBFeature::WindowFrameExclude) => false,
+
+            (GaussDBProduct::PostgreSQL, GaussDBFeature::MultiArrayUnnest) => self.at_least(9, 4, 0),
+            (_, GaussDBFeature::MultiArrayUnnest) => false,
+
+            (GaussDBProduct::PostgreSQL, GaussDBFeature::Jsonb) => self.at_least(9, 4, 0),
+            (_, GaussDBFeature::Jsonb) => true,
+        }
+    }
+}
+
+impl fmt::Display for GaussDBVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let product = match self.product {
+            GaussDBProduct::GaussDB => "GaussDB Kernel",
+            GaussDBProduct::OpenGauss => "openGauss",
+            GaussDBProduct::PostgreSQL => "PostgreSQL",
+        };
+        write!(f, "{} {}.{}.{}", product, self.major, self.minor, self.patch)
+    }
+}
+
+fn find_after<'a>(haystack: &'a str, needle: &str) -> Option<&'a str> {
+    haystack.find(needle).map(|idx| &haystack[idx + needle.len()..])
+}
+
+/// Parse a leading `major[.minor[.patch]]` sequence, ignoring any suffix
+/// such as `.SPC0100`, `beta1` or `rc2`.
+fn parse_dotted(s: &str) -> Option<(u32, u32, u32)> {
+    let token = s.split_whitespace().next()?;
+    let mut parts = token.split('.').map(|part| {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse::<u32>().ok()
+    });
+    let major = parts.next().flatten()?;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// Parse a GaussDB kernel version, either `505.1.0` or the older
+/// `V500R002C10` release naming.
+fn parse_kernel_version(s: &str) -> Option<(u32, u32, u32)> {
+    let token = s.split_whitespace().next()?;
+    if token.starts_with('V') {
+        let component = |prefix: char| -> Option<u32> {
+            let start = token.find(prefix)? + 1;
+            let digits: String = token[start..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            digits.parse().ok()
+        };
+        let major = component('V')?;
+        return Some((major, component('R').unwrap_or(0), component('C').unwrap_or(0)));
+    }
+    parse_dotted(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gaussdb_kernel_version() {
+        let version = GaussDBVersion::parse(
+            "gaussdb (GaussDB Kernel 505.1.0 build 2ffb5b49) compiled at 2024-06-27 19:34:21 commit 0 last mr  release",
+        )
+        .unwrap();
+        assert_eq!(version.product(), GaussDBProduct::GaussDB);
+        assert_eq!((version.major(), version.minor(), version.patch()), (505, 1, 0));
+        assert_eq!(version.postgres_compat(), None);
+        assert!(version.supports(GaussDBFeature::Merge));
+        assert!(!version.supports(GaussDBFeature::WindowFrameExclude));
+    }
+
+    #[test]
+    fn test_parse_gaussdb_kernel_with_spc_suffix() {
+        let version = GaussDBVersion::parse(
+            "PostgreSQL 9.2.4 (GaussDB Kernel 503.1.0.SPC1700 build 9a4c2f39) compiled at 2023-08-21 10:11:37 commit 5091 last mr 11463 release",
+        )
+        .unwrap();
+        assert_eq!(version.product(), GaussDBProduct::GaussDB);
+        assert_eq!((version.major(), version.minor(), version.patch()), (503, 1, 0));
+        assert_eq!(version.postgres_compat(), Some((9, 2, 4)));
+    }
+
+    #[test]
+    fn test_parse_gaussdb_legacy_release_naming() {
+        let version = GaussDBVersion::parse(
+            "PostgreSQL 9.2.4 (GaussDB Kernel V500R002C10 build 7f8b1c2e) compiled at 2022-03-10 16:02:05 commit 3145 last mr 6112 release",
+        )
+        .unwrap();
+        assert_eq!(version.product(), GaussDBProduct::GaussDB);
+        assert_eq!((version.major(), version.minor(), version.patch()), (500, 2, 10));
+        assert!(version.at_least(500, 2, 0));
+        assert!(!version.at_least(503, 0, 0));
+    }
+
+    #[test]
+    fn test_parse_opengauss_version() {
+        let version = GaussDBVersion::parse(
+            "(openGauss 5.0.0 build a07d57c3) compiled at 2023-03-29 03:37:13 commit 0 last mr   on x86_64-unknown-linux-gnu, compiled by g++ (GCC) 7.3.0, 64-bit",
+        )
+        .unwrap();
+        assert_eq!(version.product(), GaussDBProduct::OpenGauss);
+        assert_eq!((version.major(), version.minor(), version.patch()), (5, 0, 0));
+        assert!(version.supports(GaussDBFeature::Merge));
+        assert!(!version.supports(GaussDBFeature::MultiArrayUnnest));
+    }
+
+    #[test]
+    fn test_parse_opengauss_with_postgres_prefix() {
+        let version = GaussDBVersion::parse(
+            "PostgreSQL 9.2.4 (openGauss 3.1.0 build 4e931f9a) compiled at 2022-09-29 14:19:24 commit 0 last mr   on aarch64-unknown-linux-gnu, compiled by g++ (GCC) 7.3.0, 64-bit",
+        )
+        .unwrap();
+        assert_eq!(version.product(), GaussDBProduct::OpenGauss);
+        assert_eq!((version.major(), version.minor(), version.patch()), (3, 1, 0));
+        assert_eq!(version.postgres_compat(), Some((9, 2, 4)));
+    }
+
+    #[test]
+    fn test_parse_vanilla_postgres_version() {
+        let version = GaussDBVersion::parse(
+            "PostgreSQL 14.5 on x86_64-pc-linux-gnu, compiled by gcc (GCC) 11.2.0, 64-bit",
+        )
+        .unwrap();
+        assert_eq!(version.product(), GaussDBProduct::PostgreSQL);
+        assert_eq!((version.major(), version.minor(), version.patch()), (14, 5, 0));
+        assert!(!version.supports(GaussDBFeature::Merge));
+        assert!(version.supports(GaussDBFeature::WindowFrameExclude));
+        assert_eq!(version.to_string(), "PostgreSQL 14.5.0");
+    }
+
+    #[test]
+    fn test_parse_unrecognized_version() {
+        assert!(GaussDBVersion::parse("").is_none());
+        assert!(GaussDBVersion::parse("MySQL 8.0.32").is_none());
+    }
+}
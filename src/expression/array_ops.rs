@@ -4,9 +4,11 @@
 //! supported by GaussDB, including containment, overlap, and comparison operators.
 
 use crate::backend::GaussDB;
-use diesel::expression::{Expression, AsExpression};
-use diesel::sql_types::{Array, Bool};
-use diesel::query_builder::{QueryFragment, AstPass};
+use diesel::expression::{
+    AppearsOnTable, AsExpression, Expression, SelectableExpression, TypedExpressionType, ValidGrouping,
+};
+use diesel::sql_types::{Array, Bool, Integer, IntoNullable, SingleValue, SqlType};
+use diesel::query_builder::{QueryFragment, QueryId, AstPass};
 use diesel::result::QueryResult;
 
 /// Trait for array containment operations
@@ -240,10 +242,173 @@ where
     }
 }
 
+/// Element access on array expressions
+///
+/// GaussDB arrays are 1-based: `index(1)` is the first element. A subscript
+/// outside the bounds of the array evaluates to `NULL` rather than raising
+/// an error, so the element is always returned as a nullable value.
+pub trait ArrayIndexOps<T>: Expression + Sized
+where
+    T: SqlType + SingleValue,
+{
+    /// Access the element at `index`
+    ///
+    /// This corresponds to the PostgreSQL `array[index]` subscript. Chain
+    /// further [`index`](ArrayIndex::index) calls to address one element of
+    /// a multidimensional array, e.g. `matrix.index(2).index(1)` for
+    /// `matrix[2][1]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # table! { test_table (id) { id -> Integer, tags -> Array<Text>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // The first tag of each row, `None` for rows without tags
+    /// let results = test_table::table
+    ///     .select((test_table::id, test_table::tags.index(1)))
+    ///     .load::<(i32, Option<String>)>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn index<I>(self, index: I) -> ArrayIndex<Self, I::Expression>
+    where
+        I: AsExpression<Integer>;
+}
+
+impl<T, E> ArrayIndexOps<T> for E
+where
+    T: SqlType + SingleValue,
+    E: Expression<SqlType = Array<T>>,
+{
+    fn index<I>(self, index: I) -> ArrayIndex<Self, I::Expression>
+    where
+        I: AsExpression<Integer>,
+    {
+        ArrayIndex::new(self, index.as_expression())
+    }
+}
+
+/// Expression for an array subscript, `(array)[index]`
+///
+/// The array is always parenthesized, since subscripting the result of a
+/// function call or cast needs parentheses in SQL.
+#[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+pub struct ArrayIndex<A, I> {
+    array: A,
+    index: I,
+}
+
+impl<A, I> ArrayIndex<A, I> {
+    /// 创建新的数组下标访问表达式
+    ///
+    /// # 参数
+    /// * `array` - 数组表达式
+    /// * `index` - 下标（从 1 开始）
+    pub fn new(array: A, index: I) -> Self {
+        ArrayIndex { array, index }
+    }
+
+    /// Add a subscript for the next dimension of the array
+    pub fn index<J>(self, index: J) -> ArraySubscript<Self, J::Expression>
+    where
+        J: AsExpression<Integer>,
+    {
+        ArraySubscript { previous: self, index: index.as_expression() }
+    }
+}
+
+impl<A, I, T> Expression for ArrayIndex<A, I>
+where
+    A: Expression<SqlType = Array<T>>,
+    I: Expression<SqlType = Integer>,
+    T: SqlType + SingleValue + IntoNullable,
+    T::Nullable: TypedExpressionType,
+{
+    type SqlType = T::Nullable;
+}
+
+impl<A, I> QueryFragment<GaussDB> for ArrayIndex<A, I>
+where
+    A: QueryFragment<GaussDB>,
+    I: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("(");
+        self.array.walk_ast(out.reborrow())?;
+        out.push_sql(")[");
+        self.index.walk_ast(out.reborrow())?;
+        out.push_sql("]");
+        Ok(())
+    }
+}
+
+impl<A, I, QS> SelectableExpression<QS> for ArrayIndex<A, I> where ArrayIndex<A, I>: AppearsOnTable<QS> {}
+
+impl<A, I, QS> AppearsOnTable<QS> for ArrayIndex<A, I>
+where
+    A: AppearsOnTable<QS>,
+    I: AppearsOnTable<QS>,
+    ArrayIndex<A, I>: Expression,
+{
+}
+
+/// Expression for a further subscript of a multidimensional array access,
+/// see [`ArrayIndex::index`]
+#[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+pub struct ArraySubscript<P, I> {
+    previous: P,
+    index: I,
+}
+
+impl<P, I> ArraySubscript<P, I> {
+    /// Add a subscript for the next dimension of the array
+    pub fn index<J>(self, index: J) -> ArraySubscript<Self, J::Expression>
+    where
+        J: AsExpression<Integer>,
+    {
+        ArraySubscript { previous: self, index: index.as_expression() }
+    }
+}
+
+impl<P, I> Expression for ArraySubscript<P, I>
+where
+    P: Expression,
+    I: Expression<SqlType = Integer>,
+{
+    type SqlType = P::SqlType;
+}
+
+impl<P, I> QueryFragment<GaussDB> for ArraySubscript<P, I>
+where
+    P: QueryFragment<GaussDB>,
+    I: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        self.previous.walk_ast(out.reborrow())?;
+        out.push_sql("[");
+        self.index.walk_ast(out.reborrow())?;
+        out.push_sql("]");
+        Ok(())
+    }
+}
+
+impl<P, I, QS> SelectableExpression<QS> for ArraySubscript<P, I> where ArraySubscript<P, I>: AppearsOnTable<QS> {}
+
+impl<P, I, QS> AppearsOnTable<QS> for ArraySubscript<P, I>
+where
+    P: AppearsOnTable<QS>,
+    I: AppearsOnTable<QS>,
+    ArraySubscript<P, I>: Expression,
+{
+}
+
 /// Additional array functions
 pub mod functions {
     use super::*;
-    use diesel::sql_types::Integer;
+    use diesel::dsl::AsExprOf;
+    use diesel::sql_types::{Nullable, Text};
 
     /// Get the length of an array
     ///
@@ -312,6 +477,580 @@ pub mod functions {
             Ok(())
         }
     }
+
+    /// Defines a one- or two-argument array function expression
+    ///
+    /// The types of the arguments and
+    /// `$returns` the SQL type of the result, all in terms of the element
+    /// type `ST`.
+    macro_rules! array_function {
+        (
+            $(#[$meta:meta])*
+            $name:ident, $sql_name:expr,
+            ($arg:ident: $arg_ty:ty) -> $returns:ty
+        ) => {
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+            pub struct $name<A> {
+                $arg: A,
+            }
+
+            impl<A> $name<A> {
+                fn new($arg: A) -> Self {
+                    $name { $arg }
+                }
+            }
+
+            impl<A, ST> Expression for $name<A>
+            where
+                A: Expression<SqlType = $arg_ty>,
+                ST: SqlType + TypedExpressionType,
+            {
+                type SqlType = $returns;
+            }
+
+            impl<A> QueryFragment<GaussDB> for $name<A>
+            where
+                A: QueryFragment<GaussDB>,
+            {
+                fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+                    out.push_sql(concat!($sql_name, "("));
+                    self.$arg.walk_ast(out.reborrow())?;
+                    out.push_sql(")");
+                    Ok(())
+                }
+            }
+
+            impl<A, QS> SelectableExpression<QS> for $name<A>
+            where
+                $name<A>: AppearsOnTable<QS>,
+            {
+            }
+
+            impl<A, QS> AppearsOnTable<QS> for $name<A>
+            where
+                A: AppearsOnTable<QS>,
+                $name<A>: Expression,
+            {
+            }
+        };
+        (
+            $(#[$meta:meta])*
+            $name:ident, $sql_name:expr,
+            ($first:ident: $first_ty:ty, $second:ident: $second_ty:ty) -> $returns:ty
+        ) => {
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+            pub struct $name<A, B> {
+                $first: A,
+                $second: B,
+            }
+
+            impl<A, B> $name<A, B> {
+                fn new($first: A, $second: B) -> Self {
+                    $name { $first, $second }
+                }
+            }
+
+            impl<A, B, ST> Expression for $name<A, B>
+            where
+                A: Expression<SqlType = $first_ty>,
+                B: Expression<SqlType = $second_ty>,
+                ST: SqlType + TypedExpressionType,
+            {
+                type SqlType = $returns;
+            }
+
+            impl<A, B> QueryFragment<GaussDB> for $name<A, B>
+            where
+                A: QueryFragment<GaussDB>,
+                B: QueryFragment<GaussDB>,
+            {
+                fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+                    out.push_sql(concat!($sql_name, "("));
+                    self.$first.walk_ast(out.reborrow())?;
+                    out.push_sql(", ");
+                    self.$second.walk_ast(out.reborrow())?;
+                    out.push_sql(")");
+                    Ok(())
+                }
+            }
+
+            impl<A, B, QS> SelectableExpression<QS> for $name<A, B>
+            where
+                $name<A, B>: AppearsOnTable<QS>,
+            {
+            }
+
+            impl<A, B, QS> AppearsOnTable<QS> for $name<A, B>
+            where
+                A: AppearsOnTable<QS>,
+                B: AppearsOnTable<QS>,
+                $name<A, B>: Expression,
+            {
+            }
+        };
+    }
+
+    array_function!(
+        /// Expression for the `array_position` function
+        ArrayPosition, "array_position",
+        (array: Array<ST>, element: ST) -> Nullable<Integer>
+    );
+
+    array_function!(
+        /// Expression for the `array_remove` function
+        ArrayRemove, "array_remove",
+        (array: Array<ST>, element: ST) -> Array<ST>
+    );
+
+    array_function!(
+        /// Expression for the `array_append` function
+        ArrayAppend, "array_append",
+        (array: Array<ST>, element: ST) -> Array<ST>
+    );
+
+    array_function!(
+        /// Expression for the `array_prepend` function
+        ArrayPrepend, "array_prepend",
+        (element: ST, array: Array<ST>) -> Array<ST>
+    );
+
+    array_function!(
+        /// Expression for the `generate_subscripts` function
+        GenerateSubscripts, "generate_subscripts",
+        (array: Array<ST>, dimension: Integer) -> Integer
+    );
+
+    array_function!(
+        /// Expression for the `array_cat` function
+        ArrayCat, "array_cat",
+        (first: Array<ST>, second: Array<ST>) -> Array<ST>
+    );
+
+    array_function!(
+        /// Expression for the `array_lower` function
+        ArrayLower, "array_lower",
+        (array: Array<ST>, dimension: Integer) -> Nullable<Integer>
+    );
+
+    array_function!(
+        /// Expression for the `array_upper` function
+        ArrayUpper, "array_upper",
+        (array: Array<ST>, dimension: Integer) -> Nullable<Integer>
+    );
+
+    array_function!(
+        /// Expression for the `array_dims` function
+        ArrayDims, "array_dims",
+        (array: Array<ST>) -> Nullable<Text>
+    );
+
+    array_function!(
+        /// Expression for the `cardinality` function
+        Cardinality, "cardinality",
+        (array: Array<ST>) -> Integer
+    );
+
+    /// Find the 1-based position of the first occurrence of `element`
+    ///
+    /// This corresponds to `array_position(array, element)`, which returns
+    /// `NULL` if the element is not present.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::array_ops::functions::array_position;
+    /// # table! { test_table (id) { id -> Integer, tags -> Array<Text>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// let results = test_table::table
+    ///     .select((test_table::id, array_position(test_table::tags, "rust")))
+    ///     .load::<(i32, Option<i32>)>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn array_position<ST, A, E>(array: A, element: E) -> ArrayPosition<A::Expression, E::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        E: AsExpression<ST>,
+    {
+        ArrayPosition::new(array.as_expression(), element.as_expression())
+    }
+
+    /// Remove all elements equal to `element` from the array
+    ///
+    /// This corresponds to `array_remove(array, element)`.
+    pub fn array_remove<ST, A, E>(array: A, element: E) -> ArrayRemove<A::Expression, E::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        E: AsExpression<ST>,
+    {
+        ArrayRemove::new(array.as_expression(), element.as_expression())
+    }
+
+    /// Append `element` to the end of the array
+    ///
+    /// This corresponds to `array_append(array, element)`.
+    pub fn array_append<ST, A, E>(array: A, element: E) -> ArrayAppend<A::Expression, E::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        E: AsExpression<ST>,
+    {
+        ArrayAppend::new(array.as_expression(), element.as_expression())
+    }
+
+    /// Prepend `element` to the beginning of the array
+    ///
+    /// This corresponds to `array_prepend(element, array)`; note that the
+    /// element comes first, as in SQL.
+    pub fn array_prepend<ST, E, A>(element: E, array: A) -> ArrayPrepend<E::Expression, A::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        E: AsExpression<ST>,
+        A: AsExpression<Array<ST>>,
+    {
+        ArrayPrepend::new(element.as_expression(), array.as_expression())
+    }
+
+    /// Concatenate two arrays of the same element type
+    ///
+    /// This corresponds to `array_cat(first, second)`.
+    pub fn array_cat<ST, A, B>(first: A, second: B) -> ArrayCat<A::Expression, B::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        B: AsExpression<Array<ST>>,
+    {
+        ArrayCat::new(first.as_expression(), second.as_expression())
+    }
+
+    /// Generate the valid subscripts of dimension `dimension` of the array
+    ///
+    /// This corresponds to the set-returning function
+    /// `generate_subscripts(array, dimension)`, which produces one row per
+    /// subscript, from the lower to the upper bound of the dimension. Empty
+    /// and `NULL` arrays produce no rows.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::array_ops::functions::generate_subscripts;
+    /// # table! { test_table (id) { id -> Integer, tags -> Array<Text>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // One row per tag, together with its position
+    /// let results = test_table::table
+    ///     .select((
+    ///         test_table::id,
+    ///         generate_subscripts(test_table::tags, 1),
+    ///         test_table::tags.index(generate_subscripts(test_table::tags, 1)),
+    ///     ))
+    ///     .load::<(i32, i32, Option<String>)>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn generate_subscripts<ST, A, D>(array: A, dimension: D) -> GenerateSubscripts<A::Expression, D::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        D: AsExpression<Integer>,
+    {
+        GenerateSubscripts::new(array.as_expression(), dimension.as_expression())
+    }
+
+    /// Get the lower bound of dimension `dimension` of the array
+    ///
+    /// This corresponds to `array_lower(array, dimension)`. Arrays start at
+    /// 1 unless they were built with explicit bounds. The result is `NULL`
+    /// for empty arrays and dimensions the array doesn't have.
+    pub fn array_lower<ST, A, D>(array: A, dimension: D) -> ArrayLower<A::Expression, D::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        D: AsExpression<Integer>,
+    {
+        ArrayLower::new(array.as_expression(), dimension.as_expression())
+    }
+
+    /// Get the upper bound of dimension `dimension` of the array
+    ///
+    /// This corresponds to `array_upper(array, dimension)`. Like
+    /// [`array_lower`], the result is `NULL` for empty arrays and dimensions
+    /// the array doesn't have.
+    pub fn array_upper<ST, A, D>(array: A, dimension: D) -> ArrayUpper<A::Expression, D::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        D: AsExpression<Integer>,
+    {
+        ArrayUpper::new(array.as_expression(), dimension.as_expression())
+    }
+
+    /// Describe the bounds of all dimensions of the array
+    ///
+    /// This corresponds to `array_dims(array)`, which returns text such as
+    /// `[1:2][1:3]` for a 2x3 array, or `NULL` for an empty array.
+    pub fn array_dims<ST, A>(array: A) -> ArrayDims<A::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+    {
+        ArrayDims::new(array.as_expression())
+    }
+
+    /// Count all elements of the array, over all dimensions
+    ///
+    /// This corresponds to `cardinality(array)`. Unlike
+    /// [`array_length`], which is `NULL` for an empty array, `cardinality`
+    /// returns 0, so it can be compared without handling `NULL`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::array_ops::functions::cardinality;
+    /// # table! { test_table (id) { id -> Integer, tags -> Array<Text>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // Rows without any tag
+    /// let untagged = test_table::table
+    ///     .filter(cardinality(test_table::tags).eq(0))
+    ///     .select(test_table::id)
+    ///     .load::<i32>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cardinality<ST, A>(array: A) -> Cardinality<A::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+    {
+        Cardinality::new(array.as_expression())
+    }
+
+    /// Join the elements of an array into a string
+    ///
+    /// This corresponds to `array_to_string(array, delimiter)`. `NULL`
+    /// elements are skipped unless a replacement is given with
+    /// [`with_null_string`](ArrayToString::with_null_string).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::array_ops::functions::array_to_string;
+    /// # table! { test_table (id) { id -> Integer, tags -> Array<Text>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // "rust, db"
+    /// let results = test_table::table
+    ///     .select(array_to_string(test_table::tags, ", "))
+    ///     .load::<String>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn array_to_string<ST, A, D>(array: A, delimiter: D) -> ArrayToString<A::Expression, D::Expression>
+    where
+        ST: SqlType + TypedExpressionType,
+        A: AsExpression<Array<ST>>,
+        D: AsExpression<Text>,
+    {
+        ArrayToString::new(array.as_expression(), delimiter.as_expression(), None)
+    }
+
+    /// Expression for the `array_to_string` function
+    ///
+    /// Without a null string the default `N` type is never rendered.
+    #[derive(Debug, Clone, Copy)]
+    pub struct ArrayToString<A, D, N = AsExprOf<&'static str, Text>> {
+        array: A,
+        delimiter: D,
+        null_string: Option<N>,
+    }
+
+    impl<A, D, N> ArrayToString<A, D, N> {
+        fn new(array: A, delimiter: D, null_string: Option<N>) -> Self {
+            ArrayToString { array, delimiter, null_string }
+        }
+
+        /// Write `null_string` in place of `NULL` elements
+        pub fn with_null_string<S>(self, null_string: S) -> ArrayToString<A, D, S::Expression>
+        where
+            S: AsExpression<Text>,
+        {
+            ArrayToString::new(self.array, self.delimiter, Some(null_string.as_expression()))
+        }
+    }
+
+    impl<A, D, N, ST> Expression for ArrayToString<A, D, N>
+    where
+        A: Expression<SqlType = Array<ST>>,
+        D: Expression<SqlType = Text>,
+        N: Expression<SqlType = Text>,
+        ST: SqlType + TypedExpressionType,
+    {
+        type SqlType = Text;
+    }
+
+    impl<A, D, N> QueryFragment<GaussDB> for ArrayToString<A, D, N>
+    where
+        A: QueryFragment<GaussDB>,
+        D: QueryFragment<GaussDB>,
+        N: QueryFragment<GaussDB>,
+    {
+        fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+            out.push_sql("array_to_string(");
+            self.array.walk_ast(out.reborrow())?;
+            out.push_sql(", ");
+            self.delimiter.walk_ast(out.reborrow())?;
+            if let Some(null_string) = &self.null_string {
+                out.push_sql(", ");
+                null_string.walk_ast(out.reborrow())?;
+            }
+            out.push_sql(")");
+            Ok(())
+        }
+    }
+
+    runtime_query_id!(ArrayToString<A, D, N>);
+
+    // Derived `ValidGrouping` doesn't support the default type parameter
+    impl<A, D, N, GB> ValidGrouping<GB> for ArrayToString<A, D, N>
+    where
+        (A, D, N): ValidGrouping<GB>,
+    {
+        type IsAggregate = <(A, D, N) as ValidGrouping<GB>>::IsAggregate;
+    }
+
+    impl<A, D, N, QS> SelectableExpression<QS> for ArrayToString<A, D, N>
+    where
+        ArrayToString<A, D, N>: AppearsOnTable<QS>,
+    {
+    }
+
+    impl<A, D, N, QS> AppearsOnTable<QS> for ArrayToString<A, D, N>
+    where
+        A: AppearsOnTable<QS>,
+        D: AppearsOnTable<QS>,
+        N: AppearsOnTable<QS>,
+        ArrayToString<A, D, N>: Expression,
+    {
+    }
+
+    /// Split a string into an array of text
+    ///
+    /// This corresponds to `string_to_array(string, delimiter)`. Use
+    /// [`with_null_string`](StringToArray::with_null_string) to turn fields
+    /// equal to a marker string into `NULL` elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::array_ops::functions::string_to_array;
+    /// # table! { test_table (id) { id -> Integer, tags -> Array<Text>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// diesel::update(test_table::table.find(1))
+    ///     .set(test_table::tags.eq(string_to_array("rust,db", ",")))
+    ///     .execute(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn string_to_array<S, D>(string: S, delimiter: D) -> StringToArray<S::Expression, D::Expression>
+    where
+        S: AsExpression<Text>,
+        D: AsExpression<Text>,
+    {
+        StringToArray::new(string.as_expression(), delimiter.as_expression(), None)
+    }
+
+    /// Expression for the `string_to_array` function
+    ///
+    /// Without a null string the default `N` type is never rendered.
+    #[derive(Debug, Clone, Copy)]
+    pub struct StringToArray<S, D, N = AsExprOf<&'static str, Text>> {
+        string: S,
+        delimiter: D,
+        null_string: Option<N>,
+    }
+
+    impl<S, D, N> StringToArray<S, D, N> {
+        fn new(string: S, delimiter: D, null_string: Option<N>) -> Self {
+            StringToArray { string, delimiter, null_string }
+        }
+
+        /// Turn fields equal to `null_string` into `NULL` elements
+        pub fn with_null_string<T>(self, null_string: T) -> StringToArray<S, D, T::Expression>
+        where
+            T: AsExpression<Text>,
+        {
+            StringToArray::new(self.string, self.delimiter, Some(null_string.as_expression()))
+        }
+    }
+
+    impl<S, D, N> Expression for StringToArray<S, D, N>
+    where
+        S: Expression<SqlType = Text>,
+        D: Expression<SqlType = Text>,
+        N: Expression<SqlType = Text>,
+    {
+        type SqlType = Array<Text>;
+    }
+
+    impl<S, D, N> QueryFragment<GaussDB> for StringToArray<S, D, N>
+    where
+        S: QueryFragment<GaussDB>,
+        D: QueryFragment<GaussDB>,
+        N: QueryFragment<GaussDB>,
+    {
+        fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+            out.push_sql("string_to_array(");
+            self.string.walk_ast(out.reborrow())?;
+            out.push_sql(", ");
+            self.delimiter.walk_ast(out.reborrow())?;
+            if let Some(null_string) = &self.null_string {
+                out.push_sql(", ");
+                null_string.walk_ast(out.reborrow())?;
+            }
+            out.push_sql(")");
+            Ok(())
+        }
+    }
+
+    runtime_query_id!(StringToArray<S, D, N>);
+
+    // Derived `ValidGrouping` doesn't support the default type parameter
+    impl<S, D, N, GB> ValidGrouping<GB> for StringToArray<S, D, N>
+    where
+        (S, D, N): ValidGrouping<GB>,
+    {
+        type IsAggregate = <(S, D, N) as ValidGrouping<GB>>::IsAggregate;
+    }
+
+    impl<S, D, N, QS> SelectableExpression<QS> for StringToArray<S, D, N>
+    where
+        StringToArray<S, D, N>: AppearsOnTable<QS>,
+    {
+    }
+
+    impl<S, D, N, QS> AppearsOnTable<QS> for StringToArray<S, D, N>
+    where
+        S: AppearsOnTable<QS>,
+        D: AppearsOnTable<QS>,
+        N: AppearsOnTable<QS>,
+        StringToArray<S, D, N>: Expression,
+    {
+    }
 }
 
 #[cfg(test)]
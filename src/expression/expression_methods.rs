@@ -4,10 +4,10 @@
 //! called on column expressions and other SQL expressions.
 
 use crate::backend::GaussDB;
-use diesel::expression::{Expression, AsExpression};
-use diesel::sql_types::{Text, Bool};
-use diesel::query_builder::{QueryFragment, AstPass};
-use diesel::result::QueryResult;
+use diesel::expression::{AppearsOnTable, Expression, AsExpression, SelectableExpression, ValidGrouping};
+use diesel::sql_types::{is_nullable, Text, Bool, Nullable, SingleValue, SqlType, Timestamp};
+use diesel::query_builder::{QueryFragment, QueryId, AstPass};
+use diesel::result::{Error as DieselError, QueryResult};
 
 /// Trait providing PostgreSQL-specific string expression methods
 ///
@@ -109,6 +109,40 @@ pub trait GaussDBStringExpressionMethods: Expression + Sized {
     fn regex_match_insensitive<T>(self, pattern: T) -> RegexMatchInsensitive<Self, T::Expression>
     where
         T: AsExpression<Text>;
+
+    /// Creates a `LIKE ... ESCAPE` expression with a custom escape character.
+    ///
+    /// The escape character is written into the SQL as a quoted literal, so
+    /// `name.like_escape("100!%", '!')` renders as
+    /// `name LIKE $1 ESCAPE '!'`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::expression_methods::GaussDBStringExpressionMethods;
+    /// # table! { users (id) { id -> Integer, name -> Text, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // Find users whose name contains a literal underscore
+    /// let results = users::table
+    ///     .filter(users::name.like_escape("%\\_%", '\\'))
+    ///     .load::<(i32, String)>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn like_escape<T>(self, pattern: T, escape: char) -> LikeEscape<Self, T::Expression>
+    where
+        T: AsExpression<Text>;
+
+    /// Creates an `ILIKE ... ESCAPE` expression with a custom escape character.
+    ///
+    /// This is the case-insensitive counterpart of
+    /// [`like_escape`](GaussDBStringExpressionMethods::like_escape).
+    fn ilike_escape<T>(self, pattern: T, escape: char) -> ILikeEscape<Self, T::Expression>
+    where
+        T: AsExpression<Text>;
 }
 
 // Implement the trait for all text expressions
@@ -143,6 +177,20 @@ where
     {
         RegexMatchInsensitive::new(self, pattern.as_expression())
     }
+
+    fn like_escape<U>(self, pattern: U, escape: char) -> LikeEscape<Self, U::Expression>
+    where
+        U: AsExpression<Text>,
+    {
+        LikeEscape::new(self, pattern.as_expression(), escape)
+    }
+
+    fn ilike_escape<U>(self, pattern: U, escape: char) -> ILikeEscape<Self, U::Expression>
+    where
+        U: AsExpression<Text>,
+    {
+        ILikeEscape::new(self, pattern.as_expression(), escape)
+    }
 }
 
 // 使用diesel的infix_operator宏来定义ILike操作符
@@ -232,6 +280,659 @@ where
     }
 }
 
+/// Write `ESCAPE '<c>'`, quoting the escape character as a string literal
+fn walk_escape_clause(escape: char, out: &mut AstPass<'_, '_, GaussDB>) -> QueryResult<()> {
+    if escape == '\0' {
+        return Err(DieselError::QueryBuilderError(
+            "ESCAPE character must not be NUL".into(),
+        ));
+    }
+
+    // The escape character is part of the SQL text, not a bind parameter
+    out.unsafe_to_cache_prepared();
+    out.push_sql(" ESCAPE '");
+    if escape == '\'' {
+        out.push_sql("''");
+    } else {
+        out.push_sql(escape.encode_utf8(&mut [0; 4]));
+    }
+    out.push_sql("'");
+    Ok(())
+}
+
+macro_rules! escaped_pattern_operator {
+    ($(#[$meta:meta])* $name:ident, $operator:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, ValidGrouping)]
+        pub struct $name<L, R> {
+            left: L,
+            right: R,
+            escape: char,
+        }
+
+        impl<L, R> $name<L, R> {
+            /// Create a new pattern match using `escape` as escape character
+            pub fn new(left: L, right: R, escape: char) -> Self {
+                $name { left, right, escape }
+            }
+        }
+
+        impl<L, R> Expression for $name<L, R>
+        where
+            L: Expression<SqlType = Text>,
+            R: Expression<SqlType = Text>,
+        {
+            type SqlType = Bool;
+        }
+
+        impl<L, R, QS> AppearsOnTable<QS> for $name<L, R>
+        where
+            L: AppearsOnTable<QS>,
+            R: AppearsOnTable<QS>,
+            Self: Expression,
+        {
+        }
+
+        impl<L, R, QS> SelectableExpression<QS> for $name<L, R>
+        where
+            L: SelectableExpression<QS>,
+            R: SelectableExpression<QS>,
+            Self: AppearsOnTable<QS>,
+        {
+        }
+
+        // The escape character is inlined into the SQL, so two instances of
+        // this type can produce different statements
+        impl<L, R> QueryId for $name<L, R> {
+            type QueryId = ();
+
+            const HAS_STATIC_QUERY_ID: bool = false;
+        }
+
+        impl<L, R> QueryFragment<GaussDB> for $name<L, R>
+        where
+            L: QueryFragment<GaussDB>,
+            R: QueryFragment<GaussDB>,
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+                self.left.walk_ast(out.reborrow())?;
+                out.push_sql($operator);
+                self.right.walk_ast(out.reborrow())?;
+                walk_escape_clause(self.escape, &mut out)
+            }
+        }
+    };
+}
+
+escaped_pattern_operator!(
+    /// Expression for `LIKE pattern ESCAPE 'c'`
+    LikeEscape,
+    " LIKE "
+);
+
+escaped_pattern_operator!(
+    /// Expression for `ILIKE pattern ESCAPE 'c'`
+    ILikeEscape,
+    " ILIKE "
+);
+
+/// Trait providing null-safe comparisons for all expressions
+///
+/// Diesel's `PgExpressionMethods` provides methods of the same names that
+/// only render for the `Pg` backend. `diesel_gaussdb::prelude` leaves that
+/// trait out, so import `diesel_gaussdb::prelude::*` instead of
+/// `diesel::prelude::*` (it re-exports everything else from it) to call
+/// these methods without ambiguity.
+pub trait GaussDBExpressionMethods: Expression + Sized {
+    /// Creates an `IS DISTINCT FROM` expression.
+    ///
+    /// This behaves like `!=`, except that `NULL` is compared like any other
+    /// value: two `NULL`s are not distinct, and `NULL` is distinct from every
+    /// non-null value. The result is never `NULL`, which makes it suitable
+    /// for detecting changes in nullable columns.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # diesel::table! { users (id) { id -> Integer, nickname -> Nullable<Text>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // Includes users without a nickname, which `ne` would skip
+    /// let renamed = users::table
+    ///     .select(users::id)
+    ///     .filter(users::nickname.is_distinct_from("sean"))
+    ///     .load::<i32>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::wrong_self_convention)] // named after the SQL operator
+    fn is_distinct_from<T>(self, other: T) -> IsDistinctFrom<Self, T::Expression>
+    where
+        Self::SqlType: SqlType,
+        T: AsExpression<Self::SqlType>,
+    {
+        IsDistinctFrom::new(self, other.as_expression())
+    }
+
+    /// Creates an `IS NOT DISTINCT FROM` expression.
+    ///
+    /// This behaves like `=`, except that two `NULL`s compare as equal and
+    /// `NULL` compared with a non-null value is false rather than `NULL`.
+    #[allow(clippy::wrong_self_convention)] // named after the SQL operator
+    fn is_not_distinct_from<T>(self, other: T) -> IsNotDistinctFrom<Self, T::Expression>
+    where
+        Self::SqlType: SqlType,
+        T: AsExpression<Self::SqlType>,
+    {
+        IsNotDistinctFrom::new(self, other.as_expression())
+    }
+
+    /// Replaces `NULL` with `default`, rendered as `COALESCE(expr, $1)`.
+    ///
+    /// Available on nullable expressions, which includes aggregates like
+    /// `sum` and `max` that are `NULL` for an empty group. The default is
+    /// sent as a bind parameter and the result is no longer nullable.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # diesel::table! { orders (id) { id -> Integer, customer_id -> Integer, total -> Integer, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // 0 instead of NULL for a customer without orders
+    /// let spent = orders::table
+    ///     .filter(orders::customer_id.eq(42))
+    ///     .select(diesel::dsl::sum(orders::total).coalesce_default(0i64))
+    ///     .get_result::<i64>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn coalesce_default<ST, T>(self, default: T) -> CoalesceDefault<Self, T::Expression>
+    where
+        Self: Expression<SqlType = Nullable<ST>>,
+        ST: SqlType + SingleValue,
+        T: AsExpression<ST>,
+    {
+        CoalesceDefault::new(self, default.as_expression())
+    }
+
+    /// Applies a collation, rendered as `(expr COLLATE "collation")`.
+    ///
+    /// The collation decides how text is compared and sorted, so this
+    /// works in filters as well as in `order_by`, where it composes with
+    /// `asc` and `desc`. The name is quoted as an identifier and therefore
+    /// matched case-sensitively; it is not split on `.`, since names like
+    /// `en_US.utf8` contain dots.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # diesel::table! { users (id) { id -> Integer, name -> Text, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // ORDER BY ("users"."name" COLLATE "C") DESC
+    /// let names = users::table
+    ///     .select(users::name)
+    ///     .order_by(users::name.collate("C").desc())
+    ///     .load::<String>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn collate(self, collation: &str) -> Collate<Self>
+    where
+        Self::SqlType: Collatable,
+    {
+        Collate::new(self, collation)
+    }
+}
+
+impl<T: Expression> GaussDBExpressionMethods for T {}
+
+/// Trait providing time zone conversions of timestamps
+///
+/// Diesel's `PgTimestampExpressionMethods` provides a method of the same
+/// name that only renders for the `Pg` backend and always returns
+/// `Timestamp`; `diesel_gaussdb::prelude` exports this trait instead.
+pub trait GaussDBTimestampExpressionMethods: Expression + Sized {
+    /// Converts between local and absolute time, rendered as
+    /// `(expr AT TIME ZONE $1)`.
+    ///
+    /// The SQL type flips as on the server: a `timestamptz` becomes the
+    /// `timestamp` shown by a wall clock in `timezone`, and a `timestamp`
+    /// is read as wall clock time in `timezone` and becomes a
+    /// `timestamptz`. The time zone is a name like `Europe/Berlin`, an
+    /// abbreviation like `UTC`, or a POSIX offset.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # diesel::table! { orders (id) { id -> Integer, placed_at -> Timestamptz, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // Orders per local calendar day in Shanghai
+    /// let local_days = orders::table
+    ///     .select(diesel::dsl::date(orders::placed_at.at_time_zone("Asia/Shanghai")))
+    ///     .load::<diesel_gaussdb::types::date_and_time::GaussDBDate>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn at_time_zone<T>(self, timezone: T) -> AtTimeZone<Self, T::Expression>
+    where
+        Self::SqlType: TimeZoneConvertible,
+        T: AsExpression<Text>,
+    {
+        AtTimeZone::new(self, timezone.as_expression())
+    }
+}
+
+impl<T> GaussDBTimestampExpressionMethods for T
+where
+    T: Expression,
+    T::SqlType: TimeZoneConvertible,
+{
+}
+
+/// Trait providing the boolean tests `IS TRUE`, `IS FALSE` and `IS UNKNOWN`
+///
+/// Unlike comparing with `eq(true)`, these tests are never `NULL`: a `NULL`
+/// operand is neither true nor false, so `NULL IS TRUE` is false and
+/// `NULL IS NOT TRUE` is true. The result is always `Bool`, even for a
+/// nullable operand.
+pub trait GaussDBBoolExpressionMethods: Expression + Sized {
+    /// Creates an `IS TRUE` expression, false for `NULL`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # diesel::table! { users (id) { id -> Integer, verified -> Nullable<Bool>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // (id, verified IS TRUE), with `false` for users never checked
+    /// let verified = users::table
+    ///     .select((users::id, users::verified.is_true()))
+    ///     .load::<(i32, bool)>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::wrong_self_convention)] // named after the SQL operator
+    fn is_true(self) -> IsTrue<Self> {
+        IsTrue::new(self)
+    }
+
+    /// Creates an `IS FALSE` expression, false for `NULL`.
+    #[allow(clippy::wrong_self_convention)] // named after the SQL operator
+    fn is_false(self) -> IsFalse<Self> {
+        IsFalse::new(self)
+    }
+
+    /// Creates an `IS NOT TRUE` expression, true for `NULL`.
+    #[allow(clippy::wrong_self_convention)] // named after the SQL operator
+    fn is_not_true(self) -> IsNotTrue<Self> {
+        IsNotTrue::new(self)
+    }
+
+    /// Creates an `IS NOT FALSE` expression, true for `NULL`.
+    #[allow(clippy::wrong_self_convention)] // named after the SQL operator
+    fn is_not_false(self) -> IsNotFalse<Self> {
+        IsNotFalse::new(self)
+    }
+
+    /// Creates an `IS UNKNOWN` expression, which is true only for `NULL`.
+    #[allow(clippy::wrong_self_convention)] // named after the SQL operator
+    fn is_unknown(self) -> IsUnknown<Self> {
+        IsUnknown::new(self)
+    }
+}
+
+impl<T> GaussDBBoolExpressionMethods for T
+where
+    T: Expression,
+    T::SqlType: diesel::sql_types::BoolOrNullableBool,
+{
+}
+
+diesel::postfix_operator!(IsTrue, " IS TRUE", backend: GaussDB);
+diesel::postfix_operator!(IsFalse, " IS FALSE", backend: GaussDB);
+diesel::postfix_operator!(IsNotTrue, " IS NOT TRUE", backend: GaussDB);
+diesel::postfix_operator!(IsNotFalse, " IS NOT FALSE", backend: GaussDB);
+diesel::postfix_operator!(IsUnknown, " IS UNKNOWN", backend: GaussDB);
+
+/// Trait providing the placement of `NULL`s in an ordering
+///
+/// `NULL` sorts after all other values, so it comes last in ascending and
+/// first in descending order. These methods change that for one ordering.
+/// They are only available on `asc()` and `desc()`, so the direction has to
+/// be spelled out even where `ASC` is implied.
+///
+/// Diesel's `PgSortExpressionMethods` provides methods of the same names that
+/// only render for the `Pg` backend; `diesel_gaussdb::prelude` exports this
+/// trait instead.
+pub trait GaussDBSortExpressionMethods: Sized {
+    /// Sorts `NULL`s before all other values, rendered as `NULLS FIRST`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # diesel::table! { users (id) { id -> Integer, age -> Nullable<Integer>, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // ORDER BY "users"."age" ASC NULLS FIRST
+    /// let ages = users::table
+    ///     .select(users::age)
+    ///     .order_by(users::age.asc().nulls_first())
+    ///     .load::<Option<i32>>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn nulls_first(self) -> NullsFirst<Self> {
+        NullsFirst::new(self)
+    }
+
+    /// Sorts `NULL`s after all other values, rendered as `NULLS LAST`.
+    fn nulls_last(self) -> NullsLast<Self> {
+        NullsLast::new(self)
+    }
+}
+
+impl<T> GaussDBSortExpressionMethods for diesel::dsl::Asc<T> {}
+impl<T> GaussDBSortExpressionMethods for diesel::dsl::Desc<T> {}
+
+diesel::postfix_operator!(
+    NullsFirst,
+    " NULLS FIRST",
+    diesel::expression::expression_types::NotSelectable,
+    backend: GaussDB
+);
+diesel::postfix_operator!(
+    NullsLast,
+    " NULLS LAST",
+    diesel::expression::expression_types::NotSelectable,
+    backend: GaussDB
+);
+
+/// Timestamp SQL types that can be converted with
+/// [`at_time_zone`](GaussDBTimestampExpressionMethods::at_time_zone)
+pub trait TimeZoneConvertible: SqlType + SingleValue {
+    /// The SQL type after the conversion
+    type Converted: SqlType + SingleValue;
+}
+
+impl TimeZoneConvertible for Timestamp {
+    type Converted = diesel::sql_types::Timestamptz;
+}
+
+impl TimeZoneConvertible for diesel::sql_types::Timestamptz {
+    type Converted = Timestamp;
+}
+
+impl TimeZoneConvertible for crate::types::sql_types::Timestamptz {
+    type Converted = Timestamp;
+}
+
+impl<T> TimeZoneConvertible for Nullable<T>
+where
+    T: TimeZoneConvertible + SqlType<IsNull = is_nullable::NotNull>,
+    T::Converted: SqlType<IsNull = is_nullable::NotNull>,
+{
+    type Converted = Nullable<T::Converted>;
+}
+
+/// SQL types that can be given a collation, see
+/// [`collate`](GaussDBExpressionMethods::collate)
+pub trait Collatable {}
+
+impl Collatable for Text {}
+impl Collatable for Nullable<Text> {}
+
+// `infix_operator!` makes the result nullable if an operand is nullable, but
+// these comparisons are true or false even for NULL operands, so the result
+// is always `Bool`
+macro_rules! null_safe_comparison {
+    ($(#[$meta:meta])* $name:ident, $operator:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+        pub struct $name<L, R> {
+            left: L,
+            right: R,
+        }
+
+        impl<L, R> $name<L, R> {
+            /// Create a new comparison of `left` and `right`
+            pub fn new(left: L, right: R) -> Self {
+                $name { left, right }
+            }
+        }
+
+        impl<L, R> Expression for $name<L, R>
+        where
+            L: Expression,
+            R: Expression,
+        {
+            type SqlType = Bool;
+        }
+
+        impl<L, R, QS> AppearsOnTable<QS> for $name<L, R>
+        where
+            L: AppearsOnTable<QS>,
+            R: AppearsOnTable<QS>,
+            Self: Expression,
+        {
+        }
+
+        impl<L, R, QS> SelectableExpression<QS> for $name<L, R>
+        where
+            L: SelectableExpression<QS>,
+            R: SelectableExpression<QS>,
+            Self: AppearsOnTable<QS>,
+        {
+        }
+
+        impl<L, R> QueryFragment<GaussDB> for $name<L, R>
+        where
+            L: QueryFragment<GaussDB>,
+            R: QueryFragment<GaussDB>,
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+                self.left.walk_ast(out.reborrow())?;
+                out.push_sql($operator);
+                self.right.walk_ast(out.reborrow())?;
+                Ok(())
+            }
+        }
+    };
+}
+
+null_safe_comparison!(
+    /// Expression for `IS DISTINCT FROM`
+    IsDistinctFrom,
+    " IS DISTINCT FROM "
+);
+
+null_safe_comparison!(
+    /// Expression for `IS NOT DISTINCT FROM`
+    IsNotDistinctFrom,
+    " IS NOT DISTINCT FROM "
+);
+
+/// Expression for `COALESCE(expr, default)`, see
+/// [`coalesce_default`](GaussDBExpressionMethods::coalesce_default)
+#[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+pub struct CoalesceDefault<E, D> {
+    expr: E,
+    default: D,
+}
+
+impl<E, D> CoalesceDefault<E, D> {
+    /// Create a new `COALESCE` of `expr` falling back to `default`
+    pub fn new(expr: E, default: D) -> Self {
+        CoalesceDefault { expr, default }
+    }
+}
+
+impl<E, D> Expression for CoalesceDefault<E, D>
+where
+    E: Expression,
+    D: Expression,
+{
+    // The default is not nullable, so neither is the result
+    type SqlType = D::SqlType;
+}
+
+impl<E, D, QS> AppearsOnTable<QS> for CoalesceDefault<E, D>
+where
+    E: AppearsOnTable<QS>,
+    D: AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+impl<E, D, QS> SelectableExpression<QS> for CoalesceDefault<E, D>
+where
+    E: SelectableExpression<QS>,
+    D: SelectableExpression<QS>,
+    Self: AppearsOnTable<QS>,
+{
+}
+
+impl<E, D> QueryFragment<GaussDB> for CoalesceDefault<E, D>
+where
+    E: QueryFragment<GaussDB>,
+    D: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("COALESCE(");
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.default.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+/// Expression for `(expr AT TIME ZONE timezone)`, see
+/// [`at_time_zone`](GaussDBTimestampExpressionMethods::at_time_zone)
+#[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+pub struct AtTimeZone<E, Tz> {
+    expr: E,
+    timezone: Tz,
+}
+
+impl<E, Tz> AtTimeZone<E, Tz> {
+    /// Create a new conversion of `expr` to or from `timezone`
+    pub fn new(expr: E, timezone: Tz) -> Self {
+        AtTimeZone { expr, timezone }
+    }
+}
+
+impl<E, Tz> Expression for AtTimeZone<E, Tz>
+where
+    E: Expression,
+    E::SqlType: TimeZoneConvertible,
+    Tz: Expression<SqlType = Text>,
+{
+    type SqlType = <E::SqlType as TimeZoneConvertible>::Converted;
+}
+
+impl<E, Tz, QS> AppearsOnTable<QS> for AtTimeZone<E, Tz>
+where
+    E: AppearsOnTable<QS>,
+    Tz: AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+impl<E, Tz, QS> SelectableExpression<QS> for AtTimeZone<E, Tz>
+where
+    E: SelectableExpression<QS>,
+    Tz: SelectableExpression<QS>,
+    Self: AppearsOnTable<QS>,
+{
+}
+
+impl<E, Tz> QueryFragment<GaussDB> for AtTimeZone<E, Tz>
+where
+    E: QueryFragment<GaussDB>,
+    Tz: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        // `AT TIME ZONE` binds tighter than `+` and `-`, so keep it whole
+        out.push_sql("(");
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" AT TIME ZONE ");
+        self.timezone.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+/// Expression for `(expr COLLATE "collation")`, see
+/// [`collate`](GaussDBExpressionMethods::collate)
+#[derive(Debug, Clone)]
+pub struct Collate<E> {
+    expr: E,
+    collation: String,
+}
+
+impl<E> Collate<E> {
+    /// Create a new `COLLATE` of `expr`
+    pub fn new(expr: E, collation: &str) -> Self {
+        Collate { expr, collation: collation.to_string() }
+    }
+}
+
+impl<E: Expression> Expression for Collate<E> {
+    type SqlType = E::SqlType;
+}
+
+impl<E, QS> AppearsOnTable<QS> for Collate<E>
+where
+    E: AppearsOnTable<QS>,
+{
+}
+
+impl<E, QS> SelectableExpression<QS> for Collate<E>
+where
+    E: SelectableExpression<QS>,
+{
+}
+
+impl<E, GB> ValidGrouping<GB> for Collate<E>
+where
+    E: ValidGrouping<GB>,
+{
+    type IsAggregate = E::IsAggregate;
+}
+
+// The collation is part of the SQL text
+impl<E> QueryId for Collate<E> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<E> QueryFragment<GaussDB> for Collate<E>
+where
+    E: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        if self.collation.is_empty() {
+            return Err(DieselError::QueryBuilderError("Collation name must not be empty".into()));
+        }
+        // Keep the collated expression whole, like `AT TIME ZONE`
+        out.push_sql("(");
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" COLLATE ");
+        out.push_identifier(&self.collation)?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,12 +976,16 @@ mod tests {
         let not_ilike = NotILike::new((), ());
         let regex_match = RegexMatch::new((), ());
         let regex_match_insensitive = RegexMatchInsensitive::new((), ());
+        let like_escape = LikeEscape::new((), (), '\\');
+        let ilike_escape = ILikeEscape::new((), (), '!');
         
         // Test that debug formatting works
         let _debug_ilike = format!("{:?}", ilike);
         let _debug_not_ilike = format!("{:?}", not_ilike);
         let _debug_regex = format!("{:?}", regex_match);
         let _debug_regex_insensitive = format!("{:?}", regex_match_insensitive);
+        let _debug_like_escape = format!("{:?}", like_escape);
+        let _debug_ilike_escape = format!("{:?}", ilike_escape);
         
         assert!(true);
     }
@@ -0,0 +1,817 @@
+//! Aggregate functions for GaussDB
+//!
+//! This module provides the PostgreSQL-compatible aggregates `SUM`, `AVG`,
+//! `BOOL_AND`, `BOOL_OR`, `BIT_AND` and `BIT_OR`. Like all aggregates they
+//! can be combined with `GROUP BY`, and each of them can be restricted to a
+//! subset of the rows with `aggregate_filter`. `count_if` counts the rows
+//! matching a predicate in the same way.
+//!
+//! The ordered-set aggregates `PERCENTILE_CONT`, `PERCENTILE_DISC` and
+//! `MODE` aggregate the values of their `WITHIN GROUP (ORDER BY ...)`
+//! clause instead of an argument. They only become expressions once
+//! `within_group` is called.
+
+use crate::backend::GaussDB;
+use diesel::expression::{
+    is_aggregate, AppearsOnTable, AsExpression, Expression, SelectableExpression,
+    TypedExpressionType, ValidGrouping,
+};
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::result::QueryResult;
+use diesel::sql_types::{
+    is_nullable, BigInt, Bool, BoolOrNullableBool, Double, Float, Integer, Interval, IntoNullable,
+    Nullable, Numeric, SingleValue, SmallInt, SqlType,
+};
+use std::marker::PhantomData;
+
+/// Integer types accepted by `BIT_AND` and `BIT_OR`
+///
+/// The result has the type of the input and is `NULL` if no row is
+/// aggregated.
+pub trait BitAggregate: SqlType + SingleValue {
+    /// The SQL type of the aggregated value
+    type Output: SqlType + SingleValue;
+}
+
+impl BitAggregate for SmallInt {
+    type Output = Nullable<SmallInt>;
+}
+
+impl BitAggregate for Integer {
+    type Output = Nullable<Integer>;
+}
+
+impl BitAggregate for BigInt {
+    type Output = Nullable<BigInt>;
+}
+
+impl<ST> BitAggregate for Nullable<ST>
+where
+    ST: BitAggregate + SqlType<IsNull = is_nullable::NotNull>,
+{
+    type Output = Nullable<ST>;
+}
+
+/// Numeric types accepted by `SUM` and `AVG`
+///
+/// The result types follow the server: integer sums widen to `BigInt`, or
+/// to `Numeric` for `BigInt` input, averages of integers and `Numeric` are
+/// `Numeric`, and floating point input stays floating point. `Numeric` is
+/// never converted to a float. Both results are `NULL` if no row is
+/// aggregated.
+pub trait NumericAggregate: SqlType + SingleValue {
+    /// The SQL type of `SUM`
+    type Sum: SqlType + SingleValue;
+    /// The SQL type of `AVG`
+    type Avg: SqlType + SingleValue;
+}
+
+impl NumericAggregate for SmallInt {
+    type Sum = Nullable<BigInt>;
+    type Avg = Nullable<Numeric>;
+}
+
+impl NumericAggregate for Integer {
+    type Sum = Nullable<BigInt>;
+    type Avg = Nullable<Numeric>;
+}
+
+impl NumericAggregate for BigInt {
+    type Sum = Nullable<Numeric>;
+    type Avg = Nullable<Numeric>;
+}
+
+impl NumericAggregate for Float {
+    type Sum = Nullable<Float>;
+    type Avg = Nullable<Double>;
+}
+
+impl NumericAggregate for Double {
+    type Sum = Nullable<Double>;
+    type Avg = Nullable<Double>;
+}
+
+impl NumericAggregate for Numeric {
+    type Sum = Nullable<Numeric>;
+    type Avg = Nullable<Numeric>;
+}
+
+impl<ST> NumericAggregate for Nullable<ST>
+where
+    ST: NumericAggregate + SqlType<IsNull = is_nullable::NotNull>,
+{
+    type Sum = ST::Sum;
+    type Avg = ST::Avg;
+}
+
+/// The argument of an aggregate restricted by `aggregate_filter`
+///
+/// Rendered as `CASE WHEN predicate THEN expr END`. GaussDB doesn't accept
+/// the `FILTER (WHERE ...)` clause, but the aggregates in this module ignore
+/// `NULL` inputs, so skipping the rows this way gives the same result.
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct AggregateFilter<Expr, Predicate> {
+    expr: Expr,
+    predicate: Predicate,
+}
+
+impl<Expr, Predicate> Expression for AggregateFilter<Expr, Predicate>
+where
+    Expr: Expression,
+    Expr::SqlType: IntoNullable,
+    <Expr::SqlType as IntoNullable>::Nullable: SingleValue,
+    Predicate: Expression,
+    Predicate::SqlType: BoolOrNullableBool,
+{
+    type SqlType = <Expr::SqlType as IntoNullable>::Nullable;
+}
+
+impl<Expr, Predicate> QueryFragment<GaussDB> for AggregateFilter<Expr, Predicate>
+where
+    Expr: QueryFragment<GaussDB>,
+    Predicate: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("CASE WHEN ");
+        self.predicate.walk_ast(out.reborrow())?;
+        out.push_sql(" THEN ");
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(" END");
+        Ok(())
+    }
+}
+
+impl<Expr, Predicate, QS> SelectableExpression<QS> for AggregateFilter<Expr, Predicate>
+where
+    AggregateFilter<Expr, Predicate>: AppearsOnTable<QS>,
+{
+}
+
+impl<Expr, Predicate, QS> AppearsOnTable<QS> for AggregateFilter<Expr, Predicate>
+where
+    AggregateFilter<Expr, Predicate>: Expression,
+    Expr: AppearsOnTable<QS>,
+    Predicate: AppearsOnTable<QS>,
+{
+}
+
+macro_rules! aggregate_function {
+    (
+        $(#[$meta:meta])*
+        $fn_name:ident, $name:ident, $sql_name:expr, $bound:path => $output:ty
+    ) => {
+        $(#[$meta])*
+        pub fn $fn_name<Expr>(expr: Expr) -> $name<Expr>
+        where
+            Expr: Expression,
+            Expr::SqlType: $bound,
+        {
+            $name { expr }
+        }
+
+        #[doc = concat!("PostgreSQL `", $sql_name, "` aggregate function")]
+        #[derive(Debug, Clone, QueryId)]
+        pub struct $name<Expr> {
+            expr: Expr,
+        }
+
+        impl<Expr> $name<Expr> {
+            /// Only aggregate the rows for which `predicate` is true
+            pub fn aggregate_filter<Predicate>(
+                self,
+                predicate: Predicate,
+            ) -> $name<AggregateFilter<Expr, Predicate>>
+            where
+                Predicate: Expression,
+                Predicate::SqlType: BoolOrNullableBool,
+            {
+                $name {
+                    expr: AggregateFilter {
+                        expr: self.expr,
+                        predicate,
+                    },
+                }
+            }
+        }
+
+        impl<Expr> Expression for $name<Expr>
+        where
+            Expr: Expression,
+            Expr::SqlType: $bound,
+        {
+            type SqlType = $output;
+        }
+
+        impl<Expr, GB> ValidGrouping<GB> for $name<Expr> {
+            type IsAggregate = is_aggregate::Yes;
+        }
+
+        impl<Expr> QueryFragment<GaussDB> for $name<Expr>
+        where
+            Expr: QueryFragment<GaussDB>,
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+                out.push_sql(concat!($sql_name, "("));
+                self.expr.walk_ast(out.reborrow())?;
+                out.push_sql(")");
+                Ok(())
+            }
+        }
+
+        impl<Expr, QS> SelectableExpression<QS> for $name<Expr>
+        where
+            $name<Expr>: AppearsOnTable<QS>,
+        {
+        }
+
+        impl<Expr, QS> AppearsOnTable<QS> for $name<Expr>
+        where
+            $name<Expr>: Expression,
+            Expr: AppearsOnTable<QS>,
+        {
+        }
+    };
+}
+
+aggregate_function!(
+    /// Creates a PostgreSQL `SUM(expr)` aggregate expression.
+    ///
+    /// Returns the sum of all non-null values, and `NULL` if there are
+    /// none. The sum of a `Numeric` expression is `Numeric`; see
+    /// [`NumericAggregate`] for the other result types.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::sum;
+    /// # use diesel::sql_types::Numeric;
+    /// // SUM(amount)
+    /// let total = sum(diesel::dsl::sql::<Numeric>("amount"));
+    /// ```
+    sum, SumFunction, "SUM", NumericAggregate => <Expr::SqlType as NumericAggregate>::Sum
+);
+
+aggregate_function!(
+    /// Creates a PostgreSQL `AVG(expr)` aggregate expression.
+    ///
+    /// Returns the average of all non-null values, and `NULL` if there are
+    /// none. The average of a `Numeric` expression is `Numeric` with the
+    /// scale chosen by the server; see [`NumericAggregate`] for the other
+    /// result types.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::avg;
+    /// # use diesel::sql_types::Numeric;
+    /// // AVG(amount)
+    /// let average = avg(diesel::dsl::sql::<Numeric>("amount"));
+    /// ```
+    avg, AvgFunction, "AVG", NumericAggregate => <Expr::SqlType as NumericAggregate>::Avg
+);
+
+aggregate_function!(
+    /// Creates a PostgreSQL `BOOL_AND(expr)` aggregate expression.
+    ///
+    /// Returns true if all aggregated values are true, and `NULL` if there
+    /// are no non-null values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::bool_and;
+    /// # use diesel::sql_types::Bool;
+    /// // BOOL_AND(published)
+    /// let all_published = bool_and(diesel::dsl::sql::<Bool>("published"));
+    /// ```
+    bool_and, BoolAndFunction, "BOOL_AND", BoolOrNullableBool => Nullable<Bool>
+);
+
+aggregate_function!(
+    /// Creates a PostgreSQL `BOOL_OR(expr)` aggregate expression.
+    ///
+    /// Returns true if at least one aggregated value is true, and `NULL` if
+    /// there are no non-null values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::bool_or;
+    /// # use diesel::sql_types::Bool;
+    /// // BOOL_OR(published)
+    /// let any_published = bool_or(diesel::dsl::sql::<Bool>("published"));
+    /// ```
+    bool_or, BoolOrFunction, "BOOL_OR", BoolOrNullableBool => Nullable<Bool>
+);
+
+aggregate_function!(
+    /// Creates a PostgreSQL `BIT_AND(expr)` aggregate expression.
+    ///
+    /// Returns the bitwise AND of all non-null values, and `NULL` if there
+    /// are none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::bit_and;
+    /// # use diesel::sql_types::Integer;
+    /// // BIT_AND(flags)
+    /// let common_flags = bit_and(diesel::dsl::sql::<Integer>("flags"));
+    /// ```
+    bit_and, BitAndFunction, "BIT_AND", BitAggregate => <Expr::SqlType as BitAggregate>::Output
+);
+
+aggregate_function!(
+    /// Creates a PostgreSQL `BIT_OR(expr)` aggregate expression.
+    ///
+    /// Returns the bitwise OR of all non-null values, and `NULL` if there
+    /// are none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::bit_or;
+    /// # use diesel::sql_types::Integer;
+    /// // BIT_OR(flags)
+    /// let any_flags = bit_or(diesel::dsl::sql::<Integer>("flags"));
+    /// ```
+    bit_or, BitOrFunction, "BIT_OR", BitAggregate => <Expr::SqlType as BitAggregate>::Output
+);
+
+/// Creates a `COUNT(CASE WHEN predicate THEN 1 END)` aggregate expression.
+///
+/// Counts the rows of a group for which `predicate` is true; rows where it
+/// is false or `NULL` are not counted. This is the portable form of
+/// `COUNT(*) FILTER (WHERE predicate)`, which GaussDB doesn't accept. Like
+/// `COUNT`, the result is a `BigInt` and 0 if no row matches.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::count_if;
+/// # diesel::table! { posts (id) { id -> Integer, user_id -> Integer, published -> Bool, } }
+/// // COUNT(CASE WHEN published THEN 1 END)
+/// let published_posts = count_if(posts::published);
+/// ```
+pub fn count_if<Predicate>(predicate: Predicate) -> CountIf<Predicate>
+where
+    Predicate: Expression,
+    Predicate::SqlType: BoolOrNullableBool,
+{
+    CountIf { predicate }
+}
+
+/// The `COUNT(CASE WHEN ... THEN 1 END)` aggregate, see [`count_if`]
+#[derive(Debug, Clone, QueryId)]
+pub struct CountIf<Predicate> {
+    predicate: Predicate,
+}
+
+impl<Predicate> Expression for CountIf<Predicate>
+where
+    Predicate: Expression,
+    Predicate::SqlType: BoolOrNullableBool,
+{
+    type SqlType = BigInt;
+}
+
+impl<Predicate, GB> ValidGrouping<GB> for CountIf<Predicate> {
+    type IsAggregate = is_aggregate::Yes;
+}
+
+impl<Predicate> QueryFragment<GaussDB> for CountIf<Predicate>
+where
+    Predicate: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("COUNT(CASE WHEN ");
+        self.predicate.walk_ast(out.reborrow())?;
+        out.push_sql(" THEN 1 END)");
+        Ok(())
+    }
+}
+
+impl<Predicate, QS> SelectableExpression<QS> for CountIf<Predicate> where CountIf<Predicate>: AppearsOnTable<QS> {}
+
+impl<Predicate, QS> AppearsOnTable<QS> for CountIf<Predicate>
+where
+    CountIf<Predicate>: Expression,
+    Predicate: AppearsOnTable<QS>,
+{
+}
+
+/// Types accepted by `PERCENTILE_CONT`
+///
+/// The server interpolates in `double precision` or `interval`, so numbers
+/// of every type give a `Double` and intervals an `Interval`. The result
+/// is `NULL` if no row is aggregated.
+pub trait PercentileContType: SqlType + SingleValue {
+    /// The SQL type of the interpolated value
+    type Output: SqlType + SingleValue;
+}
+
+macro_rules! percentile_cont_types {
+    ($($ty:ty => $output:ty),*) => {
+        $(
+            impl PercentileContType for $ty {
+                type Output = Nullable<$output>;
+            }
+        )*
+    };
+}
+
+percentile_cont_types!(
+    SmallInt => Double,
+    Integer => Double,
+    BigInt => Double,
+    Float => Double,
+    Double => Double,
+    Numeric => Double,
+    Interval => Interval
+);
+
+impl<ST> PercentileContType for Nullable<ST>
+where
+    ST: PercentileContType + SqlType<IsNull = is_nullable::NotNull>,
+{
+    type Output = ST::Output;
+}
+
+/// How an ordered-set aggregate derives its result type from the type of
+/// its `WITHIN GROUP` ordering
+pub trait OrderedSetKind<ST> {
+    /// The SQL type of the aggregate
+    type Output: SqlType + TypedExpressionType;
+}
+
+/// `PERCENTILE_DISC` and `MODE`, which return one of the ordered values
+#[derive(Debug, Clone, Copy)]
+pub struct ReturnsInput;
+
+/// `PERCENTILE_CONT`, which interpolates between the ordered values
+#[derive(Debug, Clone, Copy)]
+pub struct Interpolates;
+
+impl<ST> OrderedSetKind<ST> for ReturnsInput
+where
+    ST: IntoNullable,
+    ST::Nullable: SqlType + TypedExpressionType,
+{
+    type Output = ST::Nullable;
+}
+
+impl<ST> OrderedSetKind<ST> for Interpolates
+where
+    ST: PercentileContType,
+    ST::Output: TypedExpressionType,
+{
+    type Output = ST::Output;
+}
+
+/// The direct arguments of an ordered-set aggregate: `()` for `MODE` and
+/// the fraction `(f,)` for the percentiles
+pub trait OrderedSetArguments<QS> {}
+
+impl<QS> OrderedSetArguments<QS> for () {}
+
+impl<F, QS> OrderedSetArguments<QS> for (F,) where F: AppearsOnTable<QS> {}
+
+/// Creates a PostgreSQL `PERCENTILE_CONT(fraction)` ordered-set aggregate.
+///
+/// Returns the value at `fraction` (between 0 and 1) of the ordered values,
+/// interpolating between the two nearest ones, so the median of `1, 2, 3,
+/// 4` is `2.5`. The values are given with [`within_group`]; see
+/// [`PercentileContType`] for the result types.
+///
+/// [`within_group`]: OrderedSetFunction::within_group
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::percentile_cont;
+/// # use diesel::sql_types::Integer;
+/// // PERCENTILE_CONT($1) WITHIN GROUP (ORDER BY response_ms)
+/// let median = percentile_cont(0.5).within_group(diesel::dsl::sql::<Integer>("response_ms"));
+/// ```
+pub fn percentile_cont<F>(fraction: F) -> OrderedSetFunction<(F::Expression,), Interpolates>
+where
+    F: AsExpression<Double>,
+{
+    OrderedSetFunction::new("PERCENTILE_CONT", (fraction.as_expression(),))
+}
+
+/// Creates a PostgreSQL `PERCENTILE_DISC(fraction)` ordered-set aggregate.
+///
+/// Returns the first of the ordered values whose position is at or after
+/// `fraction` (between 0 and 1). Unlike [`percentile_cont`] the result is
+/// always one of the values, never an interpolation, so the discrete median
+/// of `1, 2, 3, 4` is `2`. The values are given with [`within_group`], and
+/// the result has their type, made nullable for an empty group.
+///
+/// [`within_group`]: OrderedSetFunction::within_group
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::percentile_disc;
+/// # use diesel::sql_types::Integer;
+/// // PERCENTILE_DISC($1) WITHIN GROUP (ORDER BY rating)
+/// let median_rating = percentile_disc(0.5).within_group(diesel::dsl::sql::<Integer>("rating"));
+/// ```
+pub fn percentile_disc<F>(fraction: F) -> OrderedSetFunction<(F::Expression,), ReturnsInput>
+where
+    F: AsExpression<Double>,
+{
+    OrderedSetFunction::new("PERCENTILE_DISC", (fraction.as_expression(),))
+}
+
+/// Creates a PostgreSQL `MODE()` ordered-set aggregate.
+///
+/// Returns the most frequent of the values given with [`within_group`],
+/// with their type made nullable for an empty group. Ties go to the value
+/// that sorts first, so [`within_group_desc`] picks the largest one.
+///
+/// [`within_group`]: OrderedSetFunction::within_group
+/// [`within_group_desc`]: OrderedSetFunction::within_group_desc
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::mode;
+/// # use diesel::sql_types::Text;
+/// // MODE() WITHIN GROUP (ORDER BY country)
+/// let most_common_country = mode().within_group(diesel::dsl::sql::<Text>("country"));
+/// ```
+pub fn mode() -> OrderedSetFunction<(), ReturnsInput> {
+    OrderedSetFunction::new("MODE", ())
+}
+
+/// An ordered-set aggregate that still needs its `WITHIN GROUP` clause
+///
+/// Not an expression by itself: call [`within_group`](Self::within_group)
+/// or [`within_group_desc`](Self::within_group_desc) with the values to
+/// aggregate. Selecting it without them doesn't compile:
+///
+/// ```rust,compile_fail
+/// # use diesel::prelude::*;
+/// # use diesel_gaussdb::expression::functions::mode;
+/// # diesel::table! { reviews (id) { id -> Integer, rating -> Integer, } }
+/// let query = reviews::table.select(mode());
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[must_use = "ordered-set aggregates need a `within_group` ordering"]
+pub struct OrderedSetFunction<Args, Kind> {
+    function: &'static str,
+    args: Args,
+    kind: PhantomData<Kind>,
+}
+
+impl<Args, Kind> OrderedSetFunction<Args, Kind> {
+    fn new(function: &'static str, args: Args) -> Self {
+        OrderedSetFunction {
+            function,
+            args,
+            kind: PhantomData,
+        }
+    }
+
+    /// Aggregate the values of `order`, sorted in ascending order
+    pub fn within_group<Order>(
+        self,
+        order: Order,
+    ) -> OrderedSetAggregate<Args, Order, <Kind as OrderedSetKind<Order::SqlType>>::Output>
+    where
+        Order: Expression,
+        Kind: OrderedSetKind<Order::SqlType>,
+    {
+        self.with_order(order, false)
+    }
+
+    /// Aggregate the values of `order`, sorted in descending order
+    pub fn within_group_desc<Order>(
+        self,
+        order: Order,
+    ) -> OrderedSetAggregate<Args, Order, <Kind as OrderedSetKind<Order::SqlType>>::Output>
+    where
+        Order: Expression,
+        Kind: OrderedSetKind<Order::SqlType>,
+    {
+        self.with_order(order, true)
+    }
+
+    fn with_order<Order, ST>(self, order: Order, descending: bool) -> OrderedSetAggregate<Args, Order, ST> {
+        OrderedSetAggregate {
+            function: self.function,
+            args: self.args,
+            order,
+            descending,
+            sql_type: PhantomData,
+        }
+    }
+}
+
+/// PostgreSQL `PERCENTILE_CONT`, `PERCENTILE_DISC` and `MODE` ordered-set
+/// aggregates with their `WITHIN GROUP (ORDER BY ...)` clause, returning
+/// `ST`
+#[derive(Debug, Clone)]
+pub struct OrderedSetAggregate<Args, Order, ST> {
+    function: &'static str,
+    args: Args,
+    order: Order,
+    descending: bool,
+    sql_type: PhantomData<ST>,
+}
+
+impl<Args, Order, ST> Expression for OrderedSetAggregate<Args, Order, ST>
+where
+    Order: Expression,
+    ST: TypedExpressionType,
+{
+    type SqlType = ST;
+}
+
+impl<Args, Order, ST> QueryFragment<GaussDB> for OrderedSetAggregate<Args, Order, ST>
+where
+    Args: QueryFragment<GaussDB>,
+    Order: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql(self.function);
+        out.push_sql("(");
+        self.args.walk_ast(out.reborrow())?;
+        out.push_sql(") WITHIN GROUP (ORDER BY ");
+        self.order.walk_ast(out.reborrow())?;
+        if self.descending {
+            out.push_sql(" DESC");
+        }
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Args, Order, ST, GB> ValidGrouping<GB> for OrderedSetAggregate<Args, Order, ST> {
+    type IsAggregate = is_aggregate::Yes;
+}
+
+runtime_query_id!(OrderedSetAggregate<Args, Order, ST>);
+
+impl<Args, Order, ST, QS> SelectableExpression<QS> for OrderedSetAggregate<Args, Order, ST>
+where
+    Self: AppearsOnTable<QS>,
+{
+}
+
+impl<Args, Order, ST, QS> AppearsOnTable<QS> for OrderedSetAggregate<Args, Order, ST>
+where
+    Self: Expression,
+    Args: OrderedSetArguments<QS>,
+    Order: AppearsOnTable<QS>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+
+    diesel::table! {
+        posts (id) {
+            id -> Integer,
+            user_id -> Integer,
+            published -> Bool,
+            flags -> Nullable<BigInt>,
+            price -> Numeric,
+            rating -> Nullable<Double>,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(query: &T) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_aggregate_sql() {
+        assert_eq!(generate_sql(&bool_and(posts::published)), r#"BOOL_AND("posts"."published")"#);
+        assert_eq!(generate_sql(&bool_or(posts::published)), r#"BOOL_OR("posts"."published")"#);
+        assert_eq!(generate_sql(&bit_and(posts::flags)), r#"BIT_AND("posts"."flags")"#);
+        assert_eq!(generate_sql(&bit_or(posts::id)), r#"BIT_OR("posts"."id")"#);
+    }
+
+    #[test]
+    fn test_aggregate_filter_sql() {
+        let expr = bool_or(posts::published).aggregate_filter(posts::id.gt(10));
+        assert_eq!(
+            generate_sql(&expr),
+            r#"BOOL_OR(CASE WHEN ("posts"."id" > $1) THEN "posts"."published" END)"#
+        );
+    }
+
+    #[test]
+    fn test_group_by_sql() {
+        let query = posts::table
+            .group_by(posts::user_id)
+            .select((posts::user_id, bool_and(posts::published), bit_or(posts::flags)));
+        assert_eq!(
+            generate_sql(&query),
+            r#"SELECT "posts"."user_id", BOOL_AND("posts"."published"), BIT_OR("posts"."flags") FROM "posts" GROUP BY "posts"."user_id""#
+        );
+    }
+
+    #[test]
+    fn test_result_types() {
+        fn sql_type<E: Expression>(_: &E) -> std::marker::PhantomData<E::SqlType> {
+            std::marker::PhantomData
+        }
+
+        let _: std::marker::PhantomData<Nullable<Bool>> = sql_type(&bool_and(posts::published));
+        let _: std::marker::PhantomData<Nullable<Integer>> = sql_type(&bit_and(posts::id));
+        let _: std::marker::PhantomData<Nullable<BigInt>> = sql_type(&bit_or(posts::flags));
+        let _: std::marker::PhantomData<Nullable<Integer>> =
+            sql_type(&bit_and(posts::id).aggregate_filter(posts::published));
+    }
+
+    #[test]
+    fn test_count_if_sql() {
+        let query = posts::table
+            .group_by(posts::user_id)
+            .select((posts::user_id, count_if(posts::published), count_if(posts::rating.gt(4.0))));
+        assert_eq!(
+            generate_sql(&query),
+            r#"SELECT "posts"."user_id", COUNT(CASE WHEN "posts"."published" THEN 1 END), COUNT(CASE WHEN ("posts"."rating" > $1) THEN 1 END) FROM "posts" GROUP BY "posts"."user_id""#
+        );
+
+        fn sql_type<E: Expression>(_: &E) -> std::marker::PhantomData<E::SqlType> {
+            std::marker::PhantomData
+        }
+        // Never NULL, also for a nullable predicate
+        let _: std::marker::PhantomData<BigInt> = sql_type(&count_if(posts::rating.gt(4.0)));
+    }
+
+    #[test]
+    fn test_sum_avg_sql() {
+        assert_eq!(generate_sql(&sum(posts::price)), r#"SUM("posts"."price")"#);
+        assert_eq!(generate_sql(&avg(posts::price)), r#"AVG("posts"."price")"#);
+    }
+
+    #[test]
+    fn test_sum_avg_result_types() {
+        fn sql_type<E: Expression>(_: &E) -> std::marker::PhantomData<E::SqlType> {
+            std::marker::PhantomData
+        }
+
+        // Numeric input is never coerced to a float
+        let _: std::marker::PhantomData<Nullable<Numeric>> = sql_type(&sum(posts::price));
+        let _: std::marker::PhantomData<Nullable<Numeric>> = sql_type(&avg(posts::price));
+        let _: std::marker::PhantomData<Nullable<BigInt>> = sql_type(&sum(posts::id));
+        let _: std::marker::PhantomData<Nullable<Numeric>> = sql_type(&avg(posts::id));
+        let _: std::marker::PhantomData<Nullable<Numeric>> = sql_type(&sum(posts::flags));
+        let _: std::marker::PhantomData<Nullable<Double>> = sql_type(&avg(posts::rating));
+    }
+
+    #[test]
+    fn test_ordered_set_aggregate_sql() {
+        assert_eq!(
+            generate_sql(&percentile_disc(0.5).within_group(posts::price)),
+            r#"PERCENTILE_DISC($1) WITHIN GROUP (ORDER BY "posts"."price")"#
+        );
+        assert_eq!(
+            generate_sql(&percentile_cont(0.9).within_group_desc(posts::rating)),
+            r#"PERCENTILE_CONT($1) WITHIN GROUP (ORDER BY "posts"."rating" DESC)"#
+        );
+        assert_eq!(
+            generate_sql(&mode().within_group(posts::user_id)),
+            r#"MODE() WITHIN GROUP (ORDER BY "posts"."user_id")"#
+        );
+    }
+
+    #[test]
+    fn test_ordered_set_aggregate_group_by_sql() {
+        let query = posts::table
+            .group_by(posts::user_id)
+            .select((posts::user_id, mode().within_group(posts::id), percentile_disc(0.5).within_group(posts::id)));
+        assert_eq!(
+            generate_sql(&query),
+            r#"SELECT "posts"."user_id", MODE() WITHIN GROUP (ORDER BY "posts"."id"), PERCENTILE_DISC($1) WITHIN GROUP (ORDER BY "posts"."id") FROM "posts" GROUP BY "posts"."user_id""#
+        );
+    }
+
+    #[test]
+    fn test_ordered_set_aggregate_result_types() {
+        fn sql_type<E: Expression>(_: &E) -> std::marker::PhantomData<E::SqlType> {
+            std::marker::PhantomData
+        }
+
+        // Discrete percentiles and the mode keep the type of the values
+        let _: std::marker::PhantomData<Nullable<Numeric>> =
+            sql_type(&percentile_disc(0.5).within_group(posts::price));
+        let _: std::marker::PhantomData<Nullable<Integer>> = sql_type(&mode().within_group(posts::id));
+        let _: std::marker::PhantomData<Nullable<Double>> = sql_type(&mode().within_group(posts::rating));
+        // Continuous percentiles interpolate as floats
+        let _: std::marker::PhantomData<Nullable<Double>> =
+            sql_type(&percentile_cont(0.5).within_group(posts::price));
+    }
+}
@@ -5,6 +5,7 @@
 //! time zone handling.
 
 use crate::backend::GaussDB;
+use crate::types::date_and_time::GaussDBInterval;
 use crate::types::sql_types::Timestamptz;
 use diesel::expression::{
     AppearsOnTable, AsExpression, Expression, SelectableExpression,
@@ -12,12 +13,17 @@ use diesel::expression::{
 };
 use diesel::query_builder::{AstPass, QueryFragment, QueryId};
 use diesel::result::QueryResult;
-use diesel::sql_types::{Date, Time, Timestamp};
+use diesel::sql_types::{
+    Date, Interval, MaybeNullableType, Nullable, OneIsNullable, SqlType, Time, Timestamp,
+};
 
 /// Represents the SQL `NOW()` function.
 ///
-/// This function returns the current timestamp with time zone.
-/// It's equivalent to `CURRENT_TIMESTAMP` in PostgreSQL.
+/// Returns the time the current *transaction* started, so every call within
+/// one transaction yields the same value. It's equivalent to
+/// [`transaction_timestamp`] and `CURRENT_TIMESTAMP`. Use
+/// [`statement_timestamp`] or [`clock_timestamp`] if you need a value that
+/// advances during a long transaction.
 ///
 /// # Examples
 ///
@@ -49,8 +55,8 @@ impl<QS> AppearsOnTable<QS> for now {}
 
 /// Represents the SQL `CURRENT_TIMESTAMP` constant.
 ///
-/// This is equivalent to the `NOW()` function and returns the current
-/// timestamp with time zone.
+/// This is equivalent to the `NOW()` function and returns the start time
+/// of the current transaction.
 ///
 /// # Examples
 ///
@@ -80,6 +86,98 @@ impl<QS> AppearsOnTable<QS> for current_timestamp {}
 // Note: Type coercion implementations would go here
 // For now, we keep the basic Timestamptz type
 
+/// Represents the SQL `TRANSACTION_TIMESTAMP()` function.
+///
+/// Returns the time the current transaction started. The value is fixed for
+/// the whole transaction, exactly like [`now`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::transaction_timestamp;
+/// // SELECT TRANSACTION_TIMESTAMP()
+/// let started_at = transaction_timestamp;
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, QueryId, ValidGrouping)]
+pub struct transaction_timestamp;
+
+impl Expression for transaction_timestamp {
+    type SqlType = Timestamptz;
+}
+
+impl QueryFragment<GaussDB> for transaction_timestamp {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("TRANSACTION_TIMESTAMP()");
+        Ok(())
+    }
+}
+
+impl<QS> SelectableExpression<QS> for transaction_timestamp {}
+impl<QS> AppearsOnTable<QS> for transaction_timestamp {}
+
+/// Represents the SQL `STATEMENT_TIMESTAMP()` function.
+///
+/// Returns the time the current *statement* started. It is the same for
+/// every row of one statement but differs between statements of the same
+/// transaction.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::statement_timestamp;
+/// // SELECT STATEMENT_TIMESTAMP()
+/// let statement_started_at = statement_timestamp;
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, QueryId, ValidGrouping)]
+pub struct statement_timestamp;
+
+impl Expression for statement_timestamp {
+    type SqlType = Timestamptz;
+}
+
+impl QueryFragment<GaussDB> for statement_timestamp {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("STATEMENT_TIMESTAMP()");
+        Ok(())
+    }
+}
+
+impl<QS> SelectableExpression<QS> for statement_timestamp {}
+impl<QS> AppearsOnTable<QS> for statement_timestamp {}
+
+/// Represents the SQL `CLOCK_TIMESTAMP()` function.
+///
+/// Returns the actual wall-clock time, which changes even within a single
+/// statement. Use it to measure elapsed time inside a long transaction,
+/// where [`now`] would stay fixed.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::clock_timestamp;
+/// // SELECT CLOCK_TIMESTAMP()
+/// let wall_clock = clock_timestamp;
+/// ```
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, QueryId, ValidGrouping)]
+pub struct clock_timestamp;
+
+impl Expression for clock_timestamp {
+    type SqlType = Timestamptz;
+}
+
+impl QueryFragment<GaussDB> for clock_timestamp {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("CLOCK_TIMESTAMP()");
+        Ok(())
+    }
+}
+
+impl<QS> SelectableExpression<QS> for clock_timestamp {}
+impl<QS> AppearsOnTable<QS> for clock_timestamp {}
+
 /// Represents the SQL `CURRENT_DATE` constant.
 ///
 /// Returns the current date (without time).
@@ -414,6 +512,570 @@ where
 {
 }
 
+/// Creates a PostgreSQL `EXTRACT(field FROM source)` expression over an
+/// interval.
+///
+/// Use the `epoch` field to get the total length of the interval in
+/// seconds. Months count as 30 days and days as 24 hours.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::{extract_from_interval, interval_literal};
+/// // EXTRACT(epoch FROM INTERVAL '1 day 02:00:00')
+/// let seconds = extract_from_interval("epoch", interval_literal("1 day 02:00:00"));
+/// ```
+pub fn extract_from_interval<T>(field: &str, source: T) -> ExtractFunction<T::Expression>
+where
+    T: AsExpression<Interval>,
+{
+    ExtractFunction::new(field.to_string(), source.as_expression())
+}
+
+/// Creates a PostgreSQL `JUSTIFY_DAYS(interval)` expression.
+///
+/// Turns every 30 days into one month, e.g. `35 days` becomes
+/// `1 mon 5 days`. The time part is left alone.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::{days, justify_days};
+/// // JUSTIFY_DAYS($1)
+/// let normalized = justify_days(days(90));
+/// ```
+pub fn justify_days<T>(source: T) -> JustifyFunction<T::Expression>
+where
+    T: AsExpression<Interval>,
+{
+    JustifyFunction::new("JUSTIFY_DAYS", source.as_expression())
+}
+
+/// Creates a PostgreSQL `JUSTIFY_HOURS(interval)` expression.
+///
+/// Turns every 24 hours into one day, e.g. `27 hours` becomes
+/// `1 day 03:00:00`. Months are left alone.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::{hours, justify_hours};
+/// // JUSTIFY_HOURS($1)
+/// let normalized = justify_hours(hours(27));
+/// ```
+pub fn justify_hours<T>(source: T) -> JustifyFunction<T::Expression>
+where
+    T: AsExpression<Interval>,
+{
+    JustifyFunction::new("JUSTIFY_HOURS", source.as_expression())
+}
+
+/// Creates a PostgreSQL `JUSTIFY_INTERVAL(interval)` expression.
+///
+/// Combines [`justify_days`] and [`justify_hours`], and additionally makes
+/// all fields share one sign, so `1 mon -1 hour` becomes
+/// `29 days 23:00:00`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::{interval_literal, justify_interval};
+/// // JUSTIFY_INTERVAL(INTERVAL '90 days 30 hours'), i.e. '3 mons 1 day 06:00:00'
+/// let normalized = justify_interval(interval_literal("90 days 30 hours"));
+/// ```
+pub fn justify_interval<T>(source: T) -> JustifyFunction<T::Expression>
+where
+    T: AsExpression<Interval>,
+{
+    JustifyFunction::new("JUSTIFY_INTERVAL", source.as_expression())
+}
+
+/// PostgreSQL `JUSTIFY_DAYS`, `JUSTIFY_HOURS` and `JUSTIFY_INTERVAL`
+/// functions
+#[derive(Debug, Clone, ValidGrouping)]
+pub struct JustifyFunction<Expr> {
+    function: &'static str,
+    source: Expr,
+}
+
+impl<Expr> JustifyFunction<Expr> {
+    fn new(function: &'static str, source: Expr) -> Self {
+        JustifyFunction { function, source }
+    }
+}
+
+impl<Expr> Expression for JustifyFunction<Expr>
+where
+    Expr: Expression<SqlType = Interval>,
+{
+    type SqlType = Interval;
+}
+
+impl<Expr> QueryFragment<GaussDB> for JustifyFunction<Expr>
+where
+    Expr: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql(self.function);
+        out.push_sql("(");
+        self.source.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+runtime_query_id!(JustifyFunction<Expr>);
+
+impl<Expr, QS> SelectableExpression<QS> for JustifyFunction<Expr>
+where
+    JustifyFunction<Expr>: AppearsOnTable<QS>,
+{
+}
+
+impl<Expr, QS> AppearsOnTable<QS> for JustifyFunction<Expr>
+where
+    Expr: Expression<SqlType = Interval> + AppearsOnTable<QS>,
+{
+}
+
+/// Date and time SQL types accepted by [`greatest`] and [`least`]
+///
+/// Implemented for `Timestamp`, `Timestamptz` and `Date`, with or without
+/// `Nullable`.
+pub trait GreatestLeastType: SqlType {
+    /// This type without `Nullable`
+    type NotNull: SqlType + diesel::expression::TypedExpressionType;
+}
+
+macro_rules! greatest_least_types {
+    ($($ty:ty),*) => {
+        $(
+            impl GreatestLeastType for $ty {
+                type NotNull = $ty;
+            }
+
+            impl GreatestLeastType for Nullable<$ty> {
+                type NotNull = $ty;
+            }
+        )*
+    };
+}
+
+greatest_least_types!(Timestamp, Timestamptz, diesel::sql_types::Timestamptz, Date);
+
+/// Creates a PostgreSQL `GREATEST(first, second)` expression.
+///
+/// Both arguments must have the same date or time type, either of them may
+/// be nullable. `NULL` arguments are ignored, so the result is only `NULL`
+/// if both are; it is typed nullable if either argument is.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::greatest;
+/// # diesel::table! { users (id) { id -> Integer, created_at -> Timestamp, updated_at -> Nullable<Timestamp>, } }
+/// // GREATEST(updated_at, created_at), typed Nullable<Timestamp>
+/// let last_activity = greatest(users::updated_at, users::created_at);
+/// ```
+pub fn greatest<T, U>(first: T, second: U) -> GreatestLeastFunction<T, U>
+where
+    T: Expression,
+    U: Expression,
+    T::SqlType: GreatestLeastType,
+    U::SqlType: GreatestLeastType<NotNull = <T::SqlType as GreatestLeastType>::NotNull>,
+{
+    GreatestLeastFunction::new("GREATEST", first, second)
+}
+
+/// Creates a PostgreSQL `LEAST(first, second)` expression.
+///
+/// The counterpart of [`greatest`], with the same typing and `NULL`
+/// handling.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::least;
+/// # diesel::table! { orders (id) { id -> Integer, due_on -> Date, shipped_on -> Nullable<Date>, } }
+/// // LEAST(shipped_on, due_on)
+/// let deadline = least(orders::shipped_on, orders::due_on);
+/// ```
+pub fn least<T, U>(first: T, second: U) -> GreatestLeastFunction<T, U>
+where
+    T: Expression,
+    U: Expression,
+    T::SqlType: GreatestLeastType,
+    U::SqlType: GreatestLeastType<NotNull = <T::SqlType as GreatestLeastType>::NotNull>,
+{
+    GreatestLeastFunction::new("LEAST", first, second)
+}
+
+/// PostgreSQL `GREATEST` and `LEAST` functions
+#[derive(Debug, Clone, ValidGrouping)]
+pub struct GreatestLeastFunction<First, Second> {
+    function: &'static str,
+    first: First,
+    second: Second,
+}
+
+impl<First, Second> GreatestLeastFunction<First, Second> {
+    fn new(function: &'static str, first: First, second: Second) -> Self {
+        GreatestLeastFunction { function, first, second }
+    }
+}
+
+impl<First, Second> Expression for GreatestLeastFunction<First, Second>
+where
+    First: Expression,
+    Second: Expression,
+    First::SqlType: GreatestLeastType,
+    Second::SqlType: GreatestLeastType<NotNull = <First::SqlType as GreatestLeastType>::NotNull>,
+    <First::SqlType as SqlType>::IsNull: OneIsNullable<<Second::SqlType as SqlType>::IsNull>,
+    <<First::SqlType as SqlType>::IsNull as OneIsNullable<<Second::SqlType as SqlType>::IsNull>>::Out:
+        MaybeNullableType<<First::SqlType as GreatestLeastType>::NotNull>,
+{
+    type SqlType = <<<First::SqlType as SqlType>::IsNull as OneIsNullable<
+        <Second::SqlType as SqlType>::IsNull,
+    >>::Out as MaybeNullableType<<First::SqlType as GreatestLeastType>::NotNull>>::Out;
+}
+
+impl<First, Second> QueryFragment<GaussDB> for GreatestLeastFunction<First, Second>
+where
+    First: QueryFragment<GaussDB>,
+    Second: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql(self.function);
+        out.push_sql("(");
+        self.first.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.second.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+runtime_query_id!(GreatestLeastFunction<First, Second>);
+
+impl<First, Second, QS> SelectableExpression<QS> for GreatestLeastFunction<First, Second>
+where
+    First: SelectableExpression<QS>,
+    Second: SelectableExpression<QS>,
+    Self: AppearsOnTable<QS>,
+{
+}
+
+impl<First, Second, QS> AppearsOnTable<QS> for GreatestLeastFunction<First, Second>
+where
+    First: AppearsOnTable<QS>,
+    Second: AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+/// Date and time SQL types that can start a period of [`overlaps`]
+///
+/// Implemented for `Timestamp`, `Timestamptz`, `Date` and `Time`, with or
+/// without `Nullable`.
+pub trait OverlapsType: SqlType {
+    /// The interval type giving the length of a period starting with this
+    /// type, nullable if this type is
+    type Length: SqlType + diesel::expression::TypedExpressionType;
+}
+
+macro_rules! overlaps_types {
+    ($($ty:ty),*) => {
+        $(
+            impl OverlapsType for $ty {
+                type Length = Interval;
+            }
+
+            impl OverlapsType for Nullable<$ty> {
+                type Length = Nullable<Interval>;
+            }
+        )*
+    };
+}
+
+overlaps_types!(Timestamp, Timestamptz, diesel::sql_types::Timestamptz, Date, Time);
+
+/// Converts a value into a period `(start, end)` or `(start, length)`
+/// whose start has the SQL type `ST`
+///
+/// A tuple `(start, end)` is a period with both bounds of type `ST`; use
+/// [`period_with_length`] for a start and an interval.
+pub trait AsPeriod<ST> {
+    /// The start of the period
+    type Start: Expression<SqlType = ST>;
+    /// The end or the length of the period
+    type End: Expression;
+
+    /// Perform the conversion
+    #[allow(clippy::wrong_self_convention)] // named like `AsExpression::as_expression`
+    fn as_period(self) -> Period<Self::Start, Self::End>;
+}
+
+impl<ST, S, E> AsPeriod<ST> for (S, E)
+where
+    ST: SqlType + diesel::expression::TypedExpressionType,
+    S: AsExpression<ST>,
+    E: AsExpression<ST>,
+{
+    type Start = S::Expression;
+    type End = E::Expression;
+
+    fn as_period(self) -> Period<Self::Start, Self::End> {
+        Period {
+            start: self.0.as_expression(),
+            end: self.1.as_expression(),
+        }
+    }
+}
+
+/// Creates a period given by its start and an interval, for [`overlaps`]
+///
+/// `length` is an `Interval`, e.g. [`hours`] or an interval column.
+pub fn period_with_length<S, L>(start: S, length: L) -> PeriodWithLength<S, L> {
+    PeriodWithLength { start, length }
+}
+
+/// A period `(start, length)`, see [`period_with_length`]
+#[derive(Debug, Clone, Copy)]
+pub struct PeriodWithLength<S, L> {
+    start: S,
+    length: L,
+}
+
+impl<ST, S, L> AsPeriod<ST> for PeriodWithLength<S, L>
+where
+    ST: OverlapsType + diesel::expression::TypedExpressionType,
+    S: AsExpression<ST>,
+    L: AsExpression<ST::Length>,
+{
+    type Start = S::Expression;
+    type End = L::Expression;
+
+    fn as_period(self) -> Period<Self::Start, Self::End> {
+        Period {
+            start: self.start.as_expression(),
+            end: self.length.as_expression(),
+        }
+    }
+}
+
+/// One side of an `OVERLAPS` expression, rendered as `(start, end)`
+#[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+pub struct Period<S, E> {
+    start: S,
+    end: E,
+}
+
+impl<S, E> QueryFragment<GaussDB> for Period<S, E>
+where
+    S: QueryFragment<GaussDB>,
+    E: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("(");
+        self.start.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.end.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+/// Creates a SQL `(start1, end1) OVERLAPS (start2, end2)` expression.
+///
+/// Each period is either a tuple `(start, end)` or a start with a length
+/// built by [`period_with_length`], and the two forms can be mixed. Both
+/// starts must have the same date or time type, which at least one of them
+/// has to fix, e.g. by being a column; plain values and intervals such as
+/// [`hours`] are sent as bind parameters. This is the SQL-standard
+/// predicate on time periods, unrelated to the `&&` operator on arrays.
+///
+/// Periods are half-open, so two bookings where one ends exactly when the
+/// next starts do not overlap. The result is `Bool`, or `Nullable<Bool>`
+/// if the start type is nullable.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::{hours, overlaps, period_with_length};
+/// # use diesel_gaussdb::types::date_and_time::GaussDBTimestamp;
+/// # diesel::table! { bookings (id) { id -> Integer, starts_at -> Timestamp, ends_at -> Timestamp, } }
+/// # let (from, to) = (GaussDBTimestamp::new(0), GaussDBTimestamp::new(3_600_000_000));
+/// // (starts_at, ends_at) OVERLAPS ($1, $2)
+/// let clashes = overlaps((bookings::starts_at, bookings::ends_at), (from, to));
+/// // (starts_at, ends_at) OVERLAPS ($1, $2), the second period lasting two hours
+/// let clashes_with_slot = overlaps(
+///     (bookings::starts_at, bookings::ends_at),
+///     period_with_length(from, hours(2)),
+/// );
+/// ```
+pub fn overlaps<ST, L, R>(left: L, right: R) -> Overlaps<L::Start, L::End, R::Start, R::End>
+where
+    ST: OverlapsType,
+    L: AsPeriod<ST>,
+    R: AsPeriod<ST>,
+{
+    Overlaps {
+        left: left.as_period(),
+        right: right.as_period(),
+    }
+}
+
+/// The SQL `OVERLAPS` predicate, see [`overlaps`]
+#[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+pub struct Overlaps<S1, E1, S2, E2> {
+    left: Period<S1, E1>,
+    right: Period<S2, E2>,
+}
+
+// Like a comparison, `OVERLAPS` is `NULL` if it depends on a `NULL` bound,
+// so it is nullable if the start type is
+impl<S1, E1, S2, E2> Expression for Overlaps<S1, E1, S2, E2>
+where
+    S1: Expression,
+    S1::SqlType: OverlapsType,
+    <S1::SqlType as SqlType>::IsNull: MaybeNullableType<diesel::sql_types::Bool>,
+{
+    type SqlType = <<S1::SqlType as SqlType>::IsNull as MaybeNullableType<diesel::sql_types::Bool>>::Out;
+}
+
+impl<S1, E1, S2, E2> QueryFragment<GaussDB> for Overlaps<S1, E1, S2, E2>
+where
+    S1: QueryFragment<GaussDB>,
+    E1: QueryFragment<GaussDB>,
+    S2: QueryFragment<GaussDB>,
+    E2: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        self.left.walk_ast(out.reborrow())?;
+        out.push_sql(" OVERLAPS ");
+        self.right.walk_ast(out.reborrow())?;
+        Ok(())
+    }
+}
+
+impl<S1, E1, S2, E2, QS> SelectableExpression<QS> for Overlaps<S1, E1, S2, E2>
+where
+    S1: SelectableExpression<QS>,
+    E1: SelectableExpression<QS>,
+    S2: SelectableExpression<QS>,
+    E2: SelectableExpression<QS>,
+    Self: AppearsOnTable<QS>,
+{
+}
+
+impl<S1, E1, S2, E2, QS> AppearsOnTable<QS> for Overlaps<S1, E1, S2, E2>
+where
+    S1: AppearsOnTable<QS>,
+    E1: AppearsOnTable<QS>,
+    S2: AppearsOnTable<QS>,
+    E2: AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+/// Creates an `INTERVAL '...'` literal from its text form.
+///
+/// The text is sent as written, so any interval input GaussDB accepts can
+/// be used, e.g. `"30 days"`, `"1 year 2 mons"` or `"06:30:00"`. To build
+/// an interval from numbers use [`days`], [`hours`] and the other unit
+/// helpers instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::interval_literal;
+/// // INTERVAL '30 days'
+/// let grace_period = interval_literal("30 days");
+/// ```
+pub fn interval_literal(text: &str) -> IntervalLiteral {
+    IntervalLiteral {
+        text: text.to_string(),
+    }
+}
+
+/// PostgreSQL `INTERVAL '...'` literal
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct IntervalLiteral {
+    text: String,
+}
+
+impl Expression for IntervalLiteral {
+    type SqlType = Interval;
+}
+
+impl QueryFragment<GaussDB> for IntervalLiteral {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("INTERVAL '");
+        out.push_sql(&self.text.replace('\'', "''"));
+        out.push_sql("'");
+        Ok(())
+    }
+}
+
+impl<QS> SelectableExpression<QS> for IntervalLiteral {}
+impl<QS> AppearsOnTable<QS> for IntervalLiteral {}
+
+// The unit helpers return plain `GaussDBInterval` values, which are bound as
+// a single `interval` parameter. Adding them combines the months, days and
+// microseconds fields, so `days(1) + hours(6)` is one composite interval.
+
+/// An interval of `n` years.
+pub fn years(n: i32) -> GaussDBInterval {
+    months(n.checked_mul(12).expect("overflow converting years to months"))
+}
+
+/// An interval of `n` months.
+pub fn months(n: i32) -> GaussDBInterval {
+    GaussDBInterval::new(n, 0, 0)
+}
+
+/// An interval of `n` weeks.
+pub fn weeks(n: i32) -> GaussDBInterval {
+    days(n.checked_mul(7).expect("overflow converting weeks to days"))
+}
+
+/// An interval of `n` days.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::{days, hours};
+/// # use diesel_gaussdb::types::date_and_time::GaussDBInterval;
+/// # diesel::table! { events (id) { id -> Integer, starts_at -> Timestamp, } }
+/// // "events"."starts_at" + $1, bound as '1 day 06:00:00'
+/// let delay = days(1) + hours(6);
+/// let shifted = events::starts_at + delay;
+/// # assert_eq!(delay, GaussDBInterval::new(0, 1, 6 * 3_600_000_000));
+/// ```
+pub fn days(n: i32) -> GaussDBInterval {
+    GaussDBInterval::new(0, n, 0)
+}
+
+/// An interval of `n` hours.
+pub fn hours(n: i64) -> GaussDBInterval {
+    minutes(n.checked_mul(60).expect("overflow converting hours to minutes"))
+}
+
+/// An interval of `n` minutes.
+pub fn minutes(n: i64) -> GaussDBInterval {
+    seconds(n.checked_mul(60).expect("overflow converting minutes to seconds"))
+}
+
+/// An interval of `n` seconds.
+pub fn seconds(n: i64) -> GaussDBInterval {
+    microseconds(n.checked_mul(1_000_000).expect("overflow converting seconds to microseconds"))
+}
+
+/// An interval of `n` microseconds.
+pub fn microseconds(n: i64) -> GaussDBInterval {
+    GaussDBInterval::new(0, 0, n)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,4 +1148,15 @@ mod tests {
         fn assert_double_expr<T: Expression<SqlType = Double>>(_: T) {}
         assert_double_expr(date_part_expr);
     }
+
+    #[test]
+    fn test_justify_functions_are_intervals() {
+        fn assert_interval_expr<T: Expression<SqlType = Interval>>(_: T) {}
+        assert_interval_expr(justify_days(days(90)));
+        assert_interval_expr(justify_hours(interval_literal("27 hours")));
+        assert_interval_expr(justify_interval(justify_days(days(1))));
+
+        fn assert_double_expr<T: Expression<SqlType = Double>>(_: T) {}
+        assert_double_expr(extract_from_interval("epoch", days(1)));
+    }
 }
@@ -0,0 +1,329 @@
+//! JSONB functions for GaussDB
+//!
+//! This module provides the `JSONB_OBJECT_AGG` aggregate, which builds a
+//! JSON object from the rows of a group, and the set-returning functions
+//! `JSONB_ARRAY_ELEMENTS` and `JSONB_EACH`, which expand a document into
+//! rows.
+//!
+//! Like [`unnest`](crate::query_builder::unnest::unnest), the set-returning
+//! functions become relations for the `FROM` clause with `alias`. The
+//! columns of such a relation are typed expressions, and the relation can
+//! be joined to the table the document comes from or queried on its own
+//! with `as_query()`.
+
+use crate::backend::GaussDB;
+use crate::query_builder::function_relation::{FunctionRelation, RelationColumn, SetReturningFunction};
+use diesel::expression::{
+    is_aggregate, AppearsOnTable, AsExpression, Expression, MixedAggregates, SelectableExpression,
+    ValidGrouping,
+};
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::result::QueryResult;
+use diesel::sql_types::{Jsonb, Nullable, SingleValue, Text};
+
+/// Creates a PostgreSQL `JSONB_OBJECT_AGG(key, value)` aggregate expression.
+///
+/// Builds one object with a member per aggregated row. `value` may have any
+/// type and is converted to JSON by the server, `NULL` becoming `null`.
+/// Later rows win if a key repeats. The result is `NULL` if no row is
+/// aggregated.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::jsonb_object_agg;
+/// # diesel::table! { settings (id) { id -> Integer, name -> Text, value -> Integer, } }
+/// // JSONB_OBJECT_AGG(name, value)
+/// let all_settings = jsonb_object_agg(settings::name, settings::value);
+/// ```
+pub fn jsonb_object_agg<K, V>(key: K, value: V) -> JsonbObjectAgg<K::Expression, V>
+where
+    K: AsExpression<Text>,
+    V: Expression,
+    V::SqlType: SingleValue,
+{
+    JsonbObjectAgg {
+        key: key.as_expression(),
+        value,
+    }
+}
+
+/// PostgreSQL `JSONB_OBJECT_AGG` aggregate function
+#[derive(Debug, Clone, QueryId)]
+pub struct JsonbObjectAgg<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K, V> Expression for JsonbObjectAgg<K, V>
+where
+    K: Expression,
+    V: Expression,
+{
+    type SqlType = Nullable<Jsonb>;
+}
+
+// Like Diesel's aggregates, the arguments must not be aggregates themselves
+impl<K, V, GB> ValidGrouping<GB> for JsonbObjectAgg<K, V>
+where
+    K: ValidGrouping<()>,
+    V: ValidGrouping<()>,
+    K::IsAggregate: MixedAggregates<is_aggregate::No, Output = is_aggregate::No>,
+    V::IsAggregate: MixedAggregates<is_aggregate::No, Output = is_aggregate::No>,
+{
+    type IsAggregate = is_aggregate::Yes;
+}
+
+impl<K, V> QueryFragment<GaussDB> for JsonbObjectAgg<K, V>
+where
+    K: QueryFragment<GaussDB>,
+    V: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("JSONB_OBJECT_AGG(");
+        self.key.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.value.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<K, V, QS> SelectableExpression<QS> for JsonbObjectAgg<K, V> where Self: AppearsOnTable<QS> {}
+
+impl<K, V, QS> AppearsOnTable<QS> for JsonbObjectAgg<K, V>
+where
+    Self: Expression,
+    K: AppearsOnTable<QS>,
+    V: AppearsOnTable<QS>,
+{
+}
+
+/// The SQL types of JSONB documents, `Jsonb` and `Nullable<Jsonb>`
+///
+/// Bounds the JSONB functions and operators. A `NULL` document expands to
+/// no rows.
+pub trait JsonbDocument {}
+
+impl JsonbDocument for Jsonb {}
+
+impl JsonbDocument for Nullable<Jsonb> {}
+
+/// Creates a PostgreSQL `JSONB_ARRAY_ELEMENTS(json)` expression.
+///
+/// Expands a JSON array into one row per element, typed `Jsonb`. In the
+/// select list it multiplies the rows of the query; use
+/// [`alias`](JsonbArrayElements::alias) to read the elements from the
+/// `FROM` clause instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::jsonb_array_elements;
+/// # diesel::table! { orders (id) { id -> Integer, items -> Jsonb, } }
+/// // jsonb_array_elements("orders"."items") AS "item"("value")
+/// let items = jsonb_array_elements(orders::items).alias("item", "value");
+/// let item = items.value();
+/// ```
+pub fn jsonb_array_elements<E>(json: E) -> JsonbArrayElements<E>
+where
+    E: Expression,
+    E::SqlType: JsonbDocument,
+{
+    JsonbArrayElements { json }
+}
+
+/// PostgreSQL `JSONB_ARRAY_ELEMENTS` set-returning function
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct JsonbArrayElements<E> {
+    json: E,
+}
+
+impl<E> JsonbArrayElements<E> {
+    /// Use the function as a relation named `alias` in the `FROM` clause
+    ///
+    /// Renders `jsonb_array_elements(json) AS "alias"("column")`; the
+    /// elements are read with [`value`](JsonbRelation::value).
+    pub fn alias(self, alias: &str, column: &str) -> JsonbRelation<Self> {
+        FunctionRelation::new(self, alias, &[column])
+    }
+}
+
+impl<E> Expression for JsonbArrayElements<E>
+where
+    E: Expression,
+    E::SqlType: JsonbDocument,
+{
+    type SqlType = Jsonb;
+}
+
+impl<E> QueryFragment<GaussDB> for JsonbArrayElements<E>
+where
+    E: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("jsonb_array_elements(");
+        self.json.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<E, QS> SelectableExpression<QS> for JsonbArrayElements<E> where Self: AppearsOnTable<QS> {}
+
+impl<E, QS> AppearsOnTable<QS> for JsonbArrayElements<E>
+where
+    E: AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+impl<E, GB> ValidGrouping<GB> for JsonbArrayElements<E>
+where
+    E: ValidGrouping<GB>,
+{
+    type IsAggregate = E::IsAggregate;
+}
+
+/// Creates a PostgreSQL `JSONB_EACH(json)` relation.
+///
+/// Expands the top-level members of a JSON object into rows of a `Text`
+/// key and a `Jsonb` value. The rows are records, so the function is only
+/// available in the `FROM` clause, see [`JsonbEach::alias`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::jsonb_each;
+/// # diesel::table! { profiles (id) { id -> Integer, attributes -> Jsonb, } }
+/// // jsonb_each("profiles"."attributes") AS "attr"("key", "value")
+/// let attributes = jsonb_each(profiles::attributes).alias("attr");
+/// let (key, value) = (attributes.key(), attributes.value());
+/// ```
+pub fn jsonb_each<E>(json: E) -> JsonbEach<E>
+where
+    E: Expression,
+    E::SqlType: JsonbDocument,
+{
+    JsonbEach { json }
+}
+
+/// PostgreSQL `JSONB_EACH` set-returning function
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct JsonbEach<E> {
+    json: E,
+}
+
+impl<E> JsonbEach<E> {
+    /// Use the function as a relation named `alias` in the `FROM` clause
+    ///
+    /// Renders `jsonb_each(json) AS "alias"("key", "value")`.
+    pub fn alias(self, alias: &str) -> JsonbRelation<Self> {
+        FunctionRelation::new(self, alias, &["key", "value"])
+    }
+}
+
+impl<E> QueryFragment<GaussDB> for JsonbEach<E>
+where
+    E: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("jsonb_each(");
+        self.json.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+/// A JSONB set-returning function used as a relation in the `FROM` clause
+///
+/// Rendered as `function(json) AS "alias"("column", ...)`. Like an
+/// [`UnnestRelation`](crate::query_builder::unnest::UnnestRelation), it is
+/// queried with `as_query()` or joined to the table the document comes
+/// from with [`joined_to`](FunctionRelation::joined_to), see
+/// [`function_relation`](crate::query_builder::function_relation).
+pub type JsonbRelation<F> = FunctionRelation<F>;
+
+/// A column of a [`JsonbRelation`], typed `ST`, at position `N`
+pub type JsonbColumn<F, ST, const N: usize = 0> = RelationColumn<F, ST, N>;
+
+impl<E> SetReturningFunction for JsonbArrayElements<E> {
+    type DefaultSelection = JsonbColumn<Self, Jsonb>;
+
+    fn default_selection(relation: &FunctionRelation<Self>) -> Self::DefaultSelection {
+        relation.value()
+    }
+}
+
+impl<E> SetReturningFunction for JsonbEach<E> {
+    type DefaultSelection = (JsonbColumn<Self, Text>, JsonbColumn<Self, Jsonb, 1>);
+
+    fn default_selection(relation: &FunctionRelation<Self>) -> Self::DefaultSelection {
+        (relation.key(), relation.value())
+    }
+}
+
+impl<E> FunctionRelation<JsonbArrayElements<E>> {
+    /// The elements of the array, as `"alias"."column"`
+    pub fn value(&self) -> JsonbColumn<JsonbArrayElements<E>, Jsonb> {
+        self.nth_column()
+    }
+}
+
+impl<E> FunctionRelation<JsonbEach<E>> {
+    /// The keys of the object, as `"alias"."key"`
+    pub fn key(&self) -> JsonbColumn<JsonbEach<E>, Text> {
+        self.nth_column()
+    }
+
+    /// The values of the object, as `"alias"."value"`
+    pub fn value(&self) -> JsonbColumn<JsonbEach<E>, Jsonb, 1> {
+        self.nth_column()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::query_builder::QueryBuilder;
+
+    diesel::table! {
+        orders (id) {
+            id -> Integer,
+            items -> Jsonb,
+            notes -> Nullable<Jsonb>,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(fragment: &T) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        fragment.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_jsonb_relation_sql() {
+        let elements = jsonb_array_elements(orders::notes).alias("n", "note");
+        assert_eq!(
+            generate_sql(&elements),
+            r#"jsonb_array_elements("orders"."notes") AS "n"("note")"#
+        );
+        assert_eq!(generate_sql(&elements.value()), r#""n"."note""#);
+
+        let members = jsonb_each(orders::items).alias("m");
+        assert_eq!(generate_sql(&members), r#"jsonb_each("orders"."items") AS "m"("key", "value")"#);
+        assert_eq!(generate_sql(&members.key()), r#""m"."key""#);
+        assert_eq!(generate_sql(&members.value()), r#""m"."value""#);
+    }
+
+    #[test]
+    fn test_result_types() {
+        fn assert_type<ST, T: Expression<SqlType = ST>>(_: T) {}
+
+        assert_type::<Nullable<Jsonb>, _>(jsonb_object_agg("total", orders::items));
+        assert_type::<Jsonb, _>(jsonb_array_elements(orders::items));
+        assert_type::<Jsonb, _>(jsonb_array_elements(orders::notes).alias("n", "note").value());
+        assert_type::<Text, _>(jsonb_each(orders::items).alias("m").key());
+    }
+}
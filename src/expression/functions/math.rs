@@ -9,9 +9,15 @@ use diesel::expression::{
     AppearsOnTable, AsExpression, Expression, SelectableExpression,
     ValidGrouping,
 };
-use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::dsl::{AsExprOf, Select};
+use diesel::query_builder::{AstPass, Query, QueryFragment, QueryId};
+use diesel::query_dsl::methods::SelectDsl;
 use diesel::result::QueryResult;
-use diesel::sql_types::{Double, Integer};
+use diesel::sql_types::{
+    is_nullable, Array, BigInt, Double, Float, Integer, IntoNullable, Nullable, Numeric, SingleValue, SmallInt,
+    SqlType,
+};
+use diesel::RunQueryDsl;
 
 /// Creates a PostgreSQL `ABS(number)` expression.
 ///
@@ -474,6 +480,681 @@ where
 {
 }
 
+/// Numeric SQL types accepted by [`safe_divide`]
+///
+/// Implemented for the integer, floating point and `numeric` types, both
+/// nullable and not null.
+pub trait SafeDivideOperand: SqlType + SingleValue + IntoNullable {}
+
+macro_rules! safe_divide_operands {
+    ($($ty:ty),*) => {
+        $(
+            impl SafeDivideOperand for $ty {}
+            impl SafeDivideOperand for Nullable<$ty> {}
+        )*
+    };
+}
+
+safe_divide_operands!(SmallInt, Integer, BigInt, Float, Double, Numeric);
+
+/// Creates a `(numerator / NULLIF(denominator, 0))` expression.
+///
+/// Divides without failing on a zero denominator: the result is `NULL`
+/// instead of a `division by zero` error. It keeps the SQL type of the
+/// numerator, made nullable, so integers still use integer division;
+/// divide `numeric` or `double precision` values for a fractional ratio.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::safe_divide;
+/// # diesel::table! { campaigns (id) { id -> Integer, clicks -> Double, views -> Double, } }
+/// // ("campaigns"."clicks" / NULLIF("campaigns"."views", 0))
+/// let click_rate = safe_divide(campaigns::clicks, campaigns::views);
+/// ```
+pub fn safe_divide<T, U>(numerator: T, denominator: U) -> SafeDivideFunction<T, U::Expression>
+where
+    T: Expression,
+    T::SqlType: SafeDivideOperand,
+    U: AsExpression<T::SqlType>,
+{
+    SafeDivideFunction {
+        numerator,
+        denominator: denominator.as_expression(),
+    }
+}
+
+/// Division returning `NULL` for a zero denominator, see [`safe_divide`]
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct SafeDivideFunction<Num, Denom> {
+    numerator: Num,
+    denominator: Denom,
+}
+
+impl<Num, Denom> Expression for SafeDivideFunction<Num, Denom>
+where
+    Num: Expression,
+    Num::SqlType: SafeDivideOperand,
+    Denom: Expression<SqlType = Num::SqlType>,
+    <Num::SqlType as IntoNullable>::Nullable: SingleValue,
+{
+    type SqlType = <Num::SqlType as IntoNullable>::Nullable;
+}
+
+impl<Num, Denom> QueryFragment<GaussDB> for SafeDivideFunction<Num, Denom>
+where
+    Num: QueryFragment<GaussDB>,
+    Denom: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        // Parenthesized, so the division binds as a whole in larger expressions
+        out.push_sql("(");
+        self.numerator.walk_ast(out.reborrow())?;
+        out.push_sql(" / NULLIF(");
+        self.denominator.walk_ast(out.reborrow())?;
+        out.push_sql(", 0))");
+        Ok(())
+    }
+}
+
+impl<Num, Denom, QS> SelectableExpression<QS> for SafeDivideFunction<Num, Denom>
+where
+    SafeDivideFunction<Num, Denom>: AppearsOnTable<QS>,
+{
+}
+
+impl<Num, Denom, QS> AppearsOnTable<QS> for SafeDivideFunction<Num, Denom>
+where
+    SafeDivideFunction<Num, Denom>: Expression,
+    Num: AppearsOnTable<QS>,
+    Denom: AppearsOnTable<QS>,
+{
+}
+
+/// Defines a single-argument `Double` function following the
+/// [`SqrtFunction`] pattern: a constructor function and its expression type.
+macro_rules! double_function {
+    (
+        $(#[$meta:meta])*
+        $fn_name:ident, $name:ident, $sql_name:expr
+    ) => {
+        $(#[$meta])*
+        pub fn $fn_name<T>(number: T) -> $name<T::Expression>
+        where
+            T: AsExpression<Double>,
+        {
+            $name::new(number.as_expression())
+        }
+
+        #[doc = concat!("PostgreSQL `", $sql_name, "` function")]
+        #[derive(Debug, Clone, QueryId, ValidGrouping)]
+        pub struct $name<Expr> {
+            number: Expr,
+        }
+
+        impl<Expr> $name<Expr> {
+            fn new(number: Expr) -> Self {
+                $name { number }
+            }
+        }
+
+        impl<Expr> Expression for $name<Expr>
+        where
+            Expr: Expression<SqlType = Double>,
+        {
+            type SqlType = Double;
+        }
+
+        impl<Expr> QueryFragment<GaussDB> for $name<Expr>
+        where
+            Expr: QueryFragment<GaussDB>,
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+                out.push_sql(concat!($sql_name, "("));
+                self.number.walk_ast(out.reborrow())?;
+                out.push_sql(")");
+                Ok(())
+            }
+        }
+
+        impl<Expr, QS> SelectableExpression<QS> for $name<Expr>
+        where
+            $name<Expr>: AppearsOnTable<QS>,
+        {
+        }
+
+        impl<Expr, QS> AppearsOnTable<QS> for $name<Expr>
+        where
+            Expr: Expression<SqlType = Double> + AppearsOnTable<QS>,
+        {
+        }
+    };
+}
+
+double_function!(
+    /// Creates a PostgreSQL `LN(number)` expression.
+    ///
+    /// Returns the natural logarithm of the number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::ln;
+    /// # use diesel::sql_types::Double;
+    /// // LN(2.718281828)
+    /// let natural_log = ln(diesel::dsl::sql::<Double>("2.718281828"));
+    /// ```
+    ln, LnFunction, "LN"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `EXP(number)` expression.
+    ///
+    /// Returns e raised to the power of the number.
+    exp, ExpFunction, "EXP"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `SIN(number)` expression.
+    ///
+    /// Returns the sine of an angle in radians.
+    sin, SinFunction, "SIN"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `COS(number)` expression.
+    ///
+    /// Returns the cosine of an angle in radians.
+    cos, CosFunction, "COS"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `TAN(number)` expression.
+    ///
+    /// Returns the tangent of an angle in radians.
+    tan, TanFunction, "TAN"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `ASIN(number)` expression.
+    ///
+    /// Returns the arc sine in radians.
+    asin, AsinFunction, "ASIN"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `ACOS(number)` expression.
+    ///
+    /// Returns the arc cosine in radians.
+    acos, AcosFunction, "ACOS"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `ATAN(number)` expression.
+    ///
+    /// Returns the arc tangent in radians. Use [`atan2`] to get the angle
+    /// of a point.
+    atan, AtanFunction, "ATAN"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `SIGN(number)` expression.
+    ///
+    /// Returns -1, 0 or 1 depending on the sign of the number.
+    sign, SignFunction, "SIGN"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `TRUNC(number)` expression.
+    ///
+    /// Truncates the number toward zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use diesel_gaussdb::expression::functions::trunc;
+    /// # use diesel::sql_types::Double;
+    /// // TRUNC(-4.7) is -4
+    /// let truncated = trunc(diesel::dsl::sql::<Double>("-4.7"));
+    /// ```
+    trunc, TruncFunction, "TRUNC"
+);
+
+double_function!(
+    /// Creates a PostgreSQL `CBRT(number)` expression.
+    ///
+    /// Returns the cube root of the number.
+    cbrt, CbrtFunction, "CBRT"
+);
+
+/// Creates a PostgreSQL `LOG(number)` expression.
+///
+/// Returns the base 10 logarithm of the number. Use [`log`] for other
+/// bases.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::log10;
+/// # use diesel::sql_types::Double;
+/// // LOG(1000)
+/// let digits = log10(diesel::dsl::sql::<Double>("1000"));
+/// ```
+pub fn log10<T>(number: T) -> LogFunction<T::Expression>
+where
+    T: AsExpression<Double>,
+{
+    LogFunction::new(None, number.as_expression())
+}
+
+/// Creates a PostgreSQL `LOG(base, number)` expression.
+///
+/// Returns the logarithm of the number to the given base. GaussDB only
+/// provides this form for `numeric`, so both arguments are cast to
+/// `NUMERIC` and the result back to `DOUBLE PRECISION`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::log;
+/// # use diesel::sql_types::Double;
+/// // LOG(2, 8)
+/// let exponent = log(
+///     diesel::dsl::sql::<Double>("2"),
+///     diesel::dsl::sql::<Double>("8")
+/// );
+/// ```
+pub fn log<T, U>(base: T, number: U) -> LogFunction<U::Expression, T::Expression>
+where
+    T: AsExpression<Double>,
+    U: AsExpression<Double>,
+{
+    LogFunction::new(Some(base.as_expression()), number.as_expression())
+}
+
+/// PostgreSQL `LOG` function, with or without a base
+#[derive(Debug, Clone)]
+pub struct LogFunction<NumExpr, BaseExpr = AsExprOf<f64, Double>> {
+    base: Option<BaseExpr>,
+    number: NumExpr,
+}
+
+impl<NumExpr, BaseExpr> LogFunction<NumExpr, BaseExpr> {
+    fn new(base: Option<BaseExpr>, number: NumExpr) -> Self {
+        LogFunction { base, number }
+    }
+}
+
+runtime_query_id!(LogFunction<NumExpr, BaseExpr>);
+
+// Derived `ValidGrouping` doesn't support the default type parameter
+impl<NumExpr, BaseExpr, GB> ValidGrouping<GB> for LogFunction<NumExpr, BaseExpr>
+where
+    (NumExpr, BaseExpr): ValidGrouping<GB>,
+{
+    type IsAggregate = <(NumExpr, BaseExpr) as ValidGrouping<GB>>::IsAggregate;
+}
+
+impl<NumExpr, BaseExpr> Expression for LogFunction<NumExpr, BaseExpr>
+where
+    NumExpr: Expression<SqlType = Double>,
+    BaseExpr: Expression<SqlType = Double>,
+{
+    type SqlType = Double;
+}
+
+impl<NumExpr, BaseExpr> QueryFragment<GaussDB> for LogFunction<NumExpr, BaseExpr>
+where
+    NumExpr: QueryFragment<GaussDB>,
+    BaseExpr: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        match &self.base {
+            None => {
+                out.push_sql("LOG(");
+                self.number.walk_ast(out.reborrow())?;
+                out.push_sql(")");
+            }
+            Some(base) => {
+                out.push_sql("CAST(LOG(CAST(");
+                base.walk_ast(out.reborrow())?;
+                out.push_sql(" AS NUMERIC), CAST(");
+                self.number.walk_ast(out.reborrow())?;
+                out.push_sql(" AS NUMERIC)) AS DOUBLE PRECISION)");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<NumExpr, BaseExpr, QS> SelectableExpression<QS> for LogFunction<NumExpr, BaseExpr>
+where
+    LogFunction<NumExpr, BaseExpr>: AppearsOnTable<QS>,
+{
+}
+
+impl<NumExpr, BaseExpr, QS> AppearsOnTable<QS> for LogFunction<NumExpr, BaseExpr>
+where
+    NumExpr: Expression<SqlType = Double> + AppearsOnTable<QS>,
+    BaseExpr: Expression<SqlType = Double> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `ATAN2(y, x)` expression.
+///
+/// Returns the angle in radians between the positive x axis and the
+/// point `(x, y)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::atan2;
+/// # use diesel::sql_types::Double;
+/// // ATAN2(1, 1)
+/// let angle = atan2(
+///     diesel::dsl::sql::<Double>("1"),
+///     diesel::dsl::sql::<Double>("1")
+/// );
+/// ```
+pub fn atan2<T, U>(y: T, x: U) -> Atan2Function<T::Expression, U::Expression>
+where
+    T: AsExpression<Double>,
+    U: AsExpression<Double>,
+{
+    Atan2Function::new(y.as_expression(), x.as_expression())
+}
+
+/// PostgreSQL `ATAN2` function
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct Atan2Function<YExpr, XExpr> {
+    y: YExpr,
+    x: XExpr,
+}
+
+impl<YExpr, XExpr> Atan2Function<YExpr, XExpr> {
+    fn new(y: YExpr, x: XExpr) -> Self {
+        Atan2Function { y, x }
+    }
+}
+
+impl<YExpr, XExpr> Expression for Atan2Function<YExpr, XExpr>
+where
+    YExpr: Expression<SqlType = Double>,
+    XExpr: Expression<SqlType = Double>,
+{
+    type SqlType = Double;
+}
+
+impl<YExpr, XExpr> QueryFragment<GaussDB> for Atan2Function<YExpr, XExpr>
+where
+    YExpr: QueryFragment<GaussDB>,
+    XExpr: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("ATAN2(");
+        self.y.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.x.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<YExpr, XExpr, QS> SelectableExpression<QS> for Atan2Function<YExpr, XExpr>
+where
+    Atan2Function<YExpr, XExpr>: AppearsOnTable<QS>,
+{
+}
+
+impl<YExpr, XExpr, QS> AppearsOnTable<QS> for Atan2Function<YExpr, XExpr>
+where
+    YExpr: Expression<SqlType = Double> + AppearsOnTable<QS>,
+    XExpr: Expression<SqlType = Double> + AppearsOnTable<QS>,
+{
+}
+
+/// SQL types accepted by [`width_bucket`]
+///
+/// GaussDB defines the equal-width form for `double precision` and
+/// `numeric`. Nullable operands are not accepted, as the bucket of `NULL`
+/// is `NULL`; filter them out first.
+pub trait WidthBucketOperand: SqlType + SingleValue {}
+
+impl WidthBucketOperand for Double {}
+impl WidthBucketOperand for Numeric {}
+
+/// Creates a PostgreSQL `WIDTH_BUCKET(operand, low, high, count)` expression.
+///
+/// Splits the range from `low` to `high` into `count` buckets of equal
+/// width and returns the bucket `operand` falls into, from 1 to `count`.
+/// Values below `low` are in bucket 0, values from `high` on in bucket
+/// `count + 1`. `low` and `high` have the type of `operand`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::width_bucket;
+/// # diesel::table! { products (id) { id -> Integer, price -> Double, } }
+/// // WIDTH_BUCKET("products"."price", $1, $2, $3)
+/// let bucket = width_bucket(products::price, 0.0, 1000.0, 10);
+/// ```
+pub fn width_bucket<T, L, H, C>(
+    operand: T,
+    low: L,
+    high: H,
+    count: C,
+) -> WidthBucketFunction<T, L::Expression, H::Expression, C::Expression>
+where
+    T: Expression,
+    T::SqlType: WidthBucketOperand,
+    L: AsExpression<T::SqlType>,
+    H: AsExpression<T::SqlType>,
+    C: AsExpression<Integer>,
+{
+    WidthBucketFunction {
+        operand,
+        low: low.as_expression(),
+        high: high.as_expression(),
+        count: count.as_expression(),
+    }
+}
+
+/// PostgreSQL `WIDTH_BUCKET` function with equal-width buckets
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct WidthBucketFunction<Operand, Low, High, Count> {
+    operand: Operand,
+    low: Low,
+    high: High,
+    count: Count,
+}
+
+impl<Operand, Low, High, Count> Expression for WidthBucketFunction<Operand, Low, High, Count>
+where
+    Operand: Expression,
+    Operand::SqlType: WidthBucketOperand,
+    Low: Expression<SqlType = Operand::SqlType>,
+    High: Expression<SqlType = Operand::SqlType>,
+    Count: Expression<SqlType = Integer>,
+{
+    type SqlType = Integer;
+}
+
+impl<Operand, Low, High, Count> QueryFragment<GaussDB> for WidthBucketFunction<Operand, Low, High, Count>
+where
+    Operand: QueryFragment<GaussDB>,
+    Low: QueryFragment<GaussDB>,
+    High: QueryFragment<GaussDB>,
+    Count: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("WIDTH_BUCKET(");
+        self.operand.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.low.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.high.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.count.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Operand, Low, High, Count, QS> SelectableExpression<QS> for WidthBucketFunction<Operand, Low, High, Count>
+where
+    WidthBucketFunction<Operand, Low, High, Count>: AppearsOnTable<QS>,
+{
+}
+
+impl<Operand, Low, High, Count, QS> AppearsOnTable<QS> for WidthBucketFunction<Operand, Low, High, Count>
+where
+    WidthBucketFunction<Operand, Low, High, Count>: Expression,
+    Operand: AppearsOnTable<QS>,
+    Low: AppearsOnTable<QS>,
+    High: AppearsOnTable<QS>,
+    Count: AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `WIDTH_BUCKET(operand, thresholds)` expression.
+///
+/// The buckets are given by an array of their lower bounds, sorted in
+/// ascending order, so they can have different widths and any type that
+/// can be compared. Returns the number of thresholds that are less than or
+/// equal to `operand`: 0 below the first one, the length of the array from
+/// the last one on. `operand` must not be nullable.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::width_bucket_thresholds;
+/// # diesel::table! { products (id) { id -> Integer, price -> Double, } }
+/// // WIDTH_BUCKET("products"."price", $1)
+/// let bucket = width_bucket_thresholds(products::price, vec![0.0, 10.0, 100.0, 1000.0]);
+/// ```
+pub fn width_bucket_thresholds<T, U>(operand: T, thresholds: U) -> WidthBucketThresholdsFunction<T, U::Expression>
+where
+    T: Expression,
+    T::SqlType: SqlType<IsNull = is_nullable::NotNull> + SingleValue,
+    U: AsExpression<Array<T::SqlType>>,
+{
+    WidthBucketThresholdsFunction {
+        operand,
+        thresholds: thresholds.as_expression(),
+    }
+}
+
+/// PostgreSQL `WIDTH_BUCKET` function with explicit bucket thresholds
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct WidthBucketThresholdsFunction<Operand, Thresholds> {
+    operand: Operand,
+    thresholds: Thresholds,
+}
+
+impl<Operand, Thresholds> Expression for WidthBucketThresholdsFunction<Operand, Thresholds>
+where
+    Operand: Expression,
+    Operand::SqlType: SqlType<IsNull = is_nullable::NotNull> + SingleValue,
+    Thresholds: Expression<SqlType = Array<Operand::SqlType>>,
+{
+    type SqlType = Integer;
+}
+
+impl<Operand, Thresholds> QueryFragment<GaussDB> for WidthBucketThresholdsFunction<Operand, Thresholds>
+where
+    Operand: QueryFragment<GaussDB>,
+    Thresholds: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("WIDTH_BUCKET(");
+        self.operand.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.thresholds.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Operand, Thresholds, QS> SelectableExpression<QS> for WidthBucketThresholdsFunction<Operand, Thresholds>
+where
+    WidthBucketThresholdsFunction<Operand, Thresholds>: AppearsOnTable<QS>,
+{
+}
+
+impl<Operand, Thresholds, QS> AppearsOnTable<QS> for WidthBucketThresholdsFunction<Operand, Thresholds>
+where
+    WidthBucketThresholdsFunction<Operand, Thresholds>: Expression,
+    Operand: AppearsOnTable<QS>,
+    Thresholds: AppearsOnTable<QS>,
+{
+}
+
+/// Trait providing [`histogram`](HistogramDsl::histogram) for all queries
+pub trait HistogramDsl: Sized {
+    /// Count the rows of this query per bucket
+    ///
+    /// Selects `bucket` from this query, which may be filtered, and counts
+    /// the rows of every bucket, in bucket order. Buckets without rows are
+    /// not returned. The query renders as
+    /// `SELECT "bucket", COUNT(*) FROM (query) AS "histogram"("bucket")
+    /// GROUP BY "bucket" ORDER BY "bucket"`, so the bucket expression and its
+    /// bind parameters are only written once, and loads as `(bucket, count)`
+    /// pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::functions::{width_bucket, HistogramDsl};
+    /// # diesel::table! { products (id) { id -> Integer, price -> Double, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// let price_histogram: Vec<(i32, i64)> = products::table
+    ///     .histogram(width_bucket(products::price, 0.0, 1000.0, 10))
+    ///     .load(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn histogram<E>(self, bucket: E) -> Histogram<Select<Self, E>>
+    where
+        E: Expression,
+        Self: SelectDsl<E>,
+    {
+        Histogram {
+            source: self.select(bucket),
+        }
+    }
+}
+
+impl<T> HistogramDsl for T {}
+
+/// Rows per bucket of a query, see [`HistogramDsl::histogram`]
+#[derive(Debug, Clone, QueryId)]
+pub struct Histogram<S> {
+    source: S,
+}
+
+impl<S> Query for Histogram<S>
+where
+    S: Query,
+{
+    type SqlType = (S::SqlType, BigInt);
+}
+
+impl<S> QueryFragment<GaussDB> for Histogram<S>
+where
+    S: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("SELECT \"bucket\", COUNT(*) FROM (");
+        self.source.walk_ast(out.reborrow())?;
+        out.push_sql(") AS \"histogram\"(\"bucket\") GROUP BY \"bucket\" ORDER BY \"bucket\"");
+        Ok(())
+    }
+}
+
+impl<S, Conn> RunQueryDsl<Conn> for Histogram<S> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -538,4 +1219,74 @@ mod tests {
         fn assert_double_expr<T: Expression<SqlType = Double>>(_: T) {}
         assert_double_expr(sqrt_expr);
     }
+
+    #[test]
+    fn test_logarithm_functions() {
+        let double_expr = diesel::dsl::sql::<Double>("100");
+        let ln_expr = ln(double_expr.clone());
+        assert!(format!("{:?}", ln_expr).contains("LnFunction"));
+        let log10_expr = log10(double_expr.clone());
+        let log_expr = log(2.0, double_expr);
+        assert!(format!("{:?}", log_expr).contains("LogFunction"));
+
+        fn assert_double_expr<T: Expression<SqlType = Double>>(_: T) {}
+        assert_double_expr(ln_expr);
+        assert_double_expr(log10_expr);
+        assert_double_expr(log_expr);
+    }
+
+    #[test]
+    fn test_trigonometric_functions() {
+        let double_expr = diesel::dsl::sql::<Double>("0.5");
+        let atan2_expr = atan2(double_expr.clone(), 1.0);
+        assert!(format!("{:?}", atan2_expr).contains("Atan2Function"));
+
+        fn assert_double_expr<T: Expression<SqlType = Double>>(_: T) {}
+        assert_double_expr(sin(double_expr.clone()));
+        assert_double_expr(cos(double_expr.clone()));
+        assert_double_expr(tan(double_expr.clone()));
+        assert_double_expr(asin(double_expr.clone()));
+        assert_double_expr(acos(double_expr.clone()));
+        assert_double_expr(atan(double_expr));
+        assert_double_expr(atan2_expr);
+    }
+
+    #[test]
+    fn test_sign_trunc_cbrt_exp_functions() {
+        let double_expr = diesel::dsl::sql::<Double>("-4.7");
+
+        fn assert_double_expr<T: Expression<SqlType = Double>>(_: T) {}
+        assert_double_expr(sign(double_expr.clone()));
+        assert_double_expr(trunc(double_expr.clone()));
+        assert_double_expr(cbrt(double_expr.clone()));
+        assert_double_expr(exp(double_expr));
+    }
+
+    #[test]
+    fn test_width_bucket_functions() {
+        let double_expr = diesel::dsl::sql::<Double>("4.2");
+        let numeric_expr = diesel::dsl::sql::<Numeric>("4.2");
+
+        fn assert_integer_expr<T: Expression<SqlType = Integer>>(_: T) {}
+        assert_integer_expr(width_bucket(double_expr.clone(), 0.0, 10.0, 5));
+        assert_integer_expr(width_bucket(
+            numeric_expr.clone(),
+            diesel::dsl::sql::<Numeric>("0"),
+            diesel::dsl::sql::<Numeric>("10"),
+            5,
+        ));
+        assert_integer_expr(width_bucket_thresholds(double_expr, vec![0.0, 1.0, 10.0]));
+        assert_integer_expr(width_bucket_thresholds(diesel::dsl::sql::<Integer>("7"), vec![0, 5, 10]));
+    }
+
+    #[test]
+    fn test_safe_divide_keeps_numeric_type() {
+        fn assert_type<ST, T: Expression<SqlType = ST>>(_: T) {}
+        assert_type::<Nullable<Integer>, _>(safe_divide(diesel::dsl::sql::<Integer>("7"), 2));
+        assert_type::<Nullable<Numeric>, _>(safe_divide(
+            diesel::dsl::sql::<Numeric>("7"),
+            diesel::dsl::sql::<Numeric>("2"),
+        ));
+        assert_type::<Nullable<Double>, _>(safe_divide(diesel::dsl::sql::<Nullable<Double>>("NULL"), 2.0));
+    }
 }
@@ -9,6 +9,7 @@ use diesel::expression::{
     AppearsOnTable, AsExpression, Expression, SelectableExpression,
     ValidGrouping,
 };
+use diesel::dsl::AsExprOf;
 use diesel::query_builder::{AstPass, QueryFragment, QueryId};
 use diesel::result::QueryResult;
 use diesel::sql_types::{Integer, Nullable, Text};
@@ -201,7 +202,8 @@ where
 
 /// Creates a PostgreSQL `TRIM(string)` expression.
 ///
-/// Removes leading and trailing whitespace from the string.
+/// Removes leading and trailing whitespace from the string. Use
+/// [`characters`](TrimFunction::characters) to remove other characters.
 ///
 /// # Examples
 ///
@@ -210,54 +212,162 @@ where
 /// # use diesel::sql_types::Text;
 /// // TRIM('  hello  ')
 /// let trimmed = trim(diesel::dsl::sql::<Text>("'  hello  '"));
+/// // TRIM(BOTH 'xy' FROM 'xxhelloyx')
+/// let stripped = trim(diesel::dsl::sql::<Text>("'xxhelloyx'")).characters("xy");
 /// ```
 pub fn trim<T>(string: T) -> TrimFunction<T::Expression>
 where
     T: AsExpression<Text>,
 {
-    TrimFunction::new(string.as_expression())
+    TrimFunction::new(TrimSide::Both, string.as_expression())
 }
 
-/// PostgreSQL `TRIM` function
-#[derive(Debug, Clone, QueryId, ValidGrouping)]
-pub struct TrimFunction<Expr> {
+/// Creates a PostgreSQL `LTRIM(string)` expression.
+///
+/// Removes leading whitespace from the string. Use
+/// [`characters`](TrimFunction::characters) to remove other characters.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::ltrim;
+/// # use diesel::sql_types::Text;
+/// // TRIM(LEADING '0' FROM '000420')
+/// let number = ltrim(diesel::dsl::sql::<Text>("'000420'")).characters("0");
+/// ```
+pub fn ltrim<T>(string: T) -> TrimFunction<T::Expression>
+where
+    T: AsExpression<Text>,
+{
+    TrimFunction::new(TrimSide::Leading, string.as_expression())
+}
+
+/// Creates a PostgreSQL `RTRIM(string)` expression.
+///
+/// Removes trailing whitespace from the string. Use
+/// [`characters`](TrimFunction::characters) to remove other characters.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::rtrim;
+/// # use diesel::sql_types::Text;
+/// // TRIM(TRAILING '/' FROM 'https://example.com//')
+/// let url = rtrim(diesel::dsl::sql::<Text>("'https://example.com//'")).characters("/");
+/// ```
+pub fn rtrim<T>(string: T) -> TrimFunction<T::Expression>
+where
+    T: AsExpression<Text>,
+{
+    TrimFunction::new(TrimSide::Trailing, string.as_expression())
+}
+
+/// The end of the string [`TrimFunction`] removes characters from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrimSide {
+    Both,
+    Leading,
+    Trailing,
+}
+
+/// PostgreSQL `TRIM`, `LTRIM` and `RTRIM` functions
+///
+/// [`trim`], [`ltrim`] and [`rtrim`] return the same type, so the side can
+/// be chosen at runtime. Without a character set the default `Chars` type
+/// is never rendered.
+#[derive(Debug, Clone)]
+pub struct TrimFunction<Expr, Chars = AsExprOf<&'static str, Text>> {
+    side: TrimSide,
     string: Expr,
+    characters: Option<Chars>,
 }
 
 impl<Expr> TrimFunction<Expr> {
-    fn new(string: Expr) -> Self {
-        TrimFunction { string }
+    fn new(side: TrimSide, string: Expr) -> Self {
+        TrimFunction { side, string, characters: None }
     }
 }
 
-impl<Expr> Expression for TrimFunction<Expr>
+impl<Expr, Chars> TrimFunction<Expr, Chars> {
+    /// Remove any of the characters in `characters` instead of whitespace
+    ///
+    /// Renders `TRIM(BOTH | LEADING | TRAILING characters FROM string)`
+    /// with the characters sent as a text bind parameter.
+    pub fn characters<C>(self, characters: C) -> TrimFunction<Expr, C::Expression>
+    where
+        C: AsExpression<Text>,
+    {
+        TrimFunction {
+            side: self.side,
+            string: self.string,
+            characters: Some(characters.as_expression()),
+        }
+    }
+}
+
+impl<Expr, Chars> Expression for TrimFunction<Expr, Chars>
 where
     Expr: Expression<SqlType = Text>,
+    Chars: Expression<SqlType = Text>,
 {
     type SqlType = Text;
 }
 
-impl<Expr> QueryFragment<GaussDB> for TrimFunction<Expr>
+impl<Expr, Chars> QueryFragment<GaussDB> for TrimFunction<Expr, Chars>
 where
     Expr: QueryFragment<GaussDB>,
+    Chars: QueryFragment<GaussDB>,
 {
     fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
-        out.push_sql("TRIM(");
-        self.string.walk_ast(out.reborrow())?;
+        match &self.characters {
+            None => {
+                out.push_sql(match self.side {
+                    TrimSide::Both => "TRIM(",
+                    TrimSide::Leading => "LTRIM(",
+                    TrimSide::Trailing => "RTRIM(",
+                });
+                self.string.walk_ast(out.reborrow())?;
+            }
+            Some(characters) => {
+                out.push_sql(match self.side {
+                    TrimSide::Both => "TRIM(BOTH ",
+                    TrimSide::Leading => "TRIM(LEADING ",
+                    TrimSide::Trailing => "TRIM(TRAILING ",
+                });
+                characters.walk_ast(out.reborrow())?;
+                out.push_sql(" FROM ");
+                self.string.walk_ast(out.reborrow())?;
+            }
+        }
         out.push_sql(")");
         Ok(())
     }
 }
 
-impl<Expr, QS> SelectableExpression<QS> for TrimFunction<Expr>
+// The side is chosen at runtime, so it is not part of a static query id
+impl<Expr, Chars> QueryId for TrimFunction<Expr, Chars> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+// Derived `ValidGrouping` doesn't support the default type parameter
+impl<Expr, Chars, GB> ValidGrouping<GB> for TrimFunction<Expr, Chars>
+where
+    (Expr, Chars): ValidGrouping<GB>,
+{
+    type IsAggregate = <(Expr, Chars) as ValidGrouping<GB>>::IsAggregate;
+}
+
+impl<Expr, Chars, QS> SelectableExpression<QS> for TrimFunction<Expr, Chars>
 where
-    TrimFunction<Expr>: AppearsOnTable<QS>,
+    TrimFunction<Expr, Chars>: AppearsOnTable<QS>,
 {
 }
 
-impl<Expr, QS> AppearsOnTable<QS> for TrimFunction<Expr>
+impl<Expr, Chars, QS> AppearsOnTable<QS> for TrimFunction<Expr, Chars>
 where
     Expr: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    Chars: Expression<SqlType = Text> + AppearsOnTable<QS>,
 {
 }
 
@@ -481,6 +591,629 @@ where
 {
 }
 
+/// Creates a PostgreSQL `LEFT(string, n)` expression.
+///
+/// Returns the first `n` characters of the string. A negative `n` returns
+/// all but the last `|n|` characters.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::left;
+/// # use diesel::sql_types::Text;
+/// // LEFT('hello', 2)
+/// let prefix = left(diesel::dsl::sql::<Text>("'hello'"), 2);
+/// ```
+pub fn left<T, U>(string: T, n: U) -> LeftFunction<T::Expression, U::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Integer>,
+{
+    LeftFunction::new(string.as_expression(), n.as_expression())
+}
+
+/// PostgreSQL `LEFT` function
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct LeftFunction<Str, N> {
+    string: Str,
+    n: N,
+}
+
+impl<Str, N> LeftFunction<Str, N> {
+    fn new(string: Str, n: N) -> Self {
+        LeftFunction { string, n }
+    }
+}
+
+impl<Str, N> Expression for LeftFunction<Str, N>
+where
+    Str: Expression<SqlType = Text>,
+    N: Expression<SqlType = Integer>,
+{
+    type SqlType = Text;
+}
+
+impl<Str, N> QueryFragment<GaussDB> for LeftFunction<Str, N>
+where
+    Str: QueryFragment<GaussDB>,
+    N: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("LEFT(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.n.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Str, N, QS> SelectableExpression<QS> for LeftFunction<Str, N>
+where
+    LeftFunction<Str, N>: AppearsOnTable<QS>,
+{
+}
+
+impl<Str, N, QS> AppearsOnTable<QS> for LeftFunction<Str, N>
+where
+    Str: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    N: Expression<SqlType = Integer> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `RIGHT(string, n)` expression.
+///
+/// Returns the last `n` characters of the string. A negative `n` returns
+/// all but the first `|n|` characters.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::right;
+/// # use diesel::sql_types::Text;
+/// // RIGHT('hello', 3)
+/// let suffix = right(diesel::dsl::sql::<Text>("'hello'"), 3);
+/// ```
+pub fn right<T, U>(string: T, n: U) -> RightFunction<T::Expression, U::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Integer>,
+{
+    RightFunction::new(string.as_expression(), n.as_expression())
+}
+
+/// PostgreSQL `RIGHT` function
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct RightFunction<Str, N> {
+    string: Str,
+    n: N,
+}
+
+impl<Str, N> RightFunction<Str, N> {
+    fn new(string: Str, n: N) -> Self {
+        RightFunction { string, n }
+    }
+}
+
+impl<Str, N> Expression for RightFunction<Str, N>
+where
+    Str: Expression<SqlType = Text>,
+    N: Expression<SqlType = Integer>,
+{
+    type SqlType = Text;
+}
+
+impl<Str, N> QueryFragment<GaussDB> for RightFunction<Str, N>
+where
+    Str: QueryFragment<GaussDB>,
+    N: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("RIGHT(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.n.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Str, N, QS> SelectableExpression<QS> for RightFunction<Str, N>
+where
+    RightFunction<Str, N>: AppearsOnTable<QS>,
+{
+}
+
+impl<Str, N, QS> AppearsOnTable<QS> for RightFunction<Str, N>
+where
+    Str: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    N: Expression<SqlType = Integer> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `LPAD(string, length)` expression.
+///
+/// Pads the string on the left to `length` characters, by default with spaces. Use
+/// [`with_fill`](LpadFunction::with_fill) to pad with another string. Strings
+/// longer than `length` are truncated.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::lpad;
+/// # use diesel::sql_types::Text;
+/// // LPAD('42', 5, '0')
+/// let padded = lpad(diesel::dsl::sql::<Text>("'42'"), 5).with_fill("0");
+/// ```
+pub fn lpad<T, U>(string: T, length: U) -> LpadFunction<T::Expression, U::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Integer>,
+{
+    LpadFunction::new(string.as_expression(), length.as_expression(), None)
+}
+
+/// PostgreSQL `LPAD` function
+///
+/// Without a fill string the default `Fill` type is never rendered.
+#[derive(Debug, Clone)]
+pub struct LpadFunction<Str, Len, Fill = AsExprOf<&'static str, Text>> {
+    string: Str,
+    length: Len,
+    fill: Option<Fill>,
+}
+
+impl<Str, Len, Fill> LpadFunction<Str, Len, Fill> {
+    fn new(string: Str, length: Len, fill: Option<Fill>) -> Self {
+        LpadFunction { string, length, fill }
+    }
+
+    /// Pad with `fill` instead of spaces
+    pub fn with_fill<F>(self, fill: F) -> LpadFunction<Str, Len, F::Expression>
+    where
+        F: AsExpression<Text>,
+    {
+        LpadFunction::new(self.string, self.length, Some(fill.as_expression()))
+    }
+}
+
+impl<Str, Len, Fill> Expression for LpadFunction<Str, Len, Fill>
+where
+    Str: Expression<SqlType = Text>,
+    Len: Expression<SqlType = Integer>,
+    Fill: Expression<SqlType = Text>,
+{
+    type SqlType = Text;
+}
+
+impl<Str, Len, Fill> QueryFragment<GaussDB> for LpadFunction<Str, Len, Fill>
+where
+    Str: QueryFragment<GaussDB>,
+    Len: QueryFragment<GaussDB>,
+    Fill: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("LPAD(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.length.walk_ast(out.reborrow())?;
+        if let Some(fill) = &self.fill {
+            out.push_sql(", ");
+            fill.walk_ast(out.reborrow())?;
+        }
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+runtime_query_id!(LpadFunction<Str, Len, Fill>);
+
+// Derived `ValidGrouping` doesn't support the default type parameter
+impl<Str, Len, Fill, GB> ValidGrouping<GB> for LpadFunction<Str, Len, Fill>
+where
+    (Str, Len, Fill): ValidGrouping<GB>,
+{
+    type IsAggregate = <(Str, Len, Fill) as ValidGrouping<GB>>::IsAggregate;
+}
+
+impl<Str, Len, Fill, QS> SelectableExpression<QS> for LpadFunction<Str, Len, Fill>
+where
+    LpadFunction<Str, Len, Fill>: AppearsOnTable<QS>,
+{
+}
+
+impl<Str, Len, Fill, QS> AppearsOnTable<QS> for LpadFunction<Str, Len, Fill>
+where
+    Str: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    Len: Expression<SqlType = Integer> + AppearsOnTable<QS>,
+    Fill: Expression<SqlType = Text> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `RPAD(string, length)` expression.
+///
+/// Pads the string on the right to `length` characters, by default with spaces. Use
+/// [`with_fill`](RpadFunction::with_fill) to pad with another string. Strings
+/// longer than `length` are truncated.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::rpad;
+/// # use diesel::sql_types::Text;
+/// // RPAD('42', 5, '0')
+/// let padded = rpad(diesel::dsl::sql::<Text>("'42'"), 5).with_fill("0");
+/// ```
+pub fn rpad<T, U>(string: T, length: U) -> RpadFunction<T::Expression, U::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Integer>,
+{
+    RpadFunction::new(string.as_expression(), length.as_expression(), None)
+}
+
+/// PostgreSQL `RPAD` function
+///
+/// Without a fill string the default `Fill` type is never rendered.
+#[derive(Debug, Clone)]
+pub struct RpadFunction<Str, Len, Fill = AsExprOf<&'static str, Text>> {
+    string: Str,
+    length: Len,
+    fill: Option<Fill>,
+}
+
+impl<Str, Len, Fill> RpadFunction<Str, Len, Fill> {
+    fn new(string: Str, length: Len, fill: Option<Fill>) -> Self {
+        RpadFunction { string, length, fill }
+    }
+
+    /// Pad with `fill` instead of spaces
+    pub fn with_fill<F>(self, fill: F) -> RpadFunction<Str, Len, F::Expression>
+    where
+        F: AsExpression<Text>,
+    {
+        RpadFunction::new(self.string, self.length, Some(fill.as_expression()))
+    }
+}
+
+impl<Str, Len, Fill> Expression for RpadFunction<Str, Len, Fill>
+where
+    Str: Expression<SqlType = Text>,
+    Len: Expression<SqlType = Integer>,
+    Fill: Expression<SqlType = Text>,
+{
+    type SqlType = Text;
+}
+
+impl<Str, Len, Fill> QueryFragment<GaussDB> for RpadFunction<Str, Len, Fill>
+where
+    Str: QueryFragment<GaussDB>,
+    Len: QueryFragment<GaussDB>,
+    Fill: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("RPAD(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.length.walk_ast(out.reborrow())?;
+        if let Some(fill) = &self.fill {
+            out.push_sql(", ");
+            fill.walk_ast(out.reborrow())?;
+        }
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+runtime_query_id!(RpadFunction<Str, Len, Fill>);
+
+// Derived `ValidGrouping` doesn't support the default type parameter
+impl<Str, Len, Fill, GB> ValidGrouping<GB> for RpadFunction<Str, Len, Fill>
+where
+    (Str, Len, Fill): ValidGrouping<GB>,
+{
+    type IsAggregate = <(Str, Len, Fill) as ValidGrouping<GB>>::IsAggregate;
+}
+
+impl<Str, Len, Fill, QS> SelectableExpression<QS> for RpadFunction<Str, Len, Fill>
+where
+    RpadFunction<Str, Len, Fill>: AppearsOnTable<QS>,
+{
+}
+
+impl<Str, Len, Fill, QS> AppearsOnTable<QS> for RpadFunction<Str, Len, Fill>
+where
+    Str: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    Len: Expression<SqlType = Integer> + AppearsOnTable<QS>,
+    Fill: Expression<SqlType = Text> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `REPEAT(string, n)` expression.
+///
+/// Repeats the string `n` times.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::repeat;
+/// # use diesel::sql_types::Text;
+/// // REPEAT('ab', 3)
+/// let repeated = repeat(diesel::dsl::sql::<Text>("'ab'"), 3);
+/// ```
+pub fn repeat<T, U>(string: T, n: U) -> RepeatFunction<T::Expression, U::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Integer>,
+{
+    RepeatFunction::new(string.as_expression(), n.as_expression())
+}
+
+/// PostgreSQL `REPEAT` function
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct RepeatFunction<Str, N> {
+    string: Str,
+    n: N,
+}
+
+impl<Str, N> RepeatFunction<Str, N> {
+    fn new(string: Str, n: N) -> Self {
+        RepeatFunction { string, n }
+    }
+}
+
+impl<Str, N> Expression for RepeatFunction<Str, N>
+where
+    Str: Expression<SqlType = Text>,
+    N: Expression<SqlType = Integer>,
+{
+    type SqlType = Text;
+}
+
+impl<Str, N> QueryFragment<GaussDB> for RepeatFunction<Str, N>
+where
+    Str: QueryFragment<GaussDB>,
+    N: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("REPEAT(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.n.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Str, N, QS> SelectableExpression<QS> for RepeatFunction<Str, N>
+where
+    RepeatFunction<Str, N>: AppearsOnTable<QS>,
+{
+}
+
+impl<Str, N, QS> AppearsOnTable<QS> for RepeatFunction<Str, N>
+where
+    Str: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    N: Expression<SqlType = Integer> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `REPLACE(string, from, to)` expression.
+///
+/// Replaces every occurrence of `from` in the string with `to`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::replace;
+/// # use diesel::sql_types::Text;
+/// // REPLACE('a-b-c', '-', '/')
+/// let replaced = replace(diesel::dsl::sql::<Text>("'a-b-c'"), "-", "/");
+/// ```
+pub fn replace<T, U, V>(string: T, from: U, to: V) -> ReplaceFunction<T::Expression, U::Expression, V::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Text>,
+    V: AsExpression<Text>,
+{
+    ReplaceFunction::new(string.as_expression(), from.as_expression(), to.as_expression())
+}
+
+/// PostgreSQL `REPLACE` function
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct ReplaceFunction<Str, FromExpr, ToExpr> {
+    string: Str,
+    from: FromExpr,
+    to: ToExpr,
+}
+
+impl<Str, FromExpr, ToExpr> ReplaceFunction<Str, FromExpr, ToExpr> {
+    fn new(string: Str, from: FromExpr, to: ToExpr) -> Self {
+        ReplaceFunction { string, from, to }
+    }
+}
+
+impl<Str, FromExpr, ToExpr> Expression for ReplaceFunction<Str, FromExpr, ToExpr>
+where
+    Str: Expression<SqlType = Text>,
+    FromExpr: Expression<SqlType = Text>,
+    ToExpr: Expression<SqlType = Text>,
+{
+    type SqlType = Text;
+}
+
+impl<Str, FromExpr, ToExpr> QueryFragment<GaussDB> for ReplaceFunction<Str, FromExpr, ToExpr>
+where
+    Str: QueryFragment<GaussDB>,
+    FromExpr: QueryFragment<GaussDB>,
+    ToExpr: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("REPLACE(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.from.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.to.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Str, FromExpr, ToExpr, QS> SelectableExpression<QS> for ReplaceFunction<Str, FromExpr, ToExpr>
+where
+    ReplaceFunction<Str, FromExpr, ToExpr>: AppearsOnTable<QS>,
+{
+}
+
+impl<Str, FromExpr, ToExpr, QS> AppearsOnTable<QS> for ReplaceFunction<Str, FromExpr, ToExpr>
+where
+    Str: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    FromExpr: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    ToExpr: Expression<SqlType = Text> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `SPLIT_PART(string, delimiter, field)` expression.
+///
+/// Splits the string at `delimiter` and returns the `field`-th part,
+/// counting from 1. Returns an empty string if there are fewer fields.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::split_part;
+/// # use diesel::sql_types::Text;
+/// // SPLIT_PART('2024-01-31', '-', 2)
+/// let month = split_part(diesel::dsl::sql::<Text>("'2024-01-31'"), "-", 2);
+/// ```
+pub fn split_part<T, U, V>(string: T, delimiter: U, field: V) -> SplitPartFunction<T::Expression, U::Expression, V::Expression>
+where
+    T: AsExpression<Text>,
+    U: AsExpression<Text>,
+    V: AsExpression<Integer>,
+{
+    SplitPartFunction::new(string.as_expression(), delimiter.as_expression(), field.as_expression())
+}
+
+/// PostgreSQL `SPLIT_PART` function
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct SplitPartFunction<Str, Delim, Field> {
+    string: Str,
+    delimiter: Delim,
+    field: Field,
+}
+
+impl<Str, Delim, Field> SplitPartFunction<Str, Delim, Field> {
+    fn new(string: Str, delimiter: Delim, field: Field) -> Self {
+        SplitPartFunction { string, delimiter, field }
+    }
+}
+
+impl<Str, Delim, Field> Expression for SplitPartFunction<Str, Delim, Field>
+where
+    Str: Expression<SqlType = Text>,
+    Delim: Expression<SqlType = Text>,
+    Field: Expression<SqlType = Integer>,
+{
+    type SqlType = Text;
+}
+
+impl<Str, Delim, Field> QueryFragment<GaussDB> for SplitPartFunction<Str, Delim, Field>
+where
+    Str: QueryFragment<GaussDB>,
+    Delim: QueryFragment<GaussDB>,
+    Field: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("SPLIT_PART(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.delimiter.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.field.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Str, Delim, Field, QS> SelectableExpression<QS> for SplitPartFunction<Str, Delim, Field>
+where
+    SplitPartFunction<Str, Delim, Field>: AppearsOnTable<QS>,
+{
+}
+
+impl<Str, Delim, Field, QS> AppearsOnTable<QS> for SplitPartFunction<Str, Delim, Field>
+where
+    Str: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    Delim: Expression<SqlType = Text> + AppearsOnTable<QS>,
+    Field: Expression<SqlType = Integer> + AppearsOnTable<QS>,
+{
+}
+
+/// Creates a PostgreSQL `INITCAP(string)` expression.
+///
+/// Converts the first letter of each word to uppercase and the rest to
+/// lowercase.
+///
+/// # Examples
+///
+/// ```rust
+/// # use diesel_gaussdb::expression::functions::initcap;
+/// # use diesel::sql_types::Text;
+/// // INITCAP('hello WORLD')
+/// let title = initcap(diesel::dsl::sql::<Text>("'hello WORLD'"));
+/// ```
+pub fn initcap<T>(string: T) -> InitcapFunction<T::Expression>
+where
+    T: AsExpression<Text>,
+{
+    InitcapFunction::new(string.as_expression())
+}
+
+/// PostgreSQL `INITCAP` function
+#[derive(Debug, Clone, QueryId, ValidGrouping)]
+pub struct InitcapFunction<Expr> {
+    string: Expr,
+}
+
+impl<Expr> InitcapFunction<Expr> {
+    fn new(string: Expr) -> Self {
+        InitcapFunction { string }
+    }
+}
+
+impl<Expr> Expression for InitcapFunction<Expr>
+where
+    Expr: Expression<SqlType = Text>,
+{
+    type SqlType = Text;
+}
+
+impl<Expr> QueryFragment<GaussDB> for InitcapFunction<Expr>
+where
+    Expr: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("INITCAP(");
+        self.string.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<Expr, QS> SelectableExpression<QS> for InitcapFunction<Expr>
+where
+    InitcapFunction<Expr>: AppearsOnTable<QS>,
+{
+}
+
+impl<Expr, QS> AppearsOnTable<QS> for InitcapFunction<Expr>
+where
+    Expr: Expression<SqlType = Text> + AppearsOnTable<QS>,
+{
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -545,4 +1278,57 @@ mod tests {
         fn assert_text_expr<T: Expression<SqlType = Text>>(_: T) {}
         assert_text_expr(substring_expr);
     }
+
+    #[test]
+    fn test_left_and_right_functions() {
+        let text_expr = diesel::dsl::sql::<Text>("'hello'");
+        let left_expr = left(text_expr.clone(), 2);
+        assert!(format!("{:?}", left_expr).contains("LeftFunction"));
+        let right_expr = right(text_expr, 3);
+        assert!(format!("{:?}", right_expr).contains("RightFunction"));
+
+        fn assert_text_expr<T: Expression<SqlType = Text>>(_: T) {}
+        assert_text_expr(left_expr);
+        assert_text_expr(right_expr);
+    }
+
+    #[test]
+    fn test_pad_functions() {
+        let text_expr = diesel::dsl::sql::<Text>("'42'");
+        let lpad_expr = lpad(text_expr.clone(), 5);
+        assert!(format!("{:?}", lpad_expr).contains("LpadFunction"));
+        let rpad_expr = rpad(text_expr, 5).with_fill(".");
+        assert!(format!("{:?}", rpad_expr).contains("RpadFunction"));
+
+        fn assert_text_expr<T: Expression<SqlType = Text>>(_: T) {}
+        assert_text_expr(lpad_expr.clone());
+        assert_text_expr(lpad_expr.with_fill("0"));
+        assert_text_expr(rpad_expr);
+    }
+
+    #[test]
+    fn test_repeat_and_replace_functions() {
+        let text_expr = diesel::dsl::sql::<Text>("'ab'");
+        let repeat_expr = repeat(text_expr.clone(), 3);
+        assert!(format!("{:?}", repeat_expr).contains("RepeatFunction"));
+        let replace_expr = replace(text_expr, "a", "b");
+        assert!(format!("{:?}", replace_expr).contains("ReplaceFunction"));
+
+        fn assert_text_expr<T: Expression<SqlType = Text>>(_: T) {}
+        assert_text_expr(repeat_expr);
+        assert_text_expr(replace_expr);
+    }
+
+    #[test]
+    fn test_split_part_and_initcap_functions() {
+        let text_expr = diesel::dsl::sql::<Text>("'a,b,c'");
+        let split_part_expr = split_part(text_expr.clone(), ",", 2);
+        assert!(format!("{:?}", split_part_expr).contains("SplitPartFunction"));
+        let initcap_expr = initcap(text_expr);
+        assert!(format!("{:?}", initcap_expr).contains("InitcapFunction"));
+
+        fn assert_text_expr<T: Expression<SqlType = Text>>(_: T) {}
+        assert_text_expr(split_part_expr);
+        assert_text_expr(initcap_expr);
+    }
 }
@@ -0,0 +1,204 @@
+//! JSONB operators for GaussDB
+//!
+//! This module provides the JSONB key-existence operators `?`, `?|` and
+//! `?&`. Unlike containment (`@>`) they only look at the top-level keys of
+//! an object, which makes them the usual filter for sparse documents.
+//!
+//! The methods carry a `jsonb_` prefix because Diesel's own `has_key` family
+//! is in scope through `diesel::prelude` but only builds queries for `Pg`.
+
+use crate::backend::GaussDB;
+use crate::expression::functions::json::JsonbDocument;
+use diesel::expression::{AsExpression, Expression};
+use diesel::infix_operator;
+use diesel::sql_types::{Array, Text};
+
+/// Trait providing the JSONB key-existence operators
+///
+/// Implemented for `Jsonb` and `Nullable<Jsonb>` expressions; on a `NULL`
+/// document the operators return `NULL`.
+pub trait GaussDBJsonbExpressionMethods: Expression + Sized {
+    /// Creates a `?` expression, checking if the document has the top-level
+    /// key `key`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::jsonb_ops::GaussDBJsonbExpressionMethods;
+    /// # table! { events (id) { id -> Integer, payload -> Jsonb, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // Find events that carry an error code
+    /// let ids = events::table
+    ///     .filter(events::payload.jsonb_has_key("error_code"))
+    ///     .select(events::id)
+    ///     .load::<i32>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn jsonb_has_key<T>(self, key: T) -> HasKey<Self, T::Expression>
+    where
+        T: AsExpression<Text>;
+
+    /// Creates a `?|` expression, checking if the document has at least one
+    /// of the top-level `keys`.
+    ///
+    /// The keys are sent as a single `text[]` bind parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::jsonb_ops::GaussDBJsonbExpressionMethods;
+    /// # table! { events (id) { id -> Integer, payload -> Jsonb, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// let ids = events::table
+    ///     .filter(events::payload.jsonb_has_any_key(vec!["error_code", "warning"]))
+    ///     .select(events::id)
+    ///     .load::<i32>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn jsonb_has_any_key<T>(self, keys: T) -> HasAnyKey<Self, T::Expression>
+    where
+        T: AsExpression<Array<Text>>;
+
+    /// Creates a `?&` expression, checking if the document has all of the
+    /// top-level `keys`.
+    ///
+    /// The keys are sent as a single `text[]` bind parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel::prelude::*;
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::expression::jsonb_ops::GaussDBJsonbExpressionMethods;
+    /// # table! { events (id) { id -> Integer, payload -> Jsonb, } }
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// let ids = events::table
+    ///     .filter(events::payload.jsonb_has_all_keys(vec!["user", "session"]))
+    ///     .select(events::id)
+    ///     .load::<i32>(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn jsonb_has_all_keys<T>(self, keys: T) -> HasAllKeys<Self, T::Expression>
+    where
+        T: AsExpression<Array<Text>>;
+}
+
+// Implement the trait for all jsonb expressions
+impl<T> GaussDBJsonbExpressionMethods for T
+where
+    T: Expression,
+    T::SqlType: JsonbDocument,
+{
+    fn jsonb_has_key<U>(self, key: U) -> HasKey<Self, U::Expression>
+    where
+        U: AsExpression<Text>,
+    {
+        HasKey::new(self, key.as_expression())
+    }
+
+    fn jsonb_has_any_key<U>(self, keys: U) -> HasAnyKey<Self, U::Expression>
+    where
+        U: AsExpression<Array<Text>>,
+    {
+        HasAnyKey::new(self, keys.as_expression())
+    }
+
+    fn jsonb_has_all_keys<U>(self, keys: U) -> HasAllKeys<Self, U::Expression>
+    where
+        U: AsExpression<Array<Text>>,
+    {
+        HasAllKeys::new(self, keys.as_expression())
+    }
+}
+
+infix_operator!(HasKey, " ? ", backend: GaussDB);
+infix_operator!(HasAnyKey, " ?| ", backend: GaussDB);
+infix_operator!(HasAllKeys, " ?& ", backend: GaussDB);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::TestLookup;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::prelude::*;
+    use diesel::query_builder::{QueryBuilder, QueryFragment};
+    use diesel::sql_types::{Bool, Nullable};
+
+    diesel::table! {
+        documents (id) {
+            id -> Integer,
+            body -> Jsonb,
+            extra -> Nullable<Jsonb>,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(query: T) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_has_key_sql() {
+        let query = documents::table
+            .filter(documents::body.jsonb_has_key("tags"))
+            .select(documents::id);
+
+        assert_eq!(
+            generate_sql(query),
+            r#"SELECT "documents"."id" FROM "documents" WHERE "documents"."body" ? $1"#
+        );
+    }
+
+    #[test]
+    fn test_has_any_and_all_keys_sql() {
+        let query = documents::table
+            .filter(documents::body.jsonb_has_any_key(vec!["a", "b"]))
+            .filter(documents::body.jsonb_has_all_keys(&["c", "d"][..]))
+            .select(documents::id);
+
+        assert_eq!(
+            generate_sql(query),
+            r#"SELECT "documents"."id" FROM "documents" WHERE ("documents"."body" ?| $1 AND "documents"."body" ?& $2)"#
+        );
+    }
+
+    #[test]
+    fn test_nullable_document() {
+        let has_key = documents::extra.jsonb_has_key("tags");
+        fn assert_nullable_bool<T: Expression<SqlType = Nullable<Bool>>>(_: &T) {}
+        assert_nullable_bool(&has_key);
+
+        let query = documents::table.filter(has_key).select(documents::id);
+        assert_eq!(
+            generate_sql(query),
+            r#"SELECT "documents"."id" FROM "documents" WHERE "documents"."extra" ? $1"#
+        );
+    }
+
+    #[test]
+    fn test_key_list_is_one_text_array_bind() {
+        use diesel::query_builder::bind_collector::RawBytesBindCollector;
+
+        let expression = documents::body.jsonb_has_any_key(vec!["a", "bc"]);
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        expression.collect_binds(&mut collector, &mut TestLookup::builtin(), &GaussDB).unwrap();
+
+        // A single `text[]` parameter holding both keys
+        assert_eq!(collector.metadata.len(), 1);
+        assert_eq!(collector.metadata[0].oid().unwrap(), 1009);
+        let bytes = collector.binds[0].as_deref().unwrap();
+        assert_eq!(&bytes[..12], &[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 25]);
+        assert_eq!(&bytes[12..16], &2i32.to_be_bytes());
+    }
+}
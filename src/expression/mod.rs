@@ -7,6 +7,21 @@
 // For now, we'll provide a simplified expression system
 // The full implementation will be added in future phases
 
+/// Implement `QueryId` without a static query id
+///
+/// For expression types that render differently depending on a value
+/// chosen at runtime, such as several SQL functions sharing one type and
+/// storing the function name, or an optional argument. A static id would
+/// let two different statements share a cached prepared statement.
+macro_rules! runtime_query_id {
+    ($name:ident<$($param:ident),+>) => {
+        impl<$($param),+> diesel::query_builder::QueryId for $name<$($param),+> {
+            type QueryId = ();
+            const HAS_STATIC_QUERY_ID: bool = false;
+        }
+    };
+}
+
 /// Array operations and expressions
 pub mod array_ops;
 
@@ -16,6 +31,15 @@ pub mod array_comparison;
 /// GaussDB specific expression methods
 pub mod expression_methods;
 
+/// JSONB operators for GaussDB
+pub mod jsonb_ops;
+
+/// Aliases for selected expressions
+pub mod select_alias;
+
+/// Row values and row comparisons
+pub mod row;
+
 /// GaussDB specific functions
 pub mod functions {
     //! GaussDB specific functions
@@ -26,6 +50,9 @@ pub mod functions {
     pub mod date_and_time;
     pub mod string;
     pub mod math;
+    pub mod aggregate;
+    pub mod formatting;
+    pub mod json;
 
     /// Re-export date and time functions
     pub use self::date_and_time::*;
@@ -33,6 +60,12 @@ pub mod functions {
     pub use self::string::*;
     /// Re-export math functions
     pub use self::math::*;
+    /// Re-export aggregate functions
+    pub use self::aggregate::*;
+    /// Re-export formatting functions
+    pub use self::formatting::*;
+    /// Re-export JSONB functions
+    pub use self::json::*;
 
     /// Placeholder for other functions
     pub fn functions_placeholder() {
@@ -54,21 +87,45 @@ pub mod operators {
 pub mod dsl {
     pub use super::functions::date_and_time::{
         current_date, current_time, current_timestamp, date_part, extract, now,
-        age, date_trunc,
+        age, date_trunc, transaction_timestamp, statement_timestamp, clock_timestamp,
+        extract_from_interval, justify_days, justify_hours, justify_interval,
+        greatest, least, overlaps, period_with_length,
+        interval_literal, years, months, weeks, days, hours, minutes, seconds, microseconds,
     };
     pub use super::functions::string::{
-        length, lower, substring, trim, upper, concat, position,
+        length, lower, substring, trim, ltrim, rtrim, upper, concat, position,
+        left, right, lpad, rpad, repeat, replace, split_part, initcap,
     };
     pub use super::functions::math::{
         abs, ceil, floor, round, sqrt, power, mod_func,
+        ln, log, log10, exp, sin, cos, tan, asin, acos, atan, atan2, sign, trunc, cbrt,
+        width_bucket, width_bucket_thresholds, HistogramDsl, safe_divide,
+    };
+    pub use super::functions::aggregate::{
+        sum, avg, bool_and, bool_or, bit_and, bit_or, count_if,
+        percentile_cont, percentile_disc, mode,
+    };
+    pub use super::functions::formatting::{
+        to_char, to_date, to_timestamp, to_number,
+    };
+    pub use super::functions::json::{
+        jsonb_array_elements, jsonb_each, jsonb_object_agg,
     };
     pub use super::array_ops::{
-        ArrayContainmentOps,
-        functions::array_length,
+        ArrayContainmentOps, ArrayIndexOps,
+        functions::{
+            array_append, array_cat, array_dims, array_length, array_lower, array_position,
+            array_prepend, array_remove, array_to_string, array_upper, cardinality,
+            generate_subscripts, string_to_array,
+        },
     };
     pub use super::expression_methods::{
-        GaussDBStringExpressionMethods,
+        GaussDBExpressionMethods, GaussDBSortExpressionMethods, GaussDBStringExpressionMethods,
+        GaussDBTimestampExpressionMethods,
     };
+    pub use super::jsonb_ops::GaussDBJsonbExpressionMethods;
+    pub use super::select_alias::GaussDBAliasExpressionMethods;
+    pub use super::row::row;
     pub use super::array_comparison::{
         any, all, Any, All, AsArrayExpression,
     };
@@ -0,0 +1,271 @@
+//! Row values for GaussDB
+//!
+//! [`row`] builds a row value such as `(created_at, id)`. Row values are
+//! compared element by element from left to right, which is what keyset
+//! pagination needs: `(created_at, id) < ($1, $2)` selects the rows that
+//! sort before the last row of the previous page.
+
+use crate::backend::GaussDB;
+use diesel::expression::{
+    AppearsOnTable, AsExpression, Expression, SelectableExpression, TypedExpressionType,
+    ValidGrouping,
+};
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::result::QueryResult;
+use diesel::sql_types::{Bool, MaybeNullableType, OneIsNullable, SqlType};
+
+/// Creates a row value from a tuple of expressions
+///
+/// The elements are usually columns; plain Rust values have no single SQL
+/// type and can only appear on the right-hand side. The comparison methods
+/// of the result take a tuple with one value or expression per element,
+/// whose types must match the element types. Values are sent as bind
+/// parameters.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel_gaussdb::expression::dsl::row;
+/// # diesel::table! { posts (id) { id -> Integer, created_at -> Timestamp, title -> Text, } }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+/// # let (last_created_at, last_id) = (diesel::dsl::now, 10);
+/// // WHERE (created_at, id) < ($1, $2) ORDER BY created_at DESC, id DESC
+/// let next_page = posts::table
+///     .filter(row((posts::created_at, posts::id)).lt((last_created_at, last_id)))
+///     .order((posts::created_at.desc(), posts::id.desc()))
+///     .limit(20)
+///     .select(posts::title)
+///     .load::<String>(&mut conn)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn row<ST, T>(values: T) -> RowValue<T::Expression>
+where
+    T: AsRowExpression<ST>,
+{
+    RowValue::new(values.as_row_expression())
+}
+
+/// Converts a tuple into the elements of a row value with the SQL types `ST`
+///
+/// Implemented for tuples of up to 6 values or expressions, where each
+/// element can be converted to the matching SQL type of `ST`.
+pub trait AsRowExpression<ST> {
+    /// The tuple of expressions making up the row
+    type Expression: Expression<SqlType = ST>;
+
+    /// Perform the conversion
+    #[allow(clippy::wrong_self_convention)] // named like `AsExpression::as_expression`
+    fn as_row_expression(self) -> Self::Expression;
+}
+
+macro_rules! row_tuples {
+    ($(($($T:ident: $ST:ident),+))+) => {$(
+        impl<$($T,)+ $($ST,)+> AsRowExpression<($($ST,)+)> for ($($T,)+)
+        where
+            $($T: AsExpression<$ST>, $ST: SqlType + TypedExpressionType,)+
+        {
+            type Expression = ($($T::Expression,)+);
+
+            #[allow(non_snake_case)]
+            fn as_row_expression(self) -> Self::Expression {
+                let ($($T,)+) = self;
+                ($($T.as_expression(),)+)
+            }
+        }
+    )+};
+}
+
+row_tuples! {
+    (T1: ST1, T2: ST2)
+    (T1: ST1, T2: ST2, T3: ST3)
+    (T1: ST1, T2: ST2, T3: ST3, T4: ST4)
+    (T1: ST1, T2: ST2, T3: ST3, T4: ST4, T5: ST5)
+    (T1: ST1, T2: ST2, T3: ST3, T4: ST4, T5: ST5, T6: ST6)
+}
+
+impl<T> AsRowExpression<T::SqlType> for RowValue<T>
+where
+    T: Expression,
+{
+    type Expression = T;
+
+    fn as_row_expression(self) -> T {
+        self.values
+    }
+}
+
+/// A row value `(a, b, ...)`, see [`row`]
+#[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+pub struct RowValue<T> {
+    values: T,
+}
+
+impl<T> RowValue<T> {
+    fn new(values: T) -> Self {
+        RowValue { values }
+    }
+}
+
+macro_rules! row_comparison_methods {
+    ($($(#[$meta:meta])* $method:ident -> $Op:ident,)+) => {$(
+        $(#[$meta])*
+        pub fn $method<U>(self, other: U) -> $Op<Self, RowValue<U::Expression>>
+        where
+            U: AsRowExpression<T::SqlType>,
+        {
+            $Op {
+                left: self,
+                right: row(other),
+            }
+        }
+    )+};
+}
+
+impl<T> RowValue<T>
+where
+    T: Expression,
+{
+    row_comparison_methods! {
+        /// Creates a `(a, b) = (x, y)` expression
+        eq -> RowEq,
+        /// Creates a `(a, b) != (x, y)` expression
+        ne -> RowNotEq,
+        /// Creates a `(a, b) < (x, y)` expression
+        lt -> RowLt,
+        /// Creates a `(a, b) <= (x, y)` expression
+        le -> RowLtEq,
+        /// Creates a `(a, b) > (x, y)` expression
+        gt -> RowGt,
+        /// Creates a `(a, b) >= (x, y)` expression
+        ge -> RowGtEq,
+    }
+}
+
+impl<T> Expression for RowValue<T>
+where
+    T: Expression,
+{
+    type SqlType = T::SqlType;
+}
+
+impl<T, QS> AppearsOnTable<QS> for RowValue<T>
+where
+    T: AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+impl<T, QS> SelectableExpression<QS> for RowValue<T>
+where
+    T: SelectableExpression<QS>,
+    Self: AppearsOnTable<QS>,
+{
+}
+
+impl<T> QueryFragment<GaussDB> for RowValue<T>
+where
+    T: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("(");
+        self.values.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+// Like `=` and `<` on single values, a comparison of rows is `NULL` if it
+// depends on a `NULL` element, so it is nullable if either row is
+macro_rules! row_comparison {
+    ($(#[$meta:meta])* $name:ident, $operator:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, QueryId, ValidGrouping)]
+        pub struct $name<L, R> {
+            left: L,
+            right: R,
+        }
+
+        impl<L, R> Expression for $name<L, R>
+        where
+            L: Expression,
+            R: Expression,
+            L::SqlType: SqlType,
+            R::SqlType: SqlType,
+            <L::SqlType as SqlType>::IsNull: OneIsNullable<<R::SqlType as SqlType>::IsNull>,
+            <<L::SqlType as SqlType>::IsNull as OneIsNullable<<R::SqlType as SqlType>::IsNull>>::Out:
+                MaybeNullableType<Bool>,
+        {
+            type SqlType = <<<L::SqlType as SqlType>::IsNull as OneIsNullable<
+                <R::SqlType as SqlType>::IsNull,
+            >>::Out as MaybeNullableType<Bool>>::Out;
+        }
+
+        impl<L, R, QS> AppearsOnTable<QS> for $name<L, R>
+        where
+            L: AppearsOnTable<QS>,
+            R: AppearsOnTable<QS>,
+            Self: Expression,
+        {
+        }
+
+        impl<L, R, QS> SelectableExpression<QS> for $name<L, R>
+        where
+            L: SelectableExpression<QS>,
+            R: SelectableExpression<QS>,
+            Self: AppearsOnTable<QS>,
+        {
+        }
+
+        impl<L, R> QueryFragment<GaussDB> for $name<L, R>
+        where
+            L: QueryFragment<GaussDB>,
+            R: QueryFragment<GaussDB>,
+        {
+            fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+                self.left.walk_ast(out.reborrow())?;
+                out.push_sql($operator);
+                self.right.walk_ast(out.reborrow())?;
+                Ok(())
+            }
+        }
+    };
+}
+
+row_comparison!(
+    /// Expression for `=` on row values
+    RowEq,
+    " = "
+);
+
+row_comparison!(
+    /// Expression for `!=` on row values
+    RowNotEq,
+    " != "
+);
+
+row_comparison!(
+    /// Expression for `<` on row values
+    RowLt,
+    " < "
+);
+
+row_comparison!(
+    /// Expression for `<=` on row values
+    RowLtEq,
+    " <= "
+);
+
+row_comparison!(
+    /// Expression for `>` on row values
+    RowGt,
+    " > "
+);
+
+row_comparison!(
+    /// Expression for `>=` on row values
+    RowGtEq,
+    " >= "
+);
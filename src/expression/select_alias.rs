@@ -0,0 +1,317 @@
+//! Aliases for selected expressions
+//!
+//! Diesel renders a selected expression as is, so a computed column can only
+//! be sorted on by repeating the expression. [`aliased`] names the
+//! expression in the select clause (`COUNT(*) AS "comment_count"`) and
+//! [`Aliased::alias`] refers to that name in `ORDER BY`.
+//!
+//! An alias is only valid in the select clause. Grouping by an aliased
+//! expression doesn't compile; using one anywhere else, e.g. compared in
+//! `having`, fails with a [`QueryBuilderError`] when the query is built.
+//!
+//! [`aliased`]: GaussDBAliasExpressionMethods::aliased
+//! [`QueryBuilderError`]: diesel::result::Error::QueryBuilderError
+
+use std::cell::Cell;
+
+use crate::backend::GaussDB;
+use diesel::expression::expression_types::NotSelectable;
+use diesel::expression::{AppearsOnTable, Expression, SelectableExpression, ValidGrouping};
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::query_source::QuerySource;
+use diesel::result::{Error as DieselError, QueryResult};
+
+thread_local! {
+    // Whether the clause being walked is a select clause, see `walk_clause`
+    static IN_SELECT_CLAUSE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `walk` with aliases allowed in a select clause or rejected elsewhere
+///
+/// The GaussDB select statements walk their select clause with `true` and
+/// every other clause with `false`, so a subquery in the select clause
+/// still rejects an alias in its own `WHERE`.
+pub(crate) fn walk_clause<T>(select_clause: bool, walk: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            IN_SELECT_CLAUSE.with(|in_select| in_select.set(self.0));
+        }
+    }
+
+    let _restore = Restore(IN_SELECT_CLAUSE.with(|in_select| in_select.replace(select_clause)));
+    walk()
+}
+
+/// Trait providing [`aliased`](GaussDBAliasExpressionMethods::aliased) for
+/// all expressions
+pub trait GaussDBAliasExpressionMethods: Expression + Sized {
+    /// Name this expression in the select clause
+    ///
+    /// Renders as `expression AS "alias"`. Use [`Aliased::alias`] to sort
+    /// on the named value. The aliased expression can only be selected:
+    ///
+    /// ```rust,compile_fail
+    /// # use diesel::prelude::*;
+    /// # use diesel::dsl::count_star;
+    /// # use diesel_gaussdb::expression::select_alias::GaussDBAliasExpressionMethods;
+    /// # table! { comments (id) { id -> Integer, post_id -> Integer, } }
+    /// let query = comments::table
+    ///     .group_by(comments::post_id.aliased("post"))
+    ///     .select(count_star());
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use diesel::prelude::*;
+    /// # use diesel::dsl::count_star;
+    /// # use diesel_gaussdb::expression::select_alias::GaussDBAliasExpressionMethods;
+    /// # table! { comments (id) { id -> Integer, post_id -> Integer, } }
+    /// let comment_count = count_star().aliased("comment_count");
+    ///
+    /// // SELECT "comments"."post_id", COUNT(*) AS "comment_count" FROM "comments"
+    /// // GROUP BY "comments"."post_id" ORDER BY "comment_count" DESC
+    /// let query = comments::table
+    ///     .group_by(comments::post_id)
+    ///     .select((comments::post_id, comment_count))
+    ///     .order_by(comment_count.alias().desc());
+    /// ```
+    fn aliased(self, alias: &'static str) -> Aliased<Self> {
+        Aliased { expr: self, alias }
+    }
+}
+
+impl<T: Expression> GaussDBAliasExpressionMethods for T {}
+
+/// An expression named in the select clause, see
+/// [`GaussDBAliasExpressionMethods::aliased`]
+#[derive(Debug, Clone, Copy)]
+pub struct Aliased<Expr> {
+    expr: Expr,
+    alias: &'static str,
+}
+
+impl<Expr> Aliased<Expr> {
+    /// A reference to the alias for `ORDER BY`, sorting ascending
+    pub fn alias(&self) -> AliasRef {
+        AliasRef {
+            alias: self.alias,
+            descending: false,
+        }
+    }
+}
+
+impl<Expr: Expression> Expression for Aliased<Expr> {
+    type SqlType = Expr::SqlType;
+}
+
+impl<Expr> QueryFragment<GaussDB> for Aliased<Expr>
+where
+    Expr: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        if !IN_SELECT_CLAUSE.with(Cell::get) {
+            return Err(DieselError::QueryBuilderError(
+                format!("the alias {:?} can only be used in the select clause", self.alias).into(),
+            ));
+        }
+        // `expression AS "a" AS "b"` isn't valid either
+        walk_clause(false, || self.expr.walk_ast(pass.reborrow()))?;
+        pass.push_sql(" AS ");
+        pass.push_identifier(self.alias)
+    }
+}
+
+impl<Expr> QueryId for Aliased<Expr> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Expr, GB> ValidGrouping<GB> for Aliased<Expr>
+where
+    Expr: ValidGrouping<GB>,
+{
+    type IsAggregate = Expr::IsAggregate;
+}
+
+// `group_by` checks its expression against the from clause rather than
+// the query source, so requiring a query source rules it out
+impl<Expr, QS> AppearsOnTable<QS> for Aliased<Expr>
+where
+    Expr: AppearsOnTable<QS>,
+    QS: QuerySource,
+{
+}
+
+impl<Expr, QS> SelectableExpression<QS> for Aliased<Expr>
+where
+    Expr: SelectableExpression<QS>,
+    QS: QuerySource,
+{
+}
+
+/// A sort on an alias of the select clause, see [`Aliased::alias`]
+///
+/// The server only resolves output column names in `ORDER BY`, so this is
+/// an ordering rather than a value: like `.desc()` on a column it can be
+/// passed to `order_by` and `then_order_by`, but it can't be selected,
+/// filtered on or compared. Selecting it doesn't compile:
+///
+/// ```rust,compile_fail
+/// # use diesel::prelude::*;
+/// # use diesel_gaussdb::expression::select_alias::GaussDBAliasExpressionMethods;
+/// # diesel::table! { comments (id) { id -> Integer, post_id -> Integer, } }
+/// let total = comments::id.aliased("total");
+/// let query = comments::table.select(total.alias());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AliasRef {
+    alias: &'static str,
+    descending: bool,
+}
+
+impl AliasRef {
+    /// Sort ascending, `"alias" ASC`
+    pub fn asc(self) -> Self {
+        AliasRef {
+            descending: false,
+            ..self
+        }
+    }
+
+    /// Sort descending, `"alias" DESC`
+    pub fn desc(self) -> Self {
+        AliasRef {
+            descending: true,
+            ..self
+        }
+    }
+}
+
+impl Expression for AliasRef {
+    type SqlType = NotSelectable;
+}
+
+impl QueryFragment<GaussDB> for AliasRef {
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        pass.unsafe_to_cache_prepared();
+        pass.push_identifier(self.alias)?;
+        if self.descending {
+            pass.push_sql(" DESC");
+        }
+        Ok(())
+    }
+}
+
+impl QueryId for AliasRef {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+// Required by `order_by`; the alias refers to the select clause of the
+// query it is sorted in
+impl<QS> AppearsOnTable<QS> for AliasRef {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::dsl::count_star;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+
+    diesel::table! {
+        comments (id) {
+            id -> Integer,
+            post_id -> Integer,
+            body -> Text,
+        }
+    }
+
+    diesel::table! {
+        posts (id) {
+            id -> Integer,
+        }
+    }
+
+    diesel::allow_tables_to_appear_in_same_query!(comments, posts);
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(query: T) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_aliased_aggregate_in_select() {
+        let query = comments::table
+            .group_by(comments::post_id)
+            .select((comments::post_id, count_star().aliased("comment_count")));
+
+        assert_eq!(
+            generate_sql(query),
+            r#"SELECT "comments"."post_id", COUNT(*) AS "comment_count" FROM "comments" GROUP BY "comments"."post_id""#
+        );
+    }
+
+    #[test]
+    fn test_order_by_alias() {
+        let comment_count = count_star().aliased("comment_count");
+        let query = comments::table
+            .group_by(comments::post_id)
+            .select((comments::post_id, comment_count))
+            .order_by((comment_count.alias().desc(), comments::post_id));
+
+        assert_eq!(
+            generate_sql(query),
+            r#"SELECT "comments"."post_id", COUNT(*) AS "comment_count" FROM "comments" GROUP BY "comments"."post_id" ORDER BY "comment_count" DESC, "comments"."post_id""#
+        );
+    }
+
+    #[test]
+    fn test_order_by_alias_ascending() {
+        let total = comments::id.aliased("total");
+        let query = comments::table
+            .select(total)
+            .order_by(comments::post_id)
+            .then_order_by(total.alias().desc().asc());
+
+        assert_eq!(
+            generate_sql(query),
+            r#"SELECT "comments"."id" AS "total" FROM "comments" ORDER BY "comments"."post_id", "total""#
+        );
+    }
+
+    #[test]
+    fn test_alias_outside_select_clause_is_rejected() {
+        let query = comments::table
+            .group_by(comments::post_id)
+            .select(comments::post_id)
+            .having(count_star().aliased("comment_count").gt(1));
+
+        let mut query_builder = GaussDBQueryBuilder::new();
+        let result = QueryFragment::<GaussDB>::to_sql(&query, &mut query_builder, &GaussDB);
+        assert!(matches!(result, Err(diesel::result::Error::QueryBuilderError(_))));
+    }
+
+    #[test]
+    fn test_alias_in_subquery_select_clause() {
+        let latest = comments::table
+            .select(diesel::dsl::max(comments::id).aliased("latest"))
+            .single_value();
+        let query = posts::table.select((posts::id, latest.aliased("newest")));
+
+        assert_eq!(
+            generate_sql(query),
+            r#"SELECT "posts"."id", (SELECT max("comments"."id") AS "latest" FROM "comments" LIMIT $1) AS "newest" FROM "posts""#
+        );
+    }
+
+    #[test]
+    fn test_alias_is_quoted() {
+        let total = comments::id.aliased("Total \"ids\"");
+        assert_eq!(generate_sql(total.alias()), r#""Total ""ids""""#);
+    }
+}
@@ -39,6 +39,7 @@ pub mod monitoring;
 pub mod performance;
 pub mod pool;
 pub mod query_builder;
+pub mod schema_diff;
 pub mod types;
 pub mod gaussdb_extensions;
 pub mod expression;
@@ -70,14 +71,51 @@ pub mod prelude {
     pub use crate::backend::GaussDB;
     pub use crate::connection::GaussDBConnection;
     pub use crate::query_builder::GaussDBQueryBuilder;
-    pub use crate::expression::array_ops::ArrayContainmentOps;
-    pub use crate::expression::expression_methods::GaussDBStringExpressionMethods;
-
+    pub use crate::expression::array_ops::{ArrayContainmentOps, ArrayIndexOps};
+    pub use crate::expression::expression_methods::{
+        GaussDBBoolExpressionMethods, GaussDBExpressionMethods, GaussDBSortExpressionMethods,
+        GaussDBStringExpressionMethods, GaussDBTimestampExpressionMethods,
+    };
+    pub use crate::expression::jsonb_ops::GaussDBJsonbExpressionMethods;
+    pub use crate::expression::select_alias::GaussDBAliasExpressionMethods;
+    pub use crate::query_builder::select_by::SelectByDsl;
     // Connection pool support
     #[cfg(feature = "r2d2")]
-    pub use crate::pool::{GaussDBConnectionManager, GaussDBPool, PooledGaussDBConnection};
-
-    pub use diesel::prelude::*;
+    pub use crate::pool::{GaussDBConnectionManager, GaussDBPool, GaussDBPoolExt, PooledGaussDBConnection};
+
+    // Everything from `diesel::prelude` except its `Pg*ExpressionMethods`
+    // traits and `ExecuteCopyFromDsl`, which only render for the `Pg`
+    // backend and would make the GaussDB methods above ambiguous
+    pub use diesel::associations::{Associations, GroupedBy, Identifiable};
+    pub use diesel::connection::Connection;
+    pub use diesel::deserialize::{Queryable, QueryableByName};
+    pub use diesel::expression::{
+        AppearsOnTable, BoxableExpression, Expression, IntoSql, Selectable, SelectableExpression,
+        SelectableHelper,
+    };
+    pub use diesel::expression::IntoSql as _;
+    pub use diesel::expression::functions::define_sql_function;
+    pub use diesel::expression_methods::{
+        BoolExpressionMethods, EscapeExpressionMethods, ExpressionMethods,
+        NullableExpressionMethods, PreferredBoolSqlType, TextExpressionMethods,
+    };
+    #[doc(hidden)]
+    pub use diesel::expression_methods::EqAll;
+    pub use diesel::insertable::Insertable;
+    pub use diesel::query_builder::{AsChangeset, DecoratableTarget};
+    pub use diesel::query_dsl::{
+        BelongingToDsl, CombineDsl, JoinOnDsl, QueryDsl, RunQueryDsl, SaveChangesDsl,
+    };
+    pub use diesel::query_source::SizeRestrictedColumn as _;
+    pub use diesel::query_source::{Column, JoinTo, QuerySource, Table};
+    pub use diesel::result::{
+        ConnectionError, ConnectionResult, OptionalEmptyChangesetExtension, OptionalExtension,
+        QueryResult,
+    };
+    pub use diesel::{
+        allow_columns_to_appear_in_same_group_by_clause, allow_tables_to_appear_in_same_query,
+        joinable, table,
+    };
 }
 
 #[cfg(test)]
@@ -12,6 +12,7 @@ use diesel::define_sql_function;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// Determines the OID of types at runtime for GaussDB
 ///
@@ -43,25 +44,26 @@ where
     T: Connection<Backend = GaussDB> + GetGaussDBMetadataCache + LoadConnection<DefaultLoadingMode>,
 {
     fn lookup_type(&mut self, type_name: &str, schema: Option<&str>) -> GaussDBTypeMetadata {
+        // Built-in types have fixed OIDs and never need the cache or a query
+        if let Some(metadata) = GaussDBTypeMetadata::builtin(type_name, schema) {
+            return metadata;
+        }
+
         let cache_key = GaussDBMetadataCacheKey {
             schema: schema.map(Cow::Borrowed),
             type_name: Cow::Borrowed(type_name),
         };
 
-        {
-            let metadata_cache = self.get_metadata_cache();
-
-            if let Some(metadata) = metadata_cache.lookup_type(&cache_key) {
-                return metadata;
-            }
+        let shared_cache = self.get_shared_metadata_cache();
+        if let Some(type_metadata) = cached_type(self.get_metadata_cache(), shared_cache.as_ref(), &cache_key) {
+            return GaussDBTypeMetadata::from_result(Ok((type_metadata.oid, type_metadata.array_oid)));
         }
 
         let r = lookup_type(&cache_key, self);
 
         match r {
             Ok(type_metadata) => {
-                self.get_metadata_cache()
-                    .store_type(cache_key, type_metadata);
+                store_type(self.get_metadata_cache(), shared_cache.as_ref(), cache_key, type_metadata);
                 GaussDBTypeMetadata::from_result(Ok((type_metadata.oid, type_metadata.array_oid)))
             }
             Err(_e) => GaussDBTypeMetadata::from_result(Err(FailedToLookupTypeError::new_internal(
@@ -85,27 +87,99 @@ where
 pub trait GetGaussDBMetadataCache {
     /// Get the `GaussDBMetadataCache`
     fn get_metadata_cache(&mut self) -> &mut GaussDBMetadataCache;
+
+    /// Get the cache shared with other connections, if there is one
+    ///
+    /// Types missing from the connection's own cache are looked up in the
+    /// shared cache before the database is queried, and types found in the
+    /// database are stored in both.
+    fn get_shared_metadata_cache(&self) -> Option<SharedGaussDBMetadataCache> {
+        None
+    }
+}
+
+/// A [`GaussDBMetadataCache`] shared by several connections, e.g. of a pool
+///
+/// Type OIDs are global to a database, so an OID looked up by one
+/// connection is valid for all connections to the same database. Types
+/// looked up without a schema are resolved through the `search_path`,
+/// which therefore has to be the same for all connections sharing a cache.
+pub type SharedGaussDBMetadataCache = Arc<Mutex<GaussDBMetadataCache>>;
+
+/// Lock a shared cache, also after another thread panicked while holding it
+///
+/// The cache only maps names to OIDs, an insert that was interrupted
+/// leaves it consistent.
+fn lock(shared: &SharedGaussDBMetadataCache) -> MutexGuard<'_, GaussDBMetadataCache> {
+    shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The metadata of `cache_key` from the connection's cache or the shared one
+///
+/// A hit in the shared cache is copied to the connection's cache, so the
+/// lock is only taken once per type and connection.
+fn cached_type(
+    cache: &mut GaussDBMetadataCache,
+    shared: Option<&SharedGaussDBMetadataCache>,
+    cache_key: &GaussDBMetadataCacheKey<'_>,
+) -> Option<InnerGaussDBTypeMetadata> {
+    if let Some(metadata) = cache.get(cache_key) {
+        return Some(metadata);
+    }
+    let metadata = lock(shared?).get(cache_key)?;
+    cache.store_type(cache_key.clone(), metadata);
+    Some(metadata)
+}
+
+/// Store metadata looked up in the database in both caches
+fn store_type(
+    cache: &mut GaussDBMetadataCache,
+    shared: Option<&SharedGaussDBMetadataCache>,
+    cache_key: GaussDBMetadataCacheKey<'_>,
+    metadata: InnerGaussDBTypeMetadata,
+) {
+    if let Some(shared) = shared {
+        lock(shared).store_type(cache_key.clone(), metadata);
+    }
+    cache.store_type(cache_key, metadata);
 }
 
 fn lookup_type<T: Connection<Backend = GaussDB> + LoadConnection<DefaultLoadingMode>>(
     cache_key: &GaussDBMetadataCacheKey<'_>,
-    _conn: &mut T,
+    conn: &mut T,
 ) -> QueryResult<InnerGaussDBTypeMetadata> {
-    // TODO: Implement actual type lookup from GaussDB system tables
-    // For now, return a default metadata for common types
-    let metadata = match cache_key.type_name.as_ref() {
-        "text" => InnerGaussDBTypeMetadata { oid: 25, array_oid: 1009 },
-        "int4" => InnerGaussDBTypeMetadata { oid: 23, array_oid: 1007 },
-        "int8" => InnerGaussDBTypeMetadata { oid: 20, array_oid: 1016 },
-        "bool" => InnerGaussDBTypeMetadata { oid: 16, array_oid: 1000 },
-        "bytea" => InnerGaussDBTypeMetadata { oid: 17, array_oid: 1001 },
-        _ => {
-            // Return an error for unknown types
-            return Err(diesel::result::Error::NotFound);
-        }
+    // Built-in types are resolved by `GaussDBTypeMetadata::builtin` before
+    // reaching this point, so only user defined types and types of a
+    // compatibility mode (e.g. `clob`) end up here.
+    #[derive(diesel::QueryableByName)]
+    struct TypeRow {
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        oid: i64,
+        #[diesel(sql_type = diesel::sql_types::BigInt)]
+        array_oid: i64,
+    }
+
+    let type_name = cache_key.type_name.as_ref();
+    let rows: Vec<TypeRow> = match &cache_key.schema {
+        Some(schema) => diesel::sql_query(
+            "SELECT t.oid::int8 AS oid, t.typarray::int8 AS array_oid \
+             FROM pg_type t JOIN pg_namespace n ON n.oid = t.typnamespace \
+             WHERE t.typname = $1 AND n.nspname = $2",
+        )
+        .bind::<Text, _>(type_name)
+        .bind::<Text, _>(schema.as_ref())
+        .load(conn)?,
+        // Without a schema the type must be visible on the search path
+        None => diesel::sql_query(
+            "SELECT t.oid::int8 AS oid, t.typarray::int8 AS array_oid \
+             FROM pg_type t WHERE t.typname = $1 AND pg_type_is_visible(t.oid)",
+        )
+        .bind::<Text, _>(type_name)
+        .load(conn)?,
     };
 
-    Ok(metadata)
+    let row = rows.first().ok_or(diesel::result::Error::NotFound)?;
+    Ok(InnerGaussDBTypeMetadata::from((row.oid as u32, row.array_oid as u32)))
 }
 
 /// The key used to lookup cached type oid's inside of
@@ -151,10 +225,14 @@ impl GaussDBMetadataCache {
 
     /// Lookup the OID of a custom type
     pub fn lookup_type(&self, type_name: &GaussDBMetadataCacheKey<'_>) -> Option<GaussDBTypeMetadata> {
-        let metadata = *self.cache.get(type_name)?;
+        let metadata = self.get(type_name)?;
         Some(GaussDBTypeMetadata::from_result(Ok((metadata.oid, metadata.array_oid))))
     }
 
+    fn get(&self, type_name: &GaussDBMetadataCacheKey<'_>) -> Option<InnerGaussDBTypeMetadata> {
+        self.cache.get(type_name).copied()
+    }
+
     /// Store the OID of a custom type
     pub fn store_type(
         &mut self,
@@ -358,6 +436,51 @@ mod tests {
         assert_eq!(cache.len(), 0);
     }
 
+    #[test]
+    fn test_shared_cache_is_used_by_other_connections() {
+        let shared = Arc::new(Mutex::new(GaussDBMetadataCache::new()));
+        let key = GaussDBMetadataCacheKey::new(None, Cow::Borrowed("mood"));
+        let mut lookups = 0;
+        // What the lookup of a connection does, with its own cache
+        let mut resolve = |cache: &mut GaussDBMetadataCache| {
+            cached_type(cache, Some(&shared), &key).unwrap_or_else(|| {
+                lookups += 1;
+                let metadata = InnerGaussDBTypeMetadata { oid: 16390, array_oid: 16389 };
+                store_type(cache, Some(&shared), key.clone(), metadata);
+                metadata
+            })
+        };
+
+        let mut first = GaussDBMetadataCache::new();
+        let mut second = GaussDBMetadataCache::new();
+        let from_first = resolve(&mut first);
+        let from_second = resolve(&mut second);
+
+        assert_eq!(from_first, from_second);
+        assert_eq!(lookups, 1);
+        // The hit was copied to the second connection's own cache
+        assert_eq!(second.len(), 1);
+        assert_eq!(lock(&shared).len(), 1);
+    }
+
+    #[test]
+    fn test_poisoned_shared_cache_stays_usable() {
+        let shared = Arc::new(Mutex::new(GaussDBMetadataCache::new()));
+        let key = GaussDBMetadataCacheKey::new(Some(Cow::Borrowed("app")), Cow::Borrowed("mood"));
+        let metadata = InnerGaussDBTypeMetadata { oid: 16390, array_oid: 16389 };
+        store_type(&mut GaussDBMetadataCache::new(), Some(&shared), key.clone(), metadata);
+
+        let poisoner = shared.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison the cache");
+        })
+        .join();
+
+        assert!(shared.is_poisoned());
+        assert_eq!(cached_type(&mut GaussDBMetadataCache::new(), Some(&shared), &key), Some(metadata));
+    }
+
     #[test]
     fn test_cache_key_equality() {
         let key1 = GaussDBMetadataCacheKey::new(
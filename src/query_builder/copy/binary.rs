@@ -0,0 +1,395 @@
+//! Binary `COPY FROM` encoding for GaussDB
+//!
+//! In `FORMAT binary` the server reads every field with the receive function
+//! of its column type, so each value has to be serialized exactly as that
+//! type. [`BinaryCopyEncoder`] knows the OIDs of the target columns and
+//! checks the SQL type of every written value against them before encoding
+//! it with the matching `ToSql` impl.
+
+use super::COPY_MAGIC_HEADER;
+use crate::backend::{GaussDB, GaussDBMetadataLookup};
+use byteorder::{NetworkEndian, WriteBytesExt};
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::query_builder::BindCollector;
+use diesel::result::{Error, QueryResult};
+use diesel::serialize::ToSql;
+use diesel::sql_types::HasSqlType;
+
+/// A column targeted by a binary `COPY FROM`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyColumn {
+    /// Name of the column
+    pub name: String,
+    /// OID of the column type
+    pub type_oid: u32,
+}
+
+impl CopyColumn {
+    /// Create a new column description
+    pub fn new(name: impl Into<String>, type_oid: u32) -> Self {
+        CopyColumn {
+            name: name.into(),
+            type_oid,
+        }
+    }
+}
+
+/// OIDs of `text`, `varchar`, `bpchar` and `name`, which share one binary format
+const TEXT_LIKE_OIDS: [u32; 4] = [25, 1043, 1042, 19];
+
+fn is_binary_compatible(value_oid: u32, column_oid: u32) -> bool {
+    value_oid == column_oid
+        || (TEXT_LIKE_OIDS.contains(&value_oid) && TEXT_LIKE_OIDS.contains(&column_oid))
+}
+
+/// Encoder for the `COPY ... FROM STDIN (FORMAT binary)` data stream
+///
+/// Rows are written field by field in column order. Nothing is sent to the
+/// server; [`finish`](Self::finish) returns the complete stream, so a type
+/// mismatch is reported before any data leaves the client.
+///
+/// # Example
+///
+/// ```rust
+/// # use diesel_gaussdb::query_builder::copy::binary::{BinaryCopyEncoder, CopyColumn};
+/// # use diesel_gaussdb::backend::{GaussDBMetadataLookup, GaussDBTypeMetadata};
+/// # use diesel::sql_types::{Integer, Text};
+/// # struct Builtins;
+/// # impl GaussDBMetadataLookup for Builtins {
+/// #     fn lookup_type(&mut self, name: &str, schema: Option<&str>) -> GaussDBTypeMetadata {
+/// #         GaussDBTypeMetadata::builtin(name, schema).unwrap()
+/// #     }
+/// #     fn as_any<'a>(&mut self) -> &mut (dyn std::any::Any + 'a) where Self: 'a { self }
+/// # }
+/// # fn main() -> diesel::QueryResult<()> {
+/// # let lookup = &mut Builtins;
+/// let mut encoder = BinaryCopyEncoder::new(vec![
+///     CopyColumn::new("id", 23),
+///     CopyColumn::new("name", 25),
+/// ]);
+/// encoder.begin_row()?;
+/// encoder.write_field::<Integer, _>(&1, lookup)?;
+/// encoder.write_field::<Text, _>(&"alice", lookup)?;
+/// let data = encoder.finish()?;
+/// # assert!(data.starts_with(b"PGCOPY\n\xff\r\n\0"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BinaryCopyEncoder {
+    columns: Vec<CopyColumn>,
+    buffer: Vec<u8>,
+    next_field: Option<usize>,
+    rows: usize,
+}
+
+impl BinaryCopyEncoder {
+    /// Create an encoder for rows of the given columns
+    pub fn new(columns: Vec<CopyColumn>) -> Self {
+        let mut buffer = Vec::with_capacity(COPY_MAGIC_HEADER.len() + 8);
+        buffer.extend_from_slice(&COPY_MAGIC_HEADER);
+        // Flags field and header extension length
+        buffer.extend_from_slice(&0i32.to_be_bytes());
+        buffer.extend_from_slice(&0i32.to_be_bytes());
+
+        BinaryCopyEncoder {
+            columns,
+            buffer,
+            next_field: None,
+            rows: 0,
+        }
+    }
+
+    /// The target columns
+    pub fn columns(&self) -> &[CopyColumn] {
+        &self.columns
+    }
+
+    /// Number of rows started so far
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Start a new row
+    ///
+    /// Fails if the previous row is missing fields.
+    pub fn begin_row(&mut self) -> QueryResult<()> {
+        self.check_row_complete()?;
+        let field_count = i16::try_from(self.columns.len())
+            .map_err(|_| Error::SerializationError("Too many columns for binary COPY".into()))?;
+        self.buffer.extend_from_slice(&field_count.to_be_bytes());
+        self.next_field = Some(0);
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Write the next field of the current row
+    ///
+    /// The SQL type `ST` must match the type of the target column, otherwise
+    /// an error is returned and nothing is written. Use `Nullable<ST>` to
+    /// write `None` as `NULL`.
+    pub fn write_field<ST, T>(
+        &mut self,
+        value: &T,
+        lookup: &mut (dyn GaussDBMetadataLookup + 'static),
+    ) -> QueryResult<()>
+    where
+        GaussDB: HasSqlType<ST>,
+        T: ToSql<ST, GaussDB>,
+    {
+        let index = self
+            .next_field
+            .ok_or_else(|| Error::SerializationError("begin_row must be called before write_field".into()))?;
+        let column = self.columns.get(index).ok_or_else(|| {
+            Error::SerializationError(
+                format!("Row has more fields than the {} target columns", self.columns.len()).into(),
+            )
+        })?;
+
+        let value_oid = <GaussDB as HasSqlType<ST>>::metadata(lookup)
+            .oid()
+            .map_err(|e| Error::SerializationError(Box::new(e)))?;
+        if !is_binary_compatible(value_oid, column.type_oid) {
+            return Err(Error::SerializationError(
+                format!(
+                    "Column {} has type OID {}, but the value is serialized as type OID {}",
+                    column.name, column.type_oid, value_oid
+                )
+                .into(),
+            ));
+        }
+
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector.push_bound_value::<ST, T>(value, lookup)?;
+        match collector.binds.pop().flatten() {
+            Some(bytes) => {
+                let len = i32::try_from(bytes.len())
+                    .map_err(|_| Error::SerializationError("Field too large for binary COPY".into()))?;
+                self.buffer.write_i32::<NetworkEndian>(len).map_err(|e| Error::SerializationError(Box::new(e)))?;
+                self.buffer.extend_from_slice(&bytes);
+            }
+            None => self.buffer.extend_from_slice(&(-1i32).to_be_bytes()),
+        }
+
+        self.next_field = Some(index + 1);
+        Ok(())
+    }
+
+    /// Write a complete row from a tuple of values
+    ///
+    /// Starts a new row and writes every element of `row` with the SQL type
+    /// at the same position of `ST`, see [`CopyRow`].
+    pub fn write_row<ST, R>(
+        &mut self,
+        row: &R,
+        lookup: &mut (dyn GaussDBMetadataLookup + 'static),
+    ) -> QueryResult<()>
+    where
+        R: CopyRow<ST> + ?Sized,
+    {
+        self.begin_row()?;
+        row.write_fields(self, lookup)
+    }
+
+    /// Finish the stream and return the encoded data
+    pub fn finish(mut self) -> QueryResult<Vec<u8>> {
+        self.check_row_complete()?;
+        self.buffer.extend_from_slice(&(-1i16).to_be_bytes());
+        Ok(self.buffer)
+    }
+
+    fn check_row_complete(&self) -> QueryResult<()> {
+        match self.next_field {
+            Some(written) if written != self.columns.len() => Err(Error::SerializationError(
+                format!(
+                    "Row {} has {} fields, expected {}",
+                    self.rows,
+                    written,
+                    self.columns.len()
+                )
+                .into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A row that can be written by [`BinaryCopyEncoder::write_row`]
+///
+/// Implemented for tuples of up to 16 elements, where `ST` is the tuple of
+/// the SQL types of the elements, and for references to such tuples.
+pub trait CopyRow<ST> {
+    /// Write the fields of this row in order
+    fn write_fields(
+        &self,
+        encoder: &mut BinaryCopyEncoder,
+        lookup: &mut (dyn GaussDBMetadataLookup + 'static),
+    ) -> QueryResult<()>;
+}
+
+impl<ST, R> CopyRow<ST> for &R
+where
+    R: CopyRow<ST> + ?Sized,
+{
+    fn write_fields(
+        &self,
+        encoder: &mut BinaryCopyEncoder,
+        lookup: &mut (dyn GaussDBMetadataLookup + 'static),
+    ) -> QueryResult<()> {
+        (**self).write_fields(encoder, lookup)
+    }
+}
+
+macro_rules! copy_row_tuples {
+    ($(($($T:ident: $ST:ident => $idx:tt),+))+) => {$(
+        impl<$($T, $ST),+> CopyRow<($($ST,)+)> for ($($T,)+)
+        where
+            $(GaussDB: HasSqlType<$ST>, $T: ToSql<$ST, GaussDB>,)+
+        {
+            fn write_fields(
+                &self,
+                encoder: &mut BinaryCopyEncoder,
+                lookup: &mut (dyn GaussDBMetadataLookup + 'static),
+            ) -> QueryResult<()> {
+                $(encoder.write_field::<$ST, $T>(&self.$idx, lookup)?;)+
+                Ok(())
+            }
+        }
+    )+};
+}
+
+copy_row_tuples! {
+    (T0: ST0 => 0)
+    (T0: ST0 => 0, T1: ST1 => 1)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8, T9: ST9 => 9)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8, T9: ST9 => 9, T10: ST10 => 10)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8, T9: ST9 => 9, T10: ST10 => 10, T11: ST11 => 11)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8, T9: ST9 => 9, T10: ST10 => 10, T11: ST11 => 11, T12: ST12 => 12)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8, T9: ST9 => 9, T10: ST10 => 10, T11: ST11 => 11, T12: ST12 => 12,
+     T13: ST13 => 13)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8, T9: ST9 => 9, T10: ST10 => 10, T11: ST11 => 11, T12: ST12 => 12,
+     T13: ST13 => 13, T14: ST14 => 14)
+    (T0: ST0 => 0, T1: ST1 => 1, T2: ST2 => 2, T3: ST3 => 3, T4: ST4 => 4, T5: ST5 => 5, T6: ST6 => 6,
+     T7: ST7 => 7, T8: ST8 => 8, T9: ST9 => 9, T10: ST10 => 10, T11: ST11 => 11, T12: ST12 => 12,
+     T13: ST13 => 13, T14: ST14 => 14, T15: ST15 => 15)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::TestLookup;
+    use crate::types::date_and_time::GaussDBTimestamp;
+    use diesel::sql_types::{Integer, Nullable, Text, Timestamp};
+
+    fn mixed_columns() -> Vec<CopyColumn> {
+        vec![
+            CopyColumn::new("id", 23),
+            CopyColumn::new("name", 1043),
+            CopyColumn::new("created_at", 1114),
+        ]
+    }
+
+    #[test]
+    fn test_encode_mixed_row() {
+        let mut encoder = BinaryCopyEncoder::new(mixed_columns());
+        encoder.begin_row().unwrap();
+        encoder.write_field::<Integer, _>(&7, &mut TestLookup::builtin()).unwrap();
+        encoder.write_field::<Text, _>(&"bob", &mut TestLookup::builtin()).unwrap();
+        encoder
+            .write_field::<Timestamp, _>(&GaussDBTimestamp::new(1_000_000), &mut TestLookup::builtin())
+            .unwrap();
+        let data = encoder.finish().unwrap();
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(b"PGCOPY\n\xff\r\n\0");
+        expected.extend_from_slice(&0i32.to_be_bytes());
+        expected.extend_from_slice(&0i32.to_be_bytes());
+        expected.extend_from_slice(&3i16.to_be_bytes());
+        expected.extend_from_slice(&4i32.to_be_bytes());
+        expected.extend_from_slice(&7i32.to_be_bytes());
+        expected.extend_from_slice(&3i32.to_be_bytes());
+        expected.extend_from_slice(b"bob");
+        expected.extend_from_slice(&8i32.to_be_bytes());
+        expected.extend_from_slice(&1_000_000i64.to_be_bytes());
+        expected.extend_from_slice(&(-1i16).to_be_bytes());
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_encode_null_field() {
+        let mut encoder = BinaryCopyEncoder::new(vec![CopyColumn::new("id", 23)]);
+        encoder.begin_row().unwrap();
+        encoder
+            .write_field::<Nullable<Integer>, Option<i32>>(&None, &mut TestLookup::builtin())
+            .unwrap();
+        let data = encoder.finish().unwrap();
+
+        assert_eq!(&data[19..], &[0, 1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_type_mismatch_is_rejected() {
+        let mut encoder = BinaryCopyEncoder::new(mixed_columns());
+        encoder.begin_row().unwrap();
+
+        let err = encoder.write_field::<Text, _>(&"7", &mut TestLookup::builtin()).unwrap_err();
+        assert!(err.to_string().contains("Column id has type OID 23"), "{}", err);
+    }
+
+    #[test]
+    fn test_write_row_matches_field_by_field() {
+        let row = (7, "bob", GaussDBTimestamp::new(1_000_000));
+
+        let mut by_field = BinaryCopyEncoder::new(mixed_columns());
+        by_field.begin_row().unwrap();
+        by_field.write_field::<Integer, _>(&row.0, &mut TestLookup::builtin()).unwrap();
+        by_field.write_field::<Text, _>(&row.1, &mut TestLookup::builtin()).unwrap();
+        by_field.write_field::<Timestamp, _>(&row.2, &mut TestLookup::builtin()).unwrap();
+
+        let mut by_row = BinaryCopyEncoder::new(mixed_columns());
+        by_row
+            .write_row::<(Integer, Text, Timestamp), _>(&&row, &mut TestLookup::builtin())
+            .unwrap();
+
+        assert_eq!(by_row.rows(), 1);
+        assert_eq!(by_row.finish().unwrap(), by_field.finish().unwrap());
+    }
+
+    #[test]
+    fn test_write_row_rejects_mismatched_tuple() {
+        let mut encoder = BinaryCopyEncoder::new(mixed_columns());
+        let err = encoder
+            .write_row::<(Integer, Integer, Timestamp), _>(&(7, 8, GaussDBTimestamp::new(0)), &mut TestLookup::builtin())
+            .unwrap_err();
+        assert!(err.to_string().contains("Column name has type OID 1043"), "{}", err);
+
+        let mut encoder = BinaryCopyEncoder::new(mixed_columns());
+        encoder.write_row::<(Integer, Text), _>(&(7, "bob"), &mut TestLookup::builtin()).unwrap();
+        assert!(encoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_incomplete_row_is_rejected() {
+        let mut encoder = BinaryCopyEncoder::new(mixed_columns());
+        encoder.begin_row().unwrap();
+        encoder.write_field::<Integer, _>(&7, &mut TestLookup::builtin()).unwrap();
+
+        assert!(encoder.begin_row().is_err());
+        assert!(encoder.finish().is_err());
+    }
+}
@@ -9,15 +9,22 @@ use diesel::result::QueryResult;
 use diesel::sql_types::SqlType;
 // Table trait will be used when COPY operations are fully implemented
 
+pub mod binary;
 pub mod copy_from;
 pub mod copy_to;
+pub mod table_copy;
+pub mod text;
 
+pub use self::binary::{BinaryCopyEncoder, CopyColumn, CopyRow};
 pub use self::copy_from::{CopyFromQuery, CopyHeader, ExecuteCopyFromDsl};
 pub use self::copy_to::CopyToQuery;
+pub use self::table_copy::TableCopy;
+pub use self::text::{
+    decode_text_rows, CopyTextOptions, CopyTextRow, FromCopyText, TextCopyEncoder, ToCopyText,
+};
 
 /// Magic header for PostgreSQL binary COPY format
 /// GaussDB uses the same format for compatibility
-#[allow(dead_code)] // 将在 COPY 操作完全实现时使用
 const COPY_MAGIC_HEADER: [u8; 11] = [
     0x50, 0x47, 0x43, 0x4F, 0x50, 0x59, 0x0A, 0xFF, 0x0D, 0x0A, 0x00,
 ];
@@ -26,7 +33,7 @@ const COPY_MAGIC_HEADER: [u8; 11] = [
 ///
 /// See [the PostgreSQL documentation](https://www.postgresql.org/docs/current/sql-copy.html)
 /// for details about the different formats. GaussDB supports the same formats.
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum CopyFormat {
     /// The PostgreSQL text format
     ///
@@ -0,0 +1,230 @@
+//! Server-side copies between tables
+//!
+//! [`TableCopy`] renders `INSERT INTO target (...) SELECT ... FROM source`,
+//! so rows move between two tables without leaving the server. It is the
+//! alternative to a `COPY TO` followed by a `COPY FROM` through the client.
+
+use crate::backend::GaussDB;
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::result::{Error, QueryResult};
+
+/// Copy rows from one table into another
+///
+/// Executed by [`GaussDBConnection::copy_table_to_table`], which checks the
+/// column lists against the catalog first.
+///
+/// [`GaussDBConnection::copy_table_to_table`]: crate::GaussDBConnection::copy_table_to_table
+///
+/// # Example
+///
+/// ```rust
+/// # use diesel_gaussdb::query_builder::copy::TableCopy;
+/// # use diesel_gaussdb::prelude::*;
+/// # diesel::table! { orders (id) { id -> Integer, total -> Integer, } }
+/// // INSERT INTO "orders_archive" ("id", "total")
+/// // SELECT "id", "total" FROM "orders" WHERE ("orders"."id" < $1)
+/// let copy = TableCopy::new("orders", "orders_archive")
+///     .columns(&["id", "total"], &["id", "total"])
+///     .filter(orders::id.lt(1000));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableCopy<P = ()> {
+    source: String,
+    target: String,
+    source_columns: Vec<String>,
+    target_columns: Vec<String>,
+    filter: Option<P>,
+}
+
+impl TableCopy {
+    /// Copy all columns of `source` into `target`
+    ///
+    /// Both names may be schema-qualified (`schema.table`).
+    pub fn new(source: &str, target: &str) -> Self {
+        TableCopy {
+            source: source.to_string(),
+            target: target.to_string(),
+            source_columns: Vec::new(),
+            target_columns: Vec::new(),
+            filter: None,
+        }
+    }
+}
+
+impl<P> TableCopy<P> {
+    /// Copy `source_columns` into `target_columns`, pairwise
+    ///
+    /// Without columns, all columns are copied in their declared order.
+    pub fn columns(mut self, source_columns: &[&str], target_columns: &[&str]) -> Self {
+        self.source_columns = source_columns.iter().map(|c| c.to_string()).collect();
+        self.target_columns = target_columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Only copy the rows of `source` matching `predicate`
+    pub fn filter<F>(self, predicate: F) -> TableCopy<F> {
+        TableCopy {
+            source: self.source,
+            target: self.target,
+            source_columns: self.source_columns,
+            target_columns: self.target_columns,
+            filter: Some(predicate),
+        }
+    }
+
+    /// The source table
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The target table
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// The copied columns of the source table
+    pub fn source_columns(&self) -> &[String] {
+        &self.source_columns
+    }
+
+    /// The columns of the target table receiving the data
+    pub fn target_columns(&self) -> &[String] {
+        &self.target_columns
+    }
+
+    pub(crate) fn with_resolved_columns(
+        mut self,
+        source_columns: Vec<String>,
+        target_columns: Vec<String>,
+    ) -> Self {
+        self.source_columns = source_columns;
+        self.target_columns = target_columns;
+        self
+    }
+}
+
+fn push_qualified_name(pass: &mut AstPass<'_, '_, GaussDB>, name: &str) -> QueryResult<()> {
+    if name.split('.').any(str::is_empty) {
+        return Err(Error::QueryBuilderError(format!("Invalid table name {:?}", name).into()));
+    }
+    for (i, part) in name.split('.').enumerate() {
+        if i > 0 {
+            pass.push_sql(".");
+        }
+        pass.push_identifier(part)?;
+    }
+    Ok(())
+}
+
+fn push_column_list(pass: &mut AstPass<'_, '_, GaussDB>, columns: &[String]) -> QueryResult<()> {
+    for (i, column) in columns.iter().enumerate() {
+        if i > 0 {
+            pass.push_sql(", ");
+        }
+        pass.push_identifier(column)?;
+    }
+    Ok(())
+}
+
+impl<P> QueryFragment<GaussDB> for TableCopy<P>
+where
+    P: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        if self.source_columns.len() != self.target_columns.len() {
+            return Err(Error::QueryBuilderError(
+                format!(
+                    "Cannot copy {} source columns into {} target columns",
+                    self.source_columns.len(),
+                    self.target_columns.len()
+                )
+                .into(),
+            ));
+        }
+        pass.unsafe_to_cache_prepared();
+
+        pass.push_sql("INSERT INTO ");
+        push_qualified_name(&mut pass, &self.target)?;
+        if !self.target_columns.is_empty() {
+            pass.push_sql(" (");
+            push_column_list(&mut pass, &self.target_columns)?;
+            pass.push_sql(")");
+        }
+
+        pass.push_sql(" SELECT ");
+        if self.source_columns.is_empty() {
+            pass.push_sql("*");
+        } else {
+            push_column_list(&mut pass, &self.source_columns)?;
+        }
+        pass.push_sql(" FROM ");
+        push_qualified_name(&mut pass, &self.source)?;
+
+        if let Some(ref filter) = self.filter {
+            pass.push_sql(" WHERE ");
+            filter.walk_ast(pass.reborrow())?;
+        }
+        Ok(())
+    }
+}
+
+impl<P> QueryId for TableCopy<P> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+
+    diesel::table! {
+        orders (id) {
+            id -> Integer,
+            total -> Integer,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(query: &T) -> QueryResult<String> {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB)?;
+        Ok(query_builder.finish())
+    }
+
+    #[test]
+    fn test_copy_with_columns_and_filter() {
+        let copy = TableCopy::new("orders", "archive.orders")
+            .columns(&["id", "total"], &["order_id", "amount"])
+            .filter(orders::total.gt(100));
+
+        assert_eq!(
+            generate_sql(&copy).unwrap(),
+            r#"INSERT INTO "archive"."orders" ("order_id", "amount") SELECT "id", "total" FROM "orders" WHERE ("orders"."total" > $1)"#
+        );
+    }
+
+    #[test]
+    fn test_copy_all_columns_without_filter() {
+        let copy = TableCopy::new("orders", "orders_backup");
+
+        assert_eq!(
+            generate_sql(&copy).unwrap(),
+            r#"INSERT INTO "orders_backup" SELECT * FROM "orders""#
+        );
+    }
+
+    #[test]
+    fn test_mismatched_column_lists_are_rejected() {
+        let copy = TableCopy::new("orders", "orders_backup").columns(&["id", "total"], &["id"]);
+
+        let err = generate_sql(&copy).unwrap_err();
+        assert!(err.to_string().contains("2 source columns into 1 target columns"), "{}", err);
+    }
+
+    #[test]
+    fn test_invalid_table_name_is_rejected() {
+        assert!(generate_sql(&TableCopy::new("orders.", "orders_backup")).is_err());
+    }
+}
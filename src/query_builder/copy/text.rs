@@ -0,0 +1,1380 @@
+//! Text and CSV `COPY` encoding for GaussDB
+//!
+//! In `FORMAT text` and `FORMAT csv` the server parses every field with the
+//! input function of its column type and prints it with the output
+//! function, so values travel in their SQL text form: `12.50` for a
+//! `numeric`, `2024-03-05 14:07:09` for a `timestamp` or
+//! `1 mon 2 days 03:00:00` for an `interval`. [`ToCopyText`] and
+//! [`FromCopyText`] convert the core types to and from these forms, while
+//! [`TextCopyEncoder`] and [`decode_text_rows`] handle the delimiters, the
+//! `NULL` string and the escaping or quoting rules of [`CopyTextOptions`].
+//!
+//! Intervals are read in the default `postgres` interval style and
+//! timestamps in the `ISO` date style.
+
+use super::{CopyColumn, CopyFormat};
+use crate::query_builder::quote_literal;
+use crate::types::date_and_time::{GaussDBDate, GaussDBInterval, GaussDBTimestamp};
+use crate::types::numeric::GaussDBNumeric;
+use diesel::deserialize;
+use diesel::result::{Error, QueryResult, UnexpectedNullError};
+use diesel::serialize::{self, IsNull};
+use std::fmt::Write;
+
+/// The layout of a text or CSV `COPY` stream
+///
+/// [`text`](Self::text) and [`csv`](Self::csv) start with the defaults of
+/// the server: a tab, `\N` for `NULL` and backslash escapes for text; a
+/// comma, an unquoted empty string for `NULL` and `"` for quoting for CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyTextOptions {
+    format: CopyFormat,
+    delimiter: char,
+    null: String,
+    quote: char,
+    escape: char,
+}
+
+impl CopyTextOptions {
+    /// `FORMAT text` with the default options
+    pub fn text() -> Self {
+        CopyTextOptions {
+            format: CopyFormat::Text,
+            delimiter: '\t',
+            null: "\\N".to_string(),
+            quote: '"',
+            escape: '"',
+        }
+    }
+
+    /// `FORMAT csv` with the default options
+    pub fn csv() -> Self {
+        CopyTextOptions {
+            format: CopyFormat::Csv,
+            delimiter: ',',
+            null: String::new(),
+            quote: '"',
+            escape: '"',
+        }
+    }
+
+    /// Set the character separating the fields of a row
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the string that stands for `NULL`
+    pub fn with_null(mut self, null: impl Into<String>) -> Self {
+        self.null = null.into();
+        self
+    }
+
+    /// Set the quote character, only used by CSV
+    pub fn with_quote(mut self, quote: char) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set the character escaping quotes inside quoted CSV fields
+    ///
+    /// Defaults to the quote character, which doubles quotes.
+    pub fn with_escape(mut self, escape: char) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// The format of the stream, text or CSV
+    pub fn format(&self) -> CopyFormat {
+        self.format
+    }
+
+    /// The `NULL` string
+    pub fn null(&self) -> &str {
+        &self.null
+    }
+
+    /// The options as written inside the parentheses of a `COPY` statement
+    pub fn to_sql_options(&self) -> String {
+        let mut sql = format!(
+            "FORMAT {}, DELIMITER {}, NULL {}",
+            self.format.to_sql_format(),
+            quote_literal(&self.delimiter.to_string()),
+            quote_literal(&self.null)
+        );
+        if let CopyFormat::Csv = self.format {
+            write!(
+                sql,
+                ", QUOTE {}, ESCAPE {}",
+                quote_literal(&self.quote.to_string()),
+                quote_literal(&self.escape.to_string())
+            )
+            .expect("writing to a String can't fail");
+        }
+        sql
+    }
+
+    fn is_csv(&self) -> bool {
+        matches!(self.format, CopyFormat::Csv)
+    }
+}
+
+impl Default for CopyTextOptions {
+    fn default() -> Self {
+        CopyTextOptions::text()
+    }
+}
+
+/// Write a value in the text form of its SQL type
+///
+/// Returning [`IsNull::Yes`] writes the `NULL` string of the stream instead
+/// of the field, like `ToSql` does for bind parameters.
+pub trait ToCopyText {
+    /// Append the text form of `self` to `out`
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result;
+}
+
+/// Parse a value from the text form of its SQL type
+pub trait FromCopyText: Sized {
+    /// Parse a field, `None` being `NULL`
+    fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self>;
+}
+
+impl<T: ToCopyText + ?Sized> ToCopyText for &T {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        (**self).to_copy_text(out)
+    }
+}
+
+impl<T: ToCopyText> ToCopyText for Option<T> {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        match self {
+            Some(value) => value.to_copy_text(out),
+            None => Ok(IsNull::Yes),
+        }
+    }
+}
+
+impl<T: FromCopyText> FromCopyText for Option<T> {
+    fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+        text.map(|text| T::from_copy_text(Some(text))).transpose()
+    }
+}
+
+fn not_null(text: Option<&str>) -> deserialize::Result<&str> {
+    text.ok_or_else(|| Box::new(UnexpectedNullError) as Box<_>)
+}
+
+macro_rules! display_copy_text {
+    ($($ty:ty),*) => {
+        $(
+            impl ToCopyText for $ty {
+                fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+                    write!(out, "{}", self)?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl FromCopyText for $ty {
+                fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+                    Ok(not_null(text)?.parse()?)
+                }
+            }
+        )*
+    };
+}
+
+display_copy_text!(i16, i32, i64, u32, String);
+
+impl ToCopyText for str {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        out.push_str(self);
+        Ok(IsNull::No)
+    }
+}
+
+impl ToCopyText for bool {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        out.push(if *self { 't' } else { 'f' });
+        Ok(IsNull::No)
+    }
+}
+
+impl FromCopyText for bool {
+    fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+        let text = not_null(text)?;
+        match text.to_ascii_lowercase().as_str() {
+            "t" | "true" | "y" | "yes" | "on" | "1" => Ok(true),
+            "f" | "false" | "n" | "no" | "off" | "0" => Ok(false),
+            _ => Err(format!("Invalid boolean {:?}", text).into()),
+        }
+    }
+}
+
+macro_rules! float_copy_text {
+    ($($ty:ty),*) => {
+        $(
+            // Rust prints `inf` and `NaN`, the server `Infinity` and `NaN`
+            impl ToCopyText for $ty {
+                fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+                    if self.is_infinite() {
+                        out.push_str(if *self > 0.0 { "Infinity" } else { "-Infinity" });
+                    } else {
+                        write!(out, "{}", self)?;
+                    }
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl FromCopyText for $ty {
+                fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+                    match not_null(text)? {
+                        "Infinity" => Ok(<$ty>::INFINITY),
+                        "-Infinity" => Ok(<$ty>::NEG_INFINITY),
+                        text => Ok(text.parse()?),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float_copy_text!(f32, f64);
+
+impl ToCopyText for GaussDBNumeric {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        let (negative, weight, scale, digits) = match self {
+            GaussDBNumeric::NaN => {
+                out.push_str("NaN");
+                return Ok(IsNull::No);
+            }
+            GaussDBNumeric::Positive {
+                weight,
+                scale,
+                digits,
+            } => (false, *weight, *scale, digits),
+            GaussDBNumeric::Negative {
+                weight,
+                scale,
+                digits,
+            } => (true, *weight, *scale, digits),
+        };
+        // Base 10000 digit `i` is worth 10000^(weight - i)
+        let digit = |i: i32| {
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| digits.get(i))
+                .copied()
+                .unwrap_or(0)
+        };
+
+        if negative && digits.iter().any(|&d| d != 0) {
+            out.push('-');
+        }
+        if weight < 0 {
+            out.push('0');
+        } else {
+            write!(out, "{}", digit(0))?;
+            for i in 1..=i32::from(weight) {
+                write!(out, "{:04}", digit(i))?;
+            }
+        }
+        if scale > 0 {
+            let mut fraction = String::new();
+            let mut i = i32::from(weight) + 1;
+            while fraction.len() < usize::from(scale) {
+                write!(fraction, "{:04}", digit(i))?;
+                i += 1;
+            }
+            out.push('.');
+            out.push_str(&fraction[..usize::from(scale)]);
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl FromCopyText for GaussDBNumeric {
+    fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+        let text = not_null(text)?;
+        if text.eq_ignore_ascii_case("nan") {
+            return Ok(GaussDBNumeric::NaN);
+        }
+        let invalid = || format!("Invalid numeric {:?}", text);
+
+        let (negative, unsigned) = match text.as_bytes().first() {
+            Some(b'-') => (true, &text[1..]),
+            Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if (integer.is_empty() && fraction.is_empty())
+            || !all_digits(integer)
+            || !all_digits(fraction)
+        {
+            return Err(invalid().into());
+        }
+        let scale = u16::try_from(fraction.len()).map_err(|_| invalid())?;
+
+        // Pad both parts to whole base 10000 digits around the decimal point
+        let integer = integer.trim_start_matches('0');
+        let mut decimal = "0".repeat((4 - integer.len() % 4) % 4);
+        decimal.push_str(integer);
+        let integer_groups = decimal.len() / 4;
+        decimal.push_str(fraction);
+        decimal.push_str(&"0".repeat((4 - fraction.len() % 4) % 4));
+
+        let mut digits = decimal
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| {
+                std::str::from_utf8(chunk)
+                    .expect("ASCII digits")
+                    .parse::<i16>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut weight = i16::try_from(integer_groups).map_err(|_| invalid())? - 1;
+
+        let leading_zeros = digits.iter().take_while(|&&d| d == 0).count();
+        digits.drain(..leading_zeros);
+        weight -= i16::try_from(leading_zeros).map_err(|_| invalid())?;
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            weight = 0;
+        }
+
+        Ok(if negative && !digits.is_empty() {
+            GaussDBNumeric::negative(weight, scale, digits)
+        } else {
+            GaussDBNumeric::positive(weight, scale, digits)
+        })
+    }
+}
+
+const USECS_PER_SEC: i64 = 1_000_000;
+const USECS_PER_MINUTE: i64 = 60 * USECS_PER_SEC;
+const USECS_PER_HOUR: i64 = 60 * USECS_PER_MINUTE;
+const USECS_PER_DAY: i64 = 24 * USECS_PER_HOUR;
+/// Days from 1970-01-01 to 2000-01-01, the epoch of GaussDB dates
+const UNIX_EPOCH_TO_GAUSSDB_EPOCH_DAYS: i64 = 10_957;
+
+/// The proleptic Gregorian `(year, month, day)` of a day counted from
+/// 2000-01-01, with year 0 being 1 BC
+fn date_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + UNIX_EPOCH_TO_GAUSSDB_EPOCH_DAYS + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months counted from March, so that February ends the year
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+/// The inverse of [`date_from_days`]
+fn days_from_date(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468 - UNIX_EPOCH_TO_GAUSSDB_EPOCH_DAYS
+}
+
+fn write_date(out: &mut String, days: i64) -> std::fmt::Result {
+    let (year, month, day) = date_from_days(days);
+    let year = if year <= 0 { 1 - year } else { year };
+    write!(out, "{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn write_bc(out: &mut String, days: i64) {
+    if date_from_days(days).0 <= 0 {
+        out.push_str(" BC");
+    }
+}
+
+/// Parse `YYYY-MM-DD`, `bc` selecting the year before the common era
+fn parse_date(text: &str, bc: bool) -> deserialize::Result<i64> {
+    let invalid = || format!("Invalid date {:?}", text);
+    let mut parts = text.splitn(3, '-');
+    let mut next = || parts.next().ok_or_else(invalid);
+    let year = next()?.parse::<i64>().map_err(|_| invalid())?;
+    let month = next()?.parse::<u32>().map_err(|_| invalid())?;
+    let day = next()?.parse::<u32>().map_err(|_| invalid())?;
+    let year = if bc { 1 - year } else { year };
+
+    let days = days_from_date(year, month, day);
+    if date_from_days(days) != (year, month, day) {
+        return Err(invalid().into());
+    }
+    Ok(days)
+}
+
+/// Write `HH:MM:SS` with up to six fractional digits
+fn write_time(out: &mut String, micros: i64) -> std::fmt::Result {
+    write!(
+        out,
+        "{:02}:{:02}:{:02}",
+        micros / USECS_PER_HOUR,
+        micros % USECS_PER_HOUR / USECS_PER_MINUTE,
+        micros % USECS_PER_MINUTE / USECS_PER_SEC
+    )?;
+    let fraction = micros % USECS_PER_SEC;
+    if fraction != 0 {
+        let digits = format!("{:06}", fraction);
+        write!(out, ".{}", digits.trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
+/// Parse an unsigned `H:MM[:SS[.ffffff]]` into microseconds
+fn parse_time(text: &str) -> deserialize::Result<i64> {
+    let invalid = || format!("Invalid time {:?}", text);
+    let mut parts = text.splitn(3, ':');
+    let hours = parts
+        .next()
+        .ok_or_else(invalid)?
+        .parse::<i64>()
+        .map_err(|_| invalid())?;
+    let minutes = parts
+        .next()
+        .ok_or_else(invalid)?
+        .parse::<i64>()
+        .map_err(|_| invalid())?;
+    let (seconds, fraction) = match parts.next() {
+        Some(seconds) => seconds.split_once('.').unwrap_or((seconds, "")),
+        None => ("0", ""),
+    };
+    let seconds = seconds.parse::<i64>().map_err(|_| invalid())?;
+    if fraction.len() > 6 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid().into());
+    }
+    let fraction = format!("{:0<6}", fraction)
+        .parse::<i64>()
+        .map_err(|_| invalid())?;
+    Ok(hours * USECS_PER_HOUR + minutes * USECS_PER_MINUTE + seconds * USECS_PER_SEC + fraction)
+}
+
+// Timestamps are written in UTC with an explicit offset: a `timestamptz`
+// column reads them as UTC, a `timestamp` column ignores the offset
+impl ToCopyText for GaussDBTimestamp {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        match self.0 {
+            i64::MAX => out.push_str("infinity"),
+            i64::MIN => out.push_str("-infinity"),
+            micros => {
+                let days = micros.div_euclid(USECS_PER_DAY);
+                write_date(out, days)?;
+                out.push(' ');
+                write_time(out, micros.rem_euclid(USECS_PER_DAY))?;
+                out.push_str("+00");
+                write_bc(out, days);
+            }
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl FromCopyText for GaussDBTimestamp {
+    fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+        let text = not_null(text)?;
+        match text {
+            "infinity" => return Ok(GaussDBTimestamp(i64::MAX)),
+            "-infinity" => return Ok(GaussDBTimestamp(i64::MIN)),
+            _ => {}
+        }
+        let invalid = || format!("Invalid timestamp {:?}", text);
+
+        let (text, bc) = match text.strip_suffix(" BC") {
+            Some(text) => (text, true),
+            None => (text, false),
+        };
+        let (date, time) = text.split_once([' ', 'T']).ok_or_else(invalid)?;
+        // A `timestamptz` ends with the UTC offset of the session time zone
+        let (time, offset) = match time.find(['+', '-']) {
+            Some(sign) => {
+                let (time, offset) = time.split_at(sign);
+                let offset_micros = parse_time(&format!("{}:00", &offset[1..]))
+                    .or_else(|_| parse_time(&offset[1..]))
+                    .map_err(|_| invalid())?;
+                (
+                    time,
+                    if offset.starts_with('-') {
+                        -offset_micros
+                    } else {
+                        offset_micros
+                    },
+                )
+            }
+            None => (time, 0),
+        };
+
+        let days = parse_date(date, bc)?;
+        Ok(GaussDBTimestamp(
+            days * USECS_PER_DAY + parse_time(time)? - offset,
+        ))
+    }
+}
+
+impl ToCopyText for GaussDBDate {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        match self.0 {
+            i32::MAX => out.push_str("infinity"),
+            i32::MIN => out.push_str("-infinity"),
+            days => {
+                write_date(out, i64::from(days))?;
+                write_bc(out, i64::from(days));
+            }
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl FromCopyText for GaussDBDate {
+    fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+        let text = not_null(text)?;
+        let days = match text {
+            "infinity" => return Ok(GaussDBDate(i32::MAX)),
+            "-infinity" => return Ok(GaussDBDate(i32::MIN)),
+            _ => match text.strip_suffix(" BC") {
+                Some(date) => parse_date(date, true)?,
+                None => parse_date(text, false)?,
+            },
+        };
+        Ok(GaussDBDate(
+            i32::try_from(days).map_err(|_| format!("Date {:?} out of range", text))?,
+        ))
+    }
+}
+
+// The `postgres` interval style, e.g. `-1 years -2 mons +3 days 04:05:06.5`:
+// a part gets a `+` if it is positive and follows a negative one
+impl ToCopyText for GaussDBInterval {
+    fn to_copy_text(&self, out: &mut String) -> serialize::Result {
+        let mut is_zero = true;
+        let mut is_before = false;
+        let parts = [
+            (i64::from(self.months / 12), "year"),
+            (i64::from(self.months % 12), "mon"),
+            (i64::from(self.days), "day"),
+        ];
+        for (value, unit) in parts {
+            if value == 0 {
+                continue;
+            }
+            if !is_zero {
+                out.push(' ');
+            }
+            let sign = if is_before && value > 0 { "+" } else { "" };
+            write!(
+                out,
+                "{}{} {}{}",
+                sign,
+                value,
+                unit,
+                if value != 1 { "s" } else { "" }
+            )?;
+            is_zero = false;
+            is_before = value < 0;
+        }
+
+        let micros = self.microseconds;
+        if is_zero || micros != 0 {
+            if !is_zero {
+                out.push(' ');
+            }
+            if micros < 0 {
+                out.push('-');
+            } else if is_before {
+                out.push('+');
+            }
+            write_time(out, micros.unsigned_abs() as i64)?;
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl FromCopyText for GaussDBInterval {
+    fn from_copy_text(text: Option<&str>) -> deserialize::Result<Self> {
+        let text = not_null(text)?;
+        let invalid = || format!("Invalid interval {:?}", text);
+        let mut interval = GaussDBInterval::new(0, 0, 0);
+
+        let mut tokens = text.split_ascii_whitespace();
+        while let Some(token) = tokens.next() {
+            if token.contains(':') {
+                let (negative, time) = match token.as_bytes()[0] {
+                    b'-' => (true, &token[1..]),
+                    b'+' => (false, &token[1..]),
+                    _ => (false, token),
+                };
+                let micros = parse_time(time).map_err(|_| invalid())?;
+                interval.microseconds += if negative { -micros } else { micros };
+                continue;
+            }
+
+            let value = token.parse::<i32>().map_err(|_| invalid())?;
+            match tokens.next().ok_or_else(invalid)? {
+                "year" | "years" => interval.months += value.checked_mul(12).ok_or_else(invalid)?,
+                "mon" | "mons" => interval.months += value,
+                "day" | "days" => interval.days += value,
+                _ => return Err(invalid().into()),
+            }
+        }
+        Ok(interval)
+    }
+}
+
+/// Encoder for the `COPY ... FROM STDIN` data stream in text or CSV format
+///
+/// Works like [`BinaryCopyEncoder`](super::BinaryCopyEncoder): rows are
+/// written field by field in column order, and [`finish`](Self::finish)
+/// returns the complete stream once every row is complete. Fields are
+/// escaped or quoted as [`CopyTextOptions`] requires, so text containing
+/// the delimiter, quotes or line breaks arrives unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// # use diesel_gaussdb::query_builder::copy::{CopyColumn, CopyTextOptions, TextCopyEncoder};
+/// # fn main() -> diesel::QueryResult<()> {
+/// let mut encoder = TextCopyEncoder::new(
+///     vec![CopyColumn::new("id", 23), CopyColumn::new("note", 25)],
+///     CopyTextOptions::csv(),
+/// );
+/// encoder.begin_row()?;
+/// encoder.write_field(&1)?;
+/// encoder.write_field(&"a, b")?;
+/// encoder.begin_row()?;
+/// encoder.write_field(&2)?;
+/// encoder.write_field(&None::<&str>)?;
+/// assert_eq!(encoder.finish()?, b"1,\"a, b\"\n2,\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TextCopyEncoder {
+    columns: Vec<CopyColumn>,
+    options: CopyTextOptions,
+    buffer: String,
+    field: String,
+    next_field: Option<usize>,
+    rows: usize,
+}
+
+impl TextCopyEncoder {
+    /// Create an encoder for rows of the given columns
+    pub fn new(columns: Vec<CopyColumn>, options: CopyTextOptions) -> Self {
+        TextCopyEncoder {
+            columns,
+            options,
+            buffer: String::new(),
+            field: String::new(),
+            next_field: None,
+            rows: 0,
+        }
+    }
+
+    /// The target columns
+    pub fn columns(&self) -> &[CopyColumn] {
+        &self.columns
+    }
+
+    /// Number of rows started so far
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Start a new row
+    ///
+    /// Fails if the previous row is missing fields.
+    pub fn begin_row(&mut self) -> QueryResult<()> {
+        self.end_row()?;
+        self.next_field = Some(0);
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Write the next field of the current row
+    ///
+    /// `None` and other values serializing as [`IsNull::Yes`] are written
+    /// as the `NULL` string.
+    pub fn write_field<T>(&mut self, value: &T) -> QueryResult<()>
+    where
+        T: ToCopyText + ?Sized,
+    {
+        let index = self.next_field.ok_or_else(|| {
+            Error::SerializationError("begin_row must be called before write_field".into())
+        })?;
+        if index >= self.columns.len() {
+            return Err(Error::SerializationError(
+                format!(
+                    "Row has more fields than the {} target columns",
+                    self.columns.len()
+                )
+                .into(),
+            ));
+        }
+
+        self.field.clear();
+        let is_null = value
+            .to_copy_text(&mut self.field)
+            .map_err(Error::SerializationError)?;
+        if index > 0 {
+            self.buffer.push(self.options.delimiter);
+        }
+        match is_null {
+            IsNull::Yes => self.buffer.push_str(&self.options.null),
+            IsNull::No if self.options.is_csv() => {
+                write_csv_field(&mut self.buffer, &self.field, &self.options)
+            }
+            IsNull::No => {
+                write_text_field(&mut self.buffer, &self.field, &self.options).map_err(|e| {
+                    Error::SerializationError(
+                        format!("Column {}: {}", self.columns[index].name, e).into(),
+                    )
+                })?
+            }
+        }
+
+        self.next_field = Some(index + 1);
+        Ok(())
+    }
+
+    /// Finish the stream and return the encoded data
+    pub fn finish(mut self) -> QueryResult<Vec<u8>> {
+        self.end_row()?;
+        Ok(self.buffer.into_bytes())
+    }
+
+    fn end_row(&mut self) -> QueryResult<()> {
+        match self.next_field {
+            Some(written) if written != self.columns.len() => Err(Error::SerializationError(
+                format!(
+                    "Row {} has {} fields, expected {}",
+                    self.rows,
+                    written,
+                    self.columns.len()
+                )
+                .into(),
+            )),
+            Some(_) => {
+                self.buffer.push('\n');
+                self.next_field = None;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Backslash-escape a field of the text format
+fn write_text_field(
+    out: &mut String,
+    field: &str,
+    options: &CopyTextOptions,
+) -> Result<(), String> {
+    let start = out.len();
+    for c in field.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c == options.delimiter => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+
+    // The `NULL` string is matched before escapes are processed, so a value
+    // equal to it needs one escaped character to be read as a value
+    if out[start..] == options.null {
+        let first = out[start..]
+            .chars()
+            .next()
+            .filter(char::is_ascii)
+            .ok_or_else(|| {
+                format!(
+                    "the value {:?} can't be told apart from the NULL string",
+                    field
+                )
+            })?;
+        out.replace_range(start..start + 1, &format!("\\{:03o}", first as u32));
+    }
+    Ok(())
+}
+
+/// Quote a CSV field if it could be mistaken for a delimiter, a line break
+/// or the `NULL` string
+fn write_csv_field(out: &mut String, field: &str, options: &CopyTextOptions) {
+    let needs_quotes = field == options.null
+        || field == "\\."
+        || field.chars().any(|c| {
+            c == options.delimiter
+                || c == options.quote
+                || c == options.escape
+                || c == '\n'
+                || c == '\r'
+        });
+    if !needs_quotes {
+        out.push_str(field);
+        return;
+    }
+
+    out.push(options.quote);
+    for c in field.chars() {
+        if c == options.quote || c == options.escape {
+            out.push(options.escape);
+        }
+        out.push(c);
+    }
+    out.push(options.quote);
+}
+
+/// A row decoded from a text or CSV `COPY TO` stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyTextRow {
+    fields: Vec<Option<String>>,
+}
+
+impl CopyTextRow {
+    /// The number of fields
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Whether the row has no fields
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The raw text of the field at `index`, `None` for `NULL`
+    pub fn field(&self, index: usize) -> Option<&str> {
+        self.fields.get(index).and_then(Option::as_deref)
+    }
+
+    /// Parse the field at `index`
+    pub fn get<T: FromCopyText>(&self, index: usize) -> deserialize::Result<T> {
+        let field = self.fields.get(index).ok_or_else(|| {
+            format!(
+                "column index {} is out of range for a row of {} columns",
+                index,
+                self.fields.len()
+            )
+        })?;
+        T::from_copy_text(field.as_deref())
+    }
+}
+
+/// Split a text or CSV `COPY TO` stream into rows
+///
+/// Recognizes the `NULL` string and undoes the escaping or quoting of
+/// `options`, which must match the options of the `COPY` statement.
+pub fn decode_text_rows(
+    data: &[u8],
+    options: &CopyTextOptions,
+) -> deserialize::Result<Vec<CopyTextRow>> {
+    let data = std::str::from_utf8(data)?;
+    if options.is_csv() {
+        decode_csv_rows(data, options)
+    } else {
+        decode_text_format_rows(data, options)
+    }
+}
+
+fn decode_text_format_rows(
+    data: &str,
+    options: &CopyTextOptions,
+) -> deserialize::Result<Vec<CopyTextRow>> {
+    let mut rows = Vec::new();
+    for line in data.lines() {
+        if line == "\\." {
+            break;
+        }
+        let mut fields = Vec::new();
+        let mut raw = String::new();
+        let mut chars = line.chars();
+        // Split on unescaped delimiters, keeping escapes for `unescape_text`
+        loop {
+            match chars.next() {
+                Some('\\') => {
+                    raw.push('\\');
+                    if let Some(c) = chars.next() {
+                        raw.push(c);
+                    }
+                }
+                Some(c) if c == options.delimiter => {
+                    fields.push(text_field(&raw, options)?);
+                    raw.clear();
+                }
+                Some(c) => raw.push(c),
+                None => {
+                    fields.push(text_field(&raw, options)?);
+                    break;
+                }
+            }
+        }
+        rows.push(CopyTextRow { fields });
+    }
+    Ok(rows)
+}
+
+fn text_field(raw: &str, options: &CopyTextOptions) -> deserialize::Result<Option<String>> {
+    if raw == options.null {
+        return Ok(None);
+    }
+    unescape_text(raw).map(Some)
+}
+
+fn unescape_text(raw: &str) -> deserialize::Result<String> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        let escaped = chars
+            .next()
+            .ok_or("Incomplete escape at the end of a COPY field")?;
+        let unescaped = match escaped {
+            'b' => Some('\u{8}'),
+            'f' => Some('\u{c}'),
+            'n' => Some('\n'),
+            'r' => Some('\r'),
+            't' => Some('\t'),
+            'v' => Some('\u{b}'),
+            '0'..='7' => escaped_code(&mut chars, Some(escaped), 8, 3),
+            'x' if chars.peek().is_some_and(char::is_ascii_hexdigit) => {
+                escaped_code(&mut chars, None, 16, 2)
+            }
+            other => Some(other),
+        };
+        value.push(unescaped.ok_or("Invalid escape in a COPY field")?);
+    }
+    Ok(value)
+}
+
+/// Read the digits of an octal `\ooo` or hexadecimal `\xhh` escape
+fn escaped_code(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    first: Option<char>,
+    radix: u32,
+    max_len: usize,
+) -> Option<char> {
+    let mut digits = first.map(String::from).unwrap_or_default();
+    while digits.len() < max_len {
+        match chars.peek() {
+            Some(c) if c.is_digit(radix) => digits.push(*c),
+            _ => break,
+        }
+        chars.next();
+    }
+    u32::from_str_radix(&digits, radix)
+        .ok()
+        .and_then(char::from_u32)
+}
+
+fn decode_csv_rows(data: &str, options: &CopyTextOptions) -> deserialize::Result<Vec<CopyTextRow>> {
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = data.chars().peekable();
+
+    let end_field = |fields: &mut Vec<Option<String>>, field: &mut String, quoted: &mut bool| {
+        let value = std::mem::take(field);
+        // Only an unquoted field can be `NULL`
+        fields.push(if !*quoted && value == options.null {
+            None
+        } else {
+            Some(value)
+        });
+        *quoted = false;
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == options.quote => {
+                quoted = true;
+                loop {
+                    match chars.next() {
+                        Some(c) if c == options.escape && chars.peek() == Some(&options.quote) => {
+                            field.push(chars.next().expect("peeked"));
+                        }
+                        Some(c)
+                            if c == options.escape
+                                && c != options.quote
+                                && chars.peek() == Some(&options.escape) =>
+                        {
+                            field.push(chars.next().expect("peeked"));
+                        }
+                        Some(c) if c == options.quote => break,
+                        Some(c) => field.push(c),
+                        None => return Err("Unterminated quoted field in CSV COPY data".into()),
+                    }
+                }
+            }
+            c if c == options.delimiter => end_field(&mut fields, &mut field, &mut quoted),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                if fields.is_empty() && !quoted && field == "\\." {
+                    break;
+                }
+                end_field(&mut fields, &mut field, &mut quoted);
+                rows.push(CopyTextRow {
+                    fields: std::mem::take(&mut fields),
+                });
+            }
+            c => field.push(c),
+        }
+    }
+    if !fields.is_empty() || !field.is_empty() || quoted {
+        end_field(&mut fields, &mut field, &mut quoted);
+        rows.push(CopyTextRow { fields });
+    }
+    Ok(rows)
+}
+
+/// Counts the rows of a text or CSV `COPY TO` stream that arrives in chunks
+///
+/// Only row ends outside of quoted CSV fields are counted, so the count
+/// matches the rows of the table even if values contain line breaks.
+pub(crate) struct CopyRowCounter {
+    csv: bool,
+    // The server only accepts single-byte quote and escape characters
+    quote: u8,
+    escape: u8,
+    in_quotes: bool,
+    escaped: bool,
+    rows: u64,
+}
+
+impl CopyRowCounter {
+    pub(crate) fn new(options: &CopyTextOptions) -> Self {
+        CopyRowCounter {
+            csv: options.is_csv(),
+            quote: options.quote as u8,
+            escape: options.escape as u8,
+            in_quotes: false,
+            escaped: false,
+            rows: 0,
+        }
+    }
+
+    /// Count the row ends of the next chunk of the stream
+    pub(crate) fn update(&mut self, chunk: &[u8]) {
+        if !self.csv {
+            // Line breaks inside values are escaped in the text format
+            self.rows += chunk.iter().filter(|&&b| b == b'\n').count() as u64;
+            return;
+        }
+        for &b in chunk {
+            if self.escaped {
+                self.escaped = false;
+            } else if self.in_quotes && b == self.escape && self.escape != self.quote {
+                self.escaped = true;
+            } else if b == self.quote {
+                // A doubled quote leaves and re-enters the quoted field
+                self.in_quotes = !self.in_quotes;
+            } else if b == b'\n' && !self.in_quotes {
+                self.rows += 1;
+            }
+        }
+    }
+
+    /// The number of complete rows seen so far
+    pub(crate) fn rows(&self) -> u64 {
+        self.rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_text<T: ToCopyText>(value: T) -> String {
+        let mut out = String::new();
+        assert!(matches!(value.to_copy_text(&mut out).unwrap(), IsNull::No));
+        out
+    }
+
+    fn from_text<T: FromCopyText>(text: &str) -> T {
+        T::from_copy_text(Some(text)).unwrap()
+    }
+
+    #[test]
+    fn test_numeric_text_form() {
+        let cases = [
+            (GaussDBNumeric::positive(0, 2, vec![1234, 5000]), "1234.50"),
+            (GaussDBNumeric::positive(-1, 2, vec![3500]), "0.35"),
+            (GaussDBNumeric::negative(1, 0, vec![12, 3400]), "-123400"),
+            (GaussDBNumeric::positive(-2, 5, vec![5000]), "0.00005"),
+            (GaussDBNumeric::positive(0, 3, vec![]), "0.000"),
+            (GaussDBNumeric::positive(2, 0, vec![1]), "100000000"),
+            (GaussDBNumeric::nan(), "NaN"),
+        ];
+        for (numeric, text) in cases {
+            assert_eq!(to_text(&numeric), text);
+            assert_eq!(from_text::<GaussDBNumeric>(text), numeric, "{}", text);
+        }
+
+        assert_eq!(
+            from_text::<GaussDBNumeric>("+007.10"),
+            GaussDBNumeric::positive(0, 2, vec![7, 1000])
+        );
+        assert!(GaussDBNumeric::from_copy_text(Some("1.2.3")).is_err());
+        assert!(GaussDBNumeric::from_copy_text(Some("-")).is_err());
+    }
+
+    #[test]
+    fn test_timestamp_text_form() {
+        let timestamp = GaussDBTimestamp(
+            days_from_date(2024, 3, 5) * USECS_PER_DAY
+                + 14 * USECS_PER_HOUR
+                + 7 * USECS_PER_MINUTE
+                + 9_500_000,
+        );
+        assert_eq!(to_text(timestamp), "2024-03-05 14:07:09.5+00");
+        assert_eq!(
+            from_text::<GaussDBTimestamp>("2024-03-05 14:07:09.5"),
+            timestamp
+        );
+        assert_eq!(
+            from_text::<GaussDBTimestamp>("2024-03-05 14:07:09.5+00"),
+            timestamp
+        );
+        assert_eq!(
+            from_text::<GaussDBTimestamp>("2024-03-05 22:37:09.5+08:30"),
+            timestamp
+        );
+        assert_eq!(
+            from_text::<GaussDBTimestamp>("2024-03-05 09:07:09.5-05"),
+            timestamp
+        );
+
+        assert_eq!(to_text(GaussDBTimestamp(0)), "2000-01-01 00:00:00+00");
+        assert_eq!(
+            to_text(GaussDBTimestamp(-1)),
+            "1999-12-31 23:59:59.999999+00"
+        );
+        assert_eq!(
+            from_text::<GaussDBTimestamp>("1999-12-31 23:59:59.999999"),
+            GaussDBTimestamp(-1)
+        );
+
+        let bc = GaussDBTimestamp(days_from_date(0, 12, 31) * USECS_PER_DAY);
+        assert_eq!(to_text(bc), "0001-12-31 00:00:00+00 BC");
+        assert_eq!(from_text::<GaussDBTimestamp>("0001-12-31 00:00:00 BC"), bc);
+
+        assert_eq!(
+            from_text::<GaussDBTimestamp>("infinity"),
+            GaussDBTimestamp(i64::MAX)
+        );
+        assert!(GaussDBTimestamp::from_copy_text(Some("2024-02-30 00:00:00")).is_err());
+    }
+
+    #[test]
+    fn test_date_text_form() {
+        assert_eq!(to_text(GaussDBDate(0)), "2000-01-01");
+        assert_eq!(to_text(GaussDBDate(60)), "2000-03-01");
+        assert_eq!(from_text::<GaussDBDate>("2000-02-29"), GaussDBDate(59));
+        assert_eq!(from_text::<GaussDBDate>("1970-01-01"), GaussDBDate(-10_957));
+        assert!(GaussDBDate::from_copy_text(Some("2023-02-29")).is_err());
+    }
+
+    #[test]
+    fn test_interval_text_form() {
+        let hour = USECS_PER_HOUR;
+        let cases = [
+            (GaussDBInterval::new(0, 0, 0), "00:00:00"),
+            (
+                GaussDBInterval::new(14, 3, 4 * hour + 5 * USECS_PER_MINUTE + 6_500_000),
+                "1 year 2 mons 3 days 04:05:06.5",
+            ),
+            (GaussDBInterval::new(1, 1, 0), "1 mon 1 day"),
+            (GaussDBInterval::new(0, -1, 2 * hour), "-1 days +02:00:00"),
+            (
+                GaussDBInterval::new(-14, 3, -hour),
+                "-1 years -2 mons +3 days -01:00:00",
+            ),
+            (GaussDBInterval::new(0, 0, 27 * hour), "27:00:00"),
+        ];
+        for (interval, text) in cases {
+            assert_eq!(to_text(interval), text);
+            assert_eq!(from_text::<GaussDBInterval>(text), interval, "{}", text);
+        }
+        assert!(GaussDBInterval::from_copy_text(Some("3 fortnights")).is_err());
+    }
+
+    #[test]
+    fn test_scalar_text_forms() {
+        assert_eq!(to_text(true), "t");
+        assert!(!from_text::<bool>("false"));
+        assert_eq!(to_text(f64::NEG_INFINITY), "-Infinity");
+        assert_eq!(from_text::<f64>("Infinity"), f64::INFINITY);
+        assert_eq!(from_text::<f32>("0.25"), 0.25);
+        assert_eq!(from_text::<i64>("-42"), -42);
+
+        assert_eq!(Option::<i32>::from_copy_text(None).unwrap(), None);
+        assert!(i32::from_copy_text(None).is_err());
+    }
+
+    fn encode(options: CopyTextOptions, rows: &[[Option<&str>; 2]]) -> String {
+        let mut encoder = TextCopyEncoder::new(
+            vec![CopyColumn::new("a", 25), CopyColumn::new("b", 25)],
+            options,
+        );
+        for row in rows {
+            encoder.begin_row().unwrap();
+            encoder.write_field(&row[0]).unwrap();
+            encoder.write_field(&row[1]).unwrap();
+        }
+        String::from_utf8(encoder.finish().unwrap()).unwrap()
+    }
+
+    fn decode(data: &str, options: &CopyTextOptions) -> Vec<Vec<Option<String>>> {
+        decode_text_rows(data.as_bytes(), options)
+            .unwrap()
+            .into_iter()
+            .map(|row| row.fields)
+            .collect()
+    }
+
+    fn owned(rows: &[[Option<&str>; 2]]) -> Vec<Vec<Option<String>>> {
+        rows.iter()
+            .map(|row| row.iter().map(|f| f.map(String::from)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_text_format_escaping_and_null() {
+        let rows = [
+            [Some("tab\there"), Some("back\\slash")],
+            [Some("two\nlines"), None],
+            [Some("\\N"), Some("")],
+        ];
+        let options = CopyTextOptions::text();
+        let data = encode(options.clone(), &rows);
+        assert_eq!(
+            data,
+            "tab\\there\tback\\\\slash\ntwo\\nlines\t\\N\n\\\\N\t\n"
+        );
+        assert_eq!(decode(&data, &options), owned(&rows));
+
+        // A value spelled like a custom NULL string stays a value
+        let options = CopyTextOptions::text()
+            .with_delimiter('|')
+            .with_null("NULL");
+        let rows = [[Some("NULL"), None], [Some("a|b"), Some("x")]];
+        let data = encode(options.clone(), &rows);
+        assert_eq!(data, "\\116ULL|NULL\na\\|b|x\n");
+        assert_eq!(decode(&data, &options), owned(&rows));
+    }
+
+    #[test]
+    fn test_csv_quoting_and_null() {
+        let rows = [
+            [Some("plain"), Some("a,b")],
+            [Some("say \"hi\""), None],
+            [Some(""), Some("line\nbreak")],
+        ];
+        let options = CopyTextOptions::csv();
+        let data = encode(options.clone(), &rows);
+        assert_eq!(
+            data,
+            "plain,\"a,b\"\n\"say \"\"hi\"\"\",\n\"\",\"line\nbreak\"\n"
+        );
+        assert_eq!(decode(&data, &options), owned(&rows));
+
+        let options = CopyTextOptions::csv()
+            .with_delimiter(';')
+            .with_null("\\N")
+            .with_quote('\'')
+            .with_escape('\\');
+        let rows = [[Some("it's"), Some("\\N")], [None, Some("a;b")]];
+        let data = encode(options.clone(), &rows);
+        assert_eq!(data, "'it\\'s';'\\\\N'\n\\N;'a;b'\n");
+        assert_eq!(decode(&data, &options), owned(&rows));
+    }
+
+    #[test]
+    fn test_encoder_checks_field_count() {
+        let mut encoder =
+            TextCopyEncoder::new(vec![CopyColumn::new("id", 23)], CopyTextOptions::text());
+        assert!(encoder.write_field(&1).is_err());
+        encoder.begin_row().unwrap();
+        encoder.write_field(&1).unwrap();
+        assert!(encoder.write_field(&2).is_err());
+
+        let mut encoder = TextCopyEncoder::new(
+            vec![CopyColumn::new("id", 23), CopyColumn::new("x", 23)],
+            CopyTextOptions::text(),
+        );
+        encoder.begin_row().unwrap();
+        encoder.write_field(&1).unwrap();
+        assert!(encoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_sql_options() {
+        assert_eq!(
+            CopyTextOptions::text().to_sql_options(),
+            "FORMAT text, DELIMITER E'\t', NULL E'\\\\N'"
+        );
+        assert_eq!(
+            CopyTextOptions::csv().with_null("it's").to_sql_options(),
+            "FORMAT csv, DELIMITER E',', NULL E'it''s', QUOTE E'\"', ESCAPE E'\"'"
+        );
+    }
+
+    #[test]
+    fn test_row_accessors() {
+        let rows = decode_text_rows(b"7\t12.50\t\\N\n", &CopyTextOptions::text()).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<i32>(0).unwrap(), 7);
+        assert_eq!(
+            rows[0].get::<GaussDBNumeric>(1).unwrap(),
+            GaussDBNumeric::positive(0, 2, vec![12, 5000])
+        );
+        assert_eq!(rows[0].get::<Option<String>>(2).unwrap(), None);
+        assert_eq!(rows[0].field(1), Some("12.50"));
+        assert!(rows[0].get::<i32>(3).is_err());
+    }
+
+    #[test]
+    fn test_row_counter_across_chunks() {
+        let mut counter = CopyRowCounter::new(&CopyTextOptions::text());
+        for chunk in [&b"1\tline\\nbreak\n2\t"[..], b"x\n"] {
+            counter.update(chunk);
+        }
+        assert_eq!(counter.rows(), 2);
+
+        let mut counter = CopyRowCounter::new(&CopyTextOptions::csv());
+        for chunk in [&b"1,\"a\n\"\""[..], b"b\"\n2,", b"c\n"] {
+            counter.update(chunk);
+        }
+        assert_eq!(counter.rows(), 2);
+
+        let mut counter = CopyRowCounter::new(&CopyTextOptions::csv().with_escape('\\'));
+        for chunk in [&b"1,\"a\\"[..], b"\"\n\"\n2,b\n"] {
+            counter.update(chunk);
+        }
+        assert_eq!(counter.rows(), 2);
+    }
+}
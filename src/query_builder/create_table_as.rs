@@ -0,0 +1,185 @@
+//! CREATE TABLE ... AS SELECT for GaussDB
+//!
+//! [`create_table_as`] stores the result of a query in a new table, which is
+//! the usual way to materialize an intermediate result, e.g. a temporary
+//! table that later queries join against. Executing the statement returns
+//! the number of rows written to the new table.
+
+use crate::backend::GaussDB;
+use diesel::connection::Connection;
+use diesel::query_builder::{AstPass, QueryFragment, QueryId};
+use diesel::result::{Error, QueryResult};
+use diesel::RunQueryDsl;
+
+/// Creates a `CREATE [TEMPORARY] TABLE name AS query` statement
+///
+/// `name` may be schema-qualified (`schema.table`); each part is quoted. A
+/// temporary table is dropped at the end of the session and lives in the
+/// session's own temporary schema, so its name must not be qualified. Use
+/// [`with_no_data`](CreateTableAs::with_no_data) to only copy the column
+/// definitions of the query.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use diesel::prelude::*;
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel_gaussdb::query_builder::create_table_as;
+/// # diesel::table! { orders (id) { id -> Integer, customer_id -> Integer, total -> Integer, } }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+/// // CREATE TEMPORARY TABLE "big_orders" AS SELECT ... WHERE ("orders"."total" > $1)
+/// let copied = create_table_as(
+///     "big_orders",
+///     orders::table.filter(orders::total.gt(1000)),
+///     true,
+/// )
+/// .execute(&mut conn)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn create_table_as<Q>(name: impl Into<String>, query: Q, temporary: bool) -> CreateTableAs<Q> {
+    CreateTableAs {
+        name: name.into(),
+        query,
+        temporary,
+        with_data: true,
+    }
+}
+
+/// A `CREATE TABLE ... AS` statement, see [`create_table_as`]
+#[derive(Debug, Clone)]
+pub struct CreateTableAs<Q> {
+    name: String,
+    query: Q,
+    temporary: bool,
+    with_data: bool,
+}
+
+impl<Q> CreateTableAs<Q> {
+    /// Create the table without filling it (`WITH NO DATA`)
+    ///
+    /// The new table has the columns of the query but no rows, so executing
+    /// the statement returns `0`.
+    pub fn with_no_data(mut self) -> Self {
+        self.with_data = false;
+        self
+    }
+}
+
+impl<Q> QueryFragment<GaussDB> for CreateTableAs<Q>
+where
+    Q: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        if self.name.split('.').any(str::is_empty) {
+            return Err(Error::QueryBuilderError(
+                format!("Invalid table name {:?}", self.name).into(),
+            ));
+        }
+        if self.temporary && self.name.contains('.') {
+            return Err(Error::QueryBuilderError(
+                format!("Temporary table name {:?} must not be schema-qualified", self.name)
+                    .into(),
+            ));
+        }
+
+        // The table name is part of the SQL text
+        out.unsafe_to_cache_prepared();
+        out.push_sql("CREATE ");
+        if self.temporary {
+            out.push_sql("TEMPORARY ");
+        }
+        out.push_sql("TABLE ");
+        for (i, part) in self.name.split('.').enumerate() {
+            if i > 0 {
+                out.push_sql(".");
+            }
+            out.push_identifier(part)?;
+        }
+        out.push_sql(" AS ");
+        self.query.walk_ast(out.reborrow())?;
+        if !self.with_data {
+            out.push_sql(" WITH NO DATA");
+        }
+        Ok(())
+    }
+}
+
+impl<Q> QueryId for CreateTableAs<Q> {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Q, Conn> RunQueryDsl<Conn> for CreateTableAs<Q> where Conn: Connection<Backend = GaussDB> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+
+    diesel::table! {
+        orders (id) {
+            id -> Integer,
+            customer_id -> Integer,
+            total -> Integer,
+        }
+    }
+
+    fn generate_sql<Q: QueryFragment<GaussDB>>(query: Q) -> QueryResult<String> {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB)?;
+        Ok(query_builder.finish())
+    }
+
+    #[test]
+    fn test_create_table_as_sql() {
+        let query = create_table_as("order_totals", orders::table.select(orders::total), false);
+
+        assert_eq!(
+            generate_sql(query).unwrap(),
+            r#"CREATE TABLE "order_totals" AS SELECT "orders"."total" FROM "orders""#
+        );
+    }
+
+    #[test]
+    fn test_temporary_with_no_data_sql() {
+        let query = create_table_as(
+            "big_orders",
+            orders::table.filter(orders::total.gt(1000)),
+            true,
+        )
+        .with_no_data();
+
+        assert_eq!(
+            generate_sql(query).unwrap(),
+            r#"CREATE TEMPORARY TABLE "big_orders" AS SELECT "orders"."id", "orders"."customer_id", "orders"."total" FROM "orders" WHERE ("orders"."total" > $1) WITH NO DATA"#
+        );
+    }
+
+    #[test]
+    fn test_empty_name_part_is_rejected() {
+        let query = create_table_as("reporting.", orders::table.select(orders::id), false);
+
+        assert!(matches!(generate_sql(query), Err(Error::QueryBuilderError(_))));
+    }
+
+    #[test]
+    fn test_schema_qualified_sql() {
+        let query = create_table_as("reporting.totals", orders::table.select(orders::total), false);
+
+        assert_eq!(
+            generate_sql(query).unwrap(),
+            r#"CREATE TABLE "reporting"."totals" AS SELECT "orders"."total" FROM "orders""#
+        );
+    }
+
+    #[test]
+    fn test_schema_qualified_temporary_is_rejected() {
+        let query = create_table_as("reporting.totals", orders::table.select(orders::total), true);
+
+        assert!(matches!(generate_sql(query), Err(Error::QueryBuilderError(_))));
+    }
+}
@@ -0,0 +1,311 @@
+//! Query dumps with bind values for logging
+//!
+//! The SQL of a query only shows `$1`, `$2`, ... for its bind parameters.
+//! [`debug_query_with_binds`] writes the value of each parameter in their
+//! place, so a query from a log can be read, or reproduced by hand, without
+//! looking up the binds separately.
+//!
+//! Values are decoded from the binary format Diesel serialized them in.
+//! Strings become escape string literals, temporal types are written in
+//! their text form with a cast, `bytea` as hex, and `NULL` as `NULL`.
+//! Values of other types, e.g. arrays or custom types, are shown as the hex
+//! of their binary form with a comment naming the type OID.
+
+use crate::backend::{GaussDB, UnresolvedLookup};
+use crate::query_builder::copy::ToCopyText;
+use crate::query_builder::{quote_literal, GaussDBQueryBuilder};
+use crate::types::date_and_time::{GaussDBDate, GaussDBInterval, GaussDBTimestamp};
+use crate::types::numeric::GaussDBNumeric;
+use crate::value::GaussDBValue;
+use diesel::deserialize::FromSql;
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::query_builder::{QueryBuilder, QueryFragment};
+use diesel::result::QueryResult;
+use diesel::sql_types::{
+    BigInt, Bool, Date, Double, Float, Integer, Interval, Numeric, Oid, SmallInt, Timestamp,
+    Timestamptz,
+};
+use std::error::Error;
+use std::fmt::Write;
+
+/// The SQL of `query` with its bind values written in place of the
+/// placeholders
+///
+/// **This is for logging and debugging only.** The result is not safe SQL
+/// to execute: values of types that can't be decoded are shown as hex with
+/// a comment, floating point values may be rounded, and nothing guarantees
+/// the server would parse every literal back into the value that was
+/// bound. Always run the query itself, which sends the values as bind
+/// parameters.
+///
+/// Custom types are not looked up on a connection, so they are shown like
+/// any other value that can't be decoded.
+///
+/// # Example
+///
+/// ```rust
+/// # use diesel::prelude::*;
+/// # use diesel_gaussdb::query_builder::debug_query_with_binds;
+/// # diesel::table! { users (id) { id -> Integer, name -> Text, } }
+/// let query = users::table.filter(users::name.eq("O'Brien")).select(users::id);
+///
+/// assert_eq!(
+///     debug_query_with_binds(&query).unwrap(),
+///     r#"SELECT "users"."id" FROM "users" WHERE ("users"."name" = E'O''Brien')"#
+/// );
+/// ```
+pub fn debug_query_with_binds<T>(query: &T) -> QueryResult<String>
+where
+    T: QueryFragment<GaussDB> + ?Sized,
+{
+    let mut query_builder = GaussDBQueryBuilder::new();
+    query.to_sql(&mut query_builder, &GaussDB)?;
+    let sql = query_builder.finish();
+
+    let mut collector = RawBytesBindCollector::<GaussDB>::new();
+    query.collect_binds(&mut collector, &mut UnresolvedLookup, &GaussDB)?;
+    let values = collector
+        .binds
+        .iter()
+        .zip(&collector.metadata)
+        .map(|(bytes, metadata)| bind_literal(bytes.as_deref(), metadata.oid().ok()))
+        .collect::<Vec<_>>();
+
+    Ok(interpolate(&sql, &values))
+}
+
+type DecodeResult = Result<String, Box<dyn Error + Send + Sync>>;
+
+/// Write a bind value as an SQL literal
+fn bind_literal(bytes: Option<&[u8]>, oid: Option<u32>) -> String {
+    let Some(bytes) = bytes else {
+        return "NULL".to_string();
+    };
+    let value = |oid| GaussDBValue::new(Some(bytes), oid);
+
+    let literal: DecodeResult = match oid {
+        Some(16) => <bool as FromSql<Bool, GaussDB>>::from_sql(value(16))
+            .map(|b| if b { "TRUE" } else { "FALSE" }.to_string()),
+        Some(20) => <i64 as FromSql<BigInt, GaussDB>>::from_sql(value(20)).map(|v| v.to_string()),
+        Some(21) => <i16 as FromSql<SmallInt, GaussDB>>::from_sql(value(21)).map(|v| v.to_string()),
+        Some(23) => <i32 as FromSql<Integer, GaussDB>>::from_sql(value(23)).map(|v| v.to_string()),
+        Some(26) => <u32 as FromSql<Oid, GaussDB>>::from_sql(value(26)).map(|v| v.to_string()),
+        Some(700) => <f32 as FromSql<Float, GaussDB>>::from_sql(value(700))
+            .and_then(|v| number_literal(&v, v.is_finite(), "float4")),
+        Some(701) => <f64 as FromSql<Double, GaussDB>>::from_sql(value(701))
+            .and_then(|v| number_literal(&v, v.is_finite(), "float8")),
+        Some(1700) => <GaussDBNumeric as FromSql<Numeric, GaussDB>>::from_sql(value(1700))
+            .and_then(|v| number_literal(&v, !v.is_nan(), "numeric")),
+        Some(19 | 25 | 1042 | 1043) => std::str::from_utf8(bytes)
+            .map(quote_literal)
+            .map_err(Into::into),
+        Some(114) => std::str::from_utf8(bytes)
+            .map(|json| typed_literal(json, "json"))
+            .map_err(Into::into),
+        // `jsonb` starts with a version byte
+        Some(3802) => match bytes.split_first() {
+            Some((1, json)) => std::str::from_utf8(json)
+                .map(|json| typed_literal(json, "jsonb"))
+                .map_err(Into::into),
+            _ => Err("Unsupported jsonb version".into()),
+        },
+        Some(17) => Ok(format!(
+            "{}::bytea",
+            quote_literal(&format!("\\x{}", hex(bytes)))
+        )),
+        Some(1082) => <GaussDBDate as FromSql<Date, GaussDB>>::from_sql(value(1082))
+            .and_then(|v| text_literal(&v, "date")),
+        Some(1114) => <GaussDBTimestamp as FromSql<Timestamp, GaussDB>>::from_sql(value(1114))
+            .and_then(|v| text_literal(&v, "timestamp")),
+        Some(1184) => <GaussDBTimestamp as FromSql<Timestamptz, GaussDB>>::from_sql(value(1184))
+            .and_then(|v| text_literal(&v, "timestamptz")),
+        Some(1186) => <GaussDBInterval as FromSql<Interval, GaussDB>>::from_sql(value(1186))
+            .and_then(|v| text_literal(&v, "interval")),
+        Some(2950) if bytes.len() == 16 => {
+            let hex = hex(bytes);
+            Ok(typed_literal(
+                &format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                ),
+                "uuid",
+            ))
+        }
+        _ => Err("Unsupported type".into()),
+    };
+
+    literal.unwrap_or_else(|_| {
+        let raw = quote_literal(&format!("\\x{}", hex(bytes)));
+        match oid {
+            Some(oid) => format!("{} /* binary value of type {} */", raw, oid),
+            None => format!("{} /* binary value of a custom type */", raw),
+        }
+    })
+}
+
+/// A number as is, or as a quoted literal for `NaN` and the infinities
+fn number_literal<T: ToCopyText>(value: &T, plain: bool, type_name: &str) -> DecodeResult {
+    let mut text = String::new();
+    value.to_copy_text(&mut text)?;
+    Ok(if plain {
+        text
+    } else {
+        typed_literal(&text, type_name)
+    })
+}
+
+fn text_literal<T: ToCopyText>(value: &T, type_name: &str) -> DecodeResult {
+    let mut text = String::new();
+    value.to_copy_text(&mut text)?;
+    Ok(typed_literal(&text, type_name))
+}
+
+fn typed_literal(text: &str, type_name: &str) -> String {
+    format!("{}::{}", quote_literal(text), type_name)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+/// Replace the `$n` placeholders of `sql` outside of quotes with `values[n - 1]`
+fn interpolate(sql: &str, values: &[String]) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut quote = None;
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '$') => {
+                let mut end = i + 1;
+                while let Some(&(j, d)) = chars.peek() {
+                    if !d.is_ascii_digit() {
+                        break;
+                    }
+                    end = j + 1;
+                    chars.next();
+                }
+                let value = sql[i + 1..end]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| n.checked_sub(1))
+                    .and_then(|n| values.get(n));
+                match value {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&sql[i..end]),
+                }
+                continue;
+            }
+            _ => {}
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::prelude::*;
+
+    diesel::table! {
+        users (id) {
+            id -> Integer,
+            name -> Text,
+            active -> Bool,
+            score -> Nullable<Double>,
+            avatar -> Nullable<Binary>,
+            balance -> Numeric,
+        }
+    }
+
+    #[test]
+    fn test_mixed_bind_types() {
+        let query = users::table
+            .filter(users::name.eq(r"it's a \ test"))
+            .filter(users::active.eq(true))
+            .filter(users::score.eq(None::<f64>))
+            .filter(users::avatar.eq(vec![0xde_u8, 0xad, 0x01]))
+            .filter(users::balance.eq(GaussDBNumeric::from(-1234567_i64)))
+            .select(users::id)
+            .limit(10);
+
+        assert_eq!(
+            debug_query_with_binds(&query).unwrap(),
+            r#"SELECT "users"."id" FROM "users" WHERE ((((("users"."name" = E'it''s a \\ test') AND ("users"."active" = TRUE)) AND ("users"."score" = NULL)) AND ("users"."avatar" = E'\\xdead01'::bytea)) AND ("users"."balance" = -1234567)) LIMIT 10"#
+        );
+    }
+
+    #[test]
+    fn test_number_literals() {
+        assert_eq!(bind_literal(Some(&(-7_i16).to_be_bytes()), Some(21)), "-7");
+        assert_eq!(bind_literal(Some(&1.5_f64.to_be_bytes()), Some(701)), "1.5");
+        assert_eq!(
+            bind_literal(Some(&f32::NEG_INFINITY.to_be_bytes()), Some(700)),
+            "E'-Infinity'::float4"
+        );
+        assert_eq!(
+            bind_literal(Some(&f64::NAN.to_be_bytes()), Some(701)),
+            "E'NaN'::float8"
+        );
+    }
+
+    #[test]
+    fn test_temporal_and_other_literals() {
+        // 2000-01-02, one day after the epoch of the binary format
+        assert_eq!(
+            bind_literal(Some(&1_i32.to_be_bytes()), Some(1082)),
+            "E'2000-01-02'::date"
+        );
+        assert_eq!(
+            bind_literal(Some(&90_000_000_000_i64.to_be_bytes()), Some(1114)),
+            "E'2000-01-02 01:00:00+00'::timestamp"
+        );
+        assert_eq!(
+            bind_literal(Some(br#"{"a":1}"#), Some(114)),
+            r#"E'{"a":1}'::json"#
+        );
+        assert_eq!(bind_literal(Some(b"\x01[]"), Some(3802)), "E'[]'::jsonb");
+        assert_eq!(
+            bind_literal(Some(&[0x12; 16]), Some(2950)),
+            "E'12121212-1212-1212-1212-121212121212'::uuid"
+        );
+        assert_eq!(bind_literal(None, Some(23)), "NULL");
+    }
+
+    #[test]
+    fn test_undecodable_values_are_hex() {
+        assert_eq!(
+            bind_literal(Some(&[0, 1]), Some(1007)),
+            r"E'\\x0001' /* binary value of type 1007 */"
+        );
+        assert_eq!(
+            bind_literal(Some(&[0xff]), None),
+            r"E'\\xff' /* binary value of a custom type */"
+        );
+        // A value of the wrong width is not decoded as the declared type
+        assert_eq!(
+            bind_literal(Some(&[0, 1]), Some(23)),
+            r"E'\\x0001' /* binary value of type 23 */"
+        );
+    }
+
+    #[test]
+    fn test_placeholders_in_quotes_are_kept() {
+        let values = ["1".to_string(), "E'x'".to_string()];
+        assert_eq!(
+            interpolate(r#"SELECT '$1', "$2", $2, $1, $3 WHERE a = $10"#, &values),
+            r#"SELECT '$1', "$2", E'x', 1, $3 WHERE a = $10"#
+        );
+    }
+}
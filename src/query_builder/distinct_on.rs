@@ -4,8 +4,11 @@
 //! which are also supported by GaussDB.
 
 use crate::backend::GaussDB;
-use diesel::query_builder::{QueryFragment, AstPass};
+use diesel::expression::{is_aggregate, AppearsOnTable, Expression, ValidGrouping};
+use diesel::query_builder::{QueryFragment, AstPass, Query, QueryId};
 use diesel::result::QueryResult;
+use diesel::sql_types::BoolOrNullableBool;
+use diesel::{RunQueryDsl, Table};
 
 /// Represents a DISTINCT ON clause in a SELECT statement
 ///
@@ -143,6 +146,178 @@ pub trait DistinctOnDsl<Expr> {
     fn distinct_on(self, expr: Expr) -> Self::Output;
 }
 
+/// The latest row of each group, using `DISTINCT ON`
+///
+/// Selects all columns of the table, so the rows can be loaded into the
+/// table's model. The generated `ORDER BY` always starts with the group
+/// key, followed by the order column descending, which is the order
+/// `DISTINCT ON` needs to keep the latest row. Rows where the order column
+/// is `NULL` are sorted last, so they are only picked for groups without
+/// any other row.
+///
+/// Created with [`latest_per_group`].
+#[derive(Debug, Clone, Copy)]
+pub struct LatestPerGroup<T: Table, K, O, W = NoGroupFilter> {
+    from: T::FromClause,
+    columns: T::AllColumns,
+    key: K,
+    order: O,
+    filter: W,
+}
+
+/// Placeholder for a [`LatestPerGroup`] query without a `WHERE` clause
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoGroupFilter;
+
+/// The filters of a [`LatestPerGroup`] query, `previous` followed by
+/// `predicate`
+#[derive(Debug, Clone, Copy)]
+pub struct GroupFilter<W, P> {
+    previous: W,
+    predicate: P,
+}
+
+/// The latest row per `key` of `table`, ordered by `order`
+///
+/// `key` may be a tuple of columns to group by several columns. Both `key`
+/// and `order` have to come from `table` and must not contain aggregate
+/// functions.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel_gaussdb::query_builder::latest_per_group;
+/// # diesel::table! { comments (id) { id -> Integer, post_id -> Integer, body -> Text, created_at -> Timestamp, } }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+/// // SELECT DISTINCT ON ("comments"."post_id") "comments"."id", ... FROM "comments"
+/// // WHERE "comments"."post_id" = ANY($1)
+/// // ORDER BY "comments"."post_id", "comments"."created_at" DESC NULLS LAST
+/// let latest = latest_per_group(comments::table, comments::post_id, comments::created_at)
+///     .filter(comments::post_id.eq_any(vec![1, 2, 3]))
+///     .load::<(i32, i32, String, diesel_gaussdb::types::date_and_time::GaussDBTimestamp)>(&mut conn)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// An aggregate can't be used as the key or order:
+///
+/// ```rust,compile_fail
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel_gaussdb::query_builder::latest_per_group;
+/// # diesel::table! { comments (id) { id -> Integer, post_id -> Integer, body -> Text, created_at -> Timestamp, } }
+/// let query = latest_per_group(comments::table, comments::post_id, diesel::dsl::max(comments::id));
+/// ```
+pub fn latest_per_group<T, K, O>(table: T, key: K, order: O) -> LatestPerGroup<T, K, O>
+where
+    T: Table,
+    K: Expression + AppearsOnTable<T> + ValidGrouping<(), IsAggregate = is_aggregate::No>,
+    O: Expression + AppearsOnTable<T> + ValidGrouping<(), IsAggregate = is_aggregate::No>,
+{
+    LatestPerGroup {
+        from: table.from_clause(),
+        columns: T::all_columns(),
+        key,
+        order,
+        filter: NoGroupFilter,
+    }
+}
+
+impl<T: Table, K, O, W> LatestPerGroup<T, K, O, W> {
+    /// Only consider the rows matching `predicate`
+    ///
+    /// The filter is applied before the groups are formed, so each group
+    /// gets its latest matching row. Calling this more than once combines
+    /// the predicates with `AND`.
+    pub fn filter<P>(self, predicate: P) -> LatestPerGroup<T, K, O, GroupFilter<W, P>>
+    where
+        P: Expression + AppearsOnTable<T>,
+        P::SqlType: BoolOrNullableBool,
+    {
+        LatestPerGroup {
+            from: self.from,
+            columns: self.columns,
+            key: self.key,
+            order: self.order,
+            filter: GroupFilter {
+                previous: self.filter,
+                predicate,
+            },
+        }
+    }
+}
+
+/// The `WHERE` clause of a [`LatestPerGroup`] query
+///
+/// `NoGroupFilter` renders nothing, the predicates of a `GroupFilter`
+/// render as ` WHERE <predicate> AND <predicate> ...`.
+pub trait GroupFilterClause {
+    /// Output the `WHERE` clause, returning whether it has any predicate
+    fn walk_filter<'b>(&'b self, out: AstPass<'_, 'b, GaussDB>) -> QueryResult<bool>;
+}
+
+impl GroupFilterClause for NoGroupFilter {
+    fn walk_filter<'b>(&'b self, _out: AstPass<'_, 'b, GaussDB>) -> QueryResult<bool> {
+        Ok(false)
+    }
+}
+
+impl<W, P> GroupFilterClause for GroupFilter<W, P>
+where
+    W: GroupFilterClause,
+    P: QueryFragment<GaussDB>,
+{
+    fn walk_filter<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<bool> {
+        if self.previous.walk_filter(out.reborrow())? {
+            out.push_sql(" AND ");
+        } else {
+            out.push_sql(" WHERE ");
+        }
+        self.predicate.walk_ast(out.reborrow())?;
+        Ok(true)
+    }
+}
+
+impl<T, K, O, W> QueryFragment<GaussDB> for LatestPerGroup<T, K, O, W>
+where
+    T: Table,
+    T::FromClause: QueryFragment<GaussDB>,
+    T::AllColumns: QueryFragment<GaussDB>,
+    K: QueryFragment<GaussDB>,
+    O: QueryFragment<GaussDB>,
+    W: GroupFilterClause,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.unsafe_to_cache_prepared();
+        out.push_sql("SELECT DISTINCT ON (");
+        self.key.walk_ast(out.reborrow())?;
+        out.push_sql(") ");
+        self.columns.walk_ast(out.reborrow())?;
+        out.push_sql(" FROM ");
+        self.from.walk_ast(out.reborrow())?;
+        self.filter.walk_filter(out.reborrow())?;
+        out.push_sql(" ORDER BY ");
+        self.key.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.order.walk_ast(out.reborrow())?;
+        out.push_sql(" DESC NULLS LAST");
+        Ok(())
+    }
+}
+
+impl<T: Table, K, O, W> QueryId for LatestPerGroup<T, K, O, W> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T: Table, K, O, W> Query for LatestPerGroup<T, K, O, W> {
+    type SqlType = <T::AllColumns as Expression>::SqlType;
+}
+
+impl<T: Table, K, O, W, Conn> RunQueryDsl<Conn> for LatestPerGroup<T, K, O, W> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,426 @@
+//! Set-returning functions used as relations in the `FROM` clause
+//!
+//! Functions such as `unnest` or `jsonb_each` produce rows, and with an
+//! alias they can be queried like a table:
+//!
+//! ```sql
+//! SELECT "t"."tag" FROM unnest(ARRAY['a', 'b']) AS "t"("tag")
+//! ```
+//!
+//! [`FunctionRelation`] is such an aliased function. It is a Diesel
+//! [`QuerySource`], so it is queried through
+//! [`AsQuery::as_query`](diesel::query_builder::AsQuery::as_query) with the
+//! usual `select`, `filter`, `order`, `limit` and `into_boxed`, and joined
+//! to a table with `inner_join(relation.on(...))`. A function in the
+//! `FROM` clause is implicitly `LATERAL`, so its argument can be a column
+//! of the table it is joined to.
+//!
+//! The columns of a relation, [`RelationColumn`], are only valid in queries
+//! whose `FROM` clause contains exactly one relation of the same function
+//! type, the way a table column needs its table in the `FROM` clause. The
+//! alias itself is chosen at run time and is not part of the type, so a
+//! query can't contain two relations of the same function type, and a
+//! column must be taken from the relation the query is built from.
+//!
+//! Like tables in [`diesel::allow_tables_to_appear_in_same_query!`], a table
+//! has to be declared with [`allow_function_relations!`] before a relation
+//! can be joined to it.
+
+use crate::backend::GaussDB;
+use diesel::dsl::{sql, InnerJoin, On};
+use diesel::expression::{
+    is_aggregate, is_contained_in_group_by, AppearsOnTable, Expression, IsContainedInGroupBy,
+    SelectableExpression, SqlLiteral, ValidGrouping,
+};
+use diesel::internal::table_macro::{Inner, Join, JoinOn, LeftOuter, Pick};
+use diesel::query_builder::{AsQuery, AstPass, FromClause, QueryFragment, QueryId, SelectStatement};
+use diesel::query_dsl::{JoinOnDsl, JoinWithImplicitOnClause};
+use diesel::query_source::{AppearsInFromClause, Never, Once, QuerySource};
+use diesel::result::QueryResult;
+use diesel::sql_types::{Bool, SingleValue};
+use diesel::{QueryDsl, Table};
+use std::marker::PhantomData;
+
+/// A set-returning function that can be aliased into a [`FunctionRelation`]
+pub trait SetReturningFunction: Sized {
+    /// The columns a query selects when it doesn't call `select`
+    type DefaultSelection;
+
+    /// The default selection of `relation`
+    fn default_selection(relation: &FunctionRelation<Self>) -> Self::DefaultSelection;
+}
+
+/// A set-returning function with an alias, usable in the `FROM` clause
+///
+/// Rendered as `function(...) AS "alias"("column", ...)`.
+#[derive(Debug, Clone)]
+pub struct FunctionRelation<F> {
+    function: F,
+    alias: String,
+    columns: Vec<String>,
+}
+
+impl<F> FunctionRelation<F> {
+    pub(crate) fn new(function: F, alias: &str, columns: &[&str]) -> Self {
+        FunctionRelation {
+            function,
+            alias: alias.to_string(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+        }
+    }
+
+    /// The name of the relation
+    pub fn alias_name(&self) -> &str {
+        &self.alias
+    }
+
+    /// The names of the columns, in order
+    pub fn column_names(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(String::as_str)
+    }
+
+    /// The column at index `N`, typed `ST`
+    pub(crate) fn nth_column<ST, const N: usize>(&self) -> RelationColumn<F, ST, N> {
+        RelationColumn {
+            alias: self.alias.clone(),
+            column: self.columns[N].clone(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Join the relation to `source`, the table its argument comes from
+    ///
+    /// Renders `(<source> INNER JOIN function(...) AS "alias"(...) ON TRUE)`,
+    /// which keeps every row of `source` that the function returns rows
+    /// for. This is the same as `source.inner_join(relation.on(...))` with
+    /// an always true condition.
+    pub fn joined_to<S>(self, source: S) -> InnerJoin<S, On<Self, SqlLiteral<Bool>>>
+    where
+        S: QueryDsl + JoinWithImplicitOnClause<On<Self, SqlLiteral<Bool>>, Inner>,
+        Self: QuerySource,
+    {
+        source.inner_join(self.on(sql::<Bool>("TRUE")))
+    }
+}
+
+impl<F> QueryId for FunctionRelation<F> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<F> QueryFragment<GaussDB> for FunctionRelation<F>
+where
+    F: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        self.function.walk_ast(out.reborrow())?;
+        out.push_sql(" AS ");
+        out.push_identifier(&self.alias)?;
+        out.push_sql("(");
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                out.push_sql(", ");
+            }
+            out.push_identifier(column)?;
+        }
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<F> QuerySource for FunctionRelation<F>
+where
+    F: SetReturningFunction + Clone,
+    F::DefaultSelection: SelectableExpression<Self>,
+{
+    type FromClause = Self;
+    type DefaultSelection = F::DefaultSelection;
+
+    fn from_clause(&self) -> Self::FromClause {
+        self.clone()
+    }
+
+    fn default_selection(&self) -> Self::DefaultSelection {
+        F::default_selection(self)
+    }
+}
+
+impl<F> AsQuery for FunctionRelation<F>
+where
+    F: SetReturningFunction + Clone,
+    F::DefaultSelection: SelectableExpression<Self> + ValidGrouping<()>,
+{
+    type SqlType = <F::DefaultSelection as Expression>::SqlType;
+    type Query = SelectStatement<FromClause<Self>>;
+
+    fn as_query(self) -> Self::Query {
+        SelectStatement::simple(self)
+    }
+}
+
+// The columns of a table never come from a function relation, which lets
+// them appear in a join of their table and a relation.
+impl<F, T: Table> AppearsInFromClause<T> for FunctionRelation<F> {
+    type Count = Never;
+}
+
+impl<F> AppearsInFromClause<FunctionRelation<F>> for FunctionRelation<F> {
+    type Count = Once;
+}
+
+/// Allow function relations to be joined to the given tables
+///
+/// Diesel only lets a column appear in a query if every part of the `FROM`
+/// clause states whether it contains the column's source. A table doesn't
+/// know about [`FunctionRelation`], so it has to be told that it never
+/// contains one, the same way
+/// [`allow_tables_to_appear_in_same_query!`](diesel::allow_tables_to_appear_in_same_query)
+/// does for other tables. Invoke this once per schema, next to the `table!`
+/// definitions.
+///
+/// ```rust
+/// # use diesel::prelude::*;
+/// diesel::table! {
+///     posts (id) {
+///         id -> Integer,
+///         tags -> Array<Text>,
+///     }
+/// }
+///
+/// diesel_gaussdb::allow_function_relations!(posts);
+/// ```
+#[macro_export]
+macro_rules! allow_function_relations {
+    ($($table:ident),+ $(,)?) => {
+        $(
+            impl<F> diesel::query_source::AppearsInFromClause<
+                $crate::query_builder::function_relation::FunctionRelation<F>,
+            > for $table::table
+            {
+                type Count = diesel::query_source::Never;
+            }
+        )+
+    };
+}
+
+/// A column of a [`FunctionRelation`], rendered as `"alias"."column"`
+///
+/// `N` is the position of the column in the relation, so that two columns of
+/// the same type are still told apart when grouping. A column can't be used
+/// in a query whose `FROM` clause lacks its relation:
+///
+/// ```rust,compile_fail
+/// # use diesel::prelude::*;
+/// # use diesel_gaussdb::query_builder::unnest::unnest;
+/// # diesel::table! { posts (id) { id -> Integer, tags -> Array<Text>, } }
+/// # diesel_gaussdb::allow_function_relations!(posts);
+/// let tag = unnest(posts::tags).alias("t", "tag").column();
+/// let query = posts::table.select(tag);
+/// ```
+///
+/// Nor can a column be selected when the query is grouped by another column
+/// of the same relation:
+///
+/// ```rust,compile_fail
+/// # use diesel::prelude::*;
+/// # use diesel::dsl::sql;
+/// # use diesel::query_builder::AsQuery;
+/// # use diesel::sql_types::Jsonb;
+/// # use diesel_gaussdb::expression::functions::json::jsonb_each;
+/// let attributes = jsonb_each(sql::<Jsonb>("'{}'")).alias("attr");
+/// let query = attributes
+///     .clone()
+///     .as_query()
+///     .group_by(attributes.key())
+///     .select(attributes.value());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RelationColumn<F, ST, const N: usize = 0> {
+    alias: String,
+    column: String,
+    _marker: PhantomData<(F, ST)>,
+}
+
+impl<F, ST, const N: usize> QueryId for RelationColumn<F, ST, N> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<F, ST: SingleValue, const N: usize> Expression for RelationColumn<F, ST, N> {
+    type SqlType = ST;
+}
+
+impl<F, ST, const N: usize> QueryFragment<GaussDB> for RelationColumn<F, ST, N> {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_identifier(&self.alias)?;
+        out.push_sql(".");
+        out.push_identifier(&self.column)?;
+        Ok(())
+    }
+}
+
+// The impls below follow the ones `table!` generates for columns
+impl<QS, F, ST: SingleValue, const N: usize> AppearsOnTable<QS> for RelationColumn<F, ST, N> where
+    QS: AppearsInFromClause<FunctionRelation<F>, Count = Once>
+{
+}
+
+impl<F, ST: SingleValue, const N: usize> SelectableExpression<FunctionRelation<F>> for RelationColumn<F, ST, N> where
+    Self: AppearsOnTable<FunctionRelation<F>>
+{
+}
+
+impl<L, R, F, ST: SingleValue, const N: usize> SelectableExpression<Join<L, R, Inner>> for RelationColumn<F, ST, N>
+where
+    Self: AppearsOnTable<Join<L, R, Inner>>,
+    L: AppearsInFromClause<FunctionRelation<F>> + QuerySource,
+    R: AppearsInFromClause<FunctionRelation<F>> + QuerySource,
+    (L::Count, R::Count): Pick<L, R>,
+    Self: SelectableExpression<<(L::Count, R::Count) as Pick<L, R>>::Selection>,
+{
+}
+
+// On the right side of a left join, only `Nullable<Self>` can be selected
+impl<L, R, F, ST: SingleValue, const N: usize> SelectableExpression<Join<L, R, LeftOuter>> for RelationColumn<F, ST, N>
+where
+    Self: AppearsOnTable<Join<L, R, LeftOuter>> + SelectableExpression<L>,
+    L: QuerySource,
+    R: AppearsInFromClause<FunctionRelation<F>, Count = Never> + QuerySource,
+{
+}
+
+impl<J, Cond, F, ST: SingleValue, const N: usize> SelectableExpression<JoinOn<J, Cond>> for RelationColumn<F, ST, N> where
+    Self: SelectableExpression<J> + AppearsOnTable<JoinOn<J, Cond>>
+{
+}
+
+impl<F, ST, const N: usize> ValidGrouping<()> for RelationColumn<F, ST, N> {
+    type IsAggregate = is_aggregate::No;
+}
+
+// Like a table column, a column that is grouped by counts as an aggregate
+impl<F, ST, GB, const N: usize> ValidGrouping<GB> for RelationColumn<F, ST, N>
+where
+    GB: IsContainedInGroupBy<Self, Output = is_contained_in_group_by::Yes>,
+{
+    type IsAggregate = is_aggregate::Yes;
+}
+
+// A query contains at most one relation of type `F`, see `AppearsOnTable`,
+// so the function type and position identify the column
+impl<F, ST1, ST2, const N: usize, const M: usize> IsContainedInGroupBy<RelationColumn<F, ST2, M>>
+    for RelationColumn<F, ST1, N>
+where
+    ColumnIndex<N>: SameColumnIndex<M>,
+{
+    type Output = <ColumnIndex<N> as SameColumnIndex<M>>::Output;
+}
+
+/// The position of a [`RelationColumn`], compared by [`SameColumnIndex`]
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnIndex<const N: usize>;
+
+/// Whether two column positions are the same, as `is_contained_in_group_by`
+#[doc(hidden)]
+pub trait SameColumnIndex<const M: usize> {
+    type Output;
+}
+
+// Each position is the same as itself and different from the others listed
+macro_rules! same_column_index {
+    ($($n:literal: $($other:literal),+;)+) => {
+        $(
+            impl SameColumnIndex<$n> for ColumnIndex<$n> {
+                type Output = is_contained_in_group_by::Yes;
+            }
+
+            $(
+                impl SameColumnIndex<$other> for ColumnIndex<$n> {
+                    type Output = is_contained_in_group_by::No;
+                }
+            )+
+        )+
+    };
+}
+
+same_column_index! {
+    0: 1, 2, 3;
+    1: 0, 2, 3;
+    2: 0, 1, 3;
+    3: 0, 1, 2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::functions::json::jsonb_each;
+    use crate::query_builder::unnest::unnest;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::dsl::count_star;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+    use diesel::sql_types::{Array, Integer, Jsonb};
+
+    diesel::table! {
+        posts (id) {
+            id -> Integer,
+            tags -> Array<Text>,
+        }
+    }
+
+    crate::allow_function_relations!(posts);
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(fragment: &T) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        fragment.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_relation_query() {
+        let numbers = unnest(sql::<Array<Integer>>("ARRAY[1, 2, 3]")).alias("n", "value");
+        let value = numbers.column();
+        let query = numbers
+            .as_query()
+            .select(value.clone())
+            .filter(value.clone().gt(1))
+            .order(value.desc())
+            .limit(1);
+        assert_eq!(
+            generate_sql(&query),
+            r#"SELECT "n"."value" FROM unnest(ARRAY[1, 2, 3]) AS "n"("value") WHERE ("n"."value" > $1) ORDER BY "n"."value" DESC LIMIT $2"#
+        );
+    }
+
+    #[test]
+    fn test_relation_joined_to_table() {
+        let tags = unnest(posts::tags).alias("t", "tag");
+        let tag = tags.column();
+        let query = tags
+            .joined_to(posts::table)
+            .group_by(tag.clone())
+            .select((tag.clone(), count_star()))
+            .order(tag);
+        assert_eq!(
+            generate_sql(&query),
+            r#"SELECT "t"."tag", COUNT(*) FROM ("posts" INNER JOIN unnest("posts"."tags") AS "t"("tag") ON TRUE) GROUP BY "t"."tag" ORDER BY "t"."tag""#
+        );
+    }
+
+    #[test]
+    fn test_group_by_one_of_several_relation_columns() {
+        let attributes = jsonb_each(sql::<Jsonb>("'{}'")).alias("attr");
+        let query = attributes
+            .clone()
+            .as_query()
+            .group_by(attributes.key())
+            .select((attributes.key(), count_star()));
+        assert_eq!(
+            generate_sql(&query),
+            r#"SELECT "attr"."key", COUNT(*) FROM jsonb_each('{}') AS "attr"("key", "value") GROUP BY "attr"."key""#
+        );
+    }
+}
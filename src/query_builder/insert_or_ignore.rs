@@ -0,0 +1,169 @@
+//! INSERT ... ON CONFLICT DO NOTHING for GaussDB
+//!
+//! [`insert_or_ignore_into`] builds a regular Diesel `INSERT` statement whose
+//! values are followed by `ON CONFLICT DO NOTHING`. Rows that violate any
+//! unique constraint are skipped, so executing the statement returns the
+//! number of rows that were actually inserted, and a `RETURNING` clause only
+//! yields those rows.
+
+use crate::backend::GaussDB;
+use diesel::insertable::{CanInsertInSingleQuery, Insertable};
+use diesel::query_builder::{AstPass, InsertStatement, QueryFragment, QueryId};
+use diesel::result::QueryResult;
+use diesel::Table;
+
+/// Creates an `INSERT` statement that skips conflicting rows
+///
+/// The statement compiles to `INSERT INTO ... VALUES ... ON CONFLICT DO
+/// NOTHING` and behaves like any other Diesel insert statement: `execute`
+/// returns the number of inserted rows, which is `0` when every row
+/// conflicted, and `.returning(...)` only returns the inserted rows.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use diesel::prelude::*;
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel_gaussdb::query_builder::insert_or_ignore_into;
+/// # diesel::table! { users (id) { id -> Integer, name -> Text, } }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+/// let seeds = vec![
+///     (users::id.eq(1), users::name.eq("admin")),
+///     (users::id.eq(2), users::name.eq("guest")),
+/// ];
+///
+/// let inserted = insert_or_ignore_into(users::table)
+///     .values(&seeds)
+///     .execute(&mut conn)?;
+/// assert!(inserted <= 2);
+///
+/// let new_ids: Vec<i32> = insert_or_ignore_into(users::table)
+///     .values(&seeds)
+///     .returning(users::id)
+///     .get_results(&mut conn)?;
+/// assert!(new_ids.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub fn insert_or_ignore_into<T: Table>(target: T) -> IncompleteInsertOrIgnore<T> {
+    IncompleteInsertOrIgnore { target }
+}
+
+/// An `INSERT ... ON CONFLICT DO NOTHING` statement without values
+///
+/// Created by [`insert_or_ignore_into`].
+#[derive(Debug, Clone, Copy)]
+pub struct IncompleteInsertOrIgnore<T> {
+    target: T,
+}
+
+impl<T: Table> IncompleteInsertOrIgnore<T> {
+    /// The records to insert
+    ///
+    /// Accepts everything `diesel::insert_into(..).values(..)` accepts,
+    /// including tuples of assignments, `Insertable` structs and vectors or
+    /// slices of them for a batch insert.
+    pub fn values<U>(self, records: U) -> InsertStatement<T, IgnoreConflicts<U::Values>>
+    where
+        U: Insertable<T>,
+    {
+        diesel::insert_into(self.target).values(IgnoreConflicts(records))
+    }
+}
+
+/// Insert values followed by `ON CONFLICT DO NOTHING`
+///
+/// Used as the values of the statement returned by
+/// [`IncompleteInsertOrIgnore::values`].
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct IgnoreConflicts<V>(V);
+
+impl<T, U> Insertable<T> for IgnoreConflicts<U>
+where
+    U: Insertable<T>,
+{
+    type Values = IgnoreConflicts<U::Values>;
+
+    fn values(self) -> Self::Values {
+        IgnoreConflicts(self.0.values())
+    }
+}
+
+impl<V> CanInsertInSingleQuery<GaussDB> for IgnoreConflicts<V>
+where
+    V: CanInsertInSingleQuery<GaussDB>,
+{
+    fn rows_to_insert(&self) -> Option<usize> {
+        self.0.rows_to_insert()
+    }
+}
+
+impl<V> QueryFragment<GaussDB> for IgnoreConflicts<V>
+where
+    V: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        self.0.walk_ast(out.reborrow())?;
+        out.push_sql(" ON CONFLICT DO NOTHING");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+
+    diesel::table! {
+        users (id) {
+            id -> Integer,
+            name -> Text,
+        }
+    }
+
+    fn generate_sql<Q: QueryFragment<GaussDB>>(query: Q) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_single_row_sql() {
+        let query = insert_or_ignore_into(users::table)
+            .values((users::id.eq(1), users::name.eq("admin")));
+
+        assert_eq!(
+            generate_sql(query),
+            r#"INSERT INTO "users" ("id", "name") VALUES ($1, $2) ON CONFLICT DO NOTHING"#
+        );
+    }
+
+    #[test]
+    fn test_batch_sql() {
+        let rows = vec![
+            (users::id.eq(1), users::name.eq("admin")),
+            (users::id.eq(2), users::name.eq("guest")),
+        ];
+        let query = insert_or_ignore_into(users::table).values(&rows);
+
+        assert_eq!(
+            generate_sql(query),
+            r#"INSERT INTO "users" ("id", "name") VALUES ($1, $2), ($3, $4) ON CONFLICT DO NOTHING"#
+        );
+    }
+
+    #[test]
+    fn test_returning_follows_conflict_clause() {
+        let query = insert_or_ignore_into(users::table)
+            .values((users::id.eq(1), users::name.eq("admin")))
+            .returning(users::id);
+
+        assert_eq!(
+            generate_sql(query),
+            r#"INSERT INTO "users" ("id", "name") VALUES ($1, $2) ON CONFLICT DO NOTHING RETURNING "users"."id""#
+        );
+    }
+}
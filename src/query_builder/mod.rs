@@ -15,19 +15,39 @@ pub mod copy;
 pub mod window_functions;
 pub mod cte;
 pub mod subquery;
+pub mod set_operations;
+pub mod function_relation;
+pub mod unnest;
+pub mod insert_or_ignore;
+pub mod create_table_as;
+pub mod debug_query;
 pub mod query_fragment_impls;
 pub mod returning;
+pub mod select_by;
+pub mod search;
+pub mod truncate;
 
-pub use self::distinct_on::DistinctOnClause;
+pub use self::distinct_on::{DistinctOnClause, LatestPerGroup, latest_per_group};
 pub use self::limit_offset::LimitOffsetClause;
 pub use self::on_constraint::{OnConstraint, ConflictTarget, on_constraint};
 pub use self::copy::{CopyFormat, CopyTarget, CopyOperation};
-pub use self::window_functions::{WindowFunction, OverClause, over};
+pub use self::window_functions::{
+    WindowFunction, OverClause, WindowFrame, FrameUnit, FrameBound, FrameExclusion, over
+};
 pub use self::cte::{CteDefinition, WithClause, cte, recursive_cte, with};
 pub use self::subquery::{
     ScalarSubquery, ExistsSubquery, NotExistsSubquery, InSubquery, NotInSubquery,
     scalar_subquery, exists, not_exists, in_subquery, not_in_subquery
 };
+pub use self::set_operations::{SetOperation, SetOperator, SetOperationDsl};
+pub use self::function_relation::{FunctionRelation, RelationColumn, SetReturningFunction};
+pub use self::unnest::{Unnest, UnnestRelation, UnnestColumn, UnnestInsert, unnest, unnest_insert};
+pub use self::insert_or_ignore::{IncompleteInsertOrIgnore, IgnoreConflicts, insert_or_ignore_into};
+pub use self::create_table_as::{CreateTableAs, create_table_as};
+pub use self::debug_query::debug_query_with_binds;
+pub use self::select_by::SelectByDsl;
+pub use self::search::{SearchBuilder, contains_pattern};
+pub use self::truncate::{Truncate, truncate};
 
 /// The GaussDB query builder
 ///
@@ -58,6 +78,24 @@ impl GaussDBQueryBuilder {
 
 }
 
+/// Quote `value` as an escape string literal, for statements that can't
+/// take bind parameters
+///
+/// The `E'...'` form reads the same whatever `standard_conforming_strings`
+/// is set to.
+pub(crate) fn quote_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 3);
+    literal.push_str("E'");
+    for c in value.chars() {
+        if c == '\'' || c == '\\' {
+            literal.push(c);
+        }
+        literal.push(c);
+    }
+    literal.push('\'');
+    literal
+}
+
 impl QueryBuilder<GaussDB> for GaussDBQueryBuilder {
     fn push_sql(&mut self, sql: &str) {
         self.sql.push_str(sql);
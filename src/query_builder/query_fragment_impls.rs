@@ -5,7 +5,8 @@
 
 use diesel::query_builder::{AstPass, QueryFragment};
 use diesel::result::QueryResult;
-use crate::backend::{GaussDB, GaussDBReturningClause};
+use crate::backend::{GaussDB, GaussDBReturningClause, GaussDBSelectStatement};
+use crate::expression::select_alias::walk_clause;
 
 // 重要说明：
 // 由于diesel的内部模块（如locking_clause）在外部crate中是私有的，
@@ -29,7 +30,65 @@ where
     }
 }
 
-// 2. ILike操作符支持
+// 2. SELECT语句支持
+use diesel::query_builder::{BoxedLimitOffsetClause, BoxedSelectStatement, SelectStatement};
+
+// Same as diesel's ANSI select statement, except that only the select
+// clause allows aliased expressions
+impl<F, S, D, W, O, LOf, G, H, LC> QueryFragment<GaussDB, GaussDBSelectStatement>
+    for SelectStatement<F, S, D, W, O, LOf, G, H, LC>
+where
+    S: QueryFragment<GaussDB>,
+    F: QueryFragment<GaussDB>,
+    D: QueryFragment<GaussDB>,
+    W: QueryFragment<GaussDB>,
+    O: QueryFragment<GaussDB>,
+    LOf: QueryFragment<GaussDB>,
+    G: QueryFragment<GaussDB>,
+    H: QueryFragment<GaussDB>,
+    LC: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("SELECT ");
+        walk_clause(false, || self.distinct.walk_ast(out.reborrow()))?;
+        walk_clause(true, || self.select.walk_ast(out.reborrow()))?;
+        walk_clause(false, || {
+            self.from.walk_ast(out.reborrow())?;
+            self.where_clause.walk_ast(out.reborrow())?;
+            self.group_by.walk_ast(out.reborrow())?;
+            self.having.walk_ast(out.reborrow())?;
+            self.order.walk_ast(out.reborrow())?;
+            self.limit_offset.walk_ast(out.reborrow())?;
+            self.locking.walk_ast(out.reborrow())
+        })
+    }
+}
+
+impl<'a, ST, QS, GB> QueryFragment<GaussDB, GaussDBSelectStatement>
+    for BoxedSelectStatement<'a, ST, QS, GaussDB, GB>
+where
+    QS: QueryFragment<GaussDB>,
+    BoxedLimitOffsetClause<'a, GaussDB>: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("SELECT ");
+        walk_clause(false, || self.distinct.walk_ast(out.reborrow()))?;
+        walk_clause(true, || self.select.walk_ast(out.reborrow()))?;
+        walk_clause(false, || {
+            self.from.walk_ast(out.reborrow())?;
+            self.where_clause.walk_ast(out.reborrow())?;
+            self.group_by.walk_ast(out.reborrow())?;
+            self.having.walk_ast(out.reborrow())?;
+            if let Some(ref order) = self.order {
+                out.push_sql(" ORDER BY ");
+                order.walk_ast(out.reborrow())?;
+            }
+            self.limit_offset.walk_ast(out.reborrow())
+        })
+    }
+}
+
+// 3. ILike操作符支持
 // 导入我们自己实现的ILike操作符
 // Note: ILike import removed as it's unused in current implementation
 
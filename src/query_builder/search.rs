@@ -0,0 +1,215 @@
+//! Paginated search queries for GaussDB
+//!
+//! Search endpoints usually take a handful of optional filters from a
+//! query string. [`SearchBuilder`] adds a `WHERE` clause for each filter
+//! that is present and nothing for the ones that are not. Every value
+//! given by the user, including the `ILIKE` pattern and the page, is sent
+//! as a bind parameter, so none of it ever ends up in the SQL text.
+
+use crate::backend::GaussDB;
+use crate::expression::expression_methods::{GaussDBStringExpressionMethods, ILike};
+use diesel::dsl::{AsExprOf, GtEq, LtEq};
+use diesel::expression::{AsExpression, Expression};
+use diesel::query_builder::{BoxedSelectStatement, FromClause};
+use diesel::query_dsl::methods::{BoxedDsl, FilterDsl, LimitDsl, OffsetDsl};
+use diesel::query_source::QuerySource;
+use diesel::sql_types::{SqlType, Text};
+use diesel::ExpressionMethods;
+
+type BoxedQuery<'a, ST, QS> = BoxedSelectStatement<'a, ST, FromClause<QS>, GaussDB>;
+
+/// A boxed select statement with optional search filters and a page
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use diesel_gaussdb::prelude::*;
+/// # use diesel_gaussdb::query_builder::SearchBuilder;
+/// # diesel::table! { users (id) { id -> Integer, name -> Text, email -> Text, age -> Nullable<Integer>, } }
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+/// let name: Option<&str> = Some("ann");
+/// let min_age: Option<i32> = None;
+///
+/// // SELECT ... FROM "users" WHERE "users"."name" ILIKE $1
+/// // ORDER BY "users"."id" LIMIT $2 OFFSET $3
+/// let page = SearchBuilder::new(users::table.order(users::id))
+///     .contains(users::name, name)
+///     .range(users::age, min_age, None)
+///     .paginate(2, 20)
+///     .into_query()
+///     .load::<(i32, String, String, Option<i32>)>(&mut conn)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SearchBuilder<'a, ST, QS: QuerySource> {
+    query: BoxedQuery<'a, ST, QS>,
+}
+
+impl<'a, ST, QS: QuerySource> SearchBuilder<'a, ST, QS> {
+    /// Start a search from `query`, usually a table with its select and
+    /// order already applied
+    pub fn new<Q>(query: Q) -> Self
+    where
+        Q: BoxedDsl<'a, GaussDB, Output = BoxedQuery<'a, ST, QS>>,
+    {
+        SearchBuilder {
+            query: query.internal_into_boxed(),
+        }
+    }
+
+    /// Keep rows where the text `column` contains `term`, ignoring case
+    ///
+    /// `%`, `_` and `\` in the term match themselves, see
+    /// [`contains_pattern`]. A `None` or blank term adds no filter.
+    pub fn contains<C>(self, column: C, term: Option<&str>) -> Self
+    where
+        C: Expression<SqlType = Text>,
+        BoxedQuery<'a, ST, QS>:
+            FilterDsl<ILike<C, AsExprOf<String, Text>>, Output = BoxedQuery<'a, ST, QS>>,
+    {
+        self.filter_opt(term.and_then(contains_pattern), |pattern| {
+            column.ilike(pattern)
+        })
+    }
+
+    /// Keep rows where `column` is `= value`, if there is a value
+    pub fn equal_to<C, V>(self, column: C, value: Option<V>) -> Self
+    where
+        C: ExpressionMethods,
+        C::SqlType: SqlType,
+        V: AsExpression<C::SqlType>,
+        BoxedQuery<'a, ST, QS>: FilterDsl<diesel::dsl::Eq<C, V>, Output = BoxedQuery<'a, ST, QS>>,
+    {
+        self.filter_opt(value, |value| column.eq(value))
+    }
+
+    /// Keep rows where `column` is `>= min` and `<= max`
+    ///
+    /// Either bound may be `None` to leave that side of the range open.
+    pub fn range<C, V>(self, column: C, min: Option<V>, max: Option<V>) -> Self
+    where
+        C: ExpressionMethods + Clone,
+        C::SqlType: SqlType,
+        V: AsExpression<C::SqlType>,
+        BoxedQuery<'a, ST, QS>: FilterDsl<GtEq<C, V>, Output = BoxedQuery<'a, ST, QS>>
+            + FilterDsl<LtEq<C, V>, Output = BoxedQuery<'a, ST, QS>>,
+    {
+        let lower = column.clone();
+        self.filter_opt(min, |min| lower.ge(min))
+            .filter_opt(max, |max| column.le(max))
+    }
+
+    /// Add the predicate built from `value`, if there is a value
+    ///
+    /// This is for filters the other methods don't cover, such as one
+    /// term matched against several columns.
+    pub fn filter_opt<V, P>(mut self, value: Option<V>, predicate: impl FnOnce(V) -> P) -> Self
+    where
+        BoxedQuery<'a, ST, QS>: FilterDsl<P, Output = BoxedQuery<'a, ST, QS>>,
+    {
+        if let Some(value) = value {
+            self.query = FilterDsl::filter(self.query, predicate(value));
+        }
+        self
+    }
+
+    /// Return page `page` of `per_page` rows, counting pages from 1
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn paginate(mut self, page: i64, per_page: i64) -> Self {
+        let per_page = per_page.max(1);
+        let offset = (page.max(1) - 1).saturating_mul(per_page);
+        self.query = OffsetDsl::offset(LimitDsl::limit(self.query, per_page), offset);
+        self
+    }
+
+    /// The finished query
+    pub fn into_query(self) -> BoxedQuery<'a, ST, QS> {
+        self.query
+    }
+}
+
+/// The `ILIKE` pattern matching any text that contains `term`
+///
+/// `%`, `_` and `\` are escaped with a backslash, the default escape
+/// character of `LIKE`, so they match themselves. Returns `None` for a
+/// blank term, which would match everything.
+pub fn contains_pattern(term: &str) -> Option<String> {
+    let term = term.trim();
+    if term.is_empty() {
+        return None;
+    }
+
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for c in term.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    Some(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::prelude::*;
+    use diesel::query_builder::{QueryBuilder, QueryFragment};
+
+    diesel::table! {
+        users (id) {
+            id -> Integer,
+            name -> Text,
+            age -> Nullable<Integer>,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(query: &T) -> (String, u32) {
+        let mut query_builder = crate::query_builder::GaussDBQueryBuilder::new();
+        query.to_sql(&mut query_builder, &GaussDB).unwrap();
+        let binds = query_builder.bind_idx();
+        (query_builder.finish(), binds)
+    }
+
+    #[test]
+    fn test_contains_pattern() {
+        assert_eq!(contains_pattern("ann").as_deref(), Some("%ann%"));
+        assert_eq!(contains_pattern(" 100%_\\ ").as_deref(), Some(r"%100\%\_\\%"));
+        assert_eq!(contains_pattern(""), None);
+        assert_eq!(contains_pattern("   "), None);
+    }
+
+    #[test]
+    fn test_absent_filters_add_no_clause() {
+        let query = SearchBuilder::new(users::table.select(users::id))
+            .contains(users::name, None)
+            .contains(users::name, Some(" "))
+            .range(users::age, None::<i32>, None)
+            .equal_to(users::id, None::<i32>)
+            .into_query();
+        let (sql, binds) = generate_sql(&query);
+
+        assert_eq!(sql, r#"SELECT "users"."id" FROM "users""#);
+        assert_eq!(binds, 0);
+    }
+
+    #[test]
+    fn test_filters_are_bound() {
+        let query = SearchBuilder::new(users::table.select(users::id))
+            .contains(users::name, Some("'; DROP TABLE users; --"))
+            .range(users::age, Some(18), None)
+            .paginate(3, 25)
+            .into_query();
+        let (sql, binds) = generate_sql(&query);
+
+        assert_eq!(
+            sql,
+            r#"SELECT "users"."id" FROM "users" WHERE ("users"."name" ILIKE $1 AND ("users"."age" >= $2)) LIMIT $3 OFFSET $4"#
+        );
+        assert_eq!(binds, 4);
+        assert!(!sql.contains("DROP"));
+    }
+}
@@ -0,0 +1,86 @@
+//! Selecting into `Selectable` structs for GaussDB
+//!
+//! `#[derive(Selectable)]` builds the select clause of a struct from its
+//! fields by column name, so the order of the selected columns always
+//! matches the field order the derived `Queryable` expects.
+//! [`SelectByDsl::select_by`] is a shorthand for
+//! `.select(T::as_select())`.
+//!
+//! With `#[diesel(check_for_backend(diesel_gaussdb::GaussDB))]` the derive
+//! also checks at compile time that every field can be loaded from its
+//! column on GaussDB, so a field with the wrong type or nullability does
+//! not compile instead of failing when rows are loaded:
+//!
+//! ```rust,compile_fail
+//! # diesel::table! { users (id) { id -> Integer, name -> Nullable<Text>, } }
+//! #[derive(diesel::Queryable, diesel::Selectable)]
+//! #[diesel(table_name = users)]
+//! #[diesel(check_for_backend(diesel_gaussdb::GaussDB))]
+//! struct User {
+//!     id: i32,
+//!     // `name` is nullable, so this must be `Option<String>`
+//!     name: String,
+//! }
+//! ```
+
+use crate::backend::GaussDB;
+use diesel::dsl::{AsSelect, Select};
+use diesel::expression::Expression;
+use diesel::query_builder::AsQuery;
+use diesel::query_dsl::methods::SelectDsl;
+use diesel::{Selectable, SelectableHelper};
+
+/// Adds [`select_by`](SelectByDsl::select_by) to queries
+pub trait SelectByDsl: Sized {
+    /// Select the columns of the `Selectable` type `T`
+    ///
+    /// `T` may embed other `Selectable` structs with `#[diesel(embed)]` or
+    /// be a tuple of them, e.g. `(Post, User)` for an inner join. For a
+    /// left join, use `Option<User>` for the right side to load `None` when
+    /// no row matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use diesel_gaussdb::prelude::*;
+    /// # use diesel_gaussdb::query_builder::SelectByDsl;
+    /// # diesel::table! { users (id) { id -> Integer, name -> Text, } }
+    /// # diesel::table! { posts (id) { id -> Integer, user_id -> Integer, title -> Text, } }
+    /// # diesel::joinable!(posts -> users (user_id));
+    /// # diesel::allow_tables_to_appear_in_same_query!(users, posts);
+    /// #[derive(Queryable, Selectable)]
+    /// #[diesel(check_for_backend(GaussDB))]
+    /// struct User {
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Queryable, Selectable)]
+    /// #[diesel(check_for_backend(GaussDB))]
+    /// struct Post {
+    ///     id: i32,
+    ///     title: String,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+    /// // SELECT "posts"."id", "posts"."title", "users"."id", "users"."name"
+    /// // FROM ("posts" INNER JOIN "users" ON ...)
+    /// let posts_with_authors = posts::table
+    ///     .inner_join(users::table)
+    ///     .select_by::<(Post, User)>()
+    ///     .load(&mut conn)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn select_by<T>(self) -> Select<Self, AsSelect<T, GaussDB>>
+    where
+        T: Selectable<GaussDB>,
+        AsSelect<T, GaussDB>: Expression,
+        Self: SelectDsl<AsSelect<T, GaussDB>>,
+    {
+        SelectDsl::select(self, T::as_select())
+    }
+}
+
+impl<Q: AsQuery> SelectByDsl for Q {}
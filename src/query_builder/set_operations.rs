@@ -0,0 +1,365 @@
+//! 集合操作支持
+//!
+//! 这个模块提供了对 UNION、INTERSECT、EXCEPT（以及对应的 ALL 变体）的支持。
+//!
+//! diesel 自带的 `CombineDsl` 依赖于 crate 内部私有的 `SupportsCombinationClause`
+//! trait，第三方后端无法为其提供实现，因此这里为 GaussDB 单独实现集合操作。
+//! 每个操作数都会被括号包裹，链式调用时嵌套的集合操作同样会加括号，
+//! 从而使运算优先级在生成的 SQL 中保持显式。
+
+use crate::backend::GaussDB;
+use diesel::query_builder::{AstPass, Query, QueryFragment, QueryId};
+use diesel::result::QueryResult;
+use diesel::sql_types::BigInt;
+use diesel::RunQueryDsl;
+
+/// 集合操作符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOperator {
+    /// UNION（去重）
+    Union,
+    /// UNION ALL
+    UnionAll,
+    /// INTERSECT（去重）
+    Intersect,
+    /// INTERSECT ALL
+    IntersectAll,
+    /// EXCEPT（去重）
+    Except,
+    /// EXCEPT ALL
+    ExceptAll,
+}
+
+impl SetOperator {
+    /// 返回操作符对应的 SQL 关键字
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SetOperator::Union => "UNION",
+            SetOperator::UnionAll => "UNION ALL",
+            SetOperator::Intersect => "INTERSECT",
+            SetOperator::IntersectAll => "INTERSECT ALL",
+            SetOperator::Except => "EXCEPT",
+            SetOperator::ExceptAll => "EXCEPT ALL",
+        }
+    }
+}
+
+/// 未指定 ORDER BY 时使用的占位类型
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoSetOrder;
+
+/// 集合操作查询
+///
+/// 生成形如 `(left) UNION ALL (right) ORDER BY ... LIMIT ... OFFSET ...` 的 SQL，
+/// 其中 ORDER BY、LIMIT 和 OFFSET 作用于整个集合操作的结果。
+#[derive(Debug, Clone)]
+pub struct SetOperation<L, R, O = NoSetOrder> {
+    operator: SetOperator,
+    left: L,
+    right: R,
+    order: O,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl<L, R> SetOperation<L, R> {
+    /// 创建新的集合操作
+    pub fn new(operator: SetOperator, left: L, right: R) -> Self {
+        Self {
+            operator,
+            left,
+            right,
+            order: NoSetOrder,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+impl<L, R, O> SetOperation<L, R, O> {
+    /// 获取集合操作符
+    pub fn operator(&self) -> SetOperator {
+        self.operator
+    }
+
+    /// 为整个集合操作添加 ORDER BY 子句
+    ///
+    /// 排序表达式应当引用结果列名或列序号，例如 `sql::<Integer>("id DESC")` 或 `sql::<Integer>("1")`。
+    pub fn order_by<E>(self, expr: E) -> SetOperation<L, R, E> {
+        SetOperation {
+            operator: self.operator,
+            left: self.left,
+            right: self.right,
+            order: expr,
+            limit: self.limit,
+            offset: self.offset,
+        }
+    }
+
+    /// 为整个集合操作添加 LIMIT 子句
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 为整个集合操作添加 OFFSET 子句
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// ORDER BY 子句的渲染方式
+///
+/// `NoSetOrder` 不输出任何内容，其他表达式输出 ` ORDER BY <expr>`。
+pub trait SetOrderClause {
+    /// 输出 ORDER BY 子句
+    fn walk_order<'b>(&'b self, out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()>;
+}
+
+impl SetOrderClause for NoSetOrder {
+    fn walk_order<'b>(&'b self, _out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        Ok(())
+    }
+}
+
+impl<E> SetOrderClause for E
+where
+    E: diesel::Expression + QueryFragment<GaussDB>,
+{
+    fn walk_order<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql(" ORDER BY ");
+        self.walk_ast(out.reborrow())
+    }
+}
+
+impl<L, R, O> QueryFragment<GaussDB> for SetOperation<L, R, O>
+where
+    L: QueryFragment<GaussDB>,
+    R: QueryFragment<GaussDB>,
+    O: SetOrderClause,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.unsafe_to_cache_prepared();
+        out.push_sql("(");
+        self.left.walk_ast(out.reborrow())?;
+        out.push_sql(") ");
+        out.push_sql(self.operator.as_sql());
+        out.push_sql(" (");
+        self.right.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        self.order.walk_order(out.reborrow())?;
+        if let Some(ref limit) = self.limit {
+            out.push_sql(" LIMIT ");
+            out.push_bind_param::<BigInt, _>(limit)?;
+        }
+        if let Some(ref offset) = self.offset {
+            out.push_sql(" OFFSET ");
+            out.push_bind_param::<BigInt, _>(offset)?;
+        }
+        Ok(())
+    }
+}
+
+impl<L, R, O> QueryId for SetOperation<L, R, O> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<L, R, O> Query for SetOperation<L, R, O>
+where
+    L: Query,
+{
+    type SqlType = L::SqlType;
+}
+
+impl<L, R, O, Conn> RunQueryDsl<Conn> for SetOperation<L, R, O> {}
+
+/// 集合操作 DSL 扩展 trait
+///
+/// 这个 trait 为查询添加了 `union`、`union_all`、`intersect`、`except` 等方法。
+/// 当 diesel 的 `CombineDsl`（位于 `diesel::prelude`）同时在作用域内时，
+/// 对 `SelectStatement` 直接调用方法会产生歧义，此时请使用
+/// `SetOperationDsl::union(a, b)` 的完全限定形式或本模块提供的便捷函数。
+/// 对 `SetOperation` 本身的链式调用不存在歧义。
+pub trait SetOperationDsl: Sized {
+    /// 创建 UNION 集合操作
+    fn union<R>(self, rhs: R) -> SetOperation<Self, R> {
+        SetOperation::new(SetOperator::Union, self, rhs)
+    }
+
+    /// 创建 UNION ALL 集合操作
+    fn union_all<R>(self, rhs: R) -> SetOperation<Self, R> {
+        SetOperation::new(SetOperator::UnionAll, self, rhs)
+    }
+
+    /// 创建 INTERSECT 集合操作
+    fn intersect<R>(self, rhs: R) -> SetOperation<Self, R> {
+        SetOperation::new(SetOperator::Intersect, self, rhs)
+    }
+
+    /// 创建 INTERSECT ALL 集合操作
+    fn intersect_all<R>(self, rhs: R) -> SetOperation<Self, R> {
+        SetOperation::new(SetOperator::IntersectAll, self, rhs)
+    }
+
+    /// 创建 EXCEPT 集合操作
+    fn except<R>(self, rhs: R) -> SetOperation<Self, R> {
+        SetOperation::new(SetOperator::Except, self, rhs)
+    }
+
+    /// 创建 EXCEPT ALL 集合操作
+    fn except_all<R>(self, rhs: R) -> SetOperation<Self, R> {
+        SetOperation::new(SetOperator::ExceptAll, self, rhs)
+    }
+}
+
+impl<T> SetOperationDsl for T where T: QueryFragment<GaussDB> {}
+
+/// 便捷函数：创建 UNION 集合操作
+///
+/// # 示例
+///
+/// ```rust
+/// use diesel::dsl::sql;
+/// use diesel::sql_types::Integer;
+/// use diesel_gaussdb::query_builder::set_operations::*;
+///
+/// let query = union(sql::<Integer>("SELECT 1"), sql::<Integer>("SELECT 2"));
+/// ```
+pub fn union<L, R>(left: L, right: R) -> SetOperation<L, R> {
+    SetOperation::new(SetOperator::Union, left, right)
+}
+
+/// 便捷函数：创建 UNION ALL 集合操作
+///
+/// # 示例
+///
+/// ```rust
+/// use diesel::dsl::sql;
+/// use diesel::sql_types::Integer;
+/// use diesel_gaussdb::query_builder::set_operations::*;
+///
+/// let query = union_all(sql::<Integer>("SELECT 1"), sql::<Integer>("SELECT 2"))
+///     .order_by(sql::<Integer>("1"))
+///     .limit(10);
+/// ```
+pub fn union_all<L, R>(left: L, right: R) -> SetOperation<L, R> {
+    SetOperation::new(SetOperator::UnionAll, left, right)
+}
+
+/// 便捷函数：创建 INTERSECT 集合操作
+pub fn intersect<L, R>(left: L, right: R) -> SetOperation<L, R> {
+    SetOperation::new(SetOperator::Intersect, left, right)
+}
+
+/// 便捷函数：创建 EXCEPT 集合操作
+pub fn except<L, R>(left: L, right: R) -> SetOperation<L, R> {
+    SetOperation::new(SetOperator::Except, left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+
+    diesel::table! {
+        users (id) {
+            id -> Integer,
+            name -> Text,
+        }
+    }
+
+    diesel::table! {
+        admins (id) {
+            id -> Integer,
+            name -> Text,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(fragment: &T) -> String {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        fragment.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    #[test]
+    fn test_set_operator_sql() {
+        assert_eq!(SetOperator::Union.as_sql(), "UNION");
+        assert_eq!(SetOperator::UnionAll.as_sql(), "UNION ALL");
+        assert_eq!(SetOperator::Intersect.as_sql(), "INTERSECT");
+        assert_eq!(SetOperator::IntersectAll.as_sql(), "INTERSECT ALL");
+        assert_eq!(SetOperator::Except.as_sql(), "EXCEPT");
+        assert_eq!(SetOperator::ExceptAll.as_sql(), "EXCEPT ALL");
+    }
+
+    #[test]
+    fn test_union_all_of_two_selects() {
+        let query = union_all(
+            users::table.select(users::name),
+            admins::table.select(admins::name),
+        );
+
+        assert_eq!(
+            generate_sql(&query),
+            "(SELECT \"users\".\"name\" FROM \"users\") UNION ALL \
+             (SELECT \"admins\".\"name\" FROM \"admins\")"
+        );
+    }
+
+    #[test]
+    fn test_set_operation_dsl_methods() {
+        let left = users::table.select(users::id);
+        let right = admins::table.select(admins::id);
+
+        let union_query = SetOperationDsl::union(left, right);
+        assert!(generate_sql(&union_query).contains(") UNION ("));
+
+        let intersect_query = SetOperationDsl::intersect(left, right);
+        assert!(generate_sql(&intersect_query).contains(") INTERSECT ("));
+
+        let except_query = SetOperationDsl::except(left, right);
+        assert!(generate_sql(&except_query).contains(") EXCEPT ("));
+    }
+
+    #[test]
+    fn test_chained_set_operations_are_parenthesized() {
+        let query = union(
+            users::table.select(users::id),
+            admins::table.select(admins::id),
+        )
+        .intersect(users::table.select(users::id).filter(users::id.gt(10)));
+
+        let sql = generate_sql(&query);
+        assert!(sql.starts_with("((SELECT \"users\".\"id\" FROM \"users\") UNION "));
+        assert!(sql.contains("FROM \"admins\")) INTERSECT (SELECT"));
+        assert!(sql.ends_with("WHERE (\"users\".\"id\" > $1))"));
+    }
+
+    #[test]
+    fn test_order_by_and_limit_apply_to_whole_set() {
+        let query = union_all(
+            users::table.select(users::id),
+            admins::table.select(admins::id),
+        )
+        .order_by(diesel::dsl::sql::<diesel::sql_types::Integer>("id DESC"))
+        .limit(5)
+        .offset(10);
+
+        let sql = generate_sql(&query);
+        assert!(sql.ends_with(
+            "(SELECT \"admins\".\"id\" FROM \"admins\") ORDER BY id DESC LIMIT $1 OFFSET $2"
+        ));
+    }
+
+    #[test]
+    fn test_set_operation_debug() {
+        let query = except("SELECT 1", "SELECT 2");
+        let debug_str = format!("{:?}", query);
+        assert!(debug_str.contains("SetOperation"));
+        assert_eq!(query.operator(), SetOperator::Except);
+    }
+}
@@ -0,0 +1,687 @@
+//! UNNEST 支持
+//!
+//! 这个模块提供了类型化的 `unnest` 集合返回函数，既可以作为表达式在 SELECT
+//! 列表中使用，也可以通过别名作为关系出现在 FROM 子句中，并与所属表进行连接。
+//!
+//! GaussDB 不支持多数组并行展开（`unnest(a, b)`），对应的构造会在生成 SQL
+//! 时返回明确的错误，而不是把无法执行的语句发送到服务器。
+//!
+//! [`unnest_insert`] 基于 unnest 实现批量插入：每一列的数据作为一个数组参数绑定，
+//! 无论插入多少行都只需要每列一个参数。配合
+//! [`on_conflict_do_update`](UnnestInsert::on_conflict_do_update) 即为批量 upsert。
+
+use crate::backend::{builtin_type_name, FailedToLookupTypeError, GaussDB, GaussDBMetadataLookup, GaussDBTypeMetadata};
+use crate::query_builder::function_relation::{FunctionRelation, RelationColumn, SetReturningFunction};
+use crate::query_builder::returning::ReturningClause;
+use diesel::expression::{AppearsOnTable, AsExpression, Expression, SelectableExpression, ValidGrouping};
+use diesel::query_builder::{AstPass, Query, QueryFragment, QueryId};
+use diesel::query_dsl::RunQueryDsl;
+use diesel::result::{Error, QueryResult};
+use diesel::serialize::ToSql;
+use diesel::sql_types::{Array, HasSqlType, Nullable, SingleValue};
+use diesel::query_builder::AsChangeset;
+use diesel::{Column, Table};
+use std::fmt;
+
+/// 可以被 `unnest` 展开的数组类型
+///
+/// 为 `Array<ST>` 和 `Nullable<Array<ST>>` 实现，`Element` 为展开后每一行的类型。
+pub trait UnnestableArray {
+    /// 数组元素的 SQL 类型
+    type Element: SingleValue;
+}
+
+impl<ST: SingleValue + 'static> UnnestableArray for Array<ST> {
+    type Element = ST;
+}
+
+impl<ST: SingleValue + 'static> UnnestableArray for Nullable<Array<ST>> {
+    type Element = ST;
+}
+
+/// `unnest(array)` 表达式
+///
+/// 在 SELECT 列表中使用时，每个数组元素产生一行，表达式类型为数组元素类型。
+#[derive(Debug, Clone, Copy, QueryId)]
+pub struct Unnest<E> {
+    array: E,
+}
+
+impl<E> Unnest<E> {
+    /// 创建新的 unnest 表达式
+    pub fn new(array: E) -> Self {
+        Unnest { array }
+    }
+
+    /// 为 unnest 指定表别名和列别名，使其可以作为关系出现在 FROM 子句中
+    ///
+    /// 生成 `unnest(array) AS "alias"("column")`。
+    pub fn alias(self, alias: &str, column: &str) -> UnnestRelation<E> {
+        FunctionRelation::new(self, alias, &[column])
+    }
+}
+
+impl<E> Expression for Unnest<E>
+where
+    E: Expression,
+    E::SqlType: UnnestableArray,
+{
+    type SqlType = <E::SqlType as UnnestableArray>::Element;
+}
+
+impl<E> QueryFragment<GaussDB> for Unnest<E>
+where
+    E: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        out.push_sql("unnest(");
+        self.array.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<E, QS> SelectableExpression<QS> for Unnest<E> where Unnest<E>: AppearsOnTable<QS> {}
+
+impl<E, QS> AppearsOnTable<QS> for Unnest<E>
+where
+    E: AppearsOnTable<QS>,
+    Unnest<E>: Expression,
+{
+}
+
+impl<E, GB> ValidGrouping<GB> for Unnest<E>
+where
+    E: ValidGrouping<GB>,
+{
+    type IsAggregate = E::IsAggregate;
+}
+
+/// 作为 FROM 子句关系使用的 unnest
+///
+/// 渲染为 `unnest(array) AS "alias"("column")`。它是一个 [`QuerySource`](diesel::query_source::QuerySource)，
+/// 可以通过 `as_query()` 单独查询，也可以通过 [`joined_to`](FunctionRelation::joined_to)
+/// 与所属表连接，见 [`function_relation`](crate::query_builder::function_relation)。
+pub type UnnestRelation<E> = FunctionRelation<Unnest<E>>;
+
+/// unnest 关系中展开后的列，类型为数组元素类型 `ST`
+pub type UnnestColumn<E, ST> = RelationColumn<Unnest<E>, ST>;
+
+impl<E> SetReturningFunction for Unnest<E>
+where
+    E: Expression,
+    E::SqlType: UnnestableArray,
+{
+    type DefaultSelection = UnnestColumn<E, <E::SqlType as UnnestableArray>::Element>;
+
+    fn default_selection(relation: &FunctionRelation<Self>) -> Self::DefaultSelection {
+        relation.nth_column()
+    }
+}
+
+impl<E> FunctionRelation<Unnest<E>> {
+    /// 获取展开后的列名
+    pub fn column_name(&self) -> &str {
+        self.column_names().next().unwrap_or_default()
+    }
+
+    /// 引用展开后的列，类型为数组元素类型
+    ///
+    /// 生成 `"alias"."column"`，只能用于 FROM 子句中包含该关系的查询。
+    pub fn column(&self) -> UnnestColumn<E, <E::SqlType as UnnestableArray>::Element>
+    where
+        E: Expression,
+        E::SqlType: UnnestableArray,
+    {
+        self.nth_column()
+    }
+}
+
+/// 多数组并行展开 `unnest(a, b, ...)`
+///
+/// GaussDB 不支持该语法，生成 SQL 时总是返回 `QueryBuilderError`。
+#[derive(Debug, Clone)]
+pub struct ParallelUnnest<T> {
+    arrays: T,
+}
+
+impl<T> QueryFragment<GaussDB> for ParallelUnnest<T> {
+    fn walk_ast<'b>(&'b self, _out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        Err(Error::QueryBuilderError(
+            "GaussDB does not support unnest with multiple arrays; \
+             unnest each array separately and join the results on their ordinality"
+                .into(),
+        ))
+    }
+}
+
+/// 便捷函数：创建类型化的 unnest 表达式
+///
+/// # 示例
+///
+/// ```rust
+/// use diesel::dsl::sql;
+/// use diesel::sql_types::{Array, Text};
+/// use diesel_gaussdb::query_builder::unnest::unnest;
+///
+/// // unnest(ARRAY['a','b']) AS "t"("tag")
+/// let relation = unnest(sql::<Array<Text>>("ARRAY['a','b']")).alias("t", "tag");
+/// let tag = relation.column();
+/// ```
+pub fn unnest<E>(array: E) -> Unnest<E>
+where
+    E: Expression,
+    E::SqlType: UnnestableArray,
+{
+    Unnest::new(array)
+}
+
+/// 便捷函数：创建多数组并行展开
+///
+/// GaussDB 不支持多数组 unnest，返回的片段在生成 SQL 时会报错。
+pub fn parallel_unnest<T>(arrays: T) -> ParallelUnnest<T> {
+    ParallelUnnest { arrays }
+}
+
+impl<T> ParallelUnnest<T> {
+    /// 获取待展开的数组
+    pub fn arrays(&self) -> &T {
+        &self.arrays
+    }
+}
+
+/// 基于 unnest 的批量插入
+///
+/// 生成 `INSERT INTO "t" ("a", "b") SELECT unnest($1::int4[]), unnest($2::text[])`。
+/// 每列的数据作为一个数组参数绑定，因此不受绑定参数数量的限制，对于非常大的批次
+/// 通常比多行 `VALUES` 更快。
+///
+/// 由于 GaussDB 不支持 `unnest(a, b)`，各列在 SELECT 列表中分别展开。长度不同的数组
+/// 在这种写法下会产生错位的行，所以所有列的数组长度必须相同，否则生成 SQL 时返回
+/// `QueryBuilderError`。数组类型的列无法这样插入，因为 `unnest` 会把多维数组完全展开。
+///
+/// # 示例
+///
+/// ```rust
+/// use diesel_gaussdb::query_builder::unnest::unnest_insert;
+///
+/// diesel::table! {
+///     users (id) {
+///         id -> Integer,
+///         name -> Text,
+///     }
+/// }
+///
+/// // INSERT INTO "users" ("id", "name") SELECT unnest($1::int4[]), unnest($2::text[]) RETURNING "users"."id"
+/// let insert = unnest_insert(users::table)
+///     .column(users::id, vec![1, 2, 3])
+///     .column(users::name, vec!["a", "b", "c"])
+///     .returning(users::id);
+/// ```
+pub fn unnest_insert<T: Table>(table: T) -> UnnestInsert<T> {
+    UnnestInsert {
+        table,
+        columns: Vec::new(),
+        on_conflict: None,
+        returning: (),
+    }
+}
+
+/// 基于 unnest 的批量插入语句，由 [`unnest_insert`] 创建
+pub struct UnnestInsert<T, R = ()> {
+    table: T,
+    columns: Vec<UnnestInsertColumn>,
+    on_conflict: Option<UnnestConflict>,
+    returning: R,
+}
+
+/// `ON CONFLICT (...) DO UPDATE SET ...` 子句
+struct UnnestConflict {
+    target: Vec<&'static str>,
+    changes: Box<dyn QueryFragment<GaussDB> + Send>,
+}
+
+/// 可以作为冲突目标的列：单列或同一张表的列组成的元组
+///
+/// `Kind` 区分单列和元组两种实现，调用时由编译器推断。
+pub trait UpsertTarget<T, Kind> {
+    /// 冲突目标的列名
+    fn column_names() -> Vec<&'static str>;
+}
+
+/// 单列冲突目标的 [`UpsertTarget`] 标记
+#[derive(Debug, Clone, Copy)]
+pub struct SingleColumn;
+
+/// 多列冲突目标的 [`UpsertTarget`] 标记
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnTuple;
+
+impl<C: Column> UpsertTarget<C::Table, SingleColumn> for C {
+    fn column_names() -> Vec<&'static str> {
+        vec![C::NAME]
+    }
+}
+
+macro_rules! upsert_target_tuples {
+    ($($T:ident),+) => {
+        impl<Tab, $($T: Column<Table = Tab>),+> UpsertTarget<Tab, ColumnTuple> for ($($T,)+) {
+            fn column_names() -> Vec<&'static str> {
+                vec![$($T::NAME),+]
+            }
+        }
+    };
+}
+
+upsert_target_tuples!(A);
+upsert_target_tuples!(A, B);
+upsert_target_tuples!(A, B, C);
+upsert_target_tuples!(A, B, C, D);
+upsert_target_tuples!(A, B, C, D, E);
+upsert_target_tuples!(A, B, C, D, E, F);
+
+/// 批量插入中的一列：列名、数组元素类型以及绑定的数组
+struct UnnestInsertColumn {
+    name: &'static str,
+    cast: Option<String>,
+    len: usize,
+    values: Box<dyn QueryFragment<GaussDB> + Send>,
+}
+
+impl<T, R> UnnestInsert<T, R> {
+    /// 添加一列及其全部数据
+    ///
+    /// 数据作为 `Array<ST>` 绑定，并在 SQL 中转换为该列类型的数组，
+    /// `ST` 为列的 SQL 类型。
+    pub fn column<C, V>(mut self, _column: C, values: Vec<V>) -> Self
+    where
+        C: Column<Table = T>,
+        C::SqlType: SingleValue + 'static,
+        GaussDB: HasSqlType<C::SqlType>,
+        Vec<V>: AsExpression<Array<C::SqlType>>,
+        <Vec<V> as AsExpression<Array<C::SqlType>>>::Expression: QueryFragment<GaussDB> + Send + 'static,
+        V: ToSql<C::SqlType, GaussDB>,
+    {
+        self.columns.push(UnnestInsertColumn {
+            name: C::NAME,
+            cast: cast_type_name::<C::SqlType>(),
+            len: values.len(),
+            values: Box::new(values.as_expression()),
+        });
+        self
+    }
+
+    /// 获取插入的行数，即第一列的数组长度
+    pub fn row_count(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.len)
+    }
+
+    /// 冲突时更新已有的行，即 `ON CONFLICT (target) DO UPDATE SET changes`
+    ///
+    /// `target` 为唯一约束的列（单列或元组），`changes` 为普通的 Diesel 更新集合，
+    /// 可以通过 [`excluded`](diesel::upsert::excluded) 引用被拒绝插入的那一行的值。
+    /// 执行后返回插入和更新的总行数。
+    ///
+    /// 同一批数据中冲突目标不能重复，否则服务器会拒绝在一条语句中两次更新同一行。
+    ///
+    /// ```rust
+    /// use diesel::prelude::*;
+    /// use diesel::upsert::excluded;
+    /// use diesel_gaussdb::query_builder::unnest::unnest_insert;
+    ///
+    /// diesel::table! {
+    ///     stock (sku) {
+    ///         sku -> Text,
+    ///         quantity -> Integer,
+    ///     }
+    /// }
+    ///
+    /// // INSERT INTO "stock" ("sku", "quantity") SELECT unnest($1::text[]), unnest($2::int4[])
+    /// // ON CONFLICT ("sku") DO UPDATE SET "quantity" = ("stock"."quantity" + excluded."quantity")
+    /// let upsert = unnest_insert(stock::table)
+    ///     .column(stock::sku, vec!["a-1", "b-2"])
+    ///     .column(stock::quantity, vec![5, 7])
+    ///     .on_conflict_do_update(stock::sku, stock::quantity.eq(stock::quantity + excluded(stock::quantity)));
+    /// ```
+    pub fn on_conflict_do_update<Target, Kind, U>(mut self, _target: Target, changes: U) -> Self
+    where
+        Target: UpsertTarget<T, Kind>,
+        U: AsChangeset<Target = T>,
+        U::Changeset: QueryFragment<GaussDB> + Send + 'static,
+    {
+        self.on_conflict = Some(UnnestConflict {
+            target: Target::column_names(),
+            changes: Box::new(changes.as_changeset()),
+        });
+        self
+    }
+}
+
+impl<T> UnnestInsert<T> {
+    /// 添加 `RETURNING` 子句，返回插入的行
+    pub fn returning<E>(self, returning: E) -> UnnestInsert<T, ReturningClause<E>>
+    where
+        E: SelectableExpression<T>,
+    {
+        UnnestInsert {
+            table: self.table,
+            columns: self.columns,
+            on_conflict: self.on_conflict,
+            returning: ReturningClause::new(returning),
+        }
+    }
+}
+
+impl<T, R> fmt::Debug for UnnestInsert<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnnestInsert")
+            .field("columns", &self.columns.iter().map(|c| c.name).collect::<Vec<_>>())
+            .field("row_count", &self.row_count())
+            .field("on_conflict", &self.on_conflict.as_ref().map(|c| &c.target))
+            .finish()
+    }
+}
+
+impl<T, R> QueryFragment<GaussDB> for UnnestInsert<T, R>
+where
+    T: QueryFragment<GaussDB>,
+    R: QueryFragment<GaussDB>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        if self.columns.is_empty() {
+            return Err(Error::QueryBuilderError("unnest insert requires at least one column".into()));
+        }
+        let row_count = self.row_count();
+        if let Some(column) = self.columns.iter().find(|column| column.len != row_count) {
+            return Err(Error::QueryBuilderError(
+                format!(
+                    "unnest insert columns must have the same length: {} has {} values, {} has {}",
+                    self.columns[0].name, row_count, column.name, column.len
+                )
+                .into(),
+            ));
+        }
+
+        out.push_sql("INSERT INTO ");
+        self.table.walk_ast(out.reborrow())?;
+        out.push_sql(" (");
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                out.push_sql(", ");
+            }
+            out.push_identifier(column.name)?;
+        }
+        out.push_sql(") SELECT ");
+        for (i, column) in self.columns.iter().enumerate() {
+            let cast = column.cast.as_deref().ok_or_else(|| {
+                Error::QueryBuilderError(
+                    format!(
+                        "cannot determine the element type of column {} for unnest insert; \
+                         array columns are not supported",
+                        column.name
+                    )
+                    .into(),
+                )
+            })?;
+            if i > 0 {
+                out.push_sql(", ");
+            }
+            out.push_sql("unnest(");
+            column.values.walk_ast(out.reborrow())?;
+            out.push_sql("::");
+            out.push_sql(cast);
+            out.push_sql("[])");
+        }
+        if let Some(conflict) = &self.on_conflict {
+            out.push_sql(" ON CONFLICT (");
+            for (i, column) in conflict.target.iter().enumerate() {
+                if i > 0 {
+                    out.push_sql(", ");
+                }
+                out.push_identifier(column)?;
+            }
+            out.push_sql(") DO UPDATE SET ");
+            conflict.changes.walk_ast(out.reborrow())?;
+        }
+        self.returning.walk_ast(out.reborrow())?;
+        Ok(())
+    }
+}
+
+impl<T, R> QueryId for UnnestInsert<T, R> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T, E> Query for UnnestInsert<T, ReturningClause<E>>
+where
+    E: Expression,
+{
+    type SqlType = E::SqlType;
+}
+
+impl<T, R, Conn> RunQueryDsl<Conn> for UnnestInsert<T, R> {}
+
+/// 记录类型查找请求的 lookup，用于获取自定义类型的名称
+#[derive(Default)]
+struct CastTypeLookup {
+    requested: Option<(String, Option<String>)>,
+}
+
+impl GaussDBMetadataLookup for CastTypeLookup {
+    fn lookup_type(&mut self, type_name: &str, schema: Option<&str>) -> GaussDBTypeMetadata {
+        self.requested = Some((type_name.to_string(), schema.map(str::to_string)));
+        GaussDBTypeMetadata::from_result(Err(FailedToLookupTypeError::new(type_name)))
+    }
+
+    fn as_any<'a>(&mut self) -> &mut (dyn std::any::Any + 'a)
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+/// 获取 SQL 类型 `ST` 在类型转换中使用的名称
+///
+/// 内置类型使用 `pg_type` 中的名称，自定义类型使用其（带模式的）引用名称。
+fn cast_type_name<ST>() -> Option<String>
+where
+    GaussDB: HasSqlType<ST>,
+{
+    let mut lookup = CastTypeLookup::default();
+    let metadata = <GaussDB as HasSqlType<ST>>::metadata(&mut lookup);
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    match lookup.requested {
+        Some((name, Some(schema))) => Some(format!("{}.{}", quote(&schema), quote(&name))),
+        Some((name, None)) => Some(quote(&name)),
+        None => metadata.oid().ok().and_then(builtin_type_name).map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_builder::GaussDBQueryBuilder;
+    use diesel::dsl::sql;
+    use diesel::prelude::*;
+    use diesel::query_builder::QueryBuilder;
+    use diesel::sql_types::{Integer, Text};
+
+    diesel::table! {
+        posts (id) {
+            id -> Integer,
+            tags -> Array<Text>,
+        }
+    }
+
+    crate::allow_function_relations!(posts);
+
+    diesel::table! {
+        items (id) {
+            id -> Integer,
+            name -> Text,
+            score -> Nullable<Integer>,
+        }
+    }
+
+    fn generate_sql<T: QueryFragment<GaussDB>>(fragment: &T) -> QueryResult<String> {
+        let mut query_builder = GaussDBQueryBuilder::new();
+        fragment.to_sql(&mut query_builder, &GaussDB)?;
+        Ok(query_builder.finish())
+    }
+
+    #[test]
+    fn test_unnest_expression_sql() {
+        let expr = unnest(posts::tags);
+        assert_eq!(generate_sql(&expr).unwrap(), "unnest(\"posts\".\"tags\")");
+
+        fn assert_text_expr<T: Expression<SqlType = Text>>(_: T) {}
+        assert_text_expr(unnest(posts::tags));
+    }
+
+    #[test]
+    fn test_unnest_in_select_list() {
+        let query = posts::table.select((posts::id, unnest(posts::tags)));
+        assert_eq!(
+            generate_sql(&query).unwrap(),
+            "SELECT \"posts\".\"id\", unnest(\"posts\".\"tags\") FROM \"posts\""
+        );
+    }
+
+    #[test]
+    fn test_unnest_relation_sql() {
+        let relation = unnest(sql::<Array<Integer>>("ARRAY[1,2,3]")).alias("n", "value");
+        assert_eq!(
+            generate_sql(&relation).unwrap(),
+            "unnest(ARRAY[1,2,3]) AS \"n\"(\"value\")"
+        );
+        assert_eq!(generate_sql(&relation.column()).unwrap(), "\"n\".\"value\"");
+
+        fn assert_integer_expr<T: Expression<SqlType = Integer>>(_: T) {}
+        assert_integer_expr(relation.column());
+    }
+
+    #[test]
+    fn test_unnest_joined_to_owning_table() {
+        let tags = unnest(posts::tags).alias("t", "tag");
+        let tag = tags.column();
+        let query = tags.joined_to(posts::table).select((posts::id, tag));
+        assert_eq!(
+            generate_sql(&query).unwrap(),
+            "SELECT \"posts\".\"id\", \"t\".\"tag\" \
+             FROM (\"posts\" INNER JOIN unnest(\"posts\".\"tags\") AS \"t\"(\"tag\") ON TRUE)"
+        );
+    }
+
+    #[test]
+    fn test_unnest_insert_sql() {
+        let insert = unnest_insert(items::table)
+            .column(items::id, vec![1, 2])
+            .column(items::name, vec!["a", "b"])
+            .column(items::score, vec![Some(10), None]);
+        assert_eq!(
+            generate_sql(&insert).unwrap(),
+            "INSERT INTO \"items\" (\"id\", \"name\", \"score\") \
+             SELECT unnest($1::int4[]), unnest($2::text[]), unnest($3::int4[])"
+        );
+        assert_eq!(insert.row_count(), 2);
+
+        let insert = insert.returning(items::id);
+        assert_eq!(
+            generate_sql(&insert).unwrap(),
+            "INSERT INTO \"items\" (\"id\", \"name\", \"score\") \
+             SELECT unnest($1::int4[]), unnest($2::text[]), unnest($3::int4[]) \
+             RETURNING \"items\".\"id\""
+        );
+    }
+
+    #[test]
+    fn test_unnest_insert_rejects_array_columns() {
+        // unnest flattens multi-dimensional arrays, so there is no element type to cast to
+        let insert = unnest_insert(posts::table).column(posts::tags, vec![vec!["a"]]);
+        assert!(matches!(generate_sql(&insert), Err(Error::QueryBuilderError(_))));
+    }
+
+    #[test]
+    fn test_unnest_insert_requires_equal_lengths() {
+        let insert = unnest_insert(items::table)
+            .column(items::id, vec![1, 2, 3])
+            .column(items::name, vec!["a"]);
+        match generate_sql(&insert) {
+            Err(Error::QueryBuilderError(e)) => {
+                assert!(e.to_string().contains("id has 3 values, name has 1"), "{}", e);
+            }
+            other => panic!("expected QueryBuilderError, got {:?}", other),
+        }
+
+        let empty = unnest_insert(items::table);
+        assert!(matches!(generate_sql(&empty), Err(Error::QueryBuilderError(_))));
+    }
+
+    #[test]
+    fn test_unnest_upsert_sql() {
+        use diesel::upsert::excluded;
+
+        let upsert = unnest_insert(items::table)
+            .column(items::id, vec![1, 2])
+            .column(items::name, vec!["a", "b"])
+            .on_conflict_do_update(items::id, (items::name.eq(excluded(items::name)), items::score.eq(items::score + 1)));
+        assert_eq!(
+            generate_sql(&upsert).unwrap(),
+            "INSERT INTO \"items\" (\"id\", \"name\") \
+             SELECT unnest($1::int4[]), unnest($2::text[]) \
+             ON CONFLICT (\"id\") DO UPDATE SET \"name\" = excluded.\"name\", \"score\" = (\"items\".\"score\" + $3)"
+        );
+
+        let upsert = unnest_insert(items::table)
+            .column(items::id, vec![1])
+            .column(items::name, vec!["a"])
+            .on_conflict_do_update((items::id, items::name), items::score.eq(excluded(items::score)))
+            .returning(items::id);
+        assert_eq!(
+            generate_sql(&upsert).unwrap(),
+            "INSERT INTO \"items\" (\"id\", \"name\") \
+             SELECT unnest($1::int4[]), unnest($2::text[]) \
+             ON CONFLICT (\"id\", \"name\") DO UPDATE SET \"score\" = excluded.\"score\" \
+             RETURNING \"items\".\"id\""
+        );
+    }
+
+    #[test]
+    fn test_unnest_upsert_requires_equal_lengths() {
+        let upsert = unnest_insert(items::table)
+            .column(items::id, vec![1, 2])
+            .column(items::name, vec!["a"])
+            .on_conflict_do_update(items::id, items::name.eq("x"));
+        assert!(matches!(generate_sql(&upsert), Err(Error::QueryBuilderError(_))));
+    }
+
+    #[test]
+    fn test_cast_type_name() {
+        #[derive(diesel::sql_types::SqlType)]
+        struct Mood;
+
+        impl HasSqlType<Mood> for GaussDB {
+            fn metadata(lookup: &mut (dyn GaussDBMetadataLookup + 'static)) -> GaussDBTypeMetadata {
+                lookup.lookup_type("mood", Some("app"))
+            }
+        }
+
+        assert_eq!(cast_type_name::<Integer>().as_deref(), Some("int4"));
+        assert_eq!(cast_type_name::<Nullable<Text>>().as_deref(), Some("text"));
+        assert_eq!(cast_type_name::<Mood>().as_deref(), Some("\"app\".\"mood\""));
+    }
+
+    #[test]
+    fn test_parallel_unnest_errors() {
+        let fragment = parallel_unnest((posts::tags, posts::tags));
+        match generate_sql(&fragment) {
+            Err(Error::QueryBuilderError(e)) => {
+                assert!(e.to_string().contains("multiple arrays"));
+            }
+            other => panic!("expected QueryBuilderError, got {:?}", other),
+        }
+    }
+}
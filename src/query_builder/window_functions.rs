@@ -69,13 +69,15 @@ where
 
 /// OVER 子句构建器
 /// 
-/// 用于构建窗口函数的 OVER 子句，支持 PARTITION BY 和 ORDER BY
+/// 用于构建窗口函数的 OVER 子句，支持 PARTITION BY、ORDER BY 和窗口帧
 #[derive(Debug, Clone, QueryId)]
 pub struct OverClause<P, O> {
     /// PARTITION BY 表达式
     partition_by: Option<P>,
     /// ORDER BY 表达式
     order_by: Option<O>,
+    /// 窗口帧定义
+    frame: Option<WindowFrame>,
 }
 
 impl OverClause<(), ()> {
@@ -93,6 +95,7 @@ impl OverClause<(), ()> {
         OverClause {
             partition_by: None,
             order_by: None,
+            frame: None,
         }
     }
 }
@@ -116,6 +119,7 @@ impl<P, O> OverClause<P, O> {
         OverClause {
             partition_by: Some(expr),
             order_by: self.order_by,
+            frame: self.frame,
         }
     }
 
@@ -137,6 +141,30 @@ impl<P, O> OverClause<P, O> {
         OverClause {
             partition_by: self.partition_by,
             order_by: Some(expr),
+            frame: self.frame,
+        }
+    }
+
+    /// 设置窗口帧
+    /// 
+    /// 窗口帧写在 ORDER BY 之后，决定聚合函数在每一行上看到哪些行。
+    /// 
+    /// # 示例
+    /// 
+    /// ```rust
+    /// use diesel_gaussdb::query_builder::window_functions::*;
+    /// use diesel::sql_types::Date;
+    /// 
+    /// // OVER (ORDER BY day ROWS BETWEEN 3 PRECEDING AND CURRENT ROW)
+    /// let over = OverClause::new()
+    ///     .order_by(diesel::dsl::sql::<Date>("day"))
+    ///     .frame(WindowFrame::rows_between(FrameBound::Preceding(3), FrameBound::CurrentRow));
+    /// ```
+    pub fn frame(self, frame: WindowFrame) -> OverClause<P, O> {
+        OverClause {
+            partition_by: self.partition_by,
+            order_by: self.order_by,
+            frame: Some(frame),
         }
     }
 }
@@ -148,19 +176,28 @@ where
 {
     fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
         pass.push_sql("(");
+        let mut needs_space = false;
         
         if let Some(ref partition) = self.partition_by {
             pass.push_sql("PARTITION BY ");
             partition.walk_ast(pass.reborrow())?;
-            
-            if self.order_by.is_some() {
-                pass.push_sql(" ");
-            }
+            needs_space = true;
         }
         
         if let Some(ref order) = self.order_by {
+            if needs_space {
+                pass.push_sql(" ");
+            }
             pass.push_sql("ORDER BY ");
             order.walk_ast(pass.reborrow())?;
+            needs_space = true;
+        }
+
+        if let Some(ref frame) = self.frame {
+            if needs_space {
+                pass.push_sql(" ");
+            }
+            frame.walk_ast(pass.reborrow())?;
         }
         
         pass.push_sql(")");
@@ -168,6 +205,151 @@ where
     }
 }
 
+/// 窗口帧的单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameUnit {
+    /// `ROWS`：按物理行计数
+    Rows,
+    /// `RANGE`：按 ORDER BY 的值划分，值相同的行属于同一组
+    Range,
+}
+
+/// 窗口帧的边界
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameBound {
+    /// `UNBOUNDED PRECEDING`
+    UnboundedPreceding,
+    /// `n PRECEDING`
+    Preceding(u64),
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `n FOLLOWING`
+    Following(u64),
+    /// `UNBOUNDED FOLLOWING`
+    UnboundedFollowing,
+}
+
+/// 窗口帧的 EXCLUDE 子句
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameExclusion {
+    /// `EXCLUDE CURRENT ROW`：排除当前行
+    CurrentRow,
+    /// `EXCLUDE GROUP`：排除当前行及其所有同值行
+    Group,
+    /// `EXCLUDE TIES`：只排除当前行的同值行，保留当前行
+    Ties,
+    /// `EXCLUDE NO OTHERS`：不排除任何行，即默认行为
+    NoOthers,
+}
+
+/// 窗口帧定义
+/// 
+/// 生成 `ROWS`/`RANGE` 帧子句，可选地带有 EXCLUDE 子句。边界偏移量直接
+/// 写入 SQL，因此包含窗口帧的查询不会缓存预处理语句。
+/// 
+/// EXCLUDE 子句需要服务端支持，可以通过
+/// [`GaussDBFeature::WindowFrameExclude`](crate::connection::version::GaussDBFeature::WindowFrameExclude)
+/// 检查。
+/// 
+/// # 示例
+/// 
+/// ```rust
+/// use diesel_gaussdb::query_builder::window_functions::*;
+/// 
+/// // ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING EXCLUDE CURRENT ROW
+/// let frame = WindowFrame::rows_between(FrameBound::Preceding(1), FrameBound::Following(1))
+///     .exclude(FrameExclusion::CurrentRow);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowFrame {
+    unit: FrameUnit,
+    start: FrameBound,
+    end: Option<FrameBound>,
+    exclusion: Option<FrameExclusion>,
+}
+
+impl WindowFrame {
+    /// 创建 `ROWS start` 窗口帧，结束边界为当前行
+    pub fn rows(start: FrameBound) -> Self {
+        Self::new(FrameUnit::Rows, start, None)
+    }
+
+    /// 创建 `ROWS BETWEEN start AND end` 窗口帧
+    pub fn rows_between(start: FrameBound, end: FrameBound) -> Self {
+        Self::new(FrameUnit::Rows, start, Some(end))
+    }
+
+    /// 创建 `RANGE start` 窗口帧，结束边界为当前行
+    pub fn range(start: FrameBound) -> Self {
+        Self::new(FrameUnit::Range, start, None)
+    }
+
+    /// 创建 `RANGE BETWEEN start AND end` 窗口帧
+    pub fn range_between(start: FrameBound, end: FrameBound) -> Self {
+        Self::new(FrameUnit::Range, start, Some(end))
+    }
+
+    fn new(unit: FrameUnit, start: FrameBound, end: Option<FrameBound>) -> Self {
+        WindowFrame {
+            unit,
+            start,
+            end,
+            exclusion: None,
+        }
+    }
+
+    /// 添加 EXCLUDE 子句
+    pub fn exclude(self, exclusion: FrameExclusion) -> Self {
+        WindowFrame {
+            exclusion: Some(exclusion),
+            ..self
+        }
+    }
+}
+
+impl FrameBound {
+    fn to_sql(self) -> String {
+        match self {
+            FrameBound::UnboundedPreceding => "UNBOUNDED PRECEDING".to_string(),
+            FrameBound::Preceding(offset) => format!("{} PRECEDING", offset),
+            FrameBound::CurrentRow => "CURRENT ROW".to_string(),
+            FrameBound::Following(offset) => format!("{} FOLLOWING", offset),
+            FrameBound::UnboundedFollowing => "UNBOUNDED FOLLOWING".to_string(),
+        }
+    }
+}
+
+impl QueryFragment<GaussDB> for WindowFrame {
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, GaussDB>) -> QueryResult<()> {
+        // 偏移量不属于查询类型的一部分
+        pass.unsafe_to_cache_prepared();
+
+        pass.push_sql(match self.unit {
+            FrameUnit::Rows => "ROWS ",
+            FrameUnit::Range => "RANGE ",
+        });
+        match self.end {
+            Some(end) => {
+                pass.push_sql("BETWEEN ");
+                pass.push_sql(&self.start.to_sql());
+                pass.push_sql(" AND ");
+                pass.push_sql(&end.to_sql());
+            }
+            None => pass.push_sql(&self.start.to_sql()),
+        }
+
+        if let Some(exclusion) = self.exclusion {
+            pass.push_sql(match exclusion {
+                FrameExclusion::CurrentRow => " EXCLUDE CURRENT ROW",
+                FrameExclusion::Group => " EXCLUDE GROUP",
+                FrameExclusion::Ties => " EXCLUDE TIES",
+                FrameExclusion::NoOthers => " EXCLUDE NO OTHERS",
+            });
+        }
+        Ok(())
+    }
+}
+
 /// 常用窗口函数定义
 pub mod functions {
     use super::*;
@@ -354,6 +536,78 @@ mod tests {
         // OVER 子句构建器测试通过
     }
 
+    fn generate_sql<T: QueryFragment<GaussDB>>(fragment: &T) -> String {
+        use crate::query_builder::GaussDBQueryBuilder;
+        use diesel::query_builder::QueryBuilder;
+
+        let mut query_builder = GaussDBQueryBuilder::new();
+        fragment.to_sql(&mut query_builder, &GaussDB).unwrap();
+        query_builder.finish()
+    }
+
+    diesel::table! {
+        scores (id) {
+            id -> Integer,
+            player -> Integer,
+            points -> Integer,
+        }
+    }
+
+    #[test]
+    fn test_rows_frame_exclude_current_row_sql() {
+        // 测试 ROWS 窗口帧与 EXCLUDE CURRENT ROW
+        let window_fn = WindowFunction::new(
+            count(scores::points),
+            over().order_by(scores::id).frame(
+                WindowFrame::rows_between(FrameBound::Preceding(1), FrameBound::Following(1))
+                    .exclude(FrameExclusion::CurrentRow),
+            ),
+        );
+
+        assert_eq!(
+            generate_sql(&window_fn),
+            r#"COUNT("scores"."points") OVER (ORDER BY "scores"."id" ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING EXCLUDE CURRENT ROW)"#
+        );
+    }
+
+    #[test]
+    fn test_range_frame_exclusions_sql() {
+        // 测试 RANGE 窗口帧与各种 EXCLUDE 子句
+        let frame = WindowFrame::range_between(FrameBound::UnboundedPreceding, FrameBound::UnboundedFollowing);
+        let cases = [
+            (FrameExclusion::Group, "EXCLUDE GROUP"),
+            (FrameExclusion::Ties, "EXCLUDE TIES"),
+            (FrameExclusion::NoOthers, "EXCLUDE NO OTHERS"),
+        ];
+
+        for (exclusion, expected) in cases {
+            let over_clause = over()
+                .partition_by(scores::player)
+                .order_by(scores::points)
+                .frame(frame.exclude(exclusion));
+            assert_eq!(
+                generate_sql(&over_clause),
+                format!(
+                    r#"(PARTITION BY "scores"."player" ORDER BY "scores"."points" RANGE BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING {})"#,
+                    expected
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_frame_without_between_sql() {
+        // 测试只有起始边界的窗口帧
+        assert_eq!(
+            generate_sql(&over().frame(WindowFrame::rows(FrameBound::UnboundedPreceding))),
+            "(ROWS UNBOUNDED PRECEDING)"
+        );
+        assert_eq!(
+            generate_sql(&over().partition_by(scores::player)),
+            r#"(PARTITION BY "scores"."player")"#
+        );
+    }
+
     #[test]
     fn test_window_count() {
         // 测试 COUNT 窗口函数
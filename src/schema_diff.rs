@@ -0,0 +1,282 @@
+//! Schema comparison against a live GaussDB database
+//!
+//! This module checks that the tables and columns an application expects,
+//! e.g. from its Diesel `table!` definitions, exist in the database with
+//! matching types. It is built on the `information_schema` helpers in
+//! [`metadata_lookup`](crate::metadata_lookup).
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use diesel_gaussdb::prelude::*;
+//! # use diesel_gaussdb::schema_diff::{diff_schema, ExpectedTable};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! #     let mut conn = GaussDBConnection::establish("gaussdb://localhost/test")?;
+//! let expected = [ExpectedTable::new("users")
+//!     .column("id", "Integer")
+//!     .column("name", "Varchar")
+//!     .column("created_at", "Timestamptz")];
+//!
+//! let diff = diff_schema(&mut conn, &expected, None)?;
+//! assert!(diff.is_empty(), "schema drift: {:?}", diff);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::connection::GaussDBConnection;
+use crate::metadata_lookup::{get_table_columns, table_exists, ColumnInfo};
+use diesel::result::QueryResult;
+
+/// A table the application expects to exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedTable {
+    /// Name of the table
+    pub name: String,
+    /// Columns of the table
+    pub columns: Vec<ExpectedColumn>,
+}
+
+impl ExpectedTable {
+    /// Create an expected table without columns
+    pub fn new(name: impl Into<String>) -> Self {
+        ExpectedTable {
+            name: name.into(),
+            columns: Vec::new(),
+        }
+    }
+
+    /// Add an expected column
+    ///
+    /// `sql_type` may be a GaussDB type name (`character varying`,
+    /// `varchar(255)`, `int4`) or a Diesel SQL type name (`Varchar`,
+    /// `Nullable<Integer>`); see [`normalize_type_name`].
+    pub fn column(mut self, name: impl Into<String>, sql_type: impl Into<String>) -> Self {
+        self.columns.push(ExpectedColumn {
+            name: name.into(),
+            sql_type: sql_type.into(),
+        });
+        self
+    }
+}
+
+/// A column the application expects to exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedColumn {
+    /// Name of the column
+    pub name: String,
+    /// Expected SQL type of the column
+    pub sql_type: String,
+}
+
+/// A column identified by its table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnRef {
+    /// Name of the table
+    pub table: String,
+    /// Name of the column
+    pub column: String,
+}
+
+/// A column whose type differs from the expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeMismatch {
+    /// The affected column
+    pub column: ColumnRef,
+    /// The expected type, normalized
+    pub expected: String,
+    /// The type found in the database, normalized
+    pub actual: String,
+}
+
+/// Differences between the expected and the live schema
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    /// Expected tables that don't exist
+    pub missing_tables: Vec<String>,
+    /// Expected columns that don't exist in an existing table
+    pub missing_columns: Vec<ColumnRef>,
+    /// Columns of an expected table that were not expected
+    pub extra_columns: Vec<ColumnRef>,
+    /// Columns with a different type than expected
+    pub type_mismatches: Vec<TypeMismatch>,
+}
+
+impl SchemaDiff {
+    /// Whether the live schema matches the expected one
+    pub fn is_empty(&self) -> bool {
+        self.missing_tables.is_empty()
+            && self.missing_columns.is_empty()
+            && self.extra_columns.is_empty()
+            && self.type_mismatches.is_empty()
+    }
+}
+
+/// Compare the expected tables with the live schema
+///
+/// `schema` defaults to `public`. Only the listed tables are inspected;
+/// other tables in the database are ignored.
+pub fn diff_schema(
+    conn: &mut GaussDBConnection,
+    expected: &[ExpectedTable],
+    schema: Option<&str>,
+) -> QueryResult<SchemaDiff> {
+    let mut diff = SchemaDiff::default();
+
+    for table in expected {
+        if !table_exists(conn, &table.name, schema)? {
+            diff.missing_tables.push(table.name.clone());
+            continue;
+        }
+        let columns = get_table_columns(conn, &table.name, schema)?;
+        diff_table(table, &columns, &mut diff);
+    }
+
+    Ok(diff)
+}
+
+/// Compare one existing table with its columns from `information_schema`
+fn diff_table(expected: &ExpectedTable, actual: &[ColumnInfo], diff: &mut SchemaDiff) {
+    let column_ref = |column: &str| ColumnRef {
+        table: expected.name.clone(),
+        column: column.to_string(),
+    };
+
+    for column in &expected.columns {
+        match actual.iter().find(|c| c.column_name == column.name) {
+            None => diff.missing_columns.push(column_ref(&column.name)),
+            Some(found) => {
+                let expected_type = normalize_type_name(&column.sql_type);
+                let actual_type = normalize_type_name(&found.data_type);
+                // Enums and other custom types can't be compared by name
+                if actual_type != "user-defined" && expected_type != actual_type {
+                    diff.type_mismatches.push(TypeMismatch {
+                        column: column_ref(&column.name),
+                        expected: expected_type,
+                        actual: actual_type,
+                    });
+                }
+            }
+        }
+    }
+
+    for found in actual {
+        if !expected.columns.iter().any(|c| c.name == found.column_name) {
+            diff.extra_columns.push(column_ref(&found.column_name));
+        }
+    }
+}
+
+/// Normalize a type name so that spellings of the same type compare equal
+///
+/// Accepts the names reported by `information_schema.columns.data_type`,
+/// `pg_type` names and Diesel SQL type names, and maps them to the `pg_type`
+/// name, e.g. `character varying`, `varchar(255)` and `Varchar` all become
+/// `varchar`. Length and precision modifiers and a Diesel `Nullable<..>`
+/// wrapper are dropped. Arrays normalize to `array`, as
+/// `information_schema` doesn't report their element type.
+pub fn normalize_type_name(type_name: &str) -> String {
+    let mut name = type_name.trim().to_lowercase();
+
+    if let Some(inner) = name.strip_prefix("nullable<").and_then(|n| n.strip_suffix('>')) {
+        name = inner.trim().to_string();
+    }
+    if name.ends_with("[]") || name.starts_with("array<") || name.starts_with('_') {
+        return "array".to_string();
+    }
+
+    // `timestamp(3) with time zone` keeps its suffix once the modifier is gone
+    if let Some(start) = name.find('(') {
+        let end = name[start..].find(')').map_or(name.len(), |end| start + end + 1);
+        name.replace_range(start..end, "");
+    }
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let normalized = match name.as_str() {
+        "smallint" | "int2" | "smallserial" => "int2",
+        "integer" | "int" | "int4" | "serial" => "int4",
+        "bigint" | "int8" | "bigserial" => "int8",
+        "real" | "float" | "float4" => "float4",
+        "double precision" | "double" | "float8" => "float8",
+        "numeric" | "decimal" => "numeric",
+        "boolean" | "bool" => "bool",
+        "character varying" | "varchar" => "varchar",
+        "character" | "char" | "bpchar" => "bpchar",
+        "binary" | "bytea" => "bytea",
+        "timestamp without time zone" | "timestamp" => "timestamp",
+        "timestamp with time zone" | "timestamptz" => "timestamptz",
+        "time without time zone" | "time" => "time",
+        "time with time zone" | "timetz" => "timetz",
+        other => other,
+    };
+    normalized.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str) -> ColumnInfo {
+        ColumnInfo {
+            column_name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable: true,
+            ordinal_position: 0,
+            column_default: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_type_name_synonyms() {
+        assert_eq!(normalize_type_name("character varying"), "varchar");
+        assert_eq!(normalize_type_name("varchar(255)"), "varchar");
+        assert_eq!(normalize_type_name("Varchar"), "varchar");
+        assert_eq!(normalize_type_name("integer"), "int4");
+        assert_eq!(normalize_type_name("Nullable<Integer>"), "int4");
+        assert_eq!(normalize_type_name("double precision"), "float8");
+        assert_eq!(normalize_type_name("Double"), "float8");
+        assert_eq!(normalize_type_name("numeric(10,2)"), "numeric");
+        assert_eq!(normalize_type_name("timestamp(3) with time zone"), "timestamptz");
+        assert_eq!(normalize_type_name("Timestamptz"), "timestamptz");
+        assert_eq!(normalize_type_name("ARRAY"), "array");
+        assert_eq!(normalize_type_name("Array<Text>"), "array");
+        assert_eq!(normalize_type_name("_int4"), "array");
+    }
+
+    #[test]
+    fn test_diff_table_matching_spellings() {
+        let expected = ExpectedTable::new("users")
+            .column("id", "Integer")
+            .column("name", "varchar(100)");
+        let actual = [column("id", "integer"), column("name", "character varying")];
+
+        let mut diff = SchemaDiff::default();
+        diff_table(&expected, &actual, &mut diff);
+        assert!(diff.is_empty(), "{:?}", diff);
+    }
+
+    #[test]
+    fn test_diff_table_reports_differences() {
+        let expected = ExpectedTable::new("users")
+            .column("id", "BigInt")
+            .column("email", "Text");
+        let actual = [column("id", "integer"), column("legacy_flag", "boolean")];
+
+        let mut diff = SchemaDiff::default();
+        diff_table(&expected, &actual, &mut diff);
+
+        let column_ref = |column: &str| ColumnRef {
+            table: "users".to_string(),
+            column: column.to_string(),
+        };
+        assert_eq!(diff.missing_columns, vec![column_ref("email")]);
+        assert_eq!(diff.extra_columns, vec![column_ref("legacy_flag")]);
+        assert_eq!(
+            diff.type_mismatches,
+            vec![TypeMismatch {
+                column: column_ref("id"),
+                expected: "int8".to_string(),
+                actual: "int4".to_string(),
+            }]
+        );
+    }
+}
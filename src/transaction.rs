@@ -8,6 +8,7 @@ use diesel::connection::{AnsiTransactionManager, TransactionManager};
 use diesel::prelude::*;
 use diesel::query_builder::{AstPass, QueryBuilder, QueryFragment};
 use diesel::result::Error;
+use std::panic::{self, AssertUnwindSafe};
 
 /// Used to build a transaction, specifying additional details.
 ///
@@ -22,9 +23,7 @@ use diesel::result::Error;
 #[must_use = "Transaction builder does nothing unless you call `run` on it"]
 pub struct TransactionBuilder<'a, C> {
     connection: &'a mut C,
-    isolation_level: Option<IsolationLevel>,
-    read_mode: Option<ReadMode>,
-    deferrable: Option<Deferrable>,
+    begin: BeginTransaction,
 }
 
 impl<'a, C> TransactionBuilder<'a, C>
@@ -35,9 +34,7 @@ where
     pub(crate) fn new(connection: &'a mut C) -> Self {
         Self {
             connection,
-            isolation_level: None,
-            read_mode: None,
-            deferrable: None,
+            begin: BeginTransaction::default(),
         }
     }
 
@@ -59,7 +56,7 @@ where
     /// # }
     /// ```
     pub fn read_only(mut self) -> Self {
-        self.read_mode = Some(ReadMode::ReadOnly);
+        self.begin.read_mode = Some(ReadMode::ReadOnly);
         self
     }
 
@@ -82,7 +79,7 @@ where
     /// # }
     /// ```
     pub fn read_write(mut self) -> Self {
-        self.read_mode = Some(ReadMode::ReadWrite);
+        self.begin.read_mode = Some(ReadMode::ReadWrite);
         self
     }
 
@@ -99,7 +96,7 @@ where
     /// # }
     /// ```
     pub fn deferrable(mut self) -> Self {
-        self.deferrable = Some(Deferrable::Deferrable);
+        self.begin.deferrable = Some(Deferrable::Deferrable);
         self
     }
 
@@ -119,7 +116,7 @@ where
     /// # }
     /// ```
     pub fn not_deferrable(mut self) -> Self {
-        self.deferrable = Some(Deferrable::NotDeferrable);
+        self.begin.deferrable = Some(Deferrable::NotDeferrable);
         self
     }
 
@@ -139,7 +136,7 @@ where
     /// # }
     /// ```
     pub fn read_committed(mut self) -> Self {
-        self.isolation_level = Some(IsolationLevel::ReadCommitted);
+        self.begin.isolation_level = Some(IsolationLevel::ReadCommitted);
         self
     }
 
@@ -158,7 +155,7 @@ where
     /// # }
     /// ```
     pub fn repeatable_read(mut self) -> Self {
-        self.isolation_level = Some(IsolationLevel::RepeatableRead);
+        self.begin.isolation_level = Some(IsolationLevel::RepeatableRead);
         self
     }
 
@@ -175,7 +172,17 @@ where
     /// # }
     /// ```
     pub fn serializable(mut self) -> Self {
-        self.isolation_level = Some(IsolationLevel::Serializable);
+        self.begin.isolation_level = Some(IsolationLevel::Serializable);
+        self
+    }
+
+    /// Sets the isolation level of the transaction
+    ///
+    /// The same as calling [`read_committed`](Self::read_committed),
+    /// [`repeatable_read`](Self::repeatable_read) or
+    /// [`serializable`](Self::serializable), for a level chosen at runtime.
+    pub fn isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.begin.isolation_level = Some(level);
         self
     }
 
@@ -194,6 +201,8 @@ where
     /// [`Error::RollbackErrorOnCommit`](diesel::result::Error::RollbackErrorOnCommit),
     /// from which you will be able to extract both the original commit error and
     /// the rollback error.
+    /// If the closure panics, the transaction is rolled back before the
+    /// panic is resumed.
     /// In addition, the connection will be considered broken
     /// as it contains a uncommitted unabortable open transaction. Any further
     /// interaction with the transaction system will result in an returned error
@@ -207,28 +216,95 @@ where
         self.to_sql(&mut query_builder, &GaussDB)?;
         let sql = query_builder.finish();
 
-        AnsiTransactionManager::begin_transaction_sql(&mut *self.connection, &sql)?;
-        match f(&mut *self.connection) {
-            Ok(value) => {
-                AnsiTransactionManager::commit_transaction(&mut *self.connection)?;
-                Ok(value)
-            }
-            Err(user_error) => {
-                match AnsiTransactionManager::rollback_transaction(&mut *self.connection) {
-                    Ok(()) => Err(user_error),
-                    Err(Error::BrokenTransactionManager) => {
-                        // In this case we are probably more interested by the
-                        // original error, which likely caused this
-                        Err(user_error)
-                    }
-                    Err(rollback_error) => Err(rollback_error.into()),
+        run_transaction(
+            &mut *self.connection,
+            |conn| AnsiTransactionManager::begin_transaction_sql(conn, &sql),
+            f,
+        )
+    }
+}
+
+/// Run `f` after `begin`, committing if it returns `Ok` and rolling back
+/// if it returns `Err` or panics
+///
+/// `begin` has to open one transaction level, a transaction or a
+/// savepoint. A savepoint that was rolled back is released afterwards, so
+/// no savepoint outlives the call.
+pub(crate) fn run_transaction<C, T, E, F>(
+    connection: &mut C,
+    begin: impl FnOnce(&mut C) -> diesel::QueryResult<()>,
+    f: F,
+) -> Result<T, E>
+where
+    C: Connection<Backend = GaussDB, TransactionManager = AnsiTransactionManager>,
+    F: FnOnce(&mut C) -> Result<T, E>,
+    E: From<Error>,
+{
+    let outer_depth = match AnsiTransactionManager::transaction_manager_status_mut(connection).transaction_depth() {
+        Ok(depth) => depth.map_or(0, |depth| depth.get()),
+        Err(_) => return Err(Error::BrokenTransactionManager.into()),
+    };
+
+    begin(connection)?;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| f(&mut *connection)));
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => {
+            // The panic is what the caller needs to see, not a failed rollback
+            let _ = rollback(connection, outer_depth);
+            panic::resume_unwind(payload);
+        }
+    };
+    match result {
+        Ok(value) => {
+            AnsiTransactionManager::commit_transaction(connection)?;
+            Ok(value)
+        }
+        Err(user_error) => {
+            match rollback(connection, outer_depth) {
+                Ok(()) => Err(user_error),
+                Err(Error::BrokenTransactionManager) => {
+                    // In this case we are probably more interested by the
+                    // original error, which likely caused this
+                    Err(user_error)
                 }
+                Err(rollback_error) => Err(rollback_error.into()),
             }
         }
     }
 }
 
+/// Roll back the level opened at `outer_depth` and release its savepoint
+///
+/// `ROLLBACK TO SAVEPOINT` keeps the savepoint, it would stay around until
+/// the outer transaction ends.
+fn rollback<C>(connection: &mut C, outer_depth: u32) -> diesel::QueryResult<()>
+where
+    C: Connection<Backend = GaussDB, TransactionManager = AnsiTransactionManager>,
+{
+    AnsiTransactionManager::rollback_transaction(connection)?;
+    if outer_depth > 0 {
+        // Named like the savepoints of `AnsiTransactionManager`
+        connection.batch_execute(&format!("RELEASE SAVEPOINT diesel_savepoint_{}", outer_depth))?;
+    }
+    Ok(())
+}
+
 impl<C> QueryFragment<GaussDB> for TransactionBuilder<'_, C> {
+    fn walk_ast<'b>(&'b self, out: AstPass<'_, 'b, GaussDB>) -> diesel::QueryResult<()> {
+        self.begin.walk_ast(out)
+    }
+}
+
+/// The `BEGIN TRANSACTION` statement built by a [`TransactionBuilder`]
+#[derive(Debug, Clone, Copy, Default)]
+struct BeginTransaction {
+    isolation_level: Option<IsolationLevel>,
+    read_mode: Option<ReadMode>,
+    deferrable: Option<Deferrable>,
+}
+
+impl QueryFragment<GaussDB> for BeginTransaction {
     fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> diesel::QueryResult<()> {
         out.push_sql("BEGIN TRANSACTION");
         if let Some(ref isolation_level) = self.isolation_level {
@@ -244,10 +320,19 @@ impl<C> QueryFragment<GaussDB> for TransactionBuilder<'_, C> {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum IsolationLevel {
+/// The isolation level of a transaction
+///
+/// See [`GaussDBConnection::transaction_with_isolation`] and
+/// [`TransactionBuilder::isolation_level`].
+///
+/// [`GaussDBConnection::transaction_with_isolation`]: crate::GaussDBConnection::transaction_with_isolation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// `READ COMMITTED`, the default
     ReadCommitted,
+    /// `REPEATABLE READ`
     RepeatableRead,
+    /// `SERIALIZABLE`
     Serializable,
 }
 
@@ -295,6 +380,110 @@ impl QueryFragment<GaussDB> for Deferrable {
     }
 }
 
+/// The constraints affected by a `SET CONSTRAINTS` statement
+#[derive(Debug, Clone, Copy)]
+pub enum ConstraintSet<'a> {
+    /// `SET CONSTRAINTS ALL`, every deferrable constraint
+    All,
+    /// The listed constraints, optionally schema-qualified (`schema.name`)
+    Named(&'a [&'a str]),
+}
+
+/// When constraints are checked, as set by `SET CONSTRAINTS`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintMode {
+    /// Check constraints at commit time
+    Deferred,
+    /// Check constraints at the end of each statement
+    Immediate,
+}
+
+/// A `SET CONSTRAINTS ... DEFERRED | IMMEDIATE` statement
+///
+/// Only deferrable constraints are affected, and only until the end of the
+/// current transaction. See [`GaussDBConnection::set_constraints_deferred`].
+///
+/// [`GaussDBConnection::set_constraints_deferred`]: crate::GaussDBConnection::set_constraints_deferred
+#[derive(Debug, Clone, Copy)]
+pub struct SetConstraints<'a> {
+    constraints: ConstraintSet<'a>,
+    mode: ConstraintMode,
+}
+
+impl<'a> SetConstraints<'a> {
+    /// Create a new `SET CONSTRAINTS` statement
+    pub fn new(constraints: ConstraintSet<'a>, mode: ConstraintMode) -> Self {
+        SetConstraints { constraints, mode }
+    }
+}
+
+impl QueryFragment<GaussDB> for SetConstraints<'_> {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, GaussDB>) -> diesel::QueryResult<()> {
+        out.push_sql("SET CONSTRAINTS ");
+        match self.constraints {
+            ConstraintSet::All => out.push_sql("ALL"),
+            ConstraintSet::Named([]) => {
+                return Err(Error::QueryBuilderError(
+                    "SET CONSTRAINTS requires at least one constraint name".into(),
+                ));
+            }
+            ConstraintSet::Named(names) => {
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        out.push_sql(", ");
+                    }
+                    for (j, part) in name.split('.').enumerate() {
+                        if j > 0 {
+                            out.push_sql(".");
+                        }
+                        out.push_identifier(part)?;
+                    }
+                }
+            }
+        }
+        match self.mode {
+            ConstraintMode::Deferred => out.push_sql(" DEFERRED"),
+            ConstraintMode::Immediate => out.push_sql(" IMMEDIATE"),
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`GaussDBConnection::batched_transaction`]
+///
+/// Chunks before the failing one stay committed; the failing chunk was
+/// rolled back.
+///
+/// [`GaussDBConnection::batched_transaction`]: crate::GaussDBConnection::batched_transaction
+#[derive(Debug)]
+pub struct BatchError<E> {
+    /// Number of chunks committed before the error
+    pub committed_chunks: usize,
+    /// Number of items in the committed chunks
+    pub committed_items: usize,
+    /// The error that aborted the failing chunk
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for BatchError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "batch failed after {} committed chunk(s) ({} items): {}",
+            self.committed_chunks, self.committed_items, self.error
+        )
+    }
+}
+
+impl<E> std::error::Error for BatchError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,6 +509,37 @@ mod tests {
         assert_eq!(query_builder.finish(), " ISOLATION LEVEL SERIALIZABLE");
     }
 
+    #[test]
+    fn test_begin_transaction_with_isolation_level() {
+        let cases = [
+            (IsolationLevel::ReadCommitted, "BEGIN TRANSACTION ISOLATION LEVEL READ COMMITTED"),
+            (IsolationLevel::RepeatableRead, "BEGIN TRANSACTION ISOLATION LEVEL REPEATABLE READ"),
+            (IsolationLevel::Serializable, "BEGIN TRANSACTION ISOLATION LEVEL SERIALIZABLE"),
+        ];
+
+        for (level, expected) in cases {
+            let begin = BeginTransaction {
+                isolation_level: Some(level),
+                ..BeginTransaction::default()
+            };
+            let mut query_builder = <GaussDB as Backend>::QueryBuilder::default();
+            begin.to_sql(&mut query_builder, &GaussDB).unwrap();
+            assert_eq!(query_builder.finish(), expected);
+        }
+
+        let begin = BeginTransaction {
+            isolation_level: Some(IsolationLevel::Serializable),
+            read_mode: Some(ReadMode::ReadOnly),
+            deferrable: Some(Deferrable::Deferrable),
+        };
+        let mut query_builder = <GaussDB as Backend>::QueryBuilder::default();
+        begin.to_sql(&mut query_builder, &GaussDB).unwrap();
+        assert_eq!(
+            query_builder.finish(),
+            "BEGIN TRANSACTION ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE"
+        );
+    }
+
     #[test]
     fn test_read_modes() {
         let read_only = ReadMode::ReadOnly;
@@ -348,6 +568,32 @@ mod tests {
         assert_eq!(query_builder.finish(), " NOT DEFERRABLE");
     }
 
+    #[test]
+    fn test_set_constraints() {
+        let mut query_builder = <GaussDB as Backend>::QueryBuilder::default();
+        SetConstraints::new(ConstraintSet::All, ConstraintMode::Deferred)
+            .to_sql(&mut query_builder, &GaussDB)
+            .unwrap();
+        assert_eq!(query_builder.finish(), "SET CONSTRAINTS ALL DEFERRED");
+
+        let mut query_builder = <GaussDB as Backend>::QueryBuilder::default();
+        SetConstraints::new(
+            ConstraintSet::Named(&["orders_customer_fk", "shop.order_items_order_fk"]),
+            ConstraintMode::Immediate,
+        )
+        .to_sql(&mut query_builder, &GaussDB)
+        .unwrap();
+        assert_eq!(
+            query_builder.finish(),
+            "SET CONSTRAINTS \"orders_customer_fk\", \"shop\".\"order_items_order_fk\" IMMEDIATE"
+        );
+
+        let mut query_builder = <GaussDB as Backend>::QueryBuilder::default();
+        let result = SetConstraints::new(ConstraintSet::Named(&[]), ConstraintMode::Deferred)
+            .to_sql(&mut query_builder, &GaussDB);
+        assert!(matches!(result, Err(Error::QueryBuilderError(_))));
+    }
+
     #[test]
     fn test_transaction_builder_basic() {
         // Test that transaction builder can be created and configured
@@ -365,4 +611,17 @@ mod tests {
                 .deferrable();
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_batch_error_display() {
+        let err = BatchError {
+            committed_chunks: 1,
+            committed_items: 10,
+            error: Error::RollbackTransaction,
+        };
+        assert_eq!(
+            err.to_string(),
+            format!("batch failed after 1 committed chunk(s) (10 items): {}", Error::RollbackTransaction)
+        );
+    }
+}
@@ -3,12 +3,16 @@
 //! This module provides support for PostgreSQL-style array types,
 //! which are also supported by GaussDB.
 
-use byteorder::{NetworkEndian, ReadBytesExt};
+use byteorder::{NetworkEndian, ReadBytesExt, WriteBytesExt};
+use std::io::Write;
 
 use crate::backend::{GaussDB, GaussDBTypeMetadata};
 use crate::value::GaussDBValue;
 use diesel::deserialize::{self, FromSql};
-use diesel::sql_types::{Array, HasSqlType};
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::query_builder::BindCollector;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::{Array, HasSqlType, Nullable};
 
 /// Implement HasSqlType for Array types
 impl<T> HasSqlType<Array<T>> for GaussDB
@@ -55,9 +59,9 @@ where
         for _ in 0..num_elements {
             let elem_size = bytes.read_i32::<NetworkEndian>()?;
             if has_null && elem_size == -1 {
-                // Skip NULL elements for now
-                // In a full implementation, we'd need Vec<Option<T>>
-                continue;
+                // Decodes to `None` for `Vec<Option<T>>` and fails for
+                // non-nullable element types
+                result.push(T::from_nullable_sql(None)?);
             } else {
                 let elem_size_usize: usize = elem_size.try_into()
                     .map_err(|_| "Invalid element size")?;
@@ -76,8 +80,76 @@ where
     }
 }
 
-// Note: ToSql implementation for arrays is complex and requires proper
-// element serialization with metadata lookup. This is planned for future implementation.
+/// Serialize a slice as a one-dimensional array in the binary wire format
+///
+/// The whole slice is sent as a single bind parameter, so it can be used as
+/// `WHERE id = ANY($1)` instead of expanding into an `IN` list.
+impl<ST, T> ToSql<Array<ST>, GaussDB> for [T]
+where
+    GaussDB: HasSqlType<ST>,
+    T: ToSql<ST, GaussDB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        // Serialize the elements first, the header needs to know about NULLs
+        let mut elements = RawBytesBindCollector::<GaussDB>::new();
+        for elem in self {
+            elements.push_bound_value::<ST, T>(elem, out.metadata_lookup())?;
+        }
+        let has_null = elements.binds.iter().any(Option::is_none);
+
+        let num_dimensions = 1;
+        out.write_i32::<NetworkEndian>(num_dimensions)?;
+        out.write_i32::<NetworkEndian>(has_null as i32)?;
+        let element_oid = <GaussDB as HasSqlType<ST>>::metadata(out.metadata_lookup()).oid()?;
+        out.write_u32::<NetworkEndian>(element_oid)?;
+        out.write_i32::<NetworkEndian>(self.len().try_into()?)?;
+        let lower_bound = 1;
+        out.write_i32::<NetworkEndian>(lower_bound)?;
+
+        for elem in &elements.binds {
+            match elem {
+                Some(bytes) => {
+                    out.write_i32::<NetworkEndian>(bytes.len().try_into()?)?;
+                    out.write_all(bytes)?;
+                }
+                None => out.write_i32::<NetworkEndian>(-1)?,
+            }
+        }
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<ST, T> ToSql<Nullable<Array<ST>>, GaussDB> for [T]
+where
+    [T]: ToSql<Array<ST>, GaussDB>,
+    ST: 'static,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        ToSql::<Array<ST>, GaussDB>::to_sql(self, out)
+    }
+}
+
+impl<ST, T> ToSql<Array<ST>, GaussDB> for Vec<T>
+where
+    ST: 'static,
+    [T]: ToSql<Array<ST>, GaussDB>,
+    T: std::fmt::Debug,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        (self as &[T]).to_sql(out)
+    }
+}
+
+impl<ST, T> ToSql<Nullable<Array<ST>>, GaussDB> for Vec<T>
+where
+    ST: 'static,
+    Vec<T>: ToSql<Array<ST>, GaussDB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        ToSql::<Array<ST>, GaussDB>::to_sql(self, out)
+    }
+}
 
 // Note: AsExpression implementations are provided by Diesel's generic implementations
 // for Vec<T> and &[T] types. We don't need to implement them manually here as they
@@ -86,6 +158,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::TestLookup;
     use diesel::sql_types::{Array, Integer, Text};
     use byteorder::{NetworkEndian, WriteBytesExt};
 
@@ -104,8 +177,63 @@ mod tests {
         test_array_type::<Text>();
     }
 
-    // Note: ToSql tests require a proper Output setup which is complex to mock.
-    // The ToSql implementations are tested through integration tests with real connections.
+    #[test]
+    fn test_array_serialization_binary_format() {
+        let values = vec![1i32, 2];
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector
+            .push_bound_value::<Array<Integer>, Vec<i32>>(&values, &mut TestLookup::builtin())
+            .unwrap();
+
+        let mut expected = Vec::new();
+        expected.write_i32::<NetworkEndian>(1).unwrap(); // num_dimensions
+        expected.write_i32::<NetworkEndian>(0).unwrap(); // no NULL elements
+        expected.write_i32::<NetworkEndian>(23).unwrap(); // element type OID (int4)
+        expected.write_i32::<NetworkEndian>(2).unwrap(); // num_elements
+        expected.write_i32::<NetworkEndian>(1).unwrap(); // lower_bound
+        for value in &values {
+            expected.write_i32::<NetworkEndian>(4).unwrap();
+            expected.write_i32::<NetworkEndian>(*value).unwrap();
+        }
+
+        assert_eq!(collector.binds, vec![Some(expected)]);
+        assert_eq!(collector.metadata[0].oid().unwrap(), 1007);
+    }
+
+    #[test]
+    fn test_array_serialization_round_trip() {
+        let values = vec![7i32, -3, 42];
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector
+            .push_bound_value::<Array<Integer>, Vec<i32>>(&values, &mut TestLookup::builtin())
+            .unwrap();
+
+        let bytes = collector.binds[0].as_ref().unwrap();
+        let value = GaussDBValue::new(Some(bytes), 1007);
+        let decoded = <Vec<i32> as FromSql<Array<Integer>, GaussDB>>::from_sql(value).unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn test_array_deserialization_null_elements() {
+        let mut bytes = Vec::new();
+        bytes.write_i32::<NetworkEndian>(1).unwrap(); // num_dimensions
+        bytes.write_i32::<NetworkEndian>(1).unwrap(); // has NULL elements
+        bytes.write_i32::<NetworkEndian>(23).unwrap(); // element type OID (int4)
+        bytes.write_i32::<NetworkEndian>(2).unwrap(); // num_elements
+        bytes.write_i32::<NetworkEndian>(1).unwrap(); // lower_bound
+        bytes.write_i32::<NetworkEndian>(-1).unwrap(); // NULL
+        bytes.write_i32::<NetworkEndian>(4).unwrap();
+        bytes.write_i32::<NetworkEndian>(5).unwrap();
+
+        let value = GaussDBValue::new(Some(&bytes), 1007);
+        let decoded =
+            <Vec<Option<i32>> as FromSql<Array<Nullable<Integer>>, GaussDB>>::from_sql(value).unwrap();
+        assert_eq!(decoded, vec![None, Some(5)]);
+
+        // NULL elements can't be silently dropped from a non-nullable array
+        assert!(<Vec<i32> as FromSql<Array<Integer>, GaussDB>>::from_sql(value).is_err());
+    }
 
     #[test]
     fn test_array_deserialization_empty() {
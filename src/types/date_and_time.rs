@@ -4,12 +4,14 @@
 //! for GaussDB, following the same wire protocol and representation.
 
 use crate::backend::GaussDB;
+use crate::types::sql_types::Timetz;
 use crate::value::GaussDBValue;
 use diesel::deserialize::{self, FromSql, FromSqlRow};
 use diesel::expression::AsExpression;
 use diesel::serialize::{self, IsNull, Output, ToSql};
 use diesel::sql_types::{Date, Interval, Time, Timestamp, Timestamptz};
 use byteorder::{NetworkEndian, ReadBytesExt, WriteBytesExt};
+use std::ops::{Add, Sub};
 
 /// Timestamps are represented in GaussDB as a 64 bit signed integer representing the number of
 /// microseconds since January 1st 2000. This struct is a dumb wrapper type, meant only to indicate
@@ -87,6 +89,47 @@ impl Default for GaussDBTime {
     }
 }
 
+/// Time with time zone is represented in GaussDB as a 64 bit signed integer representing the
+/// number of microseconds since midnight, followed by a 32 bit signed integer holding the zone
+/// offset in seconds.
+///
+/// The zone offset uses the server's sign convention: it counts seconds *west* of UTC, so
+/// `12:00:00+08` is stored with a `zone` of `-28800`. Use [`GaussDBTimeTz::from_utc_offset`]
+/// and [`GaussDBTimeTz::utc_offset`] to work with the usual ISO 8601 sign instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Timetz)]
+pub struct GaussDBTimeTz {
+    /// Number of microseconds since midnight, local to the zone
+    pub microseconds: i64,
+    /// Zone offset in seconds west of UTC
+    pub zone: i32,
+}
+
+impl GaussDBTimeTz {
+    /// Create a new time with time zone from microseconds since midnight and
+    /// a zone offset in seconds west of UTC
+    pub fn new(microseconds: i64, zone: i32) -> Self {
+        GaussDBTimeTz { microseconds, zone }
+    }
+
+    /// Create a new time with time zone from microseconds since midnight and
+    /// an ISO 8601 offset in seconds east of UTC
+    pub fn from_utc_offset(microseconds: i64, utc_offset: i32) -> Self {
+        GaussDBTimeTz::new(microseconds, -utc_offset)
+    }
+
+    /// Get the ISO 8601 offset in seconds east of UTC
+    pub fn utc_offset(&self) -> i32 {
+        -self.zone
+    }
+}
+
+impl Default for GaussDBTimeTz {
+    fn default() -> Self {
+        GaussDBTimeTz::new(0, 0)
+    }
+}
+
 /// Intervals are represented in GaussDB as a struct containing months, days, and microseconds.
 /// This follows the PostgreSQL wire protocol format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow)]
@@ -121,6 +164,44 @@ impl Default for GaussDBInterval {
     }
 }
 
+impl Add for GaussDBInterval {
+    type Output = Self;
+    /// Adds the months, days and microseconds separately, like GaussDB does.
+    ///
+    /// # Panics
+    ///
+    /// Performs a checked addition, and will `panic!` on overflow in both `debug` and `release`.
+    fn add(self, rhs: GaussDBInterval) -> Self::Output {
+        GaussDBInterval {
+            months: self.months.checked_add(rhs.months).expect("overflow adding interval months"),
+            days: self.days.checked_add(rhs.days).expect("overflow adding interval days"),
+            microseconds: self
+                .microseconds
+                .checked_add(rhs.microseconds)
+                .expect("overflow adding interval microseconds"),
+        }
+    }
+}
+
+impl Sub for GaussDBInterval {
+    type Output = Self;
+    /// Subtracts the months, days and microseconds separately, like GaussDB does.
+    ///
+    /// # Panics
+    ///
+    /// Performs a checked subtraction, and will `panic!` on overflow in both `debug` and `release`.
+    fn sub(self, rhs: GaussDBInterval) -> Self::Output {
+        GaussDBInterval {
+            months: self.months.checked_sub(rhs.months).expect("overflow subtracting interval months"),
+            days: self.days.checked_sub(rhs.days).expect("overflow subtracting interval days"),
+            microseconds: self
+                .microseconds
+                .checked_sub(rhs.microseconds)
+                .expect("overflow subtracting interval microseconds"),
+        }
+    }
+}
+
 // FromSql implementations
 impl FromSql<Timestamp, GaussDB> for GaussDBTimestamp {
     fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
@@ -170,6 +251,19 @@ impl FromSql<Time, GaussDB> for GaussDBTime {
     }
 }
 
+impl FromSql<Timetz, GaussDB> for GaussDBTimeTz {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let bytes = value.as_bytes().ok_or("Timetz value is null")?;
+        if bytes.len() != 12 {
+            return Err("Invalid Timetz length".into());
+        }
+        let mut cursor = std::io::Cursor::new(bytes);
+        let microseconds = cursor.read_i64::<NetworkEndian>()?;
+        let zone = cursor.read_i32::<NetworkEndian>()?;
+        Ok(GaussDBTimeTz { microseconds, zone })
+    }
+}
+
 impl FromSql<Interval, GaussDB> for GaussDBInterval {
     fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
         let bytes = value.as_bytes().ok_or("Interval value is null")?;
@@ -221,6 +315,14 @@ impl ToSql<Time, GaussDB> for GaussDBTime {
     }
 }
 
+impl ToSql<Timetz, GaussDB> for GaussDBTimeTz {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        out.write_i64::<NetworkEndian>(self.microseconds)?;
+        out.write_i32::<NetworkEndian>(self.zone)?;
+        Ok(IsNull::No)
+    }
+}
+
 impl ToSql<Interval, GaussDB> for GaussDBInterval {
     fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
         out.write_i64::<NetworkEndian>(self.microseconds)?;
@@ -233,6 +335,7 @@ impl ToSql<Interval, GaussDB> for GaussDBInterval {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::TestLookup;
 
     #[test]
     fn test_gaussdb_timestamp_creation() {
@@ -252,6 +355,42 @@ mod tests {
         assert_eq!(time.microseconds(), 86400000000);
     }
 
+    #[test]
+    fn test_gaussdb_timetz_offset_sign() {
+        // 12:00:00+08 is stored as eight hours west of UTC negated
+        let time = GaussDBTimeTz::from_utc_offset(43_200_000_000, 8 * 3600);
+        assert_eq!(time.zone, -28800);
+        assert_eq!(time.utc_offset(), 28800);
+    }
+
+    #[test]
+    fn test_gaussdb_timetz_encoding() {
+        use diesel::query_builder::bind_collector::RawBytesBindCollector;
+        use diesel::query_builder::BindCollector;
+
+        let time = GaussDBTimeTz::from_utc_offset(45_045_000_001, -5 * 3600);
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector
+            .push_bound_value::<Timetz, GaussDBTimeTz>(&time, &mut TestLookup::builtin())
+            .unwrap();
+
+        let bytes = collector.binds[0].as_ref().unwrap();
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(&bytes[..8], &45_045_000_001i64.to_be_bytes());
+        assert_eq!(&bytes[8..], &18000i32.to_be_bytes());
+        assert_eq!(collector.metadata[0].oid().unwrap(), 1266);
+
+        let decoded = <GaussDBTimeTz as FromSql<Timetz, GaussDB>>::from_sql(GaussDBValue::new(Some(bytes), 1266)).unwrap();
+        assert_eq!(decoded, time);
+    }
+
+    #[test]
+    fn test_gaussdb_timetz_invalid_length() {
+        let bytes = [0u8; 8];
+        let result = <GaussDBTimeTz as FromSql<Timetz, GaussDB>>::from_sql(GaussDBValue::new(Some(&bytes), 1266));
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_gaussdb_interval_creation() {
         let interval = GaussDBInterval::new(12, 30, 3600000000); // 12 months, 30 days, 1 hour
@@ -260,11 +399,23 @@ mod tests {
         assert_eq!(interval.microseconds, 3600000000);
     }
 
+    #[test]
+    fn test_gaussdb_interval_arithmetic() {
+        let day = GaussDBInterval::new(0, 1, 0);
+        let six_hours = GaussDBInterval::new(0, 0, 6 * 3_600_000_000);
+        let month = GaussDBInterval::new(1, 0, 0);
+
+        // Fields are kept apart, a day is not normalized into hours
+        assert_eq!(day + six_hours + month, GaussDBInterval::new(1, 1, 21_600_000_000));
+        assert_eq!(day - six_hours, GaussDBInterval::new(0, 1, -21_600_000_000));
+    }
+
     #[test]
     fn test_default_values() {
         assert_eq!(GaussDBTimestamp::default().microseconds(), 0);
         assert_eq!(GaussDBDate::default().julian_days(), 0);
         assert_eq!(GaussDBTime::default().microseconds(), 0);
+        assert_eq!(GaussDBTimeTz::default(), GaussDBTimeTz::new(0, 0));
         
         let default_interval = GaussDBInterval::default();
         assert_eq!(default_interval.months, 0);
@@ -379,4 +530,86 @@ mod chrono_support {
                 .ok_or_else(|| "Invalid time value".into())
         }
     }
+
+    const MICROSECONDS_PER_DAY: i64 = 86_400_000_000;
+
+    /// Reads an interval as a fixed-length duration
+    ///
+    /// Days are treated as exactly 24 hours. Months have no fixed length, so
+    /// an interval with a non-zero month component (e.g. `'1 mon'` or
+    /// `'1 year'`) is rejected instead of being converted to a guessed
+    /// number of days. Use [`GaussDBInterval`] to read such values.
+    impl FromSql<Interval, GaussDB> for chrono::Duration {
+        fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+            let interval = <GaussDBInterval as FromSql<Interval, GaussDB>>::from_sql(value)?;
+            if interval.months != 0 {
+                return Err(format!(
+                    "Cannot convert an interval with {} month(s) into a fixed duration; \
+                     read it as GaussDBInterval instead",
+                    interval.months
+                ).into());
+            }
+
+            let microseconds = (interval.days as i64)
+                .checked_mul(MICROSECONDS_PER_DAY)
+                .and_then(|days| days.checked_add(interval.microseconds))
+                .ok_or("Interval value is out of range for a duration")?;
+            Ok(chrono::Duration::microseconds(microseconds))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn interval_value(interval: GaussDBInterval) -> Vec<u8> {
+            let mut bytes = Vec::with_capacity(16);
+            bytes.write_i64::<NetworkEndian>(interval.microseconds).unwrap();
+            bytes.write_i32::<NetworkEndian>(interval.days).unwrap();
+            bytes.write_i32::<NetworkEndian>(interval.months).unwrap();
+            bytes
+        }
+
+        fn read_duration(interval: GaussDBInterval) -> deserialize::Result<chrono::Duration> {
+            let bytes = interval_value(interval);
+            <chrono::Duration as FromSql<Interval, GaussDB>>::from_sql(GaussDBValue::new(Some(&bytes), 1186))
+        }
+
+        #[test]
+        fn test_nullable_timestamp_decoding() {
+            use diesel::sql_types::Nullable;
+
+            type Decode = fn(Option<GaussDBValue<'_>>) -> deserialize::Result<Option<NaiveDateTime>>;
+            let decode: Decode = FromSql::<Nullable<Timestamp>, GaussDB>::from_nullable_sql;
+
+            // SQL NULL never reaches the "Timestamp value is null" check
+            assert_eq!(decode(None).unwrap(), None);
+
+            // One day after the PostgreSQL epoch
+            let bytes = 86_400_000_000i64.to_be_bytes();
+            let expected = NaiveDate::from_ymd_opt(2000, 1, 2).unwrap().and_hms_opt(0, 0, 0).unwrap();
+            assert_eq!(decode(Some(GaussDBValue::new(Some(&bytes), 1114))).unwrap(), Some(expected));
+        }
+
+        #[test]
+        fn test_time_interval_to_duration() {
+            // '01:30:00.5'
+            let duration = read_duration(GaussDBInterval::new(0, 0, 5_400_500_000)).unwrap();
+            assert_eq!(duration, chrono::Duration::milliseconds(5_400_500));
+        }
+
+        #[test]
+        fn test_day_interval_to_duration() {
+            // '2 days -01:00:00'
+            let duration = read_duration(GaussDBInterval::new(0, 2, -3_600_000_000)).unwrap();
+            assert_eq!(duration, chrono::Duration::hours(47));
+        }
+
+        #[test]
+        fn test_month_interval_is_rejected() {
+            // '1 mon 3 days'
+            let err = read_duration(GaussDBInterval::new(1, 3, 0)).unwrap_err();
+            assert!(err.to_string().contains("month"));
+        }
+    }
 }
@@ -10,6 +10,10 @@ pub mod date_and_time;
 pub mod array;
 pub mod ranges;
 pub mod sql_types;
+pub mod system_vectors;
+pub mod name;
+pub mod oracle_compat;
+pub mod small_types;
 
 // JSON support (feature-gated)
 #[cfg(feature = "serde_json")]
@@ -0,0 +1,73 @@
+//! Support for the `name` system type
+//!
+//! Catalog columns such as `pg_type.typname` or `pg_namespace.nspname` use
+//! `name`, an identifier of at most 63 bytes stored in a fixed 64 byte
+//! buffer. The unused part of the buffer is filled with `NUL` bytes, so
+//! everything from the first `NUL` on is cut off when decoding. This also
+//! applies when such a column is read as `Text`.
+
+use crate::backend::GaussDB;
+use crate::types::sql_types::Name;
+use crate::value::GaussDBValue;
+use diesel::deserialize::{self, FromSql};
+
+/// The OID of the `name` type
+pub(crate) const NAME_OID: u32 = 19;
+
+impl FromSql<Name, GaussDB> for String {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let bytes = value.as_bytes().ok_or("name value is null")?;
+        decode_name(bytes).map(str::to_owned)
+    }
+}
+
+/// Decode a `name` value, dropping the `NUL` padding
+pub(crate) fn decode_name(bytes: &[u8]) -> deserialize::Result<&str> {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    crate::value::decode_utf8(&bytes[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::sql_types::Text;
+
+    fn decode(bytes: &[u8]) -> deserialize::Result<String> {
+        FromSql::<Name, GaussDB>::from_sql(GaussDBValue::new(Some(bytes), NAME_OID))
+    }
+
+    #[test]
+    fn test_decode_name_without_padding() {
+        assert_eq!(decode(b"pg_catalog").unwrap(), "pg_catalog");
+        assert_eq!(decode(b"").unwrap(), "");
+    }
+
+    #[test]
+    fn test_decode_name_drops_padding() {
+        let mut bytes = [0u8; 64];
+        bytes[..4].copy_from_slice(b"int4");
+        assert_eq!(decode(&bytes).unwrap(), "int4");
+
+        // Bytes left over in the buffer after the terminator are ignored
+        assert_eq!(decode(b"int4\0\0garbage").unwrap(), "int4");
+    }
+
+    #[test]
+    fn test_decode_name_rejects_invalid_utf8() {
+        assert!(decode(b"\xff\xfe\0").is_err());
+    }
+
+    #[test]
+    fn test_text_decoding_of_name_columns() {
+        let mut bytes = [0u8; 64];
+        bytes[..6].copy_from_slice(b"public");
+        let text: String =
+            FromSql::<Text, GaussDB>::from_sql(GaussDBValue::new(Some(&bytes), NAME_OID)).unwrap();
+        assert_eq!(text, "public");
+
+        // Other text types keep embedded bytes as they are
+        let text: String =
+            FromSql::<Text, GaussDB>::from_sql(GaussDBValue::new(Some(b"a\0b"), 25)).unwrap();
+        assert_eq!(text, "a\0b");
+    }
+}
@@ -0,0 +1,277 @@
+//! Support for the `blob`, `raw`, `clob`, `varchar2`, `nvarchar2` and
+//! `nchar` types of GaussDB's Oracle compatibility mode
+//!
+//! These types only exist in databases created with `DBCOMPATIBILITY 'A'`,
+//! so their OIDs are not fixed and are looked up through the connection's
+//! metadata lookup, which caches them per connection. On the wire they
+//! behave like `bytea` (`blob`, `raw`) and `text` (the character types).
+//!
+//! Values are loaded into `Vec<u8>` and `String` as usual and are bound
+//! with an explicit SQL type, e.g. `sql_query(..).bind::<Clob, _>(text)`.
+//! Binding a value to one of these types in a database without the
+//! compatibility mode fails with a serialization error naming the missing
+//! type, instead of letting the server guess the parameter type.
+
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+
+use crate::backend::{GaussDB, GaussDBTypeMetadata};
+use crate::types::sql_types::{Blob, Clob, Nchar, Nvarchar2, Raw, Varchar2};
+use crate::value::GaussDBValue;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::HasSqlType;
+
+/// Error returned when a compatibility type does not exist in the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatibilityTypeUnavailable {
+    type_name: &'static str,
+}
+
+impl CompatibilityTypeUnavailable {
+    /// The name of the missing type
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Display for CompatibilityTypeUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "type {} does not exist in this database; it is only available in databases \
+             created with Oracle compatibility (DBCOMPATIBILITY 'A')",
+            self.type_name
+        )
+    }
+}
+
+impl Error for CompatibilityTypeUnavailable {}
+
+/// Check that `type_name` can be resolved before a value is bound to it
+fn require_type(out: &mut Output<'_, '_, GaussDB>, type_name: &'static str) -> Result<(), CompatibilityTypeUnavailable> {
+    out.metadata_lookup()
+        .lookup_type(type_name, None)
+        .oid()
+        .map(|_| ())
+        .map_err(|_| CompatibilityTypeUnavailable { type_name })
+}
+
+impl HasSqlType<Blob> for GaussDB {
+    fn metadata(lookup: &mut Self::MetadataLookup) -> GaussDBTypeMetadata {
+        lookup.lookup_type("blob", None)
+    }
+}
+
+impl HasSqlType<Raw> for GaussDB {
+    fn metadata(lookup: &mut Self::MetadataLookup) -> GaussDBTypeMetadata {
+        lookup.lookup_type("raw", None)
+    }
+}
+
+impl HasSqlType<Clob> for GaussDB {
+    fn metadata(lookup: &mut Self::MetadataLookup) -> GaussDBTypeMetadata {
+        lookup.lookup_type("clob", None)
+    }
+}
+
+impl FromSql<Blob, GaussDB> for Vec<u8> {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let bytes = value.as_bytes().ok_or("Blob value is null")?;
+        Ok(bytes.to_vec())
+    }
+}
+
+impl FromSql<Raw, GaussDB> for Vec<u8> {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let bytes = value.as_bytes().ok_or("Raw value is null")?;
+        Ok(bytes.to_vec())
+    }
+}
+
+impl FromSql<Clob, GaussDB> for String {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let bytes = value.as_bytes().ok_or("Clob value is null")?;
+        Ok(std::str::from_utf8(bytes)?.to_owned())
+    }
+}
+
+impl ToSql<Blob, GaussDB> for [u8] {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        require_type(out, "blob")?;
+        out.write_all(self)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl ToSql<Raw, GaussDB> for [u8] {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        require_type(out, "raw")?;
+        out.write_all(self)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl ToSql<Clob, GaussDB> for str {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        require_type(out, "clob")?;
+        out.write_all(self.as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl ToSql<Blob, GaussDB> for Vec<u8> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        <[u8] as ToSql<Blob, GaussDB>>::to_sql(self.as_slice(), out)
+    }
+}
+
+impl ToSql<Raw, GaussDB> for Vec<u8> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        <[u8] as ToSql<Raw, GaussDB>>::to_sql(self.as_slice(), out)
+    }
+}
+
+impl ToSql<Clob, GaussDB> for String {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        <str as ToSql<Clob, GaussDB>>::to_sql(self.as_str(), out)
+    }
+}
+
+/// The character types are all mapped to `String` like `clob`
+macro_rules! compat_text_type {
+    ($($sql_type:ident => $type_name:literal),+ $(,)?) => {$(
+        impl HasSqlType<$sql_type> for GaussDB {
+            fn metadata(lookup: &mut Self::MetadataLookup) -> GaussDBTypeMetadata {
+                lookup.lookup_type($type_name, None)
+            }
+        }
+
+        impl FromSql<$sql_type, GaussDB> for String {
+            fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+                let bytes = value.as_bytes().ok_or(concat!(stringify!($sql_type), " value is null"))?;
+                Ok(std::str::from_utf8(bytes)?.to_owned())
+            }
+        }
+
+        impl ToSql<$sql_type, GaussDB> for str {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+                require_type(out, $type_name)?;
+                out.write_all(self.as_bytes())?;
+                Ok(IsNull::No)
+            }
+        }
+
+        impl ToSql<$sql_type, GaussDB> for String {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+                <str as ToSql<$sql_type, GaussDB>>::to_sql(self.as_str(), out)
+            }
+        }
+    )+};
+}
+
+compat_text_type! {
+    Varchar2 => "varchar2",
+    Nvarchar2 => "nvarchar2",
+    Nchar => "nchar",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::GaussDBMetadataLookup;
+    use diesel::query_builder::bind_collector::RawBytesBindCollector;
+    use diesel::query_builder::BindCollector;
+
+    /// A lookup that only knows the types of a database in A mode
+    struct Lookup {
+        compatibility_mode: bool,
+    }
+
+    impl GaussDBMetadataLookup for Lookup {
+        fn lookup_type(&mut self, type_name: &str, _schema: Option<&str>) -> GaussDBTypeMetadata {
+            match (self.compatibility_mode, type_name) {
+                (true, "raw") => GaussDBTypeMetadata::new(86, 87),
+                (true, "blob") => GaussDBTypeMetadata::new(88, 3201),
+                (true, "clob") => GaussDBTypeMetadata::new(90, 3202),
+                (true, "varchar2") => GaussDBTypeMetadata::new(91, 92),
+                (true, "nvarchar2") => GaussDBTypeMetadata::new(3969, 3968),
+                (true, "nchar") => GaussDBTypeMetadata::new(93, 94),
+                _ => GaussDBTypeMetadata::from_result(Err(crate::backend::FailedToLookupTypeError::new(type_name))),
+            }
+        }
+
+        fn as_any<'a>(&mut self) -> &mut (dyn std::any::Any + 'a)
+        where
+            Self: 'a,
+        {
+            self
+        }
+    }
+
+    fn encode<ST, T>(value: &T, compatibility_mode: bool) -> diesel::QueryResult<(Vec<u8>, u32)>
+    where
+        GaussDB: HasSqlType<ST>,
+        T: ToSql<ST, GaussDB> + ?Sized,
+    {
+        let mut lookup = Lookup { compatibility_mode };
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector.push_bound_value::<ST, T>(value, &mut lookup)?;
+        let oid = collector.metadata[0].oid().unwrap();
+        Ok((collector.binds.remove(0).unwrap(), oid))
+    }
+
+    #[test]
+    fn test_binds_use_looked_up_oids() {
+        let (bytes, oid) = encode::<Blob, _>(&vec![0u8, 159, 146, 150], true).unwrap();
+        assert_eq!((bytes, oid), (vec![0, 159, 146, 150], 88));
+
+        let (bytes, oid) = encode::<Raw, [u8]>(b"\x01\x02", true).unwrap();
+        assert_eq!((bytes, oid), (vec![1, 2], 86));
+
+        let (bytes, oid) = encode::<Clob, str>("长文本", true).unwrap();
+        assert_eq!((bytes, oid), ("长文本".as_bytes().to_vec(), 90));
+    }
+
+    #[test]
+    fn test_character_types_bind_as_text() {
+        let (bytes, oid) = encode::<Varchar2, str>("订单-001", true).unwrap();
+        assert_eq!((bytes, oid), ("订单-001".as_bytes().to_vec(), 91));
+
+        let (bytes, oid) = encode::<Nvarchar2, _>(&"名称".to_string(), true).unwrap();
+        assert_eq!((bytes, oid), ("名称".as_bytes().to_vec(), 3969));
+
+        let (bytes, oid) = encode::<Nchar, str>("ab", true).unwrap();
+        assert_eq!((bytes, oid), (b"ab".to_vec(), 93));
+
+        let error = encode::<Varchar2, str>("text", false).unwrap_err().to_string();
+        assert!(error.contains("type varchar2 does not exist"), "{}", error);
+    }
+
+    #[test]
+    fn test_missing_compatibility_mode_is_reported() {
+        let error = encode::<Clob, str>("text", false).unwrap_err().to_string();
+        assert!(error.contains("type clob does not exist"), "{}", error);
+        assert!(error.contains("DBCOMPATIBILITY 'A'"), "{}", error);
+    }
+
+    #[test]
+    fn test_from_sql() {
+        let blob = <Vec<u8> as FromSql<Blob, GaussDB>>::from_sql(GaussDBValue::new(Some(&[1, 2, 3]), 88));
+        assert_eq!(blob.unwrap(), vec![1, 2, 3]);
+
+        let clob = <String as FromSql<Clob, GaussDB>>::from_sql(GaussDBValue::new(Some("数据".as_bytes()), 90));
+        assert_eq!(clob.unwrap(), "数据");
+
+        let invalid = <String as FromSql<Clob, GaussDB>>::from_sql(GaussDBValue::new(Some(&[0xff]), 90));
+        assert!(invalid.is_err());
+
+        let varchar2 = <String as FromSql<Varchar2, GaussDB>>::from_sql(GaussDBValue::new(Some(b"abc"), 91));
+        assert_eq!(varchar2.unwrap(), "abc");
+
+        // nchar values keep their blank padding
+        let nchar = <String as FromSql<Nchar, GaussDB>>::from_sql(GaussDBValue::new(Some(b"ab  "), 93));
+        assert_eq!(nchar.unwrap(), "ab  ");
+    }
+}
@@ -133,16 +133,18 @@ impl FromSql<Float, GaussDB> for f32 {
     fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
         let bytes = value.as_bytes().ok_or("Float value is null")?;
         if bytes.len() < 4 {
-            return emit_size_error(
+            return emit_size_error(&format!(
                 "Received less than 4 bytes while decoding an f32. \
-                Got {} bytes"
-            );
+                Got {} bytes",
+                bytes.len()
+            ));
         }
         if bytes.len() > 4 {
-            return emit_size_error(
+            return emit_size_error(&format!(
                 "Received more than 4 bytes while decoding an f32. \
-                Was a double accidentally marked as float? Got {} bytes"
-            );
+                Was a double accidentally marked as float? Got {} bytes",
+                bytes.len()
+            ));
         }
         let mut cursor = std::io::Cursor::new(bytes);
         cursor.read_f32::<NetworkEndian>()
@@ -163,16 +165,18 @@ impl FromSql<Double, GaussDB> for f64 {
     fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
         let bytes = value.as_bytes().ok_or("Double value is null")?;
         if bytes.len() < 8 {
-            return emit_size_error(
+            return emit_size_error(&format!(
                 "Received less than 8 bytes while decoding an f64. \
-                Was a float accidentally marked as double? Got {} bytes"
-            );
+                Was a float accidentally marked as double? Got {} bytes",
+                bytes.len()
+            ));
         }
         if bytes.len() > 8 {
-            return emit_size_error(
+            return emit_size_error(&format!(
                 "Received more than 8 bytes while decoding an f64. \
-                Was a numeric accidentally marked as double? Got {} bytes"
-            );
+                Was a numeric accidentally marked as double? Got {} bytes",
+                bytes.len()
+            ));
         }
         let mut cursor = std::io::Cursor::new(bytes);
         cursor.read_f64::<NetworkEndian>()
@@ -188,11 +192,18 @@ impl ToSql<Double, GaussDB> for f64 {
     }
 }
 
-// Boolean implementation
+// Boolean implementation, a single byte where any nonzero value is true
 impl FromSql<Bool, GaussDB> for bool {
     fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
         let bytes = value.as_bytes().ok_or("Bool value is null")?;
-        Ok(bytes[0] != 0)
+        match bytes {
+            [byte] => Ok(*byte != 0),
+            _ => emit_size_error(&format!(
+                "Expected 1 byte while decoding a bool. \
+                Was an expression of a different type accidentally marked as Bool? Got {} bytes",
+                bytes.len()
+            )),
+        }
     }
 }
 
@@ -207,9 +218,9 @@ impl ToSql<Bool, GaussDB> for bool {
 // Text implementation (following PostgreSQL pattern)
 impl FromSql<Text, GaussDB> for *const str {
     fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
-        use std::str;
-        let bytes = value.as_bytes().ok_or("Text value is null")?;
-        let string = str::from_utf8(bytes)?;
+        // Catalog columns declared as `Text` are often of type `name`,
+        // which `as_str` strips of its padding
+        let string = value.as_str()?;
         Ok(string as *const _)
     }
 }
@@ -225,6 +236,7 @@ impl FromSql<Binary, GaussDB> for Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::TestLookup;
 
     #[test]
     fn test_type_system_completeness() {
@@ -264,4 +276,124 @@ mod tests {
 
         // 基础类型 trait 实现验证通过
     }
+
+    #[test]
+    fn test_nullable_integer_decoding() {
+        type Decode = fn(Option<GaussDBValue<'_>>) -> deserialize::Result<Option<i32>>;
+        let decode: Decode = FromSql::<Nullable<Integer>, GaussDB>::from_nullable_sql;
+
+        // SQL NULL short-circuits before the `Integer` decoder is reached
+        assert_eq!(decode(None).unwrap(), None);
+
+        let bytes = 42i32.to_be_bytes();
+        assert_eq!(decode(Some(GaussDBValue::new(Some(&bytes), 23))).unwrap(), Some(42));
+
+        // A non-nullable target still reports the NULL
+        assert!(<i32 as FromSql<Integer, GaussDB>>::from_nullable_sql(None).is_err());
+    }
+
+    /// Encode `value` as a bind parameter, returning its bytes and type OID
+    fn encode<ST, T>(value: &T) -> (Vec<u8>, u32)
+    where
+        GaussDB: diesel::sql_types::HasSqlType<ST>,
+        T: ToSql<ST, GaussDB>,
+    {
+        use diesel::query_builder::bind_collector::RawBytesBindCollector;
+        use diesel::query_builder::BindCollector;
+
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector.push_bound_value::<ST, T>(value, &mut TestLookup::builtin()).unwrap();
+        let oid = collector.metadata[0].oid().unwrap();
+        (collector.binds.remove(0).unwrap(), oid)
+    }
+
+    #[test]
+    fn test_float_encoding_uses_network_byte_order() {
+        let (bytes, oid) = encode::<Float, f32>(&1.5);
+        assert_eq!(oid, 700);
+        assert_eq!(bytes, vec![0x3f, 0xc0, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_float_special_values_round_trip() {
+        let values = [f32::INFINITY, f32::NEG_INFINITY, -0.0, f32::MIN_POSITIVE / 2.0, f32::MAX];
+        for value in values {
+            let (bytes, _) = encode::<Float, f32>(&value);
+            let decoded: f32 = FromSql::<Float, GaussDB>::from_sql(GaussDBValue::new(Some(&bytes), 700)).unwrap();
+            assert_eq!(decoded.to_bits(), value.to_bits(), "{}", value);
+        }
+
+        let (bytes, _) = encode::<Float, f32>(&f32::NAN);
+        let decoded: f32 = FromSql::<Float, GaussDB>::from_sql(GaussDBValue::new(Some(&bytes), 700)).unwrap();
+        assert!(decoded.is_nan());
+    }
+
+    #[test]
+    fn test_float_rejects_double_width_value() {
+        let bytes = 1.5f64.to_be_bytes();
+        let err = <f32 as FromSql<Float, GaussDB>>::from_sql(GaussDBValue::new(Some(&bytes), 701)).unwrap_err();
+        assert!(err.to_string().contains("Got 8 bytes"), "{}", err);
+    }
+
+    #[test]
+    fn test_bool_round_trip() {
+        assert_eq!(encode::<Bool, bool>(&true), (vec![1], 16));
+        assert_eq!(encode::<Bool, bool>(&false), (vec![0], 16));
+
+        for value in [true, false] {
+            let (bytes, _) = encode::<Bool, bool>(&value);
+            let decoded: bool = FromSql::<Bool, GaussDB>::from_sql(GaussDBValue::new(Some(&bytes), 16)).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+
+    #[test]
+    fn test_bool_decoding() {
+        let decode = |bytes: &[u8]| <bool as FromSql<Bool, GaussDB>>::from_sql(GaussDBValue::new(Some(bytes), 16));
+
+        // Any nonzero byte is true
+        assert!(decode(&[2]).unwrap());
+        assert!(decode(&[0xff]).unwrap());
+
+        let err = decode(&[]).unwrap_err();
+        assert!(err.to_string().contains("Got 0 bytes"), "{}", err);
+        let err = decode(&[0, 1]).unwrap_err();
+        assert!(err.to_string().contains("Got 2 bytes"), "{}", err);
+    }
+
+    #[test]
+    fn test_nullable_bool() {
+        type Decode = fn(Option<GaussDBValue<'_>>) -> deserialize::Result<Option<bool>>;
+        let decode: Decode = FromSql::<Nullable<Bool>, GaussDB>::from_nullable_sql;
+
+        assert_eq!(decode(None).unwrap(), None);
+        assert_eq!(decode(Some(GaussDBValue::new(Some(&[1]), 16))).unwrap(), Some(true));
+        assert!(<bool as FromSql<Bool, GaussDB>>::from_nullable_sql(None).is_err());
+
+        use diesel::query_builder::bind_collector::RawBytesBindCollector;
+        use diesel::query_builder::BindCollector;
+
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector
+            .push_bound_value::<Nullable<Bool>, Option<bool>>(&None, &mut TestLookup::builtin())
+            .unwrap();
+        collector
+            .push_bound_value::<Nullable<Bool>, Option<bool>>(&Some(false), &mut TestLookup::builtin())
+            .unwrap();
+        assert_eq!(collector.binds, vec![None, Some(vec![0])]);
+        assert_eq!(collector.metadata[0].oid().unwrap(), 16);
+    }
+
+    #[test]
+    fn test_small_int_round_trip() {
+        let (bytes, oid) = encode::<SmallInt, i16>(&-2);
+        assert_eq!(oid, 21);
+        assert_eq!(bytes, vec![0xff, 0xfe]);
+
+        for value in [i16::MIN, -1, 0, 1, i16::MAX] {
+            let (bytes, _) = encode::<SmallInt, i16>(&value);
+            let decoded: i16 = FromSql::<SmallInt, GaussDB>::from_sql(GaussDBValue::new(Some(&bytes), 21)).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
 }
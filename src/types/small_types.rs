@@ -0,0 +1,170 @@
+//! Support for the `tinyint` and `smalldatetime` types
+//!
+//! Both types come from the compatibility modes of GaussDB and are common in
+//! schemas migrated from MySQL or SQL Server. Their OIDs are not part of the
+//! PostgreSQL type set, so they are looked up through the connection's
+//! metadata lookup like the types in
+//! [`oracle_compat`](crate::types::oracle_compat).
+//!
+//! `tinyint` is a single unsigned byte. `smalldatetime` is sent like
+//! `timestamp` but only keeps whole minutes. Values the column can't hold
+//! exactly are rejected when they are bound, so nothing is silently rounded
+//! or wrapped.
+
+use std::error::Error;
+use std::io::Write;
+
+use crate::backend::{GaussDB, GaussDBTypeMetadata};
+use crate::types::sql_types::{SmallDatetime, TinyInt};
+use crate::value::GaussDBValue;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::HasSqlType;
+
+/// Check that `type_name` can be resolved before a value is bound to it
+fn require_type(out: &mut Output<'_, '_, GaussDB>, type_name: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    match out.metadata_lookup().lookup_type(type_name, None).oid() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("type {} does not exist in this database", type_name).into()),
+    }
+}
+
+impl HasSqlType<TinyInt> for GaussDB {
+    fn metadata(lookup: &mut Self::MetadataLookup) -> GaussDBTypeMetadata {
+        lookup.lookup_type("int1", None)
+    }
+}
+
+impl HasSqlType<SmallDatetime> for GaussDB {
+    fn metadata(lookup: &mut Self::MetadataLookup) -> GaussDBTypeMetadata {
+        lookup.lookup_type("smalldatetime", None)
+    }
+}
+
+impl FromSql<TinyInt, GaussDB> for u8 {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        match value.as_bytes().ok_or("TinyInt value is null")? {
+            [byte] => Ok(*byte),
+            bytes => Err(format!(
+                "Received {} bytes while decoding a tinyint, expected 1. \
+                 Was an expression of a different type accidentally marked as TinyInt?",
+                bytes.len()
+            )
+            .into()),
+        }
+    }
+}
+
+impl FromSql<TinyInt, GaussDB> for i8 {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let byte = <u8 as FromSql<TinyInt, GaussDB>>::from_sql(value)?;
+        i8::try_from(byte).map_err(|_| {
+            format!("tinyint value {} is out of range for i8; tinyint is unsigned, load it as u8", byte).into()
+        })
+    }
+}
+
+impl ToSql<TinyInt, GaussDB> for u8 {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        require_type(out, "int1")?;
+        out.write_all(&[*self])?;
+        Ok(IsNull::No)
+    }
+}
+
+impl ToSql<TinyInt, GaussDB> for i8 {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+        let byte = u8::try_from(*self)
+            .map_err(|_| format!("tinyint can't hold {}; it only stores values from 0 to 255", self))?;
+        require_type(out, "int1")?;
+        out.write_all(&[byte])?;
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use super::*;
+    use chrono::{NaiveDateTime, Timelike};
+    use diesel::sql_types::Timestamp;
+
+    impl FromSql<SmallDatetime, GaussDB> for NaiveDateTime {
+        fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+            <NaiveDateTime as FromSql<Timestamp, GaussDB>>::from_sql(value)
+        }
+    }
+
+    impl ToSql<SmallDatetime, GaussDB> for NaiveDateTime {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, GaussDB>) -> serialize::Result {
+            if self.second() != 0 || self.nanosecond() != 0 {
+                return Err(format!(
+                    "smalldatetime only stores whole minutes and would round {}; \
+                     truncate the seconds first, e.g. with `with_second(0)` and `with_nanosecond(0)`",
+                    self
+                )
+                .into());
+            }
+            require_type(out, "smalldatetime")?;
+            <NaiveDateTime as ToSql<Timestamp, GaussDB>>::to_sql(self, out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::TestLookup;
+    use diesel::query_builder::bind_collector::RawBytesBindCollector;
+    use diesel::query_builder::BindCollector;
+
+    /// The OIDs of `int1` and `smalldatetime`
+    const TYPES: &[(&str, u32, u32)] = &[("int1", 5545, 5546), ("smalldatetime", 9003, 9005)];
+
+    fn encode<ST, T>(value: &T) -> diesel::QueryResult<(Vec<u8>, u32)>
+    where
+        GaussDB: HasSqlType<ST>,
+        T: ToSql<ST, GaussDB> + ?Sized,
+    {
+        let mut collector = RawBytesBindCollector::<GaussDB>::new();
+        collector.push_bound_value::<ST, T>(value, &mut TestLookup::with_types(TYPES))?;
+        let oid = collector.metadata[0].oid().unwrap();
+        Ok((collector.binds.remove(0).unwrap(), oid))
+    }
+
+    #[test]
+    fn test_tinyint_round_trip() {
+        assert_eq!(encode::<TinyInt, _>(&255u8).unwrap(), (vec![255], 5545));
+        assert_eq!(encode::<TinyInt, _>(&127i8).unwrap(), (vec![127], 5545));
+
+        let value = |bytes: &'static [u8]| GaussDBValue::new(Some(bytes), 5545);
+        assert_eq!(<u8 as FromSql<TinyInt, GaussDB>>::from_sql(value(&[200])).unwrap(), 200);
+        assert_eq!(<i8 as FromSql<TinyInt, GaussDB>>::from_sql(value(&[100])).unwrap(), 100);
+        assert!(<u8 as FromSql<TinyInt, GaussDB>>::from_sql(value(&[0, 1])).is_err());
+    }
+
+    #[test]
+    fn test_tinyint_out_of_range() {
+        let error = encode::<TinyInt, _>(&-1i8).unwrap_err().to_string();
+        assert!(error.contains("only stores values from 0 to 255"), "{}", error);
+
+        let value = GaussDBValue::new(Some(&[200]), 5545);
+        let error = <i8 as FromSql<TinyInt, GaussDB>>::from_sql(value).unwrap_err().to_string();
+        assert!(error.contains("load it as u8"), "{}", error);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_smalldatetime_requires_whole_minutes() {
+        use chrono::{NaiveDate, NaiveDateTime, Timelike};
+
+        let minute = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        let (bytes, oid) = encode::<SmallDatetime, _>(&minute).unwrap();
+        assert_eq!(oid, 9003);
+        let decoded = <NaiveDateTime as FromSql<SmallDatetime, GaussDB>>::from_sql(GaussDBValue::new(Some(&bytes), oid));
+        assert_eq!(decoded.unwrap(), minute);
+
+        let with_seconds = minute.with_second(45).unwrap();
+        let error = encode::<SmallDatetime, _>(&with_seconds).unwrap_err().to_string();
+        assert!(error.contains("only stores whole minutes"), "{}", error);
+    }
+}
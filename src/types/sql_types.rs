@@ -30,6 +30,48 @@ pub mod sql_types {
     #[diesel(postgres_type(oid = 26, array_oid = 1028))]
     pub struct Oid;
 
+    /// The `oidvector` SQL type, a list of OIDs used by system catalogs
+    /// such as `pg_index.indclass` and `pg_proc.proargtypes`.
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`Vec<u32>`][Vec]
+    ///
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    /// [Vec]: std::vec::Vec
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    #[diesel(postgres_type(oid = 30, array_oid = 1013))]
+    pub struct OidVector;
+
+    /// The `int2vector` SQL type, a list of small integers used by system
+    /// catalogs such as `pg_index.indkey`.
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`Vec<i16>`][Vec]
+    ///
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    /// [Vec]: std::vec::Vec
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    #[diesel(postgres_type(oid = 22, array_oid = 1006))]
+    pub struct Int2Vector;
+
+    /// The `name` SQL type, used for identifiers in system catalogs such as
+    /// `pg_type.typname` or `pg_namespace.nspname`.
+    ///
+    /// Values are at most 63 bytes long and stored in a fixed size buffer
+    /// padded with `NUL` bytes, which are removed when decoding.
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`String`]
+    ///
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    /// [`String`]: std::string::String
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    #[diesel(postgres_type(oid = 19, array_oid = 1003))]
+    pub struct Name;
+
     /// The ["timestamp with time zone" SQL type][tz], which GaussDB abbreviates
     /// to `timestamptz`.
     ///
@@ -54,6 +96,25 @@ pub mod sql_types {
     #[diesel(postgres_type(oid = 1184, array_oid = 1185))]
     pub struct Timestamptz;
 
+    /// The ["time with time zone" SQL type][tz], which GaussDB abbreviates
+    /// to `timetz`.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`GaussDBTimeTz`]
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`GaussDBTimeTz`]
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    /// [`GaussDBTimeTz`]: crate::types::date_and_time::GaussDBTimeTz
+    /// [tz]: https://www.postgresql.org/docs/current/datatype-datetime.html
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    #[diesel(postgres_type(oid = 1266, array_oid = 1270))]
+    pub struct Timetz;
+
     /// The [`Array`] SQL type.
     ///
     /// This wraps another type to represent a SQL array of that type.
@@ -147,6 +208,169 @@ pub mod sql_types {
     #[diesel(postgres_type(oid = 17, array_oid = 1001))]
     pub struct Bytea;
 
+    /// The `blob` SQL type of GaussDB's Oracle compatibility mode
+    /// (`DBCOMPATIBILITY 'A'`).
+    ///
+    /// The type only exists in databases created in that mode, so its OID
+    /// is looked up at runtime.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`Vec<u8>`]
+    /// - [`&[u8]`]
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`Vec<u8>`]
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    /// [`Vec<u8>`]: std::vec::Vec
+    /// [`&[u8]`]: https://doc.rust-lang.org/std/primitive.slice.html
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct Blob;
+
+    /// The `raw` SQL type of GaussDB's Oracle compatibility mode, a
+    /// variable length byte string.
+    ///
+    /// Like [`Blob`] its OID is looked up at runtime.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`Vec<u8>`]
+    /// - [`&[u8]`]
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`Vec<u8>`]
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    /// [`Vec<u8>`]: std::vec::Vec
+    /// [`&[u8]`]: https://doc.rust-lang.org/std/primitive.slice.html
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct Raw;
+
+    /// The `clob` SQL type of GaussDB's Oracle compatibility mode, a
+    /// character large object.
+    ///
+    /// Like [`Blob`] its OID is looked up at runtime.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`String`]
+    /// - [`&str`][str]
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`String`]
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct Clob;
+
+    /// The `varchar2` SQL type of GaussDB's Oracle compatibility mode, a
+    /// length-limited variable length string.
+    ///
+    /// Like [`Blob`] its OID is looked up at runtime. On the wire it is the
+    /// same as `varchar`.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`String`]
+    /// - [`&str`][str]
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`String`]
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct Varchar2;
+
+    /// The `nvarchar2` SQL type of GaussDB's Oracle compatibility mode, a
+    /// length-limited variable length string.
+    ///
+    /// Like [`Blob`] its OID is looked up at runtime. On the wire it is the
+    /// same as `varchar`.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`String`]
+    /// - [`&str`][str]
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`String`]
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct Nvarchar2;
+
+    /// The `nchar` SQL type of GaussDB's Oracle compatibility mode, a
+    /// blank-padded fixed length string.
+    ///
+    /// Like [`Blob`] its OID is looked up at runtime. Loaded values keep
+    /// their padding, as with `char`.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`String`]
+    /// - [`&str`][str]
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`String`]
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct Nchar;
+
+    /// The `tinyint` SQL type, an unsigned one byte integer from 0 to 255,
+    /// named `int1` in the system catalogs.
+    ///
+    /// Its OID is looked up at runtime. Values outside the range of the
+    /// Rust type are reported as errors instead of wrapping.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`u8`]
+    /// - [`i8`], if not negative
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`u8`]
+    /// - [`i8`], for values up to 127
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct TinyInt;
+
+    /// The `smalldatetime` SQL type, a timestamp without time zone stored
+    /// to the minute.
+    ///
+    /// Its OID is looked up at runtime. The server would round seconds to
+    /// the nearest minute, so binding a value with seconds is an error
+    /// instead.
+    ///
+    /// ### [`ToSql`] impls
+    ///
+    /// - [`chrono::NaiveDateTime`] with `feature = "chrono"`, on a whole minute
+    ///
+    /// ### [`FromSql`] impls
+    ///
+    /// - [`chrono::NaiveDateTime`] with `feature = "chrono"`
+    ///
+    /// [`ToSql`]: diesel::serialize::ToSql
+    /// [`FromSql`]: diesel::deserialize::FromSql
+    #[derive(Debug, Clone, Copy, Default, QueryId, SqlType)]
+    pub struct SmallDatetime;
+
     /// The [`INET`] SQL type.
     ///
     /// ### [`ToSql`] impls
@@ -301,6 +525,9 @@ mod tests {
     fn test_sql_types_exist() {
         // Just test that the types can be instantiated
         let _oid = Oid;
+        let _oidvector = OidVector;
+        let _int2vector = Int2Vector;
+        let _name = Name;
         let _timestamptz = Timestamptz;
         let _uuid = Uuid;
         let _json = Json;
@@ -0,0 +1,193 @@
+//! Support for the `oidvector` and `int2vector` system types
+//!
+//! These legacy vector types appear in catalog columns such as
+//! `pg_index.indkey`, `pg_index.indclass` or `pg_proc.proargtypes`. On the
+//! wire they use the one-dimensional array layout, but unlike regular
+//! arrays they are indexed from 0 and can't contain `NULL`. Their text form
+//! is a space separated list, which is what the simple query protocol
+//! returns; both forms are decoded.
+
+use crate::backend::GaussDB;
+use crate::types::sql_types::{Int2Vector, OidVector};
+use crate::value::GaussDBValue;
+use byteorder::{NetworkEndian, ReadBytesExt};
+use diesel::deserialize::{self, FromSql};
+use std::str::FromStr;
+
+const INT2_OID: u32 = 21;
+const OID_OID: u32 = 26;
+
+impl FromSql<Int2Vector, GaussDB> for Vec<i16> {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let bytes = value.as_bytes().ok_or("int2vector value is null")?;
+        decode_vector(bytes, "int2vector", INT2_OID, |mut element| {
+            element.read_i16::<NetworkEndian>()
+        })
+    }
+}
+
+impl FromSql<OidVector, GaussDB> for Vec<u32> {
+    fn from_sql(value: GaussDBValue<'_>) -> deserialize::Result<Self> {
+        let bytes = value.as_bytes().ok_or("oidvector value is null")?;
+        decode_vector(bytes, "oidvector", OID_OID, |mut element| {
+            element.read_u32::<NetworkEndian>()
+        })
+    }
+}
+
+/// Decode either representation of a vector type
+///
+/// The binary form starts with the dimension count as a 4 byte integer,
+/// so its first byte is always 0, which never starts the text form.
+fn decode_vector<T, F>(
+    bytes: &[u8],
+    type_name: &str,
+    element_oid: u32,
+    read_element: F,
+) -> deserialize::Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+    F: Fn(&[u8]) -> std::io::Result<T>,
+{
+    match bytes.first() {
+        Some(0) => decode_binary(bytes, type_name, element_oid, read_element),
+        _ => decode_text(bytes),
+    }
+}
+
+/// Decode `ndim`, `flags`, `element_oid`, then for one dimension its length
+/// and lower bound, followed by a length-prefixed value per element
+fn decode_binary<T, F>(
+    mut bytes: &[u8],
+    type_name: &str,
+    element_oid: u32,
+    read_element: F,
+) -> deserialize::Result<Vec<T>>
+where
+    F: Fn(&[u8]) -> std::io::Result<T>,
+{
+    let num_dimensions = bytes.read_i32::<NetworkEndian>()?;
+    let has_null = bytes.read_i32::<NetworkEndian>()? != 0;
+    let oid = bytes.read_u32::<NetworkEndian>()?;
+
+    if oid != element_oid {
+        return Err(format!(
+            "Unexpected element type {} in {}, expected {}",
+            oid, type_name, element_oid
+        )
+        .into());
+    }
+    if has_null {
+        return Err(format!("{} values can't contain NULL", type_name).into());
+    }
+    match num_dimensions {
+        0 => return Ok(Vec::new()),
+        1 => {}
+        _ => return Err(format!("{} values must have one dimension", type_name).into()),
+    }
+
+    let num_elements = bytes.read_i32::<NetworkEndian>()?;
+    // The lower bound, 0 for these types
+    let _lower_bound = bytes.read_i32::<NetworkEndian>()?;
+
+    (0..num_elements)
+        .map(|_| {
+            let len = bytes.read_i32::<NetworkEndian>()?;
+            let len = usize::try_from(len)
+                .map_err(|_| format!("{} values can't contain NULL", type_name))?;
+            if bytes.len() < len {
+                return Err(format!("Unexpected end of {} value", type_name).into());
+            }
+            let (element, rest) = bytes.split_at(len);
+            bytes = rest;
+            Ok(read_element(element)?)
+        })
+        .collect()
+}
+
+fn decode_text<T>(bytes: &[u8]) -> deserialize::Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    std::str::from_utf8(bytes)?
+        .split_ascii_whitespace()
+        .map(|element| element.parse().map_err(Into::into))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The binary form of a one-dimensional vector with a lower bound of 0
+    fn binary_vector(element_oid: u32, elements: &[&[u8]]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&1i32.to_be_bytes());
+        bytes.extend_from_slice(&0i32.to_be_bytes());
+        bytes.extend_from_slice(&element_oid.to_be_bytes());
+        bytes.extend_from_slice(&(elements.len() as i32).to_be_bytes());
+        bytes.extend_from_slice(&0i32.to_be_bytes());
+        for element in elements {
+            bytes.extend_from_slice(&(element.len() as i32).to_be_bytes());
+            bytes.extend_from_slice(element);
+        }
+        bytes
+    }
+
+    fn decode_int2vector(bytes: &[u8]) -> deserialize::Result<Vec<i16>> {
+        FromSql::<Int2Vector, GaussDB>::from_sql(GaussDBValue::new(Some(bytes), 22))
+    }
+
+    fn decode_oidvector(bytes: &[u8]) -> deserialize::Result<Vec<u32>> {
+        FromSql::<OidVector, GaussDB>::from_sql(GaussDBValue::new(Some(bytes), 30))
+    }
+
+    #[test]
+    fn test_decode_binary_int2vector() {
+        let bytes = binary_vector(INT2_OID, &[&1i16.to_be_bytes(), &3i16.to_be_bytes(), &(-1i16).to_be_bytes()]);
+        assert_eq!(decode_int2vector(&bytes).unwrap(), vec![1, 3, -1]);
+    }
+
+    #[test]
+    fn test_decode_binary_oidvector() {
+        let bytes = binary_vector(OID_OID, &[&23u32.to_be_bytes(), &4_000_000_000u32.to_be_bytes()]);
+        assert_eq!(decode_oidvector(&bytes).unwrap(), vec![23, 4_000_000_000]);
+    }
+
+    #[test]
+    fn test_decode_empty_binary_vector() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&0i32.to_be_bytes());
+        bytes.extend_from_slice(&0i32.to_be_bytes());
+        bytes.extend_from_slice(&OID_OID.to_be_bytes());
+        assert_eq!(decode_oidvector(&bytes).unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_decode_text_vectors() {
+        assert_eq!(decode_int2vector(b"1 3 -1").unwrap(), vec![1, 3, -1]);
+        assert_eq!(decode_oidvector(b"23 25").unwrap(), vec![23, 25]);
+        assert_eq!(decode_oidvector(b"").unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_reject_wrong_element_type() {
+        let bytes = binary_vector(OID_OID, &[&23u32.to_be_bytes()]);
+        assert!(decode_int2vector(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_reject_truncated_value() {
+        let bytes = binary_vector(INT2_OID, &[&1i16.to_be_bytes(), &2i16.to_be_bytes()]);
+        assert!(decode_int2vector(&bytes[..bytes.len() - 3]).is_err());
+    }
+
+    #[test]
+    fn test_reject_null_element() {
+        let mut bytes = binary_vector(INT2_OID, &[]);
+        bytes[7] = 1;
+        assert!(decode_int2vector(&bytes).is_err());
+    }
+}
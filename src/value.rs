@@ -3,6 +3,7 @@
 //! This module provides the value type used for handling raw database values
 //! in GaussDB, similar to PostgreSQL's PgValue.
 
+use diesel::deserialize;
 use std::fmt;
 
 /// A raw value from a GaussDB query result
@@ -29,6 +30,26 @@ impl<'a> GaussDBValue<'a> {
         self.raw_bytes
     }
 
+    /// Borrow this value as text, without copying it
+    ///
+    /// The returned `&str` points into the row buffer and lives as long as
+    /// the row does; this is what `FromSql<Text, GaussDB>` decodes from.
+    /// Diesel's `FromSql` can't return borrowed data, so use this together
+    /// with [`GaussDBRow::get_str`] to read text columns without
+    /// allocating. Fails on `NULL` and on invalid UTF-8, naming the first
+    /// invalid byte. Values of the `name` type have their `NUL` padding
+    /// removed.
+    ///
+    /// [`GaussDBRow::get_str`]: crate::connection::row::GaussDBRow::get_str
+    pub fn as_str(&self) -> deserialize::Result<&'a str> {
+        let bytes = self.raw_bytes.ok_or("Text value is null")?;
+        if self.type_oid == crate::types::name::NAME_OID {
+            crate::types::name::decode_name(bytes)
+        } else {
+            decode_utf8(bytes)
+        }
+    }
+
     /// Get the type OID of this value
     pub fn type_oid(&self) -> u32 {
         self.type_oid
@@ -59,6 +80,29 @@ impl<'a> GaussDBValue<'a> {
     }
 }
 
+/// Decode text sent by the server
+///
+/// The driver only supports the `UTF8` client encoding, see
+/// [`GaussDBConnection::set_client_encoding`]. Invalid bytes can still show
+/// up, e.g. in a `SQL_ASCII` database, and fail with an error giving their
+/// position instead of being replaced.
+///
+/// [`GaussDBConnection::set_client_encoding`]: crate::connection::GaussDBConnection::set_client_encoding
+pub(crate) fn decode_utf8(bytes: &[u8]) -> deserialize::Result<&str> {
+    std::str::from_utf8(bytes).map_err(|e| {
+        let position = e.valid_up_to();
+        format!(
+            "Text value is not valid UTF-8: invalid byte 0x{:02x} at offset {} of {}; \
+             the value was probably stored in a database without encoding checks, \
+             such as SQL_ASCII",
+            bytes[position],
+            position,
+            bytes.len()
+        )
+        .into()
+    })
+}
+
 impl<'a> fmt::Debug for GaussDBValue<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("GaussDBValue")
@@ -119,6 +163,38 @@ mod tests {
         assert!(value.is_empty());
     }
 
+    #[test]
+    fn test_gaussdb_value_as_str() {
+        let data = String::from("zero copy");
+        let value = GaussDBValue::new(Some(data.as_bytes()), 25);
+        let text = value.as_str().unwrap();
+        assert_eq!(text, "zero copy");
+        // Borrowed from the buffer, not copied
+        assert_eq!(text.as_ptr(), data.as_ptr());
+
+        assert!(GaussDBValue::new(Some(b"\xff\xfe"), 25).as_str().is_err());
+        assert!(GaussDBValue::new(None, 25).as_str().is_err());
+    }
+
+    #[test]
+    fn test_invalid_utf8_text_error() {
+        use crate::backend::GaussDB;
+        use diesel::deserialize::FromSql;
+        use diesel::sql_types::Text;
+
+        // "café" in LATIN1
+        let latin1 = b"caf\xe9";
+        let err = <String as FromSql<Text, GaussDB>>::from_sql(GaussDBValue::new(Some(latin1), 25)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Text value is not valid UTF-8: invalid byte 0xe9 at offset 3 of 4; \
+             the value was probably stored in a database without encoding checks, such as SQL_ASCII"
+        );
+
+        // Also for `name` values read as text
+        assert!(GaussDBValue::new(Some(b"\xc3\0\0"), 19).as_str().is_err());
+    }
+
     #[test]
     fn test_gaussdb_value_debug() {
         let data = b"test";
@@ -0,0 +1,56 @@
+//! Tests for advisory locks
+//!
+//! This module tests taking and releasing session-level advisory locks
+//! across connections.
+
+mod common;
+
+use common::establish_test_connection;
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    const LOCK_KEY: i64 = 8_610_001;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_advisory_lock_across_connections() {
+        let (mut holder, mut other) = (establish_test_connection(), establish_test_connection());
+
+        holder.advisory_lock(LOCK_KEY).expect("Failed to take advisory lock");
+
+        // Held by another session: not an error, just `false`
+        assert!(!other.try_advisory_lock(LOCK_KEY).unwrap());
+        // Not held by this session
+        assert!(!other.advisory_unlock(LOCK_KEY).unwrap());
+
+        assert!(holder.advisory_unlock(LOCK_KEY).unwrap());
+
+        assert!(other.try_advisory_lock(LOCK_KEY).unwrap());
+        assert!(other.advisory_unlock(LOCK_KEY).unwrap());
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_advisory_lock_guard_unlocks_once() {
+        let (mut holder, mut other) = (establish_test_connection(), establish_test_connection());
+
+        // Take the lock twice in this session; advisory locks stack, so
+        // releasing the guard must leave exactly one level held
+        holder.advisory_lock(LOCK_KEY + 1).unwrap();
+        {
+            let guard = holder.advisory_lock_guard(LOCK_KEY + 1).unwrap();
+            assert_eq!(guard.key(), LOCK_KEY + 1);
+        }
+        assert!(other.try_advisory_lock_guard(LOCK_KEY + 1).unwrap().is_none());
+
+        let guard = holder.try_advisory_lock_guard(LOCK_KEY + 1).unwrap().unwrap();
+        assert!(guard.unlock().unwrap());
+        assert!(other.try_advisory_lock_guard(LOCK_KEY + 1).unwrap().is_none());
+
+        assert!(holder.advisory_unlock(LOCK_KEY + 1).unwrap());
+        let guard = other.try_advisory_lock_guard(LOCK_KEY + 1).unwrap();
+        assert!(guard.is_some());
+    }
+}
@@ -122,15 +122,14 @@ fn test_array_with_null_elements() {
     bytes.write_i32::<NetworkEndian>(4).unwrap(); // element size = 4 bytes
     bytes.write_i32::<NetworkEndian>(3).unwrap(); // value = 3
     
+    // A NULL element can't be decoded into a non-nullable element type
     let value = GaussDBValue::new(Some(&bytes), 1007); // int4 array OID
     let result: Result<Vec<i32>, _> = <Vec<i32> as FromSql<Array<Integer>, GaussDB>>::from_sql(value);
+    assert!(result.is_err());
 
-    // Note: Our current implementation skips NULL elements
-    // In a full implementation, we'd need Option<T> support
-    assert!(result.is_ok());
-    let array = result.unwrap();
-    assert_eq!(array.len(), 2); // Only non-null elements
-    assert_eq!(array, vec![1, 3]);
+    let value = GaussDBValue::new(Some(&bytes), 1007);
+    let array = <Vec<Option<i32>> as FromSql<Array<Nullable<Integer>>, GaussDB>>::from_sql(value).unwrap();
+    assert_eq!(array, vec![Some(1), None, Some(3)]);
 }
 
 // Note: ToSql tests are disabled since we don't implement ToSql for arrays yet
@@ -0,0 +1,90 @@
+//! Tests for cleaning up prepared statements
+//!
+//! Dropping a connection deallocates the prepared statements of its session,
+//! including the ones in its statement cache, which can only be observed
+//! with a real database.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::connection::{Connection, InstrumentationEvent, SimpleConnection};
+use diesel_gaussdb::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Record the SQL of every query started on `connection`
+fn record_queries(connection: &mut GaussDBConnection) -> Arc<Mutex<Vec<String>>> {
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&queries);
+    connection.set_instrumentation(move |event: InstrumentationEvent<'_>| {
+        if let InstrumentationEvent::StartQuery { query, .. } = event {
+            seen.lock().unwrap().push(query.to_string());
+        }
+    });
+    queries
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_drop_deallocates_prepared_statements() {
+        let mut connection = establish_test_connection();
+
+        connection
+            .batch_execute("PREPARE drop_test_stmt AS SELECT 1; EXECUTE drop_test_stmt;")
+            .unwrap();
+        let queries = record_queries(&mut connection);
+
+        drop(connection);
+
+        assert_eq!(*queries.lock().unwrap(), vec!["DEALLOCATE ALL".to_string()]);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_statements_are_prepared_once() {
+        use diesel::sql_types::Integer;
+
+        let mut connection = establish_test_connection();
+
+        let cached = Arc::new(Mutex::new(0));
+        let seen = Arc::clone(&cached);
+        connection.set_instrumentation(move |event: InstrumentationEvent<'_>| {
+            if let InstrumentationEvent::CacheQuery { .. } = event {
+                *seen.lock().unwrap() += 1;
+            }
+        });
+
+        for _ in 0..3 {
+            let value = diesel::select(1.into_sql::<Integer>()).get_result::<i32>(&mut connection).unwrap();
+            assert_eq!(value, 1);
+        }
+
+        assert_eq!(*cached.lock().unwrap(), 1);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_deallocate_all_removes_prepared_statements() {
+        let mut connection = establish_test_connection();
+
+        connection.batch_execute("PREPARE dealloc_test_stmt AS SELECT 1").unwrap();
+        connection.deallocate_all().unwrap();
+
+        assert!(connection.batch_execute("EXECUTE dealloc_test_stmt").is_err());
+    }
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_drop_of_broken_connection_does_not_panic() {
+        let mut connection = establish_test_connection();
+
+        connection.batch_execute("PREPARE broken_test_stmt AS SELECT 1").unwrap();
+        // The server ends the session, so the statement can't be deallocated
+        let _ = connection.batch_execute("SELECT pg_terminate_backend(pg_backend_pid())");
+
+        drop(connection);
+    }
+}
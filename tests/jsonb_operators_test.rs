@@ -0,0 +1,248 @@
+//! Tests for the JSONB key-existence operators and JSONB functions
+//!
+//! This module tests the SQL generated for `?`, `?|` and `?&` and filters
+//! rows of a real table by the keys of their JSONB documents. It also
+//! aggregates rows into a JSON object with `jsonb_object_agg` and expands
+//! documents into rows with `jsonb_array_elements` and `jsonb_each`.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel::query_builder::{AsQuery, QueryBuilder, QueryFragment};
+use diesel_gaussdb::backend::GaussDB;
+use diesel_gaussdb::expression::dsl::{jsonb_array_elements, jsonb_each, jsonb_object_agg};
+use diesel_gaussdb::prelude::*;
+use diesel_gaussdb::query_builder::GaussDBQueryBuilder;
+
+diesel::table! {
+    profiles (id) {
+        id -> Integer,
+        attributes -> Jsonb,
+    }
+}
+
+diesel_gaussdb::allow_function_relations!(profiles);
+
+diesel::table! {
+    settings (id) {
+        id -> Integer,
+        profile_id -> Integer,
+        name -> Text,
+        value -> Nullable<Integer>,
+    }
+}
+
+fn generate_sql<Q: QueryFragment<GaussDB>>(query: Q) -> String {
+    let mut query_builder = GaussDBQueryBuilder::new();
+    query.to_sql(&mut query_builder, &GaussDB).unwrap();
+    query_builder.finish()
+}
+
+#[test]
+fn test_key_existence_sql() {
+    let query = profiles::table
+        .filter(profiles::attributes.jsonb_has_key("nickname"))
+        .select(profiles::id);
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "profiles"."id" FROM "profiles" WHERE "profiles"."attributes" ? $1"#
+    );
+
+    let query = profiles::table
+        .filter(profiles::attributes.jsonb_has_any_key(vec!["phone", "email"]))
+        .select(profiles::id);
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "profiles"."id" FROM "profiles" WHERE "profiles"."attributes" ?| $1"#
+    );
+
+    let query = profiles::table
+        .filter(profiles::attributes.jsonb_has_all_keys(vec!["phone", "email"]))
+        .select(profiles::id);
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "profiles"."id" FROM "profiles" WHERE "profiles"."attributes" ?& $1"#
+    );
+}
+
+#[test]
+fn test_jsonb_object_agg_sql() {
+    let query = settings::table
+        .group_by(settings::profile_id)
+        .select((settings::profile_id, jsonb_object_agg(settings::name, settings::value)));
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "settings"."profile_id", JSONB_OBJECT_AGG("settings"."name", "settings"."value") FROM "settings" GROUP BY "settings"."profile_id""#
+    );
+}
+
+#[test]
+fn test_jsonb_set_functions_sql() {
+    use diesel::dsl::sql;
+    use diesel::sql_types::Jsonb;
+
+    // In the select list, one row per element
+    let query = profiles::table.select((profiles::id, jsonb_array_elements(profiles::attributes)));
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "profiles"."id", jsonb_array_elements("profiles"."attributes") FROM "profiles""#
+    );
+
+    // As a relation joined to the table holding the documents
+    let attributes = jsonb_each(profiles::attributes).alias("attr");
+    let query = attributes
+        .clone()
+        .joined_to(profiles::table)
+        .select((profiles::id, attributes.key(), attributes.value()));
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "profiles"."id", "attr"."key", "attr"."value" FROM ("profiles" INNER JOIN jsonb_each("profiles"."attributes") AS "attr"("key", "value") ON TRUE)"#
+    );
+
+    // As the only relation of a query
+    let elements = jsonb_array_elements(sql::<Jsonb>("'[1, 2]'")).alias("e", "element");
+    let query = elements.clone().as_query().select(elements.value());
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "e"."element" FROM jsonb_array_elements('[1, 2]') AS "e"("element")"#
+    );
+
+    // Filtered and ordered like any other query
+    let attributes = jsonb_each(sql::<Jsonb>("'{}'")).alias("attr");
+    let query = attributes
+        .clone()
+        .as_query()
+        .select(attributes.key())
+        .filter(attributes.key().ne("secret"))
+        .order(attributes.key());
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "attr"."key" FROM jsonb_each('{}') AS "attr"("key", "value") WHERE ("attr"."key" != $1) ORDER BY "attr"."key""#
+    );
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_filter_by_jsonb_keys() {
+        let mut connection = establish_test_connection();
+
+        let setup_sql = r#"
+            CREATE TEMPORARY TABLE profiles (id INTEGER PRIMARY KEY, attributes JSONB NOT NULL);
+            INSERT INTO profiles VALUES
+                (1, '{"nickname": "ace", "phone": "555-0100"}'),
+                (2, '{"email": "b@example.com"}'),
+                (3, '{"phone": "555-0101", "email": "c@example.com"}'),
+                (4, '{"settings": {"nickname": "nested"}}');
+        "#;
+        if connection.batch_execute(setup_sql).is_err() {
+            println!("Skipping test - could not create test table");
+            return;
+        }
+
+        // Only top-level keys count
+        let with_nickname = profiles::table
+            .filter(profiles::attributes.jsonb_has_key("nickname"))
+            .select(profiles::id)
+            .order(profiles::id)
+            .load::<i32>(&mut connection)
+            .unwrap();
+        assert_eq!(with_nickname, vec![1]);
+
+        let reachable = profiles::table
+            .filter(profiles::attributes.jsonb_has_any_key(vec!["phone", "email"]))
+            .select(profiles::id)
+            .order(profiles::id)
+            .load::<i32>(&mut connection)
+            .unwrap();
+        assert_eq!(reachable, vec![1, 2, 3]);
+
+        let complete = profiles::table
+            .filter(profiles::attributes.jsonb_has_all_keys(vec!["phone", "email"]))
+            .select(profiles::id)
+            .order(profiles::id)
+            .load::<i32>(&mut connection)
+            .unwrap();
+        assert_eq!(complete, vec![3]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    #[ignore] // Ignored by default
+    fn test_aggregate_and_expand_jsonb() {
+        use diesel::sql_types::Jsonb;
+        use serde_json::{json, Value};
+
+        let mut connection = establish_test_connection();
+
+        let setup_sql = r#"
+            CREATE TEMPORARY TABLE profiles (id INTEGER PRIMARY KEY, attributes JSONB NOT NULL);
+            CREATE TEMPORARY TABLE settings (
+                id INTEGER PRIMARY KEY,
+                profile_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                value INTEGER
+            );
+            INSERT INTO profiles VALUES
+                (1, '{"nickname": "ace", "level": 3}'),
+                (2, '{"nickname": "bee"}');
+            INSERT INTO settings VALUES
+                (1, 1, 'volume', 7),
+                (2, 1, 'brightness', NULL),
+                (3, 2, 'volume', 2);
+        "#;
+        if connection.batch_execute(setup_sql).is_err() {
+            println!("Skipping test - could not create test tables");
+            return;
+        }
+
+        // Rows into one object per profile, NULL becoming null
+        let objects = settings::table
+            .group_by(settings::profile_id)
+            .select((settings::profile_id, jsonb_object_agg(settings::name, settings::value)))
+            .order(settings::profile_id)
+            .load::<(i32, Option<Value>)>(&mut connection)
+            .unwrap();
+        assert_eq!(
+            objects,
+            vec![
+                (1, Some(json!({"volume": 7, "brightness": null}))),
+                (2, Some(json!({"volume": 2}))),
+            ]
+        );
+
+        // A bound array into one row per element
+        let elements = jsonb_array_elements(json!([1, "two", {"three": 3}]).into_sql::<Jsonb>())
+            .alias("e", "element");
+        let values = elements
+            .clone()
+            .as_query()
+            .select(elements.value())
+            .load::<Value>(&mut connection)
+            .unwrap();
+        assert_eq!(values, vec![json!(1), json!("two"), json!({"three": 3})]);
+
+        // The members of every profile's document
+        let attributes = jsonb_each(profiles::attributes).alias("attr");
+        let members = attributes
+            .clone()
+            .joined_to(profiles::table)
+            .select((profiles::id, attributes.key(), attributes.value()))
+            .order((profiles::id, attributes.key()))
+            .load::<(i32, String, Value)>(&mut connection)
+            .unwrap();
+        assert_eq!(
+            members,
+            vec![
+                (1, "level".to_string(), json!(3)),
+                (1, "nickname".to_string(), json!("ace")),
+                (2, "nickname".to_string(), json!("bee")),
+            ]
+        );
+    }
+}
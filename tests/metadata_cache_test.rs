@@ -0,0 +1,49 @@
+//! Tests for sharing the metadata cache between connections
+//!
+//! How the connection's own cache and the shared cache are consulted is
+//! covered by the unit tests of the `metadata_lookup` module; this module
+//! checks that a type looked up by one connection is resolved by another
+//! one from the shared cache alone.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::connection::SimpleConnection;
+use diesel_gaussdb::backend::GaussDBMetadataLookup;
+use diesel_gaussdb::metadata_lookup::GaussDBMetadataCache;
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_second_connection_hits_shared_cache() {
+        let (mut first, mut second) = (establish_test_connection(), establish_test_connection());
+
+        let setup_sql = r#"
+            DROP TYPE IF EXISTS shared_cache_mood;
+            CREATE TYPE shared_cache_mood AS ENUM ('happy', 'sad');
+        "#;
+        if first.batch_execute(setup_sql).is_err() {
+            println!("Skipping test - could not create test type");
+            return;
+        }
+
+        let cache = Arc::new(Mutex::new(GaussDBMetadataCache::new()));
+        first.set_shared_metadata_cache(cache.clone());
+        second.set_shared_metadata_cache(cache.clone());
+
+        let oid = first.lookup_type("shared_cache_mood", None).oid().unwrap();
+        assert_eq!(cache.lock().unwrap().len(), 1);
+
+        // The type is gone from the database, so only a cache hit can resolve it
+        first.batch_execute("DROP TYPE shared_cache_mood").unwrap();
+        assert_eq!(second.lookup_type("shared_cache_mood", None).oid().unwrap(), oid);
+
+        // A connection without the shared cache has to query and fails
+        let mut third = establish_test_connection();
+        assert!(third.lookup_type("shared_cache_mood", None).oid().is_err());
+    }
+}
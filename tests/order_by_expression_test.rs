@@ -0,0 +1,130 @@
+//! Tests for ordering by computed expressions
+//!
+//! This module tests `ORDER BY` with function calls, arithmetic and
+//! aggregates, both repeated in full and referenced through a select alias.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::connection::SimpleConnection;
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use diesel::query_builder::{QueryBuilder, QueryFragment};
+use diesel_gaussdb::backend::GaussDB;
+use diesel_gaussdb::expression::functions::length;
+use diesel_gaussdb::prelude::*;
+use diesel_gaussdb::query_builder::GaussDBQueryBuilder;
+
+diesel::table! {
+    articles (id) {
+        id -> Integer,
+        author_id -> Integer,
+        title -> Text,
+        likes -> Integer,
+    }
+}
+
+fn generate_sql<Q: QueryFragment<GaussDB>>(query: Q) -> String {
+    let mut query_builder = GaussDBQueryBuilder::new();
+    query.to_sql(&mut query_builder, &GaussDB).unwrap();
+    query_builder.finish()
+}
+
+#[test]
+fn test_order_by_function_call() {
+    let query = articles::table
+        .select(articles::title)
+        .order_by((length(articles::title).desc(), articles::id));
+
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "articles"."title" FROM "articles" ORDER BY LENGTH("articles"."title") DESC, "articles"."id""#
+    );
+}
+
+#[test]
+fn test_order_by_arithmetic() {
+    let query = articles::table
+        .select(articles::id)
+        .order_by((articles::likes * 2 + articles::id).desc());
+
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "articles"."id" FROM "articles" ORDER BY (("articles"."likes" * $1) + "articles"."id") DESC"#
+    );
+}
+
+#[test]
+fn test_order_by_count_expression() {
+    let query = articles::table
+        .group_by(articles::author_id)
+        .select((articles::author_id, count_star()))
+        .order_by((count_star().desc(), articles::author_id));
+
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "articles"."author_id", COUNT(*) FROM "articles" GROUP BY "articles"."author_id" ORDER BY COUNT(*) DESC, "articles"."author_id""#
+    );
+}
+
+#[test]
+fn test_order_by_count_alias() {
+    let article_count = count_star().aliased("article_count");
+    let query = articles::table
+        .group_by(articles::author_id)
+        .select((articles::author_id, article_count))
+        .order_by((article_count.alias().desc(), articles::author_id));
+
+    assert_eq!(
+        generate_sql(query),
+        r#"SELECT "articles"."author_id", COUNT(*) AS "article_count" FROM "articles" GROUP BY "articles"."author_id" ORDER BY "article_count" DESC, "articles"."author_id""#
+    );
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_alias_and_expression_order_agree() {
+        let mut connection = establish_test_connection();
+
+        let setup_sql = r#"
+            CREATE TEMPORARY TABLE articles (id INTEGER PRIMARY KEY, author_id INTEGER, title TEXT, likes INTEGER);
+            INSERT INTO articles VALUES
+                (1, 10, 'Short', 3), (2, 20, 'A longer title', 1),
+                (3, 20, 'Mid title', 7), (4, 30, 'x', 0), (5, 20, 'Another', 2), (6, 30, 'yy', 5);
+        "#;
+        if connection.batch_execute(setup_sql).is_err() {
+            println!("Skipping test - could not create test table");
+            return;
+        }
+
+        let titles = articles::table
+            .select(articles::title)
+            .order_by((length(articles::title).desc(), articles::id))
+            .load::<String>(&mut connection)
+            .unwrap();
+        assert_eq!(titles.first().map(String::as_str), Some("A longer title"));
+        assert_eq!(titles.last().map(String::as_str), Some("x"));
+
+        let by_expression = articles::table
+            .group_by(articles::author_id)
+            .select((articles::author_id, count_star()))
+            .order_by((count_star().desc(), articles::author_id))
+            .load::<(i32, i64)>(&mut connection)
+            .unwrap();
+
+        let article_count = count_star().aliased("article_count");
+        let by_alias = articles::table
+            .group_by(articles::author_id)
+            .select((articles::author_id, article_count))
+            .order_by((article_count.alias().desc(), articles::author_id))
+            .load::<(i32, i64)>(&mut connection)
+            .unwrap();
+
+        assert_eq!(by_expression, vec![(20, 3), (30, 2), (10, 1)]);
+        assert_eq!(by_alias, by_expression);
+    }
+}
@@ -0,0 +1,86 @@
+//! Tests for per-query timeouts
+//!
+//! This module tests `GaussDBConnection::with_timeout`: a query exceeding
+//! the timeout fails with a distinct error, the timeout does not leak into
+//! later queries, and other errors pass through unchanged.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::dsl::sql;
+use diesel::result::Error as DieselError;
+use diesel::sql_types::{Integer, Text};
+use diesel_gaussdb::connection::TimeoutError;
+use diesel_gaussdb::prelude::*;
+use std::time::Duration;
+
+fn statement_timeout(connection: &mut GaussDBConnection) -> String {
+    diesel::select(sql::<Text>("current_setting('statement_timeout')"))
+        .get_result(connection)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_query_exceeding_timeout_fails() {
+        let mut connection = establish_test_connection();
+
+        let original_timeout = statement_timeout(&mut connection);
+        let result = connection
+            .with_timeout(Duration::from_millis(100))
+            .load::<_, String>(diesel::select(sql::<Text>("pg_sleep(2)::text")));
+
+        match result.unwrap_err() {
+            TimeoutError::Timeout(timeout) => assert_eq!(timeout.timeout(), Duration::from_millis(100)),
+            TimeoutError::Query(e) => panic!("expected a timeout, got {}", e),
+        }
+
+        // The connection is still usable and the timeout is gone
+        assert_eq!(statement_timeout(&mut connection), original_timeout);
+        let value: i32 = diesel::select(sql::<Integer>("1")).get_result(&mut connection).unwrap();
+        assert_eq!(value, 1);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_timeout_does_not_leak_into_outer_transaction() {
+        let mut connection = establish_test_connection();
+
+        let original_timeout = statement_timeout(&mut connection);
+        connection
+            .transaction::<_, DieselError, _>(|conn| {
+                let value = conn
+                    .with_timeout(Duration::from_secs(5))
+                    .get_result::<_, i32>(diesel::select(sql::<Integer>("1")))?;
+                assert_eq!(value, 1);
+                assert_eq!(statement_timeout(conn), original_timeout);
+
+                let result = conn
+                    .with_timeout(Duration::from_millis(100))
+                    .execute(diesel::sql_query("SELECT pg_sleep(2)"));
+                assert!(result.is_err_and(|e| e.is_timeout()));
+                assert_eq!(statement_timeout(conn), original_timeout);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_other_errors_pass_through() {
+        let mut connection = establish_test_connection();
+
+        let result = connection
+            .with_timeout(Duration::from_secs(5))
+            .execute(diesel::sql_query("SELECT * FROM query_timeout_missing_table"));
+
+        assert!(matches!(
+            result.unwrap_err(),
+            TimeoutError::Query(DieselError::DatabaseError(_, _))
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+//! Tests for running multi-statement scripts
+//!
+//! This module tests `GaussDBConnection::run_script`, which reports the
+//! result of every statement of a script.
+
+mod common;
+
+use common::establish_test_connection;
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_three_statement_script() {
+        let mut connection = establish_test_connection();
+
+        let results = connection
+            .run_script(
+                "CREATE TEMPORARY TABLE script_items (id INTEGER PRIMARY KEY, note TEXT);
+                 INSERT INTO script_items VALUES (1, 'a;b'), (2, NULL), (3, 'c');
+                 SELECT COUNT(*) FROM script_items;",
+            )
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+
+        // DDL succeeds without a row count
+        assert_eq!(results[0].index, 0);
+        assert_eq!(results[0].rows_affected, 0);
+        assert!(results[0].rows.is_empty());
+
+        assert_eq!(results[1].rows_affected, 3);
+        assert!(results[1].rows.is_empty());
+
+        assert_eq!(results[2].statement, "SELECT COUNT(*) FROM script_items");
+        assert_eq!(results[2].rows_affected, 1);
+        assert_eq!(results[2].scalar(), Some("3"));
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_failing_statement_reports_its_index() {
+        let mut connection = establish_test_connection();
+
+        let err = connection
+            .run_script(
+                "CREATE TEMPORARY TABLE script_failures (id INTEGER PRIMARY KEY);
+                 INSERT INTO script_failures VALUES (1);
+                 INSERT INTO script_failures VALUES (1);
+                 INSERT INTO script_failures VALUES (2);",
+            )
+            .unwrap_err();
+
+        assert_eq!(err.statement_index, 2);
+        assert_eq!(err.statement, "INSERT INTO script_failures VALUES (1)");
+        assert_eq!(err.completed.len(), 2);
+        assert_eq!(err.completed[1].rows_affected, 1);
+
+        // The statement after the failing one was not run
+        let results = connection.run_script("SELECT COUNT(*) FROM script_failures").unwrap();
+        assert_eq!(results[0].scalar(), Some("1"));
+    }
+}
@@ -0,0 +1,160 @@
+//! Tests for session settings
+//!
+//! This module tests setting `application_name` and other run-time
+//! parameters on a connection and reading them back, switching the
+//! current role, and settings scoped to a single transaction.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::dsl::sql;
+use diesel::result::Error as DieselError;
+use diesel::sql_types::Text;
+use diesel_gaussdb::connection::session::LocalSettings;
+use diesel_gaussdb::prelude::*;
+
+fn current_setting(connection: &mut GaussDBConnection, key: &str) -> String {
+    diesel::select(sql::<Text>("current_setting(").bind::<Text, _>(key).sql(")"))
+        .get_result(connection)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_set_application_name() {
+        let mut connection = establish_test_connection();
+
+        // Quotes and backslashes are passed through unchanged
+        let name = r"billing's \worker";
+        connection.set_application_name(name).unwrap();
+        assert_eq!(current_setting(&mut connection, "application_name"), name);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_local_setting_ends_with_transaction() {
+        let mut connection = establish_test_connection();
+
+        let result = connection.set_config("app.tenant_id", "42", true);
+        assert!(matches!(result, Err(DieselError::NotInTransaction)));
+
+        connection.set_config("app.tenant_id", "1", false).unwrap();
+        connection
+            .transaction::<_, DieselError, _>(|conn| {
+                conn.set_config("app.tenant_id", "42", true)?;
+                assert_eq!(current_setting(conn, "app.tenant_id"), "42");
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(current_setting(&mut connection, "app.tenant_id"), "1");
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_transaction_settings_revert_after_transaction() {
+        let mut connection = establish_test_connection();
+
+        let original_timeout = current_setting(&mut connection, "statement_timeout");
+        let settings = LocalSettings::new()
+            .set("statement_timeout", "100ms")
+            .set("app.tenant_id", "7")
+            .set("app.tenant_id", "42");
+
+        // Outside of a transaction nothing is sent
+        let result = settings.apply(&mut connection);
+        assert!(matches!(result, Err(DieselError::NotInTransaction)));
+        assert_eq!(current_setting(&mut connection, "statement_timeout"), original_timeout);
+
+        connection
+            .transaction_with_settings::<_, DieselError, _>(&settings, |conn| {
+                assert_eq!(current_setting(conn, "statement_timeout"), "100ms");
+                // Applied in order, so the later value wins
+                assert_eq!(current_setting(conn, "app.tenant_id"), "42");
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(current_setting(&mut connection, "statement_timeout"), original_timeout);
+
+        // The local timeout applies inside the transaction and is gone after
+        // it rolls back
+        let result = connection.transaction_with_settings::<(), DieselError, _>(&settings, |conn| {
+            diesel::select(sql::<Text>("pg_sleep(1)::text")).get_result::<String>(conn)?;
+            Ok(())
+        });
+        assert!(result.is_err(), "statement should have timed out");
+        assert_eq!(current_setting(&mut connection, "statement_timeout"), original_timeout);
+        let _: String = diesel::select(sql::<Text>("pg_sleep(0.2)::text"))
+            .get_result(&mut connection)
+            .unwrap();
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_set_client_encoding() {
+        let mut connection = establish_test_connection();
+
+        connection.set_client_encoding("utf-8").unwrap();
+        assert_eq!(current_setting(&mut connection, "client_encoding"), "UTF8");
+
+        // Rejected before reaching the server, so the session keeps UTF8
+        let result = connection.set_client_encoding("LATIN1");
+        assert!(matches!(result, Err(DieselError::QueryBuilderError(_))));
+        assert_eq!(current_setting(&mut connection, "client_encoding"), "UTF8");
+
+        let text: String = diesel::select(sql::<Text>("'数据库 café'")).get_result(&mut connection).unwrap();
+        assert_eq!(text, "数据库 café");
+    }
+
+    #[test]
+    #[ignore] // Ignored by default, needs the CREATEROLE privilege
+    fn test_local_role_ends_with_transaction() {
+        use diesel::connection::SimpleConnection;
+
+        let mut connection = establish_test_connection();
+
+        // Roles are shared by the whole cluster, so the name is fixed and the
+        // role is recreated on every run
+        let role = "diesel_tenant_role";
+        let password = "Tenant@2024";
+        let setup_sql = format!(
+            "DROP ROLE IF EXISTS {role}; CREATE ROLE {role} PASSWORD '{password}';"
+        );
+        if connection.batch_execute(&setup_sql).is_err() {
+            println!("Skipping test - could not create test role");
+            return;
+        }
+
+        let current_user = |conn: &mut GaussDBConnection| -> String {
+            diesel::select(sql::<Text>("current_user::text")).get_result(conn).unwrap()
+        };
+        let session_user = current_user(&mut connection);
+
+        let result = connection.set_role(role, true);
+        assert!(matches!(result, Err(DieselError::NotInTransaction)));
+
+        connection
+            .transaction::<_, DieselError, _>(|conn| {
+                conn.set_role_with_password(role, password, true)?;
+                assert_eq!(current_user(conn), role);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(current_user(&mut connection), session_user);
+
+        connection.set_role_with_password(role, password, false).unwrap();
+        assert_eq!(current_user(&mut connection), role);
+        connection.reset_role().unwrap();
+        assert_eq!(current_user(&mut connection), session_user);
+
+        // A quoted name can't break out of the statement
+        let result = connection.set_role("missing\"; DROP ROLE diesel_tenant_role; --", false);
+        assert!(result.is_err());
+
+        connection.batch_execute(&format!("DROP ROLE {role}")).unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+//! Tests for the slow query logger
+//!
+//! The timing itself is tested with a fake clock in the unit tests; these
+//! tests check that a real connection reports its queries to the logger.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::connection::{Connection, SimpleConnection};
+use diesel_gaussdb::connection::slow_query::SlowQuery;
+use diesel_gaussdb::connection::SlowQueryLogger;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[test]
+fn test_logger_reports_threshold() {
+    let logger = SlowQueryLogger::new(Duration::from_millis(250));
+    assert_eq!(logger.threshold(), Duration::from_millis(250));
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_only_slow_statements_are_logged() {
+        let mut connection = establish_test_connection();
+
+        let logged: Arc<Mutex<Vec<SlowQuery>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&logged);
+        connection.set_instrumentation(
+            SlowQueryLogger::new(Duration::from_millis(200))
+                .on_slow_query(move |query| sink.lock().unwrap().push(query.clone())),
+        );
+
+        connection.batch_execute("SELECT 1").unwrap();
+        connection.batch_execute("SELECT pg_sleep(0.5)").unwrap();
+
+        let logged = logged.lock().unwrap();
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].sql, "SELECT pg_sleep(0.5)");
+        assert!(logged[0].elapsed >= Duration::from_millis(500));
+    }
+}
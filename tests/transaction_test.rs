@@ -0,0 +1,294 @@
+//! Tests for transaction helpers
+//!
+//! This module tests connection-level helpers that only make sense inside
+//! a transaction, such as `SET CONSTRAINTS` and chunked batch transactions,
+//! transactions with an isolation level, `in_transaction`, and the reported
+//! transaction depth.
+
+mod common;
+
+use common::establish_test_connection;
+use diesel::connection::SimpleConnection;
+use diesel::result::Error as DieselError;
+use diesel_gaussdb::prelude::*;
+use diesel_gaussdb::transaction::{ConstraintSet, IsolationLevel};
+
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+
+    #[test]
+    #[ignore] // Ignored by default, run with --ignored flag when database is available
+    fn test_set_constraints_outside_transaction_is_rejected() {
+        let mut connection = establish_test_connection();
+
+        let result = connection.set_constraints_deferred(ConstraintSet::All);
+        assert!(matches!(result, Err(DieselError::NotInTransaction)));
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_deferred_fk_allows_child_before_parent() {
+        let mut connection = establish_test_connection();
+
+        let setup_sql = r#"
+            CREATE TEMPORARY TABLE deferred_parents (id INTEGER PRIMARY KEY);
+            CREATE TEMPORARY TABLE deferred_children (
+                id INTEGER PRIMARY KEY,
+                parent_id INTEGER NOT NULL,
+                CONSTRAINT deferred_children_parent_fk FOREIGN KEY (parent_id)
+                    REFERENCES deferred_parents (id) DEFERRABLE INITIALLY IMMEDIATE
+            );
+        "#;
+
+        if connection.batch_execute(setup_sql).is_err() {
+            println!("Skipping test - could not create test tables");
+            return;
+        }
+
+        let result = connection.transaction::<_, DieselError, _>(|conn| {
+            conn.set_constraints_deferred(ConstraintSet::Named(&["deferred_children_parent_fk"]))?;
+            // The child row references a parent that does not exist yet
+            conn.batch_execute("INSERT INTO deferred_children VALUES (1, 10)")?;
+            conn.batch_execute("INSERT INTO deferred_parents VALUES (10)")?;
+            Ok(())
+        });
+
+        assert!(result.is_ok(), "FK check should be deferred until commit: {:?}", result);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_transaction_depth_follows_nesting() {
+        let mut connection = establish_test_connection();
+
+        assert_eq!(connection.transaction_depth(), 0);
+        assert!(!connection.is_in_transaction());
+
+        connection
+            .transaction::<_, DieselError, _>(|conn| {
+                assert_eq!(conn.transaction_depth(), 1);
+                assert!(conn.is_in_transaction());
+
+                // Nested blocks run in a savepoint
+                conn.transaction::<_, DieselError, _>(|conn| {
+                    assert_eq!(conn.transaction_depth(), 2);
+                    Ok(())
+                })?;
+
+                assert_eq!(conn.transaction_depth(), 1);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(connection.transaction_depth(), 0);
+        assert!(!connection.is_in_transaction());
+    }
+
+    fn create_batch_table(connection: &mut GaussDBConnection) -> bool {
+        let setup_sql = "CREATE TEMPORARY TABLE batch_items (id INTEGER PRIMARY KEY)";
+        if connection.batch_execute(setup_sql).is_err() {
+            println!("Skipping test - could not create test table");
+            return false;
+        }
+        true
+    }
+
+    fn count_batch_items(connection: &mut GaussDBConnection) -> i64 {
+        diesel::select(diesel::dsl::sql::<diesel::sql_types::BigInt>(
+            "(SELECT COUNT(*) FROM batch_items)",
+        ))
+        .get_result(connection)
+        .unwrap()
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_batched_transaction_commits_every_chunk() {
+        let mut connection = establish_test_connection();
+        if !create_batch_table(&mut connection) {
+            return;
+        }
+
+        let result = connection.batched_transaction(1..=25, 10, |conn, id| {
+            conn.batch_execute(&format!("INSERT INTO batch_items VALUES ({})", id))
+        });
+
+        assert_eq!(result.unwrap(), 25);
+        assert_eq!(count_batch_items(&mut connection), 25);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_batched_transaction_rolls_back_only_failing_chunk() {
+        let mut connection = establish_test_connection();
+        if !create_batch_table(&mut connection) {
+            return;
+        }
+
+        let result = connection.batched_transaction(1..=25, 10, |conn, id| {
+            if id == 15 {
+                return Err(DieselError::RollbackTransaction);
+            }
+            conn.batch_execute(&format!("INSERT INTO batch_items VALUES ({})", id))
+        });
+
+        let err = result.unwrap_err();
+        assert_eq!(err.committed_chunks, 1);
+        assert_eq!(err.committed_items, 10);
+        assert!(matches!(err.error, DieselError::RollbackTransaction));
+
+        // Items 11 to 14 were inserted in the failed chunk and rolled back
+        assert_eq!(count_batch_items(&mut connection), 10);
+    }
+
+    fn current_isolation(connection: &mut GaussDBConnection) -> String {
+        diesel::select(diesel::dsl::sql::<diesel::sql_types::Text>(
+            "current_setting('transaction_isolation')",
+        ))
+        .get_result(connection)
+        .unwrap()
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_transaction_with_isolation_sets_level() {
+        let mut connection = establish_test_connection();
+
+        let levels = [
+            (IsolationLevel::ReadCommitted, "read committed"),
+            (IsolationLevel::RepeatableRead, "repeatable read"),
+        ];
+        for (level, expected) in levels {
+            let isolation = connection
+                .transaction_with_isolation::<_, DieselError, _>(level, |conn| Ok(current_isolation(conn)))
+                .unwrap();
+            assert_eq!(isolation, expected);
+        }
+
+        // openGauss runs SERIALIZABLE as REPEATABLE READ
+        let isolation = connection
+            .transaction_with_isolation::<_, DieselError, _>(IsolationLevel::Serializable, |conn| {
+                Ok(current_isolation(conn))
+            })
+            .unwrap();
+        assert!(isolation == "serializable" || isolation == "repeatable read", "{}", isolation);
+        assert!(!connection.is_in_transaction());
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_transaction_with_isolation_rolls_back_on_error() {
+        let mut connection = establish_test_connection();
+        if !create_batch_table(&mut connection) {
+            return;
+        }
+
+        let result = connection.transaction_with_isolation(IsolationLevel::RepeatableRead, |conn| {
+            conn.batch_execute("INSERT INTO batch_items VALUES (1)")?;
+            Err::<(), _>(DieselError::RollbackTransaction)
+        });
+
+        assert!(matches!(result, Err(DieselError::RollbackTransaction)));
+        assert!(!connection.is_in_transaction());
+        assert_eq!(count_batch_items(&mut connection), 0);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_transaction_with_isolation_rolls_back_on_panic() {
+        let mut connection = establish_test_connection();
+        if !create_batch_table(&mut connection) {
+            return;
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            connection.transaction_with_isolation::<(), DieselError, _>(
+                IsolationLevel::Serializable,
+                |conn| {
+                    conn.batch_execute("INSERT INTO batch_items VALUES (1)")?;
+                    panic!("closure failed");
+                },
+            )
+        }));
+
+        assert!(result.is_err());
+        // The connection is usable again and the insert is gone
+        assert!(!connection.is_in_transaction());
+        assert_eq!(count_batch_items(&mut connection), 0);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_in_transaction_at_top_level() {
+        let mut connection = establish_test_connection();
+        if !create_batch_table(&mut connection) {
+            return;
+        }
+
+        let depth = connection
+            .in_transaction::<_, DieselError, _>(|conn| {
+                conn.batch_execute("INSERT INTO batch_items VALUES (1)")?;
+                Ok(conn.transaction_depth())
+            })
+            .unwrap();
+        assert_eq!(depth, 1);
+        assert!(!connection.is_in_transaction());
+        assert_eq!(count_batch_items(&mut connection), 1);
+
+        let result = connection.in_transaction(|conn| {
+            conn.batch_execute("INSERT INTO batch_items VALUES (2)")?;
+            Err::<(), _>(DieselError::RollbackTransaction)
+        });
+        assert!(matches!(result, Err(DieselError::RollbackTransaction)));
+        assert!(!connection.is_in_transaction());
+        assert_eq!(count_batch_items(&mut connection), 1);
+    }
+
+    #[test]
+    #[ignore] // Ignored by default
+    fn test_in_transaction_nested_uses_savepoint() {
+        let mut connection = establish_test_connection();
+        if !create_batch_table(&mut connection) {
+            return;
+        }
+
+        let result = connection.transaction::<_, DieselError, _>(|conn| {
+            conn.batch_execute("INSERT INTO batch_items VALUES (1)")?;
+
+            let depth = conn.in_transaction::<_, DieselError, _>(|conn| {
+                conn.batch_execute("INSERT INTO batch_items VALUES (2)")?;
+                Ok(conn.transaction_depth())
+            })?;
+            assert_eq!(depth, 2);
+            assert_eq!(conn.transaction_depth(), 1);
+            assert_eq!(count_batch_items(conn), 2);
+
+            let failed = conn.in_transaction(|conn| {
+                conn.batch_execute("INSERT INTO batch_items VALUES (3)")?;
+                Err::<(), _>(DieselError::RollbackTransaction)
+            });
+            assert!(matches!(failed, Err(DieselError::RollbackTransaction)));
+            assert_eq!(conn.transaction_depth(), 1);
+            assert_eq!(count_batch_items(conn), 2);
+
+            let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                conn.in_transaction::<(), DieselError, _>(|conn| {
+                    conn.batch_execute("INSERT INTO batch_items VALUES (4)")?;
+                    panic!("closure failed");
+                })
+            }));
+            assert!(panicked.is_err());
+            assert_eq!(conn.transaction_depth(), 1);
+            assert_eq!(count_batch_items(conn), 2);
+
+            // The savepoints were released, not just rolled back to
+            assert!(conn.batch_execute("ROLLBACK TO SAVEPOINT diesel_savepoint_1").is_err());
+            Err::<(), _>(DieselError::RollbackTransaction)
+        });
+
+        assert!(matches!(result, Err(DieselError::RollbackTransaction)));
+        assert!(!connection.is_in_transaction());
+        assert_eq!(count_batch_items(&mut connection), 0);
+    }
+}